@@ -145,6 +145,7 @@ const domainTypes = {
     'Boolean': 'A boolean value {0,1}',
     'Real': 'A real number',
     'NonNegativeReal': 'A non negative real number',
+    'Free': 'A real number without bounds, same as Real',
     'IntegerRange(0, 10)': 'An integer between min and max',
 }
 
@@ -367,7 +368,7 @@ const suggestedKeywords = [
     detail: keywords[k]
 }))
 const suggestedTypes = [
-    "Boolean", "Real", "NonNegativeReal", "IntegerRange(0, 10)"
+    "Boolean", "Real", "NonNegativeReal", "Free", "IntegerRange(0, 10)"
 ].map(k => ({
     label: k,
     kind: languages.CompletionItemKind.Class,
@@ -17,6 +17,8 @@ export function getFormattedRoocType(type: SerializedPrimitiveKind): string {
 		return `(${type.value.map(getFormattedRoocType).join(', ')})`
 	} else if (type.type === "Iterable") {
 		return `${getFormattedRoocType(type.value)}[]`
+	} else if (type.type === "Set") {
+		return `{${getFormattedRoocType(type.value)}}`
 	} else {
 		return type.type
 	}
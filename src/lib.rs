@@ -49,9 +49,14 @@ extern crate pest_derive;
 use crate::prelude::*;
 use indexmap::IndexMap;
 
-use parser::pre_model::{parse_problem_source, PreModel};
+use parser::pre_model::{
+    parse_problem_source_with_max_depth, PreModel, DEFAULT_MAX_EXPRESSION_DEPTH,
+};
 
-use crate::parser::model_transformer::{transform_parsed_problem, Model};
+use crate::parser::model_transformer::{
+    transform_parsed_problem_with_config, DefaultDomain, Model, StrictComparisonMode,
+    TransformerConfig, VariableNamingStrategy,
+};
 
 #[macro_use]
 mod macros;
@@ -60,7 +65,7 @@ mod parser;
 pub mod pipe;
 mod primitives;
 mod runtime_builtin;
-mod solvers;
+pub mod solvers;
 mod traits;
 mod transformers;
 pub mod type_checker;
@@ -71,6 +76,7 @@ pub use parser::*;
 pub use primitives::*;
 pub use runtime_builtin::*;
 pub use solvers::*;
+pub use traits::{DisplayWithOptions, FormatOptions, LatexOptions};
 pub use transformers::*;
 pub use utils::*;
 
@@ -113,6 +119,8 @@ mod prelude {
 #[derive(Debug, Clone)]
 pub struct RoocParser {
     source: String,
+    max_depth: usize,
+    transformer_config: TransformerConfig,
 }
 
 impl RoocParser {
@@ -121,7 +129,51 @@ impl RoocParser {
     /// # Arguments
     /// * `source` - The Rooc source code as a String
     pub fn new(source: String) -> Self {
-        Self { source }
+        Self {
+            source,
+            max_depth: DEFAULT_MAX_EXPRESSION_DEPTH,
+            transformer_config: TransformerConfig::default(),
+        }
+    }
+
+    /// Sets the maximum nesting depth of the expressions, deeper expressions
+    /// are rejected with an error instead of being evaluated.
+    ///
+    /// # Arguments
+    /// * `max_depth` - The maximum depth, defaults to [`DEFAULT_MAX_EXPRESSION_DEPTH`]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets how compound variables are named in the transformed model,
+    /// for example `x_1_2` or `x[1,2]`, and how their indexes are subscripted in LaTeX.
+    ///
+    /// # Arguments
+    /// * `naming_strategy` - The naming strategy, defaults to [`VariableNamingStrategy::Underscore`]
+    pub fn with_naming_strategy(mut self, naming_strategy: VariableNamingStrategy) -> Self {
+        self.transformer_config.naming_strategy = naming_strategy;
+        self
+    }
+
+    /// Sets what happens to strict comparisons (`<` and `>`) in the constraints,
+    /// they can be kept, rejected with an error, or relaxed to `<=` and `>=` with a warning.
+    ///
+    /// # Arguments
+    /// * `mode` - The strict comparison mode, defaults to [`StrictComparisonMode::Keep`]
+    pub fn with_strict_comparison(mut self, mode: StrictComparisonMode) -> Self {
+        self.transformer_config.strict_comparison = mode;
+        self
+    }
+
+    /// Sets the domain of the variables that are used in the model but not declared,
+    /// they can be rejected with an error, or be non negative or free reals with a warning.
+    ///
+    /// # Arguments
+    /// * `default_domain` - The default domain, defaults to [`DefaultDomain::Reject`]
+    pub fn with_default_domain(mut self, default_domain: DefaultDomain) -> Self {
+        self.transformer_config.default_domain = default_domain;
+        self
     }
 
     /// Parses the source code into a PreModel representation.
@@ -130,7 +182,9 @@ impl RoocParser {
     /// * `Ok(PreModel)` - The parsed representation of the program
     /// * `Err(CompilationError)` - If parsing fails
     pub fn parse(&self) -> Result<PreModel, CompilationError> {
-        parse_problem_source(&self.source)
+        let mut parsed = parse_problem_source_with_max_depth(&self.source, self.max_depth)?;
+        parsed.set_naming_strategy(self.transformer_config.naming_strategy);
+        Ok(parsed)
     }
 
     /// Formats the source code according to Rooc's formatting rules.
@@ -160,7 +214,12 @@ impl RoocParser {
         let parsed = self
             .parse()
             .map_err(|e| e.to_string_from_source(&self.source))?;
-        let transformed = transform_parsed_problem(parsed, constants, fns);
+        let transformed = transform_parsed_problem_with_config(
+            parsed,
+            constants,
+            fns,
+            self.transformer_config.clone(),
+        );
         match transformed {
             Ok(transformed) => Ok(transformed),
             Err(e) => Err(e
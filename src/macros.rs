@@ -4,7 +4,10 @@ mod rooc_macros {
     macro_rules! err_unexpected_token {
         ($s:literal, $arg:ident $(, $x:expr )*) => {
             Err(CompilationError::from_pair(
-                ParseError::UnexpectedToken(format!($s, $arg.as_str(), $($x),*)),
+                ParseError::UnexpectedToken {
+                    message: format!($s, $arg.as_str(), $($x),*),
+                    expected: Vec::new(),
+                },
                 &$arg,
                 false,
             ))
@@ -96,7 +99,7 @@ mod rooc_macros {
         };
 
         ($vis:vis enum $name:ident derives[$($derive:tt)+] with_wasm { $($variant:ident),* $(,)? }) => {
-            #[derive($($derive)*, Serialize)]
+            #[derive($($derive)*, Serialize, serde::Deserialize)]
             #[serde(tag = "type")]
             #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
             $vis enum $name {
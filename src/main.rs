@@ -1,10 +1,11 @@
 use indexmap::IndexMap;
+use rooc::pipe::MILPSolverPipe;
 #[allow(unused)]
 use rooc::pipe::{
     CompilerPipe, LinearModelPipe, ModelPipe, PipeContext, PipeRunner, PipeableData, PreModelPipe,
     RealSolver, StandardLinearModelPipe,
 };
-use rooc::pipe::MILPSolverPipe;
+use rooc::FormatOptions;
 
 #[allow(unused)]
 fn main() {
@@ -39,7 +40,7 @@ define
         Box::new(PreModelPipe::new()),
         Box::new(ModelPipe::new()),
         Box::new(LinearModelPipe::new()),
-        Box::new(MILPSolverPipe::new())
+        Box::new(MILPSolverPipe::new()),
     ]);
 
     let (result) = pipe_runner.run(
@@ -54,7 +55,17 @@ define
                 .map(|data| format!("//--------{}--------//\n\n{}", data.get_type(), data))
                 .collect::<Vec<String>>()
                 .join("\n\n");
-            println!("{}", last)
+            match last {
+                PipeableData::MILPSolution(solution) => {
+                    //values are rounded unless the full precision is asked for
+                    let options = match std::env::args().any(|arg| arg == "--full-precision") {
+                        true => FormatOptions::new(),
+                        false => FormatOptions::new().with_decimals(4),
+                    };
+                    println!("{}", solution.to_table_with_options(&options))
+                }
+                _ => println!("{}", last),
+            }
         }
         Err((error, context)) => {
             let context = context
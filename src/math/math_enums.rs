@@ -6,7 +6,7 @@ use crate::parser::model_transformer::TransformerContext;
 #[allow(unused_imports)]
 use crate::prelude::*;
 use crate::primitives::{Primitive, PrimitiveKind};
-use crate::traits::ToLatex;
+use crate::traits::{LatexOptions, ToLatex};
 use crate::type_checker::type_checker_context::{
     FunctionContext, TypeCheckable, TypeCheckerContext, WithType,
 };
@@ -14,7 +14,7 @@ use crate::{enum_with_variants_to_string, InputSpan, Spanned};
 use core::f64;
 use core::fmt;
 use num_traits::ToPrimitive;
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use std::str::FromStr;
 
 enum_with_variants_to_string! {
@@ -28,7 +28,7 @@ enum_with_variants_to_string! {
 }
 
 impl ToLatex for Comparison {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         match self {
             Comparison::LessOrEqual => "\\leq".to_string(),
             Comparison::GreaterOrEqual => "\\geq".to_string(),
@@ -77,7 +77,7 @@ enum_with_variants_to_string! {
 }
 
 impl ToLatex for OptimizationType {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         match self {
             OptimizationType::Min => "\\min".to_string(),
             OptimizationType::Max => "\\max".to_string(),
@@ -112,6 +112,7 @@ impl FromStr for OptimizationType {
 
 /// Represents the type of a variable before type checking and transformation
 #[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "value")]
 pub enum PreVariableType {
     /// Boolean variable (0 or 1)
     Boolean,
@@ -198,6 +199,7 @@ impl FromStr for PreVariableType {
             "Boolean" => Ok(PreVariableType::Boolean),
             "NonNegativeReal" => Ok(PreVariableType::NonNegativeReal(None, None)),
             "Real" => Ok(PreVariableType::Real(None, None)),
+            "Free" => Ok(PreVariableType::Real(None, None)),
             _ => Err(()),
         }
     }
@@ -210,10 +212,22 @@ impl PreVariableType {
             "Boolean".to_string(),
             "NonNegativeReal".to_string(),
             "Real".to_string(),
+            "Free".to_string(),
             "IntegerRange(min, max)".to_string(),
         ]
     }
 
+    /// Returns the expressions of the bounds that are written in the source
+    pub fn bounds(&self) -> Vec<&PreExp> {
+        match self {
+            PreVariableType::Boolean => vec![],
+            PreVariableType::NonNegativeReal(min, max) | PreVariableType::Real(min, max) => {
+                min.iter().chain(max.iter()).collect()
+            }
+            PreVariableType::IntegerRange(min, max) => vec![min, max],
+        }
+    }
+
     /// Converts PreVariableType to VariableType without using context
     /// This is a simplified conversion that uses default values for ranges
     pub fn to_variable_type_without_context(&self) -> VariableType {
@@ -362,7 +376,7 @@ impl PreVariableType {
 }
 
 impl ToLatex for PreVariableType {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         match self {
             PreVariableType::Boolean => "\\{0,1\\}".to_string(),
             PreVariableType::NonNegativeReal(min, max) => match (min, max) {
@@ -370,10 +384,10 @@ impl ToLatex for PreVariableType {
                 (min, max) => format!(
                     "\\{{x \\in \\mathbb{{R}}^+_0 | {} \\leq x \\leq {}\\}}",
                     min.clone()
-                        .map(|m| m.to_latex())
+                        .map(|m| m.to_latex_with(options))
                         .unwrap_or_else(|| "0".to_string()),
                     max.clone()
-                        .map(|m| m.to_latex())
+                        .map(|m| m.to_latex_with(options))
                         .unwrap_or_else(|| "\\infty".to_string())
                 ),
             },
@@ -382,17 +396,17 @@ impl ToLatex for PreVariableType {
                 (min, max) => format!(
                     "\\{{x \\in \\mathbb{{R}} | {} \\leq x \\leq {}\\}}",
                     min.clone()
-                        .map(|m| m.to_latex())
+                        .map(|m| m.to_latex_with(options))
                         .unwrap_or_else(|| "-\\infty".to_string()),
                     max.clone()
-                        .map(|m| m.to_latex())
+                        .map(|m| m.to_latex_with(options))
                         .unwrap_or_else(|| "\\infty".to_string())
                 ),
             },
             PreVariableType::IntegerRange(min, max) => format!(
                 "\\{{x \\in \\mathbb{{Z}} | {} \\leq x \\leq {}\\}}",
-                min.to_latex(),
-                max.to_latex()
+                min.to_latex_with(options),
+                max.to_latex_with(options)
             ),
         }
     }
@@ -533,16 +547,32 @@ impl fmt::Display for PreVariableType {
     }
 }
 
+/// Infinite bounds are written as `null` in JSON, a missing lower bound is minus infinity
+fn deserialize_lower_bound<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NEG_INFINITY))
+}
+
+/// Infinite bounds are written as `null` in JSON, a missing upper bound is infinity
+fn deserialize_upper_bound<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::INFINITY))
+}
+
 /// Represents the final, resolved type of a variable after being compiled
-#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 #[serde(tag = "type", content = "value")]
 pub enum VariableType {
     /// Boolean variable (0 or 1)
     Boolean,
     /// Real number greater than or equal to zero
-    NonNegativeReal(f64, f64),
+    NonNegativeReal(
+        f64,
+        #[serde(deserialize_with = "deserialize_upper_bound")] f64,
+    ),
     /// Any real number
-    Real(f64, f64),
+    Real(
+        #[serde(deserialize_with = "deserialize_lower_bound")] f64,
+        #[serde(deserialize_with = "deserialize_upper_bound")] f64,
+    ),
     /// Integer within a specified range [min, max]
     IntegerRange(i32, i32),
 }
@@ -579,6 +609,7 @@ impl VariableType {
             "Boolean".to_string(),
             "NonNegativeReal".to_string(),
             "Real".to_string(),
+            "Free".to_string(),
             "IntegerRange(min, max)".to_string(),
         ]
     }
@@ -624,7 +655,7 @@ impl fmt::Display for VariableType {
 }
 
 impl ToLatex for VariableType {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         match self {
             VariableType::Boolean => "\\{0,1\\}".to_string(),
             VariableType::NonNegativeReal(min, max) => match (*min, *max) {
@@ -635,13 +666,13 @@ impl ToLatex for VariableType {
                     if *min == 0.0 {
                         "0".to_string()
                     } else {
-                        min.to_string()
+                        min.to_latex_with(options)
                     },
                     "x",
                     if *max == f64::INFINITY {
                         "\\infty".to_string()
                     } else {
-                        max.to_string()
+                        max.to_latex_with(options)
                     }
                 ),
             },
@@ -653,13 +684,13 @@ impl ToLatex for VariableType {
                     if *min == f64::NEG_INFINITY {
                         "-\\infty".to_string()
                     } else {
-                        min.to_string()
+                        min.to_latex_with(options)
                     },
                     "x",
                     if *max == f64::INFINITY {
                         "\\infty".to_string()
                     } else {
-                        max.to_string()
+                        max.to_latex_with(options)
                     }
                 ),
             },
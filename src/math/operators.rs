@@ -7,7 +7,7 @@ use crate::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::enum_with_variants_to_string;
-use crate::traits::ToLatex;
+use crate::traits::{LatexOptions, ToLatex};
 
 enum_with_variants_to_string! {
     pub enum Operator derives[Debug, PartialEq, Clone, Copy] with_wasm {
@@ -16,6 +16,17 @@ enum_with_variants_to_string! {
         Mul,
         Div,
         Neg,
+        And,
+        Or,
+        Not,
+        Equal,
+        NotEqual,
+        Less,
+        LessOrEqual,
+        Greater,
+        GreaterOrEqual,
+        In,
+        NotIn,
     }
 }
 impl Operator {
@@ -24,9 +35,19 @@ impl Operator {
     /// Higher precedence values indicate that the operator should be evaluated first.
     pub fn precedence(&self) -> u8 {
         match self {
-            Operator::Add | Operator::Sub => 1,
-            Operator::Mul | Operator::Div => 2,
-            Operator::Neg => 3,
+            Operator::Or => 1,
+            Operator::And => 2,
+            Operator::Equal
+            | Operator::NotEqual
+            | Operator::Less
+            | Operator::LessOrEqual
+            | Operator::Greater
+            | Operator::GreaterOrEqual
+            | Operator::In
+            | Operator::NotIn => 3,
+            Operator::Add | Operator::Sub => 4,
+            Operator::Mul | Operator::Div => 5,
+            Operator::Neg | Operator::Not => 6,
         }
     }
 
@@ -36,8 +57,21 @@ impl Operator {
     /// For example, a - b - c is evaluated as (a - b) - c.
     pub fn is_left_associative(&self) -> bool {
         match self {
-            Operator::Add | Operator::Sub | Operator::Mul | Operator::Div => true,
-            Operator::Neg => false,
+            Operator::Add
+            | Operator::Sub
+            | Operator::Mul
+            | Operator::Div
+            | Operator::And
+            | Operator::Or
+            | Operator::Equal
+            | Operator::NotEqual
+            | Operator::Less
+            | Operator::LessOrEqual
+            | Operator::Greater
+            | Operator::GreaterOrEqual
+            | Operator::In
+            | Operator::NotIn => true,
+            Operator::Neg | Operator::Not => false,
         }
     }
 }
@@ -50,6 +84,17 @@ impl fmt::Display for Operator {
             Operator::Mul => "*".to_string(),
             Operator::Div => "/".to_string(),
             Operator::Neg => "-".to_string(),
+            Operator::And => "and".to_string(),
+            Operator::Or => "or".to_string(),
+            Operator::Not => "not".to_string(),
+            Operator::Equal => "==".to_string(),
+            Operator::NotEqual => "!=".to_string(),
+            Operator::Less => "<".to_string(),
+            Operator::LessOrEqual => "<=".to_string(),
+            Operator::Greater => ">".to_string(),
+            Operator::GreaterOrEqual => ">=".to_string(),
+            Operator::In => "in".to_string(),
+            Operator::NotIn => "not in".to_string(),
         };
 
         f.write_str(&s)
@@ -62,9 +107,16 @@ enum_with_variants_to_string! {
         Sub,
         Mul,
         Div,
-        //And
-        //Or
-        //Not
+        And,
+        Or,
+        Equal,
+        NotEqual,
+        Less,
+        LessOrEqual,
+        Greater,
+        GreaterOrEqual,
+        In,
+        NotIn,
         //Xor
     }
 }
@@ -73,18 +125,68 @@ impl BinOp {
     /// Returns the precedence level of the binary operator.
     pub fn precedence(&self) -> u8 {
         match self {
-            BinOp::Add | BinOp::Sub => 1,
-            BinOp::Mul | BinOp::Div => 2,
+            BinOp::Or => 1,
+            BinOp::And => 2,
+            BinOp::Equal
+            | BinOp::NotEqual
+            | BinOp::Less
+            | BinOp::LessOrEqual
+            | BinOp::Greater
+            | BinOp::GreaterOrEqual
+            | BinOp::In
+            | BinOp::NotIn => 3,
+            BinOp::Add | BinOp::Sub => 4,
+            BinOp::Mul | BinOp::Div => 5,
         }
     }
 
     /// Determines if the binary operator is left associative.
     pub fn is_left_associative(&self) -> bool {
         match self {
-            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div => true,
+            BinOp::Add
+            | BinOp::Sub
+            | BinOp::Mul
+            | BinOp::Div
+            | BinOp::And
+            | BinOp::Or
+            | BinOp::Equal
+            | BinOp::NotEqual
+            | BinOp::Less
+            | BinOp::LessOrEqual
+            | BinOp::Greater
+            | BinOp::GreaterOrEqual
+            | BinOp::In
+            | BinOp::NotIn => true,
         }
     }
 
+    /// Returns true if the operator works on booleans, those are only evaluated at compile time
+    /// and never end up in the model.
+    pub fn is_logical(&self) -> bool {
+        matches!(self, BinOp::And | BinOp::Or)
+    }
+
+    /// Returns true if the operator compares two values, producing a boolean.
+    /// Like the logical operators, those are only evaluated at compile time.
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinOp::Equal
+                | BinOp::NotEqual
+                | BinOp::Less
+                | BinOp::LessOrEqual
+                | BinOp::Greater
+                | BinOp::GreaterOrEqual
+                | BinOp::In
+                | BinOp::NotIn
+        )
+    }
+
+    /// Returns true if the operator checks whether a value is an element of a set.
+    pub fn is_membership(&self) -> bool {
+        matches!(self, BinOp::In | BinOp::NotIn)
+    }
+
     /// Converts a binary operator to the corresponding general Operator enum.
     pub fn to_operator(&self) -> Operator {
         match self {
@@ -92,17 +194,37 @@ impl BinOp {
             BinOp::Sub => Operator::Sub,
             BinOp::Mul => Operator::Mul,
             BinOp::Div => Operator::Div,
+            BinOp::And => Operator::And,
+            BinOp::Or => Operator::Or,
+            BinOp::Equal => Operator::Equal,
+            BinOp::NotEqual => Operator::NotEqual,
+            BinOp::Less => Operator::Less,
+            BinOp::LessOrEqual => Operator::LessOrEqual,
+            BinOp::Greater => Operator::Greater,
+            BinOp::GreaterOrEqual => Operator::GreaterOrEqual,
+            BinOp::In => Operator::In,
+            BinOp::NotIn => Operator::NotIn,
         }
     }
 }
 
 impl ToLatex for BinOp {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         match self {
             BinOp::Add => "+".to_string(),
             BinOp::Sub => "-".to_string(),
             BinOp::Mul => "\\cdot".to_string(),
             BinOp::Div => "\\div".to_string(),
+            BinOp::And => "\\land".to_string(),
+            BinOp::Or => "\\lor".to_string(),
+            BinOp::Equal => "=".to_string(),
+            BinOp::NotEqual => "\\neq".to_string(),
+            BinOp::Less => "<".to_string(),
+            BinOp::LessOrEqual => "\\leq".to_string(),
+            BinOp::Greater => ">".to_string(),
+            BinOp::GreaterOrEqual => "\\geq".to_string(),
+            BinOp::In => "\\in".to_string(),
+            BinOp::NotIn => "\\notin".to_string(),
         }
     }
 }
@@ -114,6 +236,16 @@ impl fmt::Display for BinOp {
             BinOp::Sub => "-".to_string(),
             BinOp::Mul => "*".to_string(),
             BinOp::Div => "/".to_string(),
+            BinOp::And => "and".to_string(),
+            BinOp::Or => "or".to_string(),
+            BinOp::Equal => "==".to_string(),
+            BinOp::NotEqual => "!=".to_string(),
+            BinOp::Less => "<".to_string(),
+            BinOp::LessOrEqual => "<=".to_string(),
+            BinOp::Greater => ">".to_string(),
+            BinOp::GreaterOrEqual => ">=".to_string(),
+            BinOp::In => "in".to_string(),
+            BinOp::NotIn => "not in".to_string(),
         };
 
         f.write_str(&s)
@@ -128,6 +260,16 @@ impl FromStr for BinOp {
             "-" => Ok(BinOp::Sub),
             "*" => Ok(BinOp::Mul),
             "/" => Ok(BinOp::Div),
+            "and" => Ok(BinOp::And),
+            "or" => Ok(BinOp::Or),
+            "==" => Ok(BinOp::Equal),
+            "!=" => Ok(BinOp::NotEqual),
+            "<" => Ok(BinOp::Less),
+            "<=" => Ok(BinOp::LessOrEqual),
+            ">" => Ok(BinOp::Greater),
+            ">=" => Ok(BinOp::GreaterOrEqual),
+            "in" => Ok(BinOp::In),
+            "not in" => Ok(BinOp::NotIn),
             _ => Err(()),
         }
     }
@@ -136,6 +278,7 @@ impl FromStr for BinOp {
 enum_with_variants_to_string! {
     pub enum UnOp derives[Debug, PartialEq, Clone, Copy] with_wasm {
         Neg,
+        Not,
     }
 }
 
@@ -143,14 +286,14 @@ impl UnOp {
     /// Returns the precedence level of the unary operator.
     pub fn precedence(&self) -> u8 {
         match self {
-            UnOp::Neg => 3,
+            UnOp::Neg | UnOp::Not => 6,
         }
     }
 
     /// Determines if the unary operator is left associative.
     pub fn is_left_associative(&self) -> bool {
         match self {
-            UnOp::Neg => false,
+            UnOp::Neg | UnOp::Not => false,
         }
     }
 
@@ -158,14 +301,16 @@ impl UnOp {
     pub fn to_operator(&self) -> Operator {
         match self {
             UnOp::Neg => Operator::Neg,
+            UnOp::Not => Operator::Not,
         }
     }
 }
 
 impl ToLatex for UnOp {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         match self {
             UnOp::Neg => "-".to_string(),
+            UnOp::Not => "\\lnot ".to_string(),
         }
     }
 }
@@ -174,6 +319,7 @@ impl fmt::Display for UnOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
             UnOp::Neg => "-".to_string(),
+            UnOp::Not => "not ".to_string(),
         };
 
         f.write_str(&s)
@@ -185,6 +331,7 @@ impl FromStr for UnOp {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "-" => Ok(UnOp::Neg),
+            "not" => Ok(UnOp::Not),
             _ => Err(()),
         }
     }
@@ -9,12 +9,13 @@ use crate::math::PreVariableType;
 use crate::model_transformer::DomainVariable;
 use crate::parser::il::CompoundVariable;
 use crate::parser::il::IterableSet;
+use crate::parser::il::PreExp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::{
     math::VariableType,
-    traits::{escape_latex, ToLatex},
+    traits::{escape_latex, variable_name_to_latex, LatexOptions, ToLatex},
     type_checker::type_checker_context::{TypeCheckable, TypeCheckerContext},
     utils::{InputSpan, Spanned},
 };
@@ -46,7 +47,7 @@ impl Display for VariableToAssert {
 }
 
 impl ToLatex for VariableToAssert {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         match self {
             VariableToAssert::Variable(name) => {
                 if name.contains("_") {
@@ -54,12 +55,16 @@ impl ToLatex for VariableToAssert {
                     //sure to have at least one element
                     let first = indexes.remove(0);
                     let rest = indexes.join("");
-                    format!("{}_{{{}}}", escape_latex(first), escape_latex(&rest))
+                    format!(
+                        "{}_{{{}}}",
+                        variable_name_to_latex(first),
+                        escape_latex(&rest)
+                    )
                 } else {
-                    escape_latex(name)
+                    variable_name_to_latex(name)
                 }
             }
-            VariableToAssert::CompoundVariable(c) => c.to_latex(),
+            VariableToAssert::CompoundVariable(c) => c.to_latex_with(options),
         }
     }
 }
@@ -141,11 +146,63 @@ impl VariablesDomainDeclaration {
             .collect()
     }
 
+    /// Returns the names of the compound variables of this domain, like `x` for `x_i` or `x[i]`
+    pub fn compound_variable_names(&self) -> Vec<String> {
+        self.variables
+            .iter()
+            .filter_map(|v| match &v.value() {
+                VariableToAssert::CompoundVariable(c) => Some(c.name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Returns reference to iteration sets
     pub fn iteration(&self) -> &Vec<IterableSet> {
         &self.iteration
     }
 
+    /// Returns the expressions of this domain, the indexes of the compound variables,
+    /// the bounds of the type and the iteration sets.
+    pub fn expressions(&self) -> Vec<&PreExp> {
+        let indexes = self.variables.iter().flat_map(|v| match v.value() {
+            VariableToAssert::Variable(_) => [].iter(),
+            VariableToAssert::CompoundVariable(c) => c.indexes.iter(),
+        });
+        indexes
+            .chain(self.as_type.bounds())
+            .chain(self.iteration.iter().map(|i| i.iterator.value()))
+            .collect()
+    }
+
+    /// Collects the spans of the declarations of `name` and of its references in the
+    /// indexes, bounds and iteration sets of this domain.
+    pub fn find_occurrences(&self, name: &str, spans: &mut Vec<InputSpan>) {
+        for v in &self.variables {
+            match v.value() {
+                VariableToAssert::Variable(variable) => {
+                    if variable == name {
+                        spans.push(v.span().clone());
+                    }
+                }
+                VariableToAssert::CompoundVariable(c) => {
+                    if c.name == name {
+                        spans.push(v.span().prefix(name.len() as u32));
+                    }
+                    for index in &c.indexes {
+                        index.find_occurrences(name, spans);
+                    }
+                }
+            }
+        }
+        for bound in self.as_type.bounds() {
+            bound.find_occurrences(name, spans);
+        }
+        for iter in &self.iteration {
+            iter.find_occurrences(name, spans);
+        }
+    }
+
     /// Computes the domain values for the current context state
     fn compute_domain_values(
         &self,
@@ -163,6 +220,7 @@ impl VariablesDomainDeclaration {
                     VariableToAssert::CompoundVariable(c) => {
                         let indexes = &c.compute_indexes(context, fn_context)?;
                         let name = context.flatten_compound_variable(&c.name, indexes)?;
+                        context.add_variable_index(name.clone(), c.name.clone(), indexes.clone());
                         let var_type = self.as_type.to_variable_type(context, fn_context)?;
                         Ok((name, var_type))
                     }
@@ -264,20 +322,20 @@ impl TypeCheckable for VariablesDomainDeclaration {
 }
 
 impl ToLatex for VariablesDomainDeclaration {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         let mut s = String::new();
         let vars = self
             .variables
             .iter()
-            .map(|v| v.to_latex())
+            .map(|v| v.to_latex_with(options))
             .collect::<Vec<String>>()
             .join(", ");
-        s.push_str(format!("{} &\\in {}", vars, self.as_type.to_latex()).as_str());
+        s.push_str(format!("{} &\\in {}", vars, self.as_type.to_latex_with(options)).as_str());
         if !self.iteration.is_empty() {
             let iters = self
                 .iteration
                 .iter()
-                .map(|iter| format!(" \\forall{{{}}} ", iter.to_latex()))
+                .map(|iter| format!(" \\forall{{{}}} ", iter.to_latex_with(options)))
                 .collect::<Vec<String>>()
                 .join(",\\");
             s.push_str(format!(" \\quad {}", iters).as_str());
@@ -8,7 +8,7 @@ use serde::Serialize;
 use crate::enum_with_variants_to_string;
 use crate::parser::il::il_exp::PreExp;
 use crate::parser::il::iterable_set::IterableSet;
-use crate::traits::ToLatex;
+use crate::traits::{LatexOptions, ToLatex};
 use crate::utils::InputSpan;
 
 enum_with_variants_to_string! {
@@ -18,6 +18,8 @@ enum_with_variants_to_string! {
         Min,
         Max,
         Avg,
+        Norm1,
+        NormInf,
     }
 }
 impl fmt::Display for BlockScopedFunctionKind {
@@ -28,19 +30,23 @@ impl fmt::Display for BlockScopedFunctionKind {
             Self::Min => "min".to_string(),
             Self::Max => "max".to_string(),
             Self::Avg => "avg".to_string(),
+            Self::Norm1 => "norm1".to_string(),
+            Self::NormInf => "norm_inf".to_string(),
         };
         f.write_str(&s)
     }
 }
 
 impl ToLatex for BlockScopedFunctionKind {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         match self {
             Self::Sum => "\\sum".to_string(),
             Self::Prod => "\\prod".to_string(),
             Self::Min => "\\min".to_string(),
             Self::Max => "\\max".to_string(),
             Self::Avg => "avg".to_string(),
+            Self::Norm1 => "\\text{norm}_1".to_string(),
+            Self::NormInf => "\\text{norm}_\\infty".to_string(),
         }
     }
 }
@@ -54,6 +60,8 @@ impl FromStr for BlockScopedFunctionKind {
             "min" => Ok(Self::Min),
             "max" => Ok(Self::Max),
             "avg" => Ok(Self::Avg),
+            "norm1" => Ok(Self::Norm1),
+            "norm_inf" => Ok(Self::NormInf),
             _ => Err(()),
         }
     }
@@ -64,15 +72,19 @@ enum_with_variants_to_string! {
         Min,
         Max,
         Avg,
+        Norm1,
+        NormInf,
     }
 }
 
 impl ToLatex for BlockFunctionKind {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         match self {
             Self::Min => "\\min".to_string(),
             Self::Max => "\\max".to_string(),
             Self::Avg => "avg".to_string(),
+            Self::Norm1 => "\\text{norm}_1".to_string(),
+            Self::NormInf => "\\text{norm}_\\infty".to_string(),
         }
     }
 }
@@ -84,6 +96,8 @@ impl FromStr for BlockFunctionKind {
             "min" => Ok(Self::Min),
             "max" => Ok(Self::Max),
             "avg" => Ok(Self::Avg),
+            "norm1" => Ok(Self::Norm1),
+            "norm_inf" => Ok(Self::NormInf),
             _ => Err(()),
         }
     }
@@ -95,6 +109,8 @@ impl fmt::Display for BlockFunctionKind {
             Self::Min => "min".to_string(),
             Self::Max => "max".to_string(),
             Self::Avg => "avg".to_string(),
+            Self::Norm1 => "norm1".to_string(),
+            Self::NormInf => "norm_inf".to_string(),
         };
         f.write_str(&s)
     }
@@ -113,30 +129,30 @@ pub struct BlockScopedFunction {
 }
 
 impl ToLatex for BlockScopedFunction {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         match self.kind {
             BlockScopedFunctionKind::Sum | BlockScopedFunctionKind::Prod => {
-                let name = self.kind.to_latex();
+                let name = self.kind.to_latex_with(options);
                 let iters = self
                     .iters
                     .iter()
-                    .map(|i| format!("{}_{{{}}}", name, i.to_latex()))
+                    .map(|i| format!("{}_{{{}}}", name, i.to_latex_with(options)))
                     .collect::<Vec<String>>()
                     .join("");
-                format!("{}{}", iters, self.exp.to_latex())
+                format!("{}{}", iters, self.exp.to_latex_with(options))
             }
             _ => {
                 let iters = self
                     .iters
                     .iter()
-                    .map(|i| i.to_latex())
+                    .map(|i| i.to_latex_with(options))
                     .collect::<Vec<String>>()
                     .join(",\\");
                 format!(
                     "{}_{{{}}} \\left\\{{ {} \\right\\}}",
-                    self.kind.to_latex(),
+                    self.kind.to_latex_with(options),
                     iters,
-                    self.exp.to_latex()
+                    self.exp.to_latex_with(options)
                 )
             }
         }
@@ -221,14 +237,14 @@ impl BlockFunction {
 }
 
 impl ToLatex for BlockFunction {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         let name = self.kind.to_string();
         format!(
             "{}\\left\\{{{}\\right\\}}",
             name,
             self.exps
                 .iter()
-                .map(|e| e.to_latex())
+                .map(|e| e.to_latex_with(options))
                 .collect::<Vec<String>>()
                 .join(", ")
         )
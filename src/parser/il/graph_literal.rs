@@ -0,0 +1,238 @@
+use core::fmt;
+
+#[allow(unused_imports)]
+use crate::prelude::*;
+use serde::Serialize;
+
+use crate::math::UnOp;
+use crate::parser::il::PreExp;
+use crate::parser::model_transformer::{TransformError, TransformerContext};
+use crate::primitives::{Graph, GraphEdge, GraphNode, PrimitiveKind};
+use crate::traits::{escape_latex, LatexOptions, ToLatex};
+use crate::type_checker::type_checker_context::{
+    FunctionContext, TypeCheckable, TypeCheckerContext, WithType,
+};
+
+/// An edge of a graph declared in the source, whose weight is an expression
+/// evaluated when the model is transformed.
+#[derive(Debug, Clone, Serialize)]
+pub struct PreGraphEdge {
+    pub to: String,
+    pub weight: Option<PreExp>,
+}
+
+/// A node of a graph declared in the source, with its outgoing edges.
+#[derive(Debug, Clone, Serialize)]
+pub struct PreGraphNode {
+    pub name: String,
+    pub edges: Vec<PreGraphEdge>,
+}
+
+/// A graph declared in the source, like `Graph { A -> [B: 2 * unit] }`, whose edge weights
+/// can be constant expressions that use the other constants of the model.
+///
+/// Graphs whose weights are all numbers are converted to a [`Graph`] while parsing,
+/// the others are evaluated when the model is transformed.
+#[derive(Debug, Clone, Serialize)]
+pub struct PreGraph {
+    pub nodes: Vec<PreGraphNode>,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IPreGraph: &'static str = r#"
+export type SerializedPreGraphEdge = {
+    to: string,
+    weight?: SerializedPreExp
+}
+export type SerializedPreGraph = {
+    nodes: {
+        name: string,
+        edges: SerializedPreGraphEdge[]
+    }[]
+}
+"#;
+
+/// Returns the value of a weight written as a number, or as a negated number
+fn literal_weight(weight: &PreExp) -> Option<f64> {
+    match weight {
+        PreExp::Primitive(p) => p.value().as_number_cast().ok(),
+        PreExp::UnaryOperation(op, exp) if **op == UnOp::Neg => literal_weight(exp).map(|w| -w),
+        _ => None,
+    }
+}
+
+impl PreGraph {
+    pub fn new(nodes: Vec<PreGraphNode>) -> Self {
+        Self { nodes }
+    }
+
+    /// Returns the weights of the edges that have one.
+    pub fn weights(&self) -> impl Iterator<Item = &PreExp> {
+        self.nodes
+            .iter()
+            .flat_map(|node| node.edges.iter().filter_map(|edge| edge.weight.as_ref()))
+    }
+
+    /// Converts the graph to a [`Graph`] if every weight is written as a number.
+    pub fn to_static_graph(&self) -> Option<Graph> {
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let mut edges = Vec::with_capacity(node.edges.len());
+            for edge in &node.edges {
+                let weight = match &edge.weight {
+                    Some(weight) => Some(literal_weight(weight)?),
+                    None => None,
+                };
+                edges.push(GraphEdge::new(node.name.clone(), edge.to.clone(), weight));
+            }
+            nodes.push(GraphNode::new(node.name.clone(), edges));
+        }
+        Some(Graph::new(nodes))
+    }
+
+    /// Evaluates the weights of the edges and builds the graph.
+    ///
+    /// # Arguments
+    /// * `context` - The constants the weights can use
+    /// * `fn_context` - The functions the weights can call
+    pub fn as_graph(
+        &self,
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Graph, TransformError> {
+        let mut nodes = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let mut edges = Vec::with_capacity(node.edges.len());
+            for edge in &node.edges {
+                let weight = match &edge.weight {
+                    Some(weight) => {
+                        let value = weight
+                            .as_primitive(context, fn_context)
+                            .and_then(|p| p.as_number_cast())
+                            .map_err(|e| {
+                                //the weights are evaluated once, so they cannot use domain variables
+                                let e = match weight.is_constant(context, fn_context) {
+                                    true => e,
+                                    false => TransformError::Other(format!(
+                                        "Edge weights must be constant expressions but got: {}",
+                                        weight
+                                    )),
+                                };
+                                e.add_span(weight.span())
+                            })?;
+                        if !value.is_finite() {
+                            return Err(TransformError::Other(format!(
+                                "Edge weight must be a finite number but got: {}",
+                                value
+                            ))
+                            .add_span(weight.span()));
+                        }
+                        Some(value)
+                    }
+                    None => None,
+                };
+                edges.push(GraphEdge::new(node.name.clone(), edge.to.clone(), weight));
+            }
+            nodes.push(GraphNode::new(node.name.clone(), edges));
+        }
+        Ok(Graph::new(nodes))
+    }
+}
+
+impl TypeCheckable for PreGraph {
+    fn type_check(
+        &self,
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        for weight in self.weights() {
+            weight.type_check(context, fn_context)?;
+            let weight_type = weight.get_type(context, fn_context);
+            if !weight_type.is_numeric() {
+                return Err(TransformError::from_wrong_type(
+                    PrimitiveKind::Number,
+                    weight_type,
+                    weight.span().clone(),
+                ));
+            }
+        }
+        Ok(())
+    }
+    fn populate_token_type_map(
+        &self,
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) {
+        for weight in self.weights() {
+            weight.populate_token_type_map(context, fn_context);
+        }
+    }
+}
+
+impl ToLatex for PreGraph {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let edges = node
+                    .edges
+                    .iter()
+                    .map(|edge| match &edge.weight {
+                        Some(weight) => {
+                            format!(
+                                "\\text{{{}:}}{}",
+                                escape_latex(&edge.to),
+                                weight.to_latex_with(options)
+                            )
+                        }
+                        None => format!("\\text{{{}}}", escape_latex(&edge.to)),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",\\ ");
+                if edges.is_empty() {
+                    node.name.clone()
+                } else {
+                    format!("{}\\to\\left\\{{{}\\right\\}}", node.name, edges)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\\\\ ");
+        if nodes.is_empty() {
+            return "\\emptyset".to_string();
+        }
+        format!("\\begin{{Bmatrix*}}[l] {} \\end{{Bmatrix*}}", nodes)
+    }
+}
+
+impl fmt::Display for PreGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let edges = node
+                    .edges
+                    .iter()
+                    .map(|edge| match &edge.weight {
+                        Some(weight) => format!("{}:{}", edge.to, weight),
+                        None => edge.to.clone(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                if edges.is_empty() {
+                    format!("    {}", node.name)
+                } else {
+                    format!("    {} -> [ {} ]", node.name, edges)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        if nodes.is_empty() {
+            return write!(f, "Graph {{ }}");
+        }
+        write!(f, "Graph {{\n{}\n}}", nodes)
+    }
+}
@@ -8,17 +8,19 @@ use crate::math::{BinOp, UnOp};
 use crate::parser::il::block_functions::{
     BlockFunction, BlockFunctionKind, BlockScopedFunction, BlockScopedFunctionKind,
 };
+use crate::parser::il::graph_literal::PreGraph;
 use crate::parser::il::il_problem::{AddressableAccess, CompoundVariable};
 use crate::parser::model_transformer::Exp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
+use crate::parser::model_transformer::VariableKind;
 use crate::parser::recursive_set_resolver::recursive_set_resolver;
 use crate::primitives::ApplyOp;
 use crate::primitives::IterableKind;
 use crate::primitives::{Graph, GraphEdge, GraphNode};
 use crate::primitives::{Primitive, PrimitiveKind};
 use crate::runtime_builtin::FunctionCall;
-use crate::traits::{escape_latex, ToLatex};
+use crate::traits::{variable_name_to_latex, LatexOptions, ToLatex};
 use crate::type_checker::type_checker_context::{
     FunctionContext, TypeCheckable, TypeCheckerContext, WithType,
 };
@@ -50,6 +52,8 @@ pub enum PreExp {
     BinaryOperation(Spanned<BinOp>, Box<PreExp>, Box<PreExp>),
     /// A unary operation like negation
     UnaryOperation(Spanned<UnOp>, Box<PreExp>),
+    /// A graph whose edge weights are expressions, like `Graph { A -> [B: 2 * unit] }`
+    Graph(Spanned<PreGraph>),
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -78,7 +82,8 @@ export type SerializedPreExp = {span: InputSpan} & (
     {type: "UnaryOperation", value: {
         op: UnOp,
         exp: SerializedPreExp,
-    }}
+    }} |
+    {type: "Graph", value: SerializedPreGraph}
 )
 "#;
 
@@ -98,6 +103,19 @@ impl TypeCheckable for PreExp {
                 rhs.type_check(context, fn_context)?;
                 let lhs_type = lhs.get_type(context, fn_context);
                 let rhs_type = rhs.get_type(context, fn_context);
+                //logical operators are evaluated at compile time, so both sides must be booleans
+                if op.is_logical() {
+                    for (exp, exp_type) in [(lhs, &lhs_type), (rhs, &rhs_type)] {
+                        if *exp_type != PrimitiveKind::Boolean {
+                            return Err(TransformError::from_wrong_type(
+                                PrimitiveKind::Boolean,
+                                exp_type.clone(),
+                                exp.span().clone(),
+                            )
+                            .add_span(op.span()));
+                        }
+                    }
+                }
                 if !lhs_type.can_apply_binary_op(**op, rhs_type.clone()) {
                     Err(TransformError::from_wrong_binop(
                         **op,
@@ -124,6 +142,9 @@ impl TypeCheckable for PreExp {
                 }
             }
             Self::Primitive(_) => Ok(()),
+            Self::Graph(g) => g
+                .type_check(context, fn_context)
+                .map_err(|e| e.add_span(g.span())),
             Self::Abs(_, exp) => {
                 exp.type_check(context, fn_context)
                     .map_err(|e| e.add_span(exp.span()))?;
@@ -204,10 +225,17 @@ impl TypeCheckable for PreExp {
                 }
                 Ok(())
             }
-            Self::ArrayAccess(array_access) => context
-                .get_addressable_value(array_access, fn_context)
-                .map(|_| ())
-                .map_err(|e| e.add_span(array_access.span())),
+            Self::ArrayAccess(array_access) => {
+                context
+                    .get_addressable_value(array_access, fn_context)
+                    .map_err(|e| e.add_span(array_access.span()))?;
+                match array_access.as_compound_variable() {
+                    Some(c) if context.value_of(&array_access.name).is_none() => context
+                        .check_compound_variable(&c.indexes, fn_context)
+                        .map_err(|e| e.add_span(array_access.span())),
+                    _ => Ok(()),
+                }
+            }
         }
     }
     fn populate_token_type_map(
@@ -225,6 +253,10 @@ impl TypeCheckable for PreExp {
             Self::Primitive(p) => {
                 context.add_token_type_or_undefined(p.value().get_type(), p.span().clone(), None)
             }
+            Self::Graph(g) => {
+                context.add_token_type_or_undefined(PrimitiveKind::Graph, g.span().clone(), None);
+                g.populate_token_type_map(context, fn_context);
+            }
             Self::Variable(name) => match context.value_of(name) {
                 Some(value) => context.add_token_type_or_undefined(
                     value.clone(),
@@ -266,15 +298,19 @@ impl TypeCheckable for PreExp {
                 exp.populate_token_type_map(context, fn_context);
             }
             Self::ArrayAccess(array_access) => {
+                let kind = match context.value_of(&array_access.name) {
+                    Some(value) => value.clone(),
+                    None if context.is_indexed_domain_variable(&array_access.name) => {
+                        PrimitiveKind::Number
+                    }
+                    None => PrimitiveKind::Undefined,
+                };
                 context.add_token_type_or_undefined(
-                    context
-                        .value_of(&array_access.name)
-                        .unwrap_or(&PrimitiveKind::Undefined)
-                        .clone(),
+                    kind,
                     array_access.span().clone(),
                     Some(array_access.name.to_string()),
                 );
-                for access in &array_access.accesses {
+                for access in array_access.indexes() {
                     access.populate_token_type_map(context, fn_context);
                 }
             }
@@ -301,6 +337,7 @@ impl WithType for PreExp {
     ) -> PrimitiveKind {
         match self {
             Self::Primitive(p) => p.value().get_type(),
+            Self::Graph(_) => PrimitiveKind::Graph,
             Self::FunctionCall(_, fun) => {
                 let f = fn_context.function(&fun.name);
                 if f.is_none() {
@@ -320,6 +357,7 @@ impl WithType for PreExp {
                     }
                 }
             }
+            Self::BinaryOperation(op, _, _) if op.is_comparison() => PrimitiveKind::Boolean,
             Self::BinaryOperation(_, lhs, _) => lhs.get_type(context, fn_context),
             Self::UnaryOperation(_, exp) => exp.get_type(context, fn_context),
             Self::Abs(_, exp) => exp.get_type(context, fn_context),
@@ -337,6 +375,79 @@ impl PreExp {
     pub fn to_boxed(self) -> Box<PreExp> {
         Box::new(self)
     }
+    /// Returns the nesting depth of the expression, a leaf has depth 1.
+    ///
+    /// This walks the tree with an explicit stack so that it can be used on
+    /// expressions which would be too deep to be visited recursively.
+    pub fn depth(&self) -> usize {
+        let mut max_depth = 0;
+        let mut stack = vec![(self, 1)];
+        while let Some((exp, depth)) = stack.pop() {
+            max_depth = max_depth.max(depth);
+            let depth = depth + 1;
+            match exp {
+                Self::Primitive(_) | Self::Variable(_) => {}
+                Self::Abs(_, exp) | Self::UnaryOperation(_, exp) => stack.push((exp, depth)),
+                Self::BinaryOperation(_, lhs, rhs) => {
+                    stack.push((lhs, depth));
+                    stack.push((rhs, depth));
+                }
+                Self::BlockFunction(f) => stack.extend(f.exps.iter().map(|e| (e, depth))),
+                Self::BlockScopedFunction(f) => {
+                    stack.push((&f.exp, depth));
+                    stack.extend(f.iters.iter().map(|i| (i.iterator.value(), depth)));
+                }
+                Self::FunctionCall(_, f) => stack.extend(f.args.iter().map(|e| (e, depth))),
+                Self::ArrayAccess(a) => stack.extend(a.indexes().map(|e| (e, depth))),
+                Self::CompoundVariable(c) => stack.extend(c.indexes.iter().map(|e| (e, depth))),
+                Self::Graph(g) => stack.extend(g.weights().map(|e| (e, depth))),
+            }
+        }
+        max_depth
+    }
+    /// Collects the spans of every reference to the variable or constant `name` in the expression,
+    /// including the iteration variables declared by the scoped functions inside it.
+    ///
+    /// For compound variables and array accesses, like `x_i` or `c[i]`, only the span of the name is collected.
+    pub fn find_occurrences(&self, name: &str, spans: &mut Vec<InputSpan>) {
+        let mut stack = vec![self];
+        while let Some(exp) = stack.pop() {
+            match exp {
+                Self::Primitive(_) => {}
+                Self::Variable(v) => {
+                    if v.value == name {
+                        spans.push(v.span().clone());
+                    }
+                }
+                Self::Abs(_, exp) | Self::UnaryOperation(_, exp) => stack.push(exp),
+                Self::BinaryOperation(_, lhs, rhs) => {
+                    stack.push(lhs);
+                    stack.push(rhs);
+                }
+                Self::BlockFunction(f) => stack.extend(f.exps.iter()),
+                Self::BlockScopedFunction(f) => {
+                    for iter in &f.iters {
+                        iter.find_occurrences(name, spans);
+                    }
+                    stack.push(&f.exp);
+                }
+                Self::FunctionCall(_, f) => stack.extend(f.args.iter()),
+                Self::ArrayAccess(a) => {
+                    if a.name == name {
+                        spans.push(a.span().prefix(name.len() as u32));
+                    }
+                    stack.extend(a.indexes());
+                }
+                Self::CompoundVariable(c) => {
+                    if c.name == name {
+                        spans.push(c.span().prefix(name.len() as u32));
+                    }
+                    stack.extend(c.indexes.iter());
+                }
+                Self::Graph(g) => stack.extend(g.weights()),
+            }
+        }
+    }
     pub fn span(&self) -> &InputSpan {
         match self {
             Self::Primitive(n) => n.span(),
@@ -349,6 +460,7 @@ impl PreExp {
             Self::ArrayAccess(array_access) => array_access.span(),
             Self::BlockScopedFunction(function) => function.span(),
             Self::FunctionCall(span, _) => span,
+            Self::Graph(g) => g.span(),
         }
     }
     pub fn into_exp(
@@ -357,6 +469,17 @@ impl PreExp {
         fn_context: &FunctionContext,
     ) -> Result<Exp, TransformError> {
         match self {
+            //logical and comparison operators are only evaluated on constants and never end up in the model
+            Self::BinaryOperation(op, _, _) if op.is_logical() || op.is_comparison() => self
+                .as_primitive(context, fn_context)
+                .and_then(|v| v.as_number_cast())
+                .map(Exp::Number)
+                .map_err(|e| e.add_span(self.span())),
+            Self::UnaryOperation(op, _) if **op == UnOp::Not => self
+                .as_primitive(context, fn_context)
+                .and_then(|v| v.as_number_cast())
+                .map(Exp::Number)
+                .map_err(|e| e.add_span(self.span())),
             Self::BinaryOperation(op, lhs, rhs) => {
                 let lhs = lhs
                     .into_exp(context, fn_context)
@@ -364,12 +487,23 @@ impl PreExp {
                 let rhs = rhs
                     .into_exp(context, fn_context)
                     .map_err(|e| e.add_span(self.span()))?;
+                //a constant divisor is known here, a division by a variable is left to the linearizer
+                if **op == BinOp::Div && matches!(rhs.simplify(), Exp::Number(n) if n == 0.0) {
+                    return Err(
+                        TransformError::Other("division by zero".to_string()).add_span(self.span())
+                    );
+                }
                 Ok(Exp::BinOp(**op, lhs.to_box(), rhs.to_box()))
             }
             Self::Primitive(n) => match n.as_number_cast() {
                 Ok(n) => Ok(Exp::Number(n)),
                 Err(e) => Err(e.add_span(self.span())),
             },
+            Self::Graph(_) => self
+                .as_primitive(context, fn_context)
+                .and_then(|v| v.as_number_cast())
+                .map(Exp::Number)
+                .map_err(|e| e.add_span(self.span())),
             Self::Abs(span, exp) => {
                 let inner = exp
                     .into_exp(context, fn_context)
@@ -398,6 +532,26 @@ impl PreExp {
                             Exp::Number(len as f64).to_box(),
                         ))
                     }
+                    BlockFunctionKind::Norm1 => {
+                        let mut sum = parsed_exp
+                            .pop()
+                            .map(|e| Exp::Abs(e.to_box()))
+                            .unwrap_or(Exp::Number(0.0));
+                        for exp in parsed_exp.into_iter().rev() {
+                            sum = Exp::BinOp(
+                                BinOp::Add,
+                                Exp::Abs(exp.to_box()).to_box(),
+                                sum.to_box(),
+                            );
+                        }
+                        Ok(sum)
+                    }
+                    BlockFunctionKind::NormInf => Ok(Exp::Max(
+                        parsed_exp
+                            .into_iter()
+                            .map(|e| Exp::Abs(e.to_box()))
+                            .collect(),
+                    )),
                 }
             }
 
@@ -438,6 +592,22 @@ impl PreExp {
                 Ok(Exp::Variable(name))
             }
             Self::ArrayAccess(array_access) => {
+                if let Some(c) = array_access.as_compound_variable() {
+                    if context.value(&array_access.name).is_none()
+                        && context.is_indexed_domain_variable(&array_access.name)
+                    {
+                        //indexed domain variables like x[i] are flattened like compound variables
+                        return Self::CompoundVariable(Spanned::new(c, self.span().clone()))
+                            .into_exp(context, fn_context);
+                    }
+                }
+                if context.variable_domain(&array_access.name).is_some() {
+                    return Err(TransformError::Other(format!(
+                        "Variable \"{}\" is a single domain variable, only families of variables declared like {}[i] can be accessed by index",
+                        array_access.name, array_access.name
+                    ))
+                    .add_span(self.span()));
+                }
                 let value = context
                     .addressable_value(array_access, fn_context)
                     .map_err(|e| e.add_span(self.span()))?;
@@ -447,52 +617,21 @@ impl PreExp {
                 }
             }
             Self::BlockScopedFunction(f) => {
-                let mut results = Vec::new();
-                recursive_set_resolver(
-                    &f.iters,
-                    context,
-                    fn_context,
-                    &mut results,
-                    0,
-                    &|context| {
-                        let inner = f
-                            .exp
-                            .into_exp(context, fn_context)
-                            .map_err(|e| e.add_span(self.span()))?;
-                        Ok(inner)
-                    },
-                )
-                .map_err(|e| e.add_span(self.span()))?;
-                match f.kind {
-                    BlockScopedFunctionKind::Sum => {
-                        let mut sum = results.pop().unwrap_or(Exp::Number(0.0));
-                        for result in results.into_iter().rev() {
-                            sum = Exp::BinOp(BinOp::Add, result.to_box(), sum.to_box());
-                        }
-                        Ok(sum)
-                    }
-                    BlockScopedFunctionKind::Prod => {
-                        let mut prod = results.pop().unwrap_or(Exp::Number(1.0));
-                        for result in results.into_iter().rev() {
-                            prod = Exp::BinOp(BinOp::Mul, result.to_box(), prod.to_box());
-                        }
-                        Ok(prod)
-                    }
-                    BlockScopedFunctionKind::Min => Ok(Exp::Min(results)),
-                    BlockScopedFunctionKind::Max => Ok(Exp::Max(results)),
-                    BlockScopedFunctionKind::Avg => {
-                        let len = results.len();
-                        let mut sum = results.pop().unwrap_or(Exp::Number(0.0));
-                        for result in results.into_iter().rev() {
-                            sum = Exp::BinOp(BinOp::Add, result.to_box(), sum.to_box());
-                        }
-                        Ok(Exp::BinOp(
-                            BinOp::Div,
-                            sum.to_box(),
-                            Exp::Number(len as f64).to_box(),
-                        ))
+                //constant blocks evaluate to the same number every time they are transformed,
+                //like in every iteration of a constraint or when repeated in another constraint,
+                //so the number is computed once
+                let key = self.constant_key(context, fn_context);
+                if let Some(value) = key.as_ref().and_then(|k| context.cached_constant(k)) {
+                    return Ok(Exp::Number(value));
+                }
+                let results = self.block_scoped_results(f, context, fn_context)?;
+                if let Some(key) = key {
+                    if let Some(value) = fold_block_results(&f.kind, &results) {
+                        context.cache_constant(key, value);
+                        return Ok(Exp::Number(value));
                     }
                 }
+                Ok(block_results_into_exp(&f.kind, results))
             }
             Self::FunctionCall(span, function) => {
                 let f = fn_context.function(&function.name);
@@ -513,6 +652,140 @@ impl PreExp {
         }
     }
 
+    fn block_scoped_results(
+        &self,
+        f: &BlockScopedFunction,
+        context: &mut TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Vec<Exp>, TransformError> {
+        let mut results = Vec::new();
+        recursive_set_resolver(&f.iters, context, fn_context, &mut results, 0, &|context| {
+            let inner = f
+                .exp
+                .into_exp(context, fn_context)
+                .map_err(|e| e.add_span(self.span()))?;
+            Ok(inner)
+        })
+        .map_err(|e| e.add_span(self.span()))?;
+        Ok(results)
+    }
+
+    /// Checks if the expression only depends on the constants of the model, and
+    /// so always evaluates to the same value.
+    ///
+    /// Expressions that use domain variables, variables declared in an outer
+    /// scope like the iteration of a constraint, or functions that are not builtin,
+    /// which might not return the same value every time, are not constant.
+    pub fn is_constant(&self, context: &TransformerContext, fn_context: &FunctionContext) -> bool {
+        self.is_constant_with_bound(context, fn_context, &mut Vec::new(), &mut Vec::new())
+    }
+
+    /// Returns the key under which the value of a constant expression is cached, or `None`
+    /// if the expression is not constant.
+    ///
+    /// The key is made of the expression as written, without its position in the source, and
+    /// of the values of the constants it uses, so that the same expression repeated in
+    /// different places shares the same key.
+    fn constant_key(
+        &self,
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Option<String> {
+        let mut constants = Vec::new();
+        if !self.is_constant_with_bound(context, fn_context, &mut Vec::new(), &mut constants) {
+            return None;
+        }
+        let mut key = self.to_string();
+        for name in constants {
+            if let Some(value) = context.value(&name) {
+                key.push_str(&format!("; {} = {}", name, value));
+            }
+        }
+        Some(key)
+    }
+
+    //collects in `constants` the names of the constants of the model used by the expression
+    fn is_constant_with_bound(
+        &self,
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+        bound: &mut Vec<String>,
+        constants: &mut Vec<String>,
+    ) -> bool {
+        fn is_known(
+            name: &str,
+            context: &TransformerContext,
+            bound: &[String],
+            constants: &mut Vec<String>,
+        ) -> bool {
+            if bound.iter().any(|b| b == name) {
+                return true;
+            }
+            if !context.is_constant(name) {
+                return false;
+            }
+            if !constants.iter().any(|c| c == name) {
+                constants.push(name.to_string());
+            }
+            true
+        }
+        match self {
+            Self::Primitive(_) => true,
+            Self::Graph(g) => g
+                .weights()
+                .all(|e| e.is_constant_with_bound(context, fn_context, bound, constants)),
+            Self::Variable(name) => is_known(name, context, bound, constants),
+            //compound variables are flattened to domain variables
+            Self::CompoundVariable(_) => false,
+            Self::ArrayAccess(a) => {
+                is_known(&a.name, context, bound, constants)
+                    && a.indexes()
+                        .all(|e| e.is_constant_with_bound(context, fn_context, bound, constants))
+            }
+            Self::Abs(_, exp) | Self::UnaryOperation(_, exp) => {
+                exp.is_constant_with_bound(context, fn_context, bound, constants)
+            }
+            Self::BinaryOperation(_, lhs, rhs) => {
+                lhs.is_constant_with_bound(context, fn_context, bound, constants)
+                    && rhs.is_constant_with_bound(context, fn_context, bound, constants)
+            }
+            Self::BlockFunction(f) => f
+                .exps
+                .iter()
+                .all(|e| e.is_constant_with_bound(context, fn_context, bound, constants)),
+            Self::FunctionCall(_, f) => {
+                fn_context.is_builtin(&f.name)
+                    && f.args
+                        .iter()
+                        .all(|e| e.is_constant_with_bound(context, fn_context, bound, constants))
+            }
+            Self::BlockScopedFunction(f) => {
+                let len = bound.len();
+                let mut is_constant = true;
+                for iter in f.iters.iter() {
+                    if !iter
+                        .iterator
+                        .is_constant_with_bound(context, fn_context, bound, constants)
+                    {
+                        is_constant = false;
+                        break;
+                    }
+                    match &iter.var {
+                        VariableKind::Single(name) => bound.push(name.value().clone()),
+                        VariableKind::Tuple(names) => {
+                            bound.extend(names.iter().map(|n| n.value().clone()))
+                        }
+                    }
+                }
+                is_constant = is_constant
+                    && f.exp
+                        .is_constant_with_bound(context, fn_context, bound, constants);
+                bound.truncate(len);
+                is_constant
+            }
+        }
+    }
+
     pub fn as_static_primitive(&self) -> Option<Primitive> {
         match self {
             Self::Primitive(p) => Some(p.value().clone()),
@@ -526,6 +799,7 @@ impl PreExp {
     ) -> Result<Primitive, TransformError> {
         match self {
             PreExp::Primitive(p) => Ok(p.value().clone()),
+            PreExp::Graph(g) => Ok(Primitive::Graph(g.as_graph(context, fn_context)?)),
             PreExp::Variable(s) => match context.value(s) {
                 Some(value) => Ok(value.clone()),
                 None => match context.variable_domain(s) {
@@ -566,6 +840,12 @@ impl PreExp {
                 Ok(value)
             }
             PreExp::ArrayAccess(a) => {
+                if let Some(c) = a.as_compound_variable() {
+                    if context.value(&a.name).is_none() && context.is_indexed_domain_variable(&a.name) {
+                        return PreExp::CompoundVariable(Spanned::new(c, a.span().clone()))
+                            .as_primitive(context, fn_context);
+                    }
+                }
                 let value = context.addressable_value(a, fn_context)?;
                 Ok(value)
             }
@@ -583,6 +863,11 @@ impl PreExp {
             PreExp::BinaryOperation(op, lhs, rhs) => {
                 let lhs = lhs.as_primitive(context, fn_context)?;
                 let rhs = rhs.as_primitive(context, fn_context)?;
+                let is_zero = rhs.get_type().is_numeric() && rhs.as_number_cast().is_ok_and(|n| n == 0.0);
+                if **op == BinOp::Div && is_zero {
+                    return Err(TransformError::Other("division by zero".to_string())
+                        .add_span(self.span()));
+                }
                 match lhs.apply_binary_op(**op, &rhs) {
                     Ok(value) => Ok(value),
                     Err(_) => Err(TransformError::from_wrong_binop(
@@ -749,7 +1034,7 @@ impl PreExp {
             _ => self.to_string(),
         }
     }
-    fn to_latex_with_precedence(&self, previous_precedence: u8) -> String {
+    fn to_latex_with_precedence(&self, previous_precedence: u8, options: &LatexOptions) -> String {
         match self {
             Self::BinaryOperation(op, lhs, rhs) => {
                 //TODO add implied multiplication like 2x 2(x + y) etc...
@@ -759,47 +1044,136 @@ impl PreExp {
                        (number | parenthesis | modulo) ~ variable
                    }
                 */
-                let lhs_str = lhs.to_latex_with_precedence(op.precedence());
-                let rhs_str = rhs.to_latex_with_precedence(op.precedence());
+                let lhs_str = lhs.to_latex_with_precedence(op.precedence(), options);
+                let rhs_str = rhs.to_latex_with_precedence(op.precedence(), options);
 
                 if op.precedence() < previous_precedence {
-                    format!("({} {} {})", lhs_str, op.to_latex(), rhs_str)
+                    format!("({} {} {})", lhs_str, op.to_latex_with(options), rhs_str)
                 } else {
-                    format!("{} {} {}", lhs_str, op.to_latex(), rhs_str)
+                    format!("{} {} {}", lhs_str, op.to_latex_with(options), rhs_str)
                 }
             }
-            _ => self.to_latex(),
+            _ => self.to_latex_with(options),
         }
     }
 }
 
 impl ToLatex for PreExp {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         match self {
-            Self::ArrayAccess(a) => a.to_latex(),
-            Self::BlockFunction(f) => f.to_latex(),
-            Self::BlockScopedFunction(f) => f.to_latex(),
+            Self::ArrayAccess(a) => a.to_latex_with(options),
+            Self::BlockFunction(f) => f.to_latex_with(options),
+            Self::BlockScopedFunction(f) => f.to_latex_with(options),
             Self::BinaryOperation(op, lhs, rhs) => {
-                let rhs = rhs.to_latex_with_precedence(op.precedence());
-                let lhs = lhs.to_latex_with_precedence(op.precedence());
+                let rhs = rhs.to_latex_with_precedence(op.precedence(), options);
+                let lhs = lhs.to_latex_with_precedence(op.precedence(), options);
                 match op.value() {
                     BinOp::Div => format!("\\frac{{{}}}{{{}}}", lhs, rhs),
-                    _ => format!("{} {} {}", lhs, op.to_latex(), rhs),
+                    _ => format!("{} {} {}", lhs, op.to_latex_with(options), rhs),
                 }
             }
             Self::UnaryOperation(op, exp) => {
                 if self.is_leaf() {
-                    format!("{}{}", op.to_latex(), exp.to_latex())
+                    format!(
+                        "{}{}",
+                        op.to_latex_with(options),
+                        exp.to_latex_with(options)
+                    )
                 } else {
-                    format!("{}({})", op.to_latex(), exp.to_latex())
+                    format!(
+                        "{}({})",
+                        op.to_latex_with(options),
+                        exp.to_latex_with(options)
+                    )
                 }
             }
-            Self::Variable(name) => escape_latex(name.value()),
-            Self::Primitive(p) => p.to_latex(),
-            Self::Abs(_, exp) => format!("|{}|", exp.to_latex()),
-            Self::CompoundVariable(c) => c.to_latex(),
-            Self::FunctionCall(_, f) => f.to_latex(),
+            Self::Variable(name) => variable_name_to_latex(name.value()),
+            Self::Primitive(p) => p.to_latex_with(options),
+            Self::Abs(_, exp) => format!("|{}|", exp.to_latex_with(options)),
+            Self::CompoundVariable(c) => c.to_latex_with(options),
+            Self::FunctionCall(_, f) => f.to_latex_with(options),
+            Self::Graph(g) => g.to_latex_with(options),
+        }
+    }
+}
+
+/// Combines the expressions each iteration of a block function evaluated to.
+fn block_results_into_exp(kind: &BlockScopedFunctionKind, mut results: Vec<Exp>) -> Exp {
+    match kind {
+        BlockScopedFunctionKind::Sum => {
+            let mut sum = results.pop().unwrap_or(Exp::Number(0.0));
+            for result in results.into_iter().rev() {
+                sum = Exp::BinOp(BinOp::Add, result.to_box(), sum.to_box());
+            }
+            sum
+        }
+        BlockScopedFunctionKind::Prod => {
+            let mut prod = results.pop().unwrap_or(Exp::Number(1.0));
+            for result in results.into_iter().rev() {
+                prod = Exp::BinOp(BinOp::Mul, result.to_box(), prod.to_box());
+            }
+            prod
+        }
+        BlockScopedFunctionKind::Min => Exp::Min(results),
+        BlockScopedFunctionKind::Max => Exp::Max(results),
+        BlockScopedFunctionKind::Avg => {
+            let len = results.len();
+            let mut sum = results.pop().unwrap_or(Exp::Number(0.0));
+            for result in results.into_iter().rev() {
+                sum = Exp::BinOp(BinOp::Add, result.to_box(), sum.to_box());
+            }
+            Exp::BinOp(BinOp::Div, sum.to_box(), Exp::Number(len as f64).to_box())
+        }
+        BlockScopedFunctionKind::Norm1 => {
+            let mut sum = results
+                .pop()
+                .map(|e| Exp::Abs(e.to_box()))
+                .unwrap_or(Exp::Number(0.0));
+            for result in results.into_iter().rev() {
+                sum = Exp::BinOp(BinOp::Add, Exp::Abs(result.to_box()).to_box(), sum.to_box());
+            }
+            sum
+        }
+        BlockScopedFunctionKind::NormInf => {
+            Exp::Max(results.into_iter().map(|e| Exp::Abs(e.to_box())).collect())
+        }
+    }
+}
+
+/// Computes the number a block function evaluates to, if every iteration evaluated to a number,
+/// the sums and products are computed in the same order as in [`block_results_into_exp`].
+fn fold_block_results(kind: &BlockScopedFunctionKind, results: &[Exp]) -> Option<f64> {
+    let values = results
+        .iter()
+        .map(|result| match result.simplify() {
+            Exp::Number(value) => Some(value),
+            _ => None,
+        })
+        .collect::<Option<Vec<f64>>>()?;
+    let sum = |values: &mut dyn DoubleEndedIterator<Item = f64>| {
+        values.rev().fold(0.0, |sum, value| value + sum)
+    };
+    match kind {
+        BlockScopedFunctionKind::Sum => Some(sum(&mut values.iter().cloned())),
+        BlockScopedFunctionKind::Prod => {
+            Some(values.iter().rev().fold(1.0, |prod, value| value * prod))
+        }
+        //an empty min, max or average is left for the linearizer to report
+        _ if values.is_empty() => None,
+        BlockScopedFunctionKind::Min => Some(values.iter().cloned().fold(f64::INFINITY, f64::min)),
+        BlockScopedFunctionKind::Max => {
+            Some(values.iter().cloned().fold(f64::NEG_INFINITY, f64::max))
+        }
+        BlockScopedFunctionKind::Avg => {
+            Some(sum(&mut values.iter().cloned()) / values.len() as f64)
         }
+        BlockScopedFunctionKind::Norm1 => Some(sum(&mut values.iter().map(|v| v.abs()))),
+        BlockScopedFunctionKind::NormInf => Some(
+            values
+                .iter()
+                .map(|v| v.abs())
+                .fold(f64::NEG_INFINITY, f64::max),
+        ),
     }
 }
 
@@ -818,6 +1192,7 @@ impl fmt::Display for PreExp {
             Self::FunctionCall(_, f) => f.to_string(),
             Self::Abs(_, exp) => format!("|{}|", **exp),
             Self::Primitive(p) => p.to_string(),
+            Self::Graph(g) => g.to_string(),
             Self::UnaryOperation(op, exp) => {
                 if self.is_leaf() {
                     format!("{}{}", **op, **exp)
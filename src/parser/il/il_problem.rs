@@ -7,7 +7,7 @@ use crate::parser::il::il_exp::PreExp;
 use crate::parser::il::iterable_set::IterableSet;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
-use crate::traits::ToLatex;
+use crate::traits::{variable_name_to_latex, LatexOptions, ToLatex};
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::{
     math::{Comparison, OptimizationType},
@@ -16,13 +16,55 @@ use crate::{
     utils::InputSpan,
 };
 
-/// Represents array-like access to a variable, such as `x[1][2]`.
+/// Represents a single access of an [`AddressableAccess`].
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "type", content = "value")]
+pub enum PointerAccess {
+    /// Access to an element of an array by its index, such as `[i]`
+    Index(PreExp),
+    /// Access to a field of a tuple by its position, such as `.0`
+    Field(usize),
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IPointerAccess: &'static str = r#"
+export type SerializedPointerAccess = {
+    type: "Index",
+    value: SerializedPreExp
+} | {
+    type: "Field",
+    value: number
+}
+"#;
+
+impl ToLatex for PointerAccess {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        match self {
+            PointerAccess::Index(exp) => format!("[{}]", exp.to_latex_with(options)),
+            PointerAccess::Field(field) => format!(".{}", field),
+        }
+    }
+}
+
+impl fmt::Display for PointerAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointerAccess::Index(exp) => write!(f, "[{}]", exp),
+            PointerAccess::Field(field) => write!(f, ".{}", field),
+        }
+    }
+}
+
+/// Represents array-like access to a variable, such as `x[1][2]`, or access
+/// to the fields of a tuple, such as `t.0`.
 #[derive(Debug, Serialize, Clone)]
 pub struct AddressableAccess {
     /// Name of the variable being accessed
     pub name: String,
-    /// Vector of expressions used as array indices
-    pub accesses: Vec<PreExp>,
+    /// Vector of array indices and tuple fields, in the order they are accessed
+    pub accesses: Vec<PointerAccess>,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -31,27 +73,51 @@ pub struct AddressableAccess {
 const IAddressableAccess: &'static str = r#"
 export type SerializedAddressableAccess = {
     name: string,
-    accesses: SerializedPreExp[],
+    accesses: SerializedPointerAccess[],
 }
 "#;
 
 impl AddressableAccess {
-    /// Creates a new AddressableAccess with the given name and access expressions.
+    /// Creates a new AddressableAccess with the given name and accesses.
     ///
     /// # Arguments
     /// * `name` - Name of the variable being accessed
-    /// * `accesses` - Vector of expressions used as array indices
-    pub fn new(name: String, accesses: Vec<PreExp>) -> Self {
+    /// * `accesses` - Vector of array indices and tuple fields
+    pub fn new(name: String, accesses: Vec<PointerAccess>) -> Self {
         Self { name, accesses }
     }
+
+    /// Returns the expressions used as array indices.
+    pub fn indexes(&self) -> impl Iterator<Item = &PreExp> {
+        self.accesses.iter().filter_map(|a| match a {
+            PointerAccess::Index(exp) => Some(exp),
+            PointerAccess::Field(_) => None,
+        })
+    }
+
+    /// Converts the access to the compound variable it refers to when the name is not a constant,
+    /// so that `x[i][j]` refers to the domain variable `x_i_j`.
+    ///
+    /// # Returns
+    /// The compound variable if every access is an array index, None if it accesses a tuple field
+    pub fn as_compound_variable(&self) -> Option<CompoundVariable> {
+        self.accesses
+            .iter()
+            .map(|a| match a {
+                PointerAccess::Index(exp) => Some(exp.clone()),
+                PointerAccess::Field(_) => None,
+            })
+            .collect::<Option<Vec<PreExp>>>()
+            .map(|indexes| CompoundVariable::new(self.name.clone(), indexes))
+    }
 }
 
 impl ToLatex for AddressableAccess {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         let rest = self
             .accesses
             .iter()
-            .map(|a| format!("[{}]", a.to_latex()))
+            .map(|a| a.to_latex_with(options))
             .collect::<Vec<String>>()
             .join("");
         format!("{}{}", self.name, rest)
@@ -63,7 +129,7 @@ impl fmt::Display for AddressableAccess {
         let rest = self
             .accesses
             .iter()
-            .map(|a| format!("[{}]", a))
+            .map(|a| a.to_string())
             .collect::<Vec<String>>()
             .join("");
         write!(f, "{}{}", self.name, rest)
@@ -120,26 +186,33 @@ impl CompoundVariable {
 }
 
 impl ToLatex for CompoundVariable {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         let indexes = self
             .indexes
             .iter()
             .map(|i| match i {
                 PreExp::Primitive(p) => {
                     if p.get_type().is_numeric() {
-                        i.to_latex().to_string()
+                        i.to_latex_with(options)
                     } else {
-                        format!("({})", i.to_latex())
+                        format!("({})", i.to_latex_with(options))
                     }
                 }
                 PreExp::Variable(name) => {
-                    let name = name.value().clone();
-                    name.to_string()
+                    let name = variable_name_to_latex(name.value());
+                    //braced so that a Greek letter is not merged with the next index
+                    if name.starts_with('\\') {
+                        format!("{{{}}}", name)
+                    } else {
+                        name
+                    }
                 }
-                _ => format!("({})", i.to_latex()),
+                _ => format!("({})", i.to_latex_with(options)),
             })
             .collect::<Vec<String>>();
-        format!("{}_{{{}}}", self.name, indexes.join(""))
+        options
+            .naming_strategy
+            .format_latex(&variable_name_to_latex(&self.name), &indexes)
     }
 }
 
@@ -174,9 +247,9 @@ pub struct PreObjective {
 }
 
 impl ToLatex for PreObjective {
-    fn to_latex(&self) -> String {
-        let rhs = self.rhs.to_latex();
-        let opt_name = self.objective_type.to_latex();
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        let rhs = self.rhs.to_latex_with(options);
+        let opt_name = self.objective_type.to_latex_with(options);
         format!("{} \\ {}", opt_name, rhs)
     }
 }
@@ -347,14 +420,14 @@ impl TypeCheckable for PreConstraint {
 }
 
 impl ToLatex for PreConstraint {
-    fn to_latex(&self) -> String {
-        let lhs = self.lhs.to_latex();
-        let rhs = self.rhs.to_latex();
-        let constraint = self.constraint_type.to_latex();
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        let lhs = self.lhs.to_latex_with(options);
+        let rhs = self.rhs.to_latex_with(options);
+        let constraint = self.constraint_type.to_latex_with(options);
         let iterations = self
             .iteration
             .iter()
-            .map(|i| format!("\\forall{{{}}}", i.to_latex()))
+            .map(|i| format!("\\forall{{{}}}", i.to_latex_with(options)))
             .collect::<Vec<String>>();
         if iterations.is_empty() {
             format!("{} \\ &{} \\ {}", lhs, constraint, rhs)
@@ -8,7 +8,7 @@ use crate::parser::il::il_exp::PreExp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::VariableKind;
 use crate::primitives::PrimitiveKind;
-use crate::traits::ToLatex;
+use crate::traits::{LatexOptions, ToLatex};
 use crate::type_checker::type_checker_context::{
     FunctionContext, TypeCheckable, TypeCheckerContext, WithType,
 };
@@ -40,9 +40,9 @@ export type SerializedIterableSet = {
 "#;
 
 impl ToLatex for IterableSet {
-    fn to_latex(&self) -> String {
-        let var = self.var.to_latex();
-        let iterator = self.iterator.to_latex();
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        let var = self.var.to_latex_with(options);
+        let iterator = self.iterator.to_latex_with(options);
         format!("{} \\in {}", var, iterator)
     }
 }
@@ -62,8 +62,25 @@ impl IterableSet {
         }
     }
 
+    /// Collects the spans of every reference to `name` in the iterator and of the
+    /// variables named `name` bound by this set.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the variable or constant to look for
+    /// * `spans` - Where the spans are collected
+    pub fn find_occurrences(&self, name: &str, spans: &mut Vec<InputSpan>) {
+        for var in self.var.variables() {
+            if var.value() == name {
+                spans.push(var.span().clone());
+            }
+        }
+        self.iterator.find_occurrences(name, spans);
+    }
+
     /// Populates type information for variables in the type checker context.
     ///
+    /// Variables whose type can't be inferred are bound as `Undefined`.
+    ///
     /// # Arguments
     /// * `context` - The type checker context to populate
     /// * `fn_context` - Function context for type checking
@@ -73,43 +90,30 @@ impl IterableSet {
         fn_context: &FunctionContext,
     ) {
         self.iterator.populate_token_type_map(context, fn_context);
-        let iter_type = self.iterator.get_type(context, fn_context);
-        let iter_type = match iter_type {
-            PrimitiveKind::Iterable(kind) => *kind,
-            _ => PrimitiveKind::Undefined, //should this be undefined or any?
-        };
-        match &self.var {
-            VariableKind::Single(name) => context.add_token_type_or_undefined(
-                iter_type,
-                self.span.clone(),
+        let types = self
+            .variable_types(context, fn_context)
+            .unwrap_or_else(|_| {
+                self.var
+                    .variables()
+                    .into_iter()
+                    .map(|v| (v, PrimitiveKind::Undefined))
+                    .collect()
+            });
+        for (name, kind) in types {
+            context.add_token_type_or_undefined(
+                kind,
+                name.span().clone(),
                 Some(name.value().clone()),
-            ),
-            VariableKind::Tuple(vars) => match &iter_type {
-                PrimitiveKind::Iterable(kind) => {
-                    for v in vars {
-                        context.add_token_type_or_undefined(
-                            *kind.clone(),
-                            v.span().clone(),
-                            Some(v.value().clone()),
-                        )
-                    }
-                }
-                _ => {
-                    let types = iter_type.can_spread_into().unwrap_or(Vec::new());
-                    for (i, v) in vars.iter().enumerate() {
-                        context.add_token_type_or_undefined(
-                            types.get(i).unwrap_or(&PrimitiveKind::Undefined).clone(),
-                            v.span().clone(),
-                            Some(v.value().clone()),
-                        )
-                    }
-                }
-            },
+            )
         }
     }
 
     /// Gets the types of variables bound by this iterable set.
     ///
+    /// A single variable takes the type of the elements of the iterator, a tuple of variables
+    /// takes the types the elements spread into, or the inner type if the elements are iterables
+    /// themselves. Elements of unknown type bind every variable as `Any`, leaving the check to runtime.
+    ///
     /// # Arguments
     /// * `context` - Type checker context
     /// * `fn_context` - Function context for type checking
@@ -125,7 +129,8 @@ impl IterableSet {
         let iter_type = self.iterator.get_type(context, fn_context);
 
         let iter_type = match iter_type {
-            PrimitiveKind::Iterable(kind) => *kind,
+            PrimitiveKind::Iterable(kind) | PrimitiveKind::Set(kind) => *kind,
+            PrimitiveKind::Any => PrimitiveKind::Any,
             _ => {
                 return Err(TransformError::from_wrong_type(
                     PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
@@ -138,14 +143,20 @@ impl IterableSet {
             VariableKind::Single(name) => Ok(vec![(name.clone(), iter_type)]),
             VariableKind::Tuple(vars) => {
                 match &iter_type {
-                    PrimitiveKind::Iterable(kind) => {
+                    PrimitiveKind::Iterable(kind) | PrimitiveKind::Set(kind) => {
                         Ok(vars
                             .iter()
                             .map(|v| (v.clone(), *kind.clone()))
                             .collect::<Vec<_>>()) //we don't know at compile time how many variables there are, so we assume all of them exist
                     }
+                    PrimitiveKind::Any => Ok(vars
+                        .iter()
+                        .map(|v| (v.clone(), PrimitiveKind::Any))
+                        .collect::<Vec<_>>()),
                     _ => {
-                        let spreads_into = iter_type.can_spread_into()?;
+                        let spreads_into = iter_type
+                            .can_spread_into()
+                            .map_err(|e| e.add_span(&self.span))?;
                         if vars.len() > spreads_into.len() {
                             let err = TransformError::SpreadError {
                                 to_spread: iter_type,
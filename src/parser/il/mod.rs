@@ -1,9 +1,11 @@
 pub mod block_functions;
+pub mod graph_literal;
 pub mod il_exp;
 pub mod il_problem;
 pub mod iterable_set;
 
 pub use block_functions::*;
+pub use graph_literal::*;
 pub use il_exp::*;
 pub use il_problem::*;
 pub use iterable_set::*;
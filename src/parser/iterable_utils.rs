@@ -113,6 +113,8 @@ pub fn flatten_primitive_array_values(values: Vec<Primitive>) -> IterableKind {
             IterableKind::Iterables(values)
         }
         PrimitiveKind::Undefined => IterableKind::Numbers(vec![]),
+        //there is no typed array of sets
+        PrimitiveKind::Set(_) => IterableKind::Anys(values),
         PrimitiveKind::Graph => {
             let values = values
                 .into_iter()
@@ -125,3 +127,34 @@ pub fn flatten_primitive_array_values(values: Vec<Primitive>) -> IterableKind {
         }
     }
 }
+
+/// Returns the shape of a (possibly nested) array literal.
+///
+/// Non iterable primitives have an empty shape, a 2x3 matrix has shape `[2, 3]`.
+/// Only the first element of each level is inspected, nested arrays are expected
+/// to have been validated already.
+///
+/// # Arguments
+/// * `value` - The primitive to get the shape of
+pub fn array_shape(value: &Primitive) -> Vec<usize> {
+    let mut shape = vec![];
+    let mut current = match value {
+        Primitive::Iterable(i) => i,
+        _ => return shape,
+    };
+    loop {
+        shape.push(current.len());
+        match current {
+            IterableKind::Iterables(v) if !v.is_empty() => current = &v[0],
+            IterableKind::Anys(v) => {
+                if let Some(Primitive::Iterable(i)) = v.first() {
+                    current = i
+                } else {
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    shape
+}
@@ -4,18 +4,23 @@ use crate::prelude::*;
 use core::fmt;
 use indexmap::IndexMap;
 use serde::Serialize;
+use std::collections::BTreeSet;
 
-use crate::math::{BinOp, UnOp};
+use crate::math::{float_eq, float_ne, BinOp, UnOp};
 use crate::math::{Comparison, OptimizationType};
 use crate::parser::il::PreExp;
 use crate::parser::il::{PreConstraint, PreObjective};
 use crate::parser::model_transformer::transform_error::TransformError;
-use crate::parser::model_transformer::transformer_context::{DomainVariable, TransformerContext};
+use crate::parser::model_transformer::transformer_context::{
+    DomainVariable, StrictComparisonMode, TransformerConfig, TransformerContext, VariableIndexes,
+};
 use crate::parser::pre_model::PreModel;
 use crate::parser::recursive_set_resolver::recursive_set_resolver;
 use crate::primitives::Constant;
 use crate::runtime_builtin::{make_std, make_std_constants, RoocFunction};
-use crate::traits::{escape_latex, ToLatex};
+use crate::traits::{
+    variable_name_to_latex, DisplayWithOptions, FormatOptions, LatexOptions, ToLatex,
+};
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::{primitives::Primitive, utils::Spanned};
 
@@ -29,6 +34,7 @@ use crate::{primitives::Primitive, utils::Spanned};
 /// - Binary operations (add, subtract, multiply, divide)
 /// - Unary operations (negation)
 #[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "value")]
 pub enum Exp {
     /// A numeric literal value
     Number(f64),
@@ -138,6 +144,18 @@ impl Exp {
                         BinOp::Sub => Exp::Number(lhs - rhs),
                         BinOp::Mul => Exp::Number(lhs * rhs),
                         BinOp::Div => Exp::Number(lhs / rhs),
+                        BinOp::And => Exp::Number((lhs != 0.0 && rhs != 0.0) as u8 as f64),
+                        BinOp::Or => Exp::Number((lhs != 0.0 || rhs != 0.0) as u8 as f64),
+                        BinOp::Equal => Exp::Number(float_eq(lhs, rhs) as u8 as f64),
+                        BinOp::NotEqual => Exp::Number(float_ne(lhs, rhs) as u8 as f64),
+                        BinOp::Less => Exp::Number((lhs < rhs) as u8 as f64),
+                        BinOp::LessOrEqual => Exp::Number((lhs <= rhs) as u8 as f64),
+                        BinOp::Greater => Exp::Number((lhs > rhs) as u8 as f64),
+                        BinOp::GreaterOrEqual => Exp::Number((lhs >= rhs) as u8 as f64),
+                        //numbers are not sets, keep it as it is
+                        BinOp::In | BinOp::NotIn => {
+                            Exp::BinOp(*op, Exp::Number(lhs).to_box(), Exp::Number(rhs).to_box())
+                        }
                     },
                     (BinOp::Add, Exp::Number(0.0), rhs) => rhs,
                     (BinOp::Add, lhs, Exp::Number(0.0)) => lhs,
@@ -159,7 +177,7 @@ impl Exp {
                     (op, Exp::Number(lhs), Exp::BinOp(op2, inner_lhs, inner_rhs)) => {
                         let inner_lhs = inner_lhs.simplify();
                         let inner_rhs = inner_rhs.simplify();
-                        if *op != op2 {
+                        if *op != op2 || op.is_membership() {
                             return Exp::BinOp(
                                 *op,
                                 Exp::Number(lhs).to_box(),
@@ -172,6 +190,15 @@ impl Exp {
                                 BinOp::Sub => lhs - rhs,
                                 BinOp::Mul => lhs * rhs,
                                 BinOp::Div => lhs / rhs,
+                                BinOp::And => (lhs != 0.0 && rhs != 0.0) as u8 as f64,
+                                BinOp::Or => (lhs != 0.0 || rhs != 0.0) as u8 as f64,
+                                BinOp::Equal => float_eq(lhs, rhs) as u8 as f64,
+                                BinOp::NotEqual => float_ne(lhs, rhs) as u8 as f64,
+                                BinOp::Less => (lhs < rhs) as u8 as f64,
+                                BinOp::LessOrEqual => (lhs <= rhs) as u8 as f64,
+                                BinOp::Greater => (lhs > rhs) as u8 as f64,
+                                BinOp::GreaterOrEqual => (lhs >= rhs) as u8 as f64,
+                                BinOp::In | BinOp::NotIn => unreachable!(),
                             };
                             Exp::BinOp(op2, Exp::Number(val).to_box(), inner_rhs.to_box())
                         } else {
@@ -193,6 +220,10 @@ impl Exp {
                         Exp::Number(value) => Exp::Number(-value),
                         _ => Exp::UnOp(UnOp::Neg, exp.to_box()),
                     },
+                    UnOp::Not => match exp {
+                        Exp::Number(value) => Exp::Number((value == 0.0) as u8 as f64),
+                        _ => Exp::UnOp(UnOp::Not, exp.to_box()),
+                    },
                 }
             }
             Exp::Max(exps) => {
@@ -233,6 +264,10 @@ impl Exp {
                     None => Exp::Min(exps.iter().map(|exp| exp.simplify()).collect::<Vec<_>>()),
                 }
             }
+            Exp::Abs(exp) => match exp.simplify() {
+                Exp::Number(value) => Exp::Number(value.abs()),
+                exp => Exp::Abs(exp.to_box()),
+            },
             exp => exp.clone(),
         }
     }
@@ -293,6 +328,26 @@ impl Exp {
         }
     }
 
+    /// Returns the names of the variables used in the expression, in the order they appear,
+    /// a variable used more than once is returned more than once.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut variables = Vec::new();
+        let mut stack = vec![self];
+        while let Some(exp) = stack.pop() {
+            match exp {
+                Exp::Number(_) => {}
+                Exp::Variable(name) => variables.push(name.as_str()),
+                Exp::Abs(exp) | Exp::UnOp(_, exp) => stack.push(exp),
+                Exp::Min(exps) | Exp::Max(exps) => stack.extend(exps.iter().rev()),
+                Exp::BinOp(_, lhs, rhs) => {
+                    stack.push(rhs);
+                    stack.push(lhs);
+                }
+            }
+        }
+        variables
+    }
+
     /// Checks if the expression is a leaf node (number or variable).
     ///
     /// # Returns
@@ -309,18 +364,40 @@ impl Exp {
     /// # Returns
     /// String representation with appropriate parentheses based on operator precedence
     pub fn to_string_with_precedence(&self, last_operator: BinOp) -> String {
+        self.to_string_with_precedence_and_options(last_operator, &FormatOptions::default())
+    }
+
+    /// Converts the expression to a string like [`Exp::to_string_with_precedence`],
+    /// formatting its numbers with the given options.
+    fn to_string_with_precedence_and_options(
+        &self,
+        last_operator: BinOp,
+        options: &FormatOptions,
+    ) -> String {
         let last_precedence = last_operator.precedence();
         match self {
             Exp::BinOp(op, lhs, rhs) => {
-                let string_lhs = lhs.to_string_with_precedence(*op);
-                let string_rhs = rhs.to_string_with_precedence(*op);
+                let string_lhs = lhs.to_string_with_precedence_and_options(*op, options);
+                let string_rhs = rhs.to_string_with_precedence_and_options(*op, options);
                 let precedence = op.precedence();
                 if precedence < last_precedence {
                     format!("({} {} {})", string_lhs, op, string_rhs)
                 } else {
                     //TODO improve this
                     match last_operator {
-                        BinOp::Add | BinOp::Mul | BinOp::Div => {
+                        BinOp::Add
+                        | BinOp::Mul
+                        | BinOp::Div
+                        | BinOp::And
+                        | BinOp::Or
+                        | BinOp::Equal
+                        | BinOp::NotEqual
+                        | BinOp::Less
+                        | BinOp::LessOrEqual
+                        | BinOp::Greater
+                        | BinOp::GreaterOrEqual
+                        | BinOp::In
+                        | BinOp::NotIn => {
                             format!("{} {} {}", string_lhs, op, string_rhs)
                         }
                         BinOp::Sub => match rhs.is_leaf() {
@@ -330,46 +407,51 @@ impl Exp {
                     }
                 }
             }
-            _ => self.to_string(),
+            _ => self.to_string_with_options(options),
         }
     }
 }
 
 impl fmt::Display for Exp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            Exp::Number(value) => value.to_string(),
+        f.write_str(&self.to_string_with_options(&FormatOptions::default()))
+    }
+}
+
+impl DisplayWithOptions for Exp {
+    fn to_string_with_options(&self, options: &FormatOptions) -> String {
+        match self {
+            Exp::Number(value) => options.format_number(*value),
             Exp::Variable(name) => name.clone(),
-            Exp::Abs(exp) => format!("|{}|", exp),
+            Exp::Abs(exp) => format!("|{}|", exp.to_string_with_options(options)),
             Exp::Min(exps) => format!(
                 "min{{ {} }}",
                 exps.iter()
-                    .map(|exp| exp.to_string())
+                    .map(|exp| exp.to_string_with_options(options))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
             Exp::Max(exps) => format!(
                 "max{{ {} }}",
                 exps.iter()
-                    .map(|exp| exp.to_string())
+                    .map(|exp| exp.to_string_with_options(options))
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
             Exp::BinOp(operator, lhs, rhs) => {
                 //TODO: add parenthesis when needed
-                let string_lhs = lhs.to_string_with_precedence(*operator);
-                let string_rhs = rhs.to_string_with_precedence(*operator);
+                let string_lhs = lhs.to_string_with_precedence_and_options(*operator, options);
+                let string_rhs = rhs.to_string_with_precedence_and_options(*operator, options);
                 format!("{} {} {}", string_lhs, operator, string_rhs)
             }
             Exp::UnOp(op, exp) => {
                 if exp.is_leaf() {
-                    format!("{}{}", op, exp)
+                    format!("{}{}", op, exp.to_string_with_options(options))
                 } else {
-                    format!("{}({})", op, exp)
+                    format!("{}({})", op, exp.to_string_with_options(options))
                 }
             }
-        };
-        f.write_str(&s)
+        }
     }
 }
 
@@ -408,7 +490,17 @@ impl Objective {
 
 impl fmt::Display for Objective {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", self.objective_type, self.rhs)
+        f.write_str(&self.to_string_with_options(&FormatOptions::default()))
+    }
+}
+
+impl DisplayWithOptions for Objective {
+    fn to_string_with_options(&self, options: &FormatOptions) -> String {
+        format!(
+            "{} {}",
+            self.objective_type,
+            self.rhs.to_string_with_options(options)
+        )
     }
 }
 
@@ -457,7 +549,18 @@ impl Constraint {
 
 impl fmt::Display for Constraint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {}", self.lhs, self.constraint_type, self.rhs)
+        f.write_str(&self.to_string_with_options(&FormatOptions::default()))
+    }
+}
+
+impl DisplayWithOptions for Constraint {
+    fn to_string_with_options(&self, options: &FormatOptions) -> String {
+        format!(
+            "{} {} {}",
+            self.lhs.to_string_with_options(options),
+            self.constraint_type,
+            self.rhs.to_string_with_options(options)
+        )
     }
 }
 
@@ -470,9 +573,13 @@ impl fmt::Display for Constraint {
 #[derive(Debug, Serialize, Clone)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub struct Model {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
     objective: Objective,
     constraints: Vec<Constraint>,
     domain: IndexMap<String, DomainVariable>,
+    variable_indexes: VariableIndexes,
+    warnings: Vec<String>,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -480,9 +587,12 @@ pub struct Model {
 #[cfg(target_arch = "wasm32")]
 pub const IModel: &'static str = r#"
 export type SerializedModel = {
+    name?: string,
     objective: SerializedObjective,
     constraints: SerializedCondition[]
     domain: Record<string, DomainVariable>
+    variable_indexes: Record<string, [string, SerializedPrimitive[]]>
+    warnings: string[]
 }
 "#;
 
@@ -499,9 +609,12 @@ impl Model {
         domain: IndexMap<String, DomainVariable>,
     ) -> Self {
         Self {
+            name: None,
             objective,
             constraints,
             domain,
+            variable_indexes: IndexMap::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -513,6 +626,16 @@ impl Model {
         (self.objective, self.constraints, self.domain)
     }
 
+    /// Gets the name of the problem, if it was declared in the source.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets the name of the problem.
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
     /// Gets a reference to the objective function.
     pub fn objective(&self) -> &Objective {
         &self.objective
@@ -532,14 +655,53 @@ impl Model {
     pub fn domain_mut(&mut self) -> &mut IndexMap<String, DomainVariable> {
         &mut self.domain
     }
+
+    /// Gets the base name and indexes of each flattened compound variable.
+    pub fn variable_indexes(&self) -> &VariableIndexes {
+        &self.variable_indexes
+    }
+
+    /// Sets the base name and indexes of each flattened compound variable.
+    pub fn set_variable_indexes(&mut self, variable_indexes: VariableIndexes) {
+        self.variable_indexes = variable_indexes;
+    }
+
+    /// Gets the warnings about parts of the model that were changed during the transformation,
+    /// like strict comparisons that were relaxed.
+    pub fn warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
+    /// Sets the warnings about parts of the model that were changed during the transformation.
+    pub fn set_warnings(&mut self, warnings: Vec<String>) {
+        self.warnings = warnings;
+    }
+
+    /// Returns the sorted names of the variables used in the objective and in the constraints,
+    /// after the iterations are expanded, useful to validate the domain before building a linear model.
+    pub fn referenced_variables(&self) -> Vec<String> {
+        let exps = std::iter::once(&self.objective.rhs)
+            .chain(self.constraints.iter().flat_map(|c| [&c.lhs, &c.rhs]));
+        exps.flat_map(|exp| exp.variables())
+            .map(|name| name.to_string())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
 }
 
 impl fmt::Display for Model {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_with_options(&FormatOptions::default()))
+    }
+}
+
+impl DisplayWithOptions for Model {
+    fn to_string_with_options(&self, options: &FormatOptions) -> String {
         let constraints = self
             .constraints
             .iter()
-            .map(|constraint| constraint.to_string())
+            .map(|constraint| constraint.to_string_with_options(options))
             .collect::<Vec<_>>()
             .join("\n    ");
         let domain: String = if !self.domain.is_empty() {
@@ -553,7 +715,17 @@ impl fmt::Display for Model {
         } else {
             "".to_string()
         };
-        write!(f, "{}\ns.t.\n    {}{}", self.objective, constraints, domain)
+        let name = match &self.name {
+            Some(name) => format!("problem \"{}\"\n", name),
+            None => String::new(),
+        };
+        format!(
+            "{}{}\ns.t.\n    {}{}",
+            name,
+            self.objective.to_string_with_options(options),
+            constraints,
+            domain
+        )
     }
 }
 
@@ -566,6 +738,15 @@ impl Model {
     pub fn serialize_wasm(&self) -> JsValue {
         serde_wasm_bindgen::to_value(&self).unwrap()
     }
+    pub fn wasm_get_variable_indexes(&self) -> Result<JsValue, JsValue> {
+        serialize_json_compatible(&self.variable_indexes).map_err(JsValue::from)
+    }
+    pub fn wasm_get_warnings(&self) -> Vec<String> {
+        self.warnings.clone()
+    }
+    pub fn wasm_get_referenced_variables(&self) -> Vec<String> {
+        self.referenced_variables()
+    }
 }
 
 /// Represents a set of primitive values.
@@ -594,15 +775,25 @@ export type SerializedVariableKind = {
 }
 "#;
 
+impl VariableKind {
+    /// Returns the names of the variables, in declaration order.
+    pub fn variables(&self) -> Vec<Spanned<String>> {
+        match self {
+            VariableKind::Single(name) => vec![name.clone()],
+            VariableKind::Tuple(names) => names.clone(),
+        }
+    }
+}
+
 impl ToLatex for VariableKind {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         match self {
-            VariableKind::Single(name) => name.to_string(),
+            VariableKind::Single(name) => variable_name_to_latex(name),
             VariableKind::Tuple(names) => format!(
                 "({})",
                 names
                     .iter()
-                    .map(|name| escape_latex(name.value()))
+                    .map(|name| variable_name_to_latex(name.value()))
                     .collect::<Vec<_>>()
                     .join(",\\ ")
             ),
@@ -643,11 +834,69 @@ pub fn transform_constraint(
 ) -> Result<Constraint, TransformError> {
     let lhs = constraint.lhs.into_exp(context, fn_context)?;
     let rhs = constraint.rhs.into_exp(context, fn_context)?;
-    Ok(Constraint::new(lhs, constraint.constraint_type, rhs))
+    match (
+        constraint.constraint_type,
+        context.config().strict_comparison,
+    ) {
+        (Comparison::Less, StrictComparisonMode::Relax { epsilon }) => {
+            let rhs = Exp::BinOp(BinOp::Sub, rhs.to_box(), Exp::Number(epsilon).to_box());
+            Ok(Constraint::new(
+                lhs,
+                Comparison::LessOrEqual,
+                rhs.simplify(),
+            ))
+        }
+        (Comparison::Greater, StrictComparisonMode::Relax { epsilon }) => {
+            let rhs = Exp::BinOp(BinOp::Add, rhs.to_box(), Exp::Number(epsilon).to_box());
+            Ok(Constraint::new(
+                lhs,
+                Comparison::GreaterOrEqual,
+                rhs.simplify(),
+            ))
+        }
+        (constraint_type, _) => Ok(Constraint::new(lhs, constraint_type, rhs)),
+    }
+}
+
+/// Applies the [`StrictComparisonMode`] of the context to a constraint, rejecting it
+/// or recording a warning that it will be kept or relaxed, if it uses a strict comparison.
+fn check_strict_comparison(
+    constraint: &PreConstraint,
+    context: &mut TransformerContext,
+) -> Result<(), TransformError> {
+    let non_strict = match constraint.constraint_type {
+        Comparison::Less => Comparison::LessOrEqual,
+        Comparison::Greater => Comparison::GreaterOrEqual,
+        _ => return Ok(()),
+    };
+    match context.config().strict_comparison {
+        StrictComparisonMode::Keep => {
+            context.add_warning(format!(
+                "The strict comparison \"{}\" in \"{}\" was kept, only the solvers that work on integers can handle it, use \"{}\" instead or enable the relaxation of strict comparisons",
+                constraint.constraint_type, constraint, non_strict
+            ));
+            Ok(())
+        }
+        StrictComparisonMode::Reject => Err(TransformError::Other(format!(
+            "The strict comparison \"{}\" in \"{}\" can't be represented by linear solvers, use \"{}\" instead or enable the relaxation of strict comparisons",
+            constraint.constraint_type, constraint, non_strict
+        ))
+        .add_span(&constraint.span)),
+        StrictComparisonMode::Relax { epsilon } => {
+            context.add_warning(format!(
+                "The strict comparison \"{}\" in \"{}\" was relaxed to \"{}\" with a margin of {}",
+                constraint.constraint_type, constraint, non_strict, epsilon
+            ));
+            Ok(())
+        }
+    }
 }
 
 /// Transforms a pre-constraint with iteration into multiple constraints.
 ///
+/// Both sides of the constraint are transformed in the scope of each iteration,
+/// so that they can depend on the iteration variables, like `x_i <= cap[i] for i in S`.
+///
 /// # Arguments
 /// * `constraint` - The pre-constraint to transform
 /// * `context` - Transformer context containing variable information
@@ -660,6 +909,7 @@ pub fn transform_constraint_with_iteration(
     context: &mut TransformerContext,
     fn_context: &FunctionContext,
 ) -> Result<Vec<Constraint>, TransformError> {
+    check_strict_comparison(constraint, context)?;
     if constraint.iteration.is_empty() {
         return Ok(vec![transform_constraint(constraint, context, fn_context)?]);
     }
@@ -717,8 +967,14 @@ pub fn transform_model(
             constraints.push(transformed_constraint);
         }
     }
+    let variable_indexes = context.variable_indexes().clone();
+    let warnings = context.warnings().clone();
     let domain = context.into_components();
-    Ok(Model::new(objective, constraints, domain))
+    let mut model = Model::new(objective, constraints, domain);
+    model.set_name(problem.name().map(|name| name.to_string()));
+    model.set_variable_indexes(variable_indexes);
+    model.set_warnings(warnings);
+    Ok(model)
 }
 
 /// Transforms a parsed problem into a complete optimization model.
@@ -735,12 +991,36 @@ pub fn transform_parsed_problem(
     constants: Vec<Constant>,
     fns: &IndexMap<String, Box<dyn RoocFunction>>,
 ) -> Result<Model, TransformError> {
+    transform_parsed_problem_with_config(pre_problem, constants, fns, TransformerConfig::default())
+}
+
+/// Transforms a parsed problem into a complete optimization model, using the given configuration.
+///
+/// # Arguments
+/// * `pre_problem` - The parsed problem to transform
+/// * `constants` - Additional constants to use
+/// * `fns` - Custom functions to use
+/// * `config` - Configuration of the transformer, like how compound variables are named
+///
+/// # Returns
+/// The transformed model or a transform error
+pub fn transform_parsed_problem_with_config(
+    pre_problem: PreModel,
+    constants: Vec<Constant>,
+    fns: &IndexMap<String, Box<dyn RoocFunction>>,
+    config: TransformerConfig,
+) -> Result<Model, TransformError> {
+    pre_problem.check_depth()?;
     let std = make_std();
     let fn_context = FunctionContext::new(fns, &std);
     let mut c = make_std_constants();
     c.extend(constants);
     c.extend(pre_problem.constants().clone());
-    let context =
-        TransformerContext::new_from_constants(c, pre_problem.domains().clone(), &fn_context)?;
+    let context = TransformerContext::new_from_constants_with_config(
+        c,
+        pre_problem.domains().clone(),
+        config,
+        &fn_context,
+    )?;
     transform_model(pre_problem, context, &fn_context)
 }
@@ -1,11 +1,11 @@
 #[allow(unused_imports)]
 use crate::prelude::*;
 use indexmap::IndexMap;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::math::VariableType;
 use crate::parser::domain_declaration::VariablesDomainDeclaration;
-use crate::parser::il::AddressableAccess;
+use crate::parser::il::{AddressableAccess, PointerAccess};
 use crate::parser::model_transformer::transform_error::TransformError;
 use crate::primitives::Constant;
 use crate::primitives::{Primitive, PrimitiveKind};
@@ -13,6 +13,88 @@ use crate::runtime_builtin::check_if_reserved_token;
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::utils::{InputSpan, Spanned};
 
+/// Controls how the name of a compound variable is built from its name and indexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum VariableNamingStrategy {
+    /// Joins the name and indexes with underscores, e.g. `x_1_2`
+    #[default]
+    Underscore,
+    /// Lists the indexes between square brackets, e.g. `x[1,2]`
+    Bracketed,
+}
+
+impl VariableNamingStrategy {
+    /// Builds the name of a compound variable from its already flattened indexes
+    pub fn format_name(&self, name: &str, indexes: &[String]) -> String {
+        match self {
+            VariableNamingStrategy::Underscore => format!("{}_{}", name, indexes.join("_")),
+            VariableNamingStrategy::Bracketed => format!("{}[{}]", name, indexes.join(",")),
+        }
+    }
+
+    /// Builds the LaTeX subscript of a compound variable from its indexes
+    pub fn format_latex(&self, name: &str, indexes: &[String]) -> String {
+        match self {
+            VariableNamingStrategy::Underscore => format!("{}_{{{}}}", name, indexes.join("")),
+            VariableNamingStrategy::Bracketed => format!("{}_{{{}}}", name, indexes.join(",")),
+        }
+    }
+}
+
+/// Controls what happens to strict comparisons (`<` and `>`) in the constraints,
+/// which can't be represented by linear solvers.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub enum StrictComparisonMode {
+    /// Keeps the strict comparison, only solvers that work on integers can handle it,
+    /// a warning is added to the model
+    #[default]
+    Keep,
+    /// Rejects the model with an error pointing at the strict constraint
+    Reject,
+    /// Turns `<` into `<=` and `>` into `>=`, moving the right hand side by `epsilon`
+    /// so that the equality is excluded, a warning is added to the model
+    Relax { epsilon: f64 },
+}
+
+/// Controls the domain of the variables that are used in the model but not declared
+/// in the `define` block, models ported from other tools assume different defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+pub enum DefaultDomain {
+    /// Rejects the model with an error pointing at the undeclared variable
+    #[default]
+    Reject,
+    /// Undeclared variables are non negative reals, like in the LP and MPS formats
+    NonNegativeReal,
+    /// Undeclared variables are free reals, without bounds
+    Real,
+}
+
+impl DefaultDomain {
+    /// Returns the domain given to undeclared variables, if they are allowed
+    pub fn variable_type(&self) -> Option<VariableType> {
+        match self {
+            DefaultDomain::Reject => None,
+            DefaultDomain::NonNegativeReal => Some(VariableType::non_negative_real()),
+            DefaultDomain::Real => Some(VariableType::Real(f64::NEG_INFINITY, f64::INFINITY)),
+        }
+    }
+}
+
+/// Maps the flattened name of a compound variable to its base name and indexes,
+/// for example `x_1_2` to `("x", [1, 2])`
+pub type VariableIndexes = IndexMap<String, (String, Vec<Primitive>)>;
+
+/// Options that change how a model is transformed
+#[derive(Debug, Clone, Default)]
+pub struct TransformerConfig {
+    /// How compound variables are named in the transformed model
+    pub naming_strategy: VariableNamingStrategy,
+    /// What to do with strict comparisons in the constraints
+    pub strict_comparison: StrictComparisonMode,
+    /// The domain of the variables that are not declared
+    pub default_domain: DefaultDomain,
+}
+
 /// Represents a single scope frame containing variable bindings.
 /// Used to implement variable scoping and shadowing.
 #[derive(Debug)]
@@ -116,7 +198,7 @@ impl<T> Default for Frame<T> {
 }
 
 /// Represents a variable in the domain of a model, tracking its type and usage.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DomainVariable {
     as_type: VariableType,
     span: InputSpan,
@@ -179,6 +261,12 @@ impl DomainVariable {
 pub struct TransformerContext {
     frames: Vec<Frame<Primitive>>,
     domain: IndexMap<String, DomainVariable>,
+    variable_indexes: VariableIndexes,
+    config: TransformerConfig,
+    warnings: Vec<String>,
+    constant_cache: IndexMap<String, f64>,
+    constant_cache_hits: usize,
+    indexed_domain: Vec<String>,
 }
 
 impl Default for TransformerContext {
@@ -203,6 +291,12 @@ impl TransformerContext {
         Self {
             frames: vec![frame],
             domain,
+            variable_indexes: IndexMap::new(),
+            config: TransformerConfig::default(),
+            warnings: Vec::new(),
+            constant_cache: IndexMap::new(),
+            constant_cache_hits: 0,
+            indexed_domain: Vec::new(),
         }
     }
 
@@ -221,13 +315,42 @@ impl TransformerContext {
         domain: Vec<VariablesDomainDeclaration>,
         fn_context: &FunctionContext,
     ) -> Result<Self, TransformError> {
-        let mut context = Self::default();
+        Self::new_from_constants_with_config(
+            constants,
+            domain,
+            TransformerConfig::default(),
+            fn_context,
+        )
+    }
+
+    /// Creates a new transformer context from constants and domain declarations,
+    /// using the given configuration.
+    ///
+    /// # Arguments
+    /// * `constants` - List of constants to initialize
+    /// * `domain` - List of domain declarations
+    /// * `config` - Configuration of the transformer
+    /// * `fn_context` - Function context for evaluating expressions
+    pub fn new_from_constants_with_config(
+        constants: Vec<Constant>,
+        domain: Vec<VariablesDomainDeclaration>,
+        config: TransformerConfig,
+        fn_context: &FunctionContext,
+    ) -> Result<Self, TransformError> {
+        let mut context = Self {
+            config,
+            ..Self::default()
+        };
 
         for constant in constants {
             let value = constant.as_primitive(&context, fn_context)?;
             let name = constant.name.value();
             context.declare_variable(name, value, true)?; //TODO should this be strict or allow for redeclaration?
         }
+        context.indexed_domain = domain
+            .iter()
+            .flat_map(|d| d.compound_variable_names())
+            .collect();
         let computed_domain = domain
             .into_iter()
             .map(|d| d.compute_domain(&mut context, fn_context))
@@ -259,7 +382,14 @@ impl TransformerContext {
         &self,
         compound_indexes: &[Primitive],
     ) -> Result<String, TransformError> {
-        let flattened = compound_indexes
+        Ok(self.flatten_indexes(compound_indexes)?.join("_"))
+    }
+
+    fn flatten_indexes(
+        &self,
+        compound_indexes: &[Primitive],
+    ) -> Result<Vec<String>, TransformError> {
+        compound_indexes
             .iter()
             .map(|value| match value {
                 Primitive::Number(value) => Ok(value.to_string()),
@@ -279,8 +409,38 @@ impl TransformerContext {
                     ],
                 }),
             })
-            .collect::<Result<Vec<_>, _>>()?;
-        Ok(flattened.join("_"))
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    /// Records the base name and indexes of a flattened compound variable
+    pub fn add_variable_index(
+        &mut self,
+        flattened_name: String,
+        name: String,
+        indexes: Vec<Primitive>,
+    ) {
+        self.variable_indexes
+            .insert(flattened_name, (name, indexes));
+    }
+
+    /// Returns the base name and indexes of the compound variables that were flattened
+    pub fn variable_indexes(&self) -> &VariableIndexes {
+        &self.variable_indexes
+    }
+
+    /// Returns the configuration of the transformer
+    pub fn config(&self) -> &TransformerConfig {
+        &self.config
+    }
+
+    /// Records a warning about a part of the model that was changed during the transformation
+    pub fn add_warning(&mut self, warning: String) {
+        self.warnings.push(warning);
+    }
+
+    /// Returns the warnings collected during the transformation
+    pub fn warnings(&self) -> &Vec<String> {
+        &self.warnings
     }
 
     /// Adds a new scope frame with existing variable bindings.
@@ -336,6 +496,57 @@ impl TransformerContext {
         None
     }
 
+    /// Checks if a variable is one of the constants of the model, declared in the
+    /// global scope and not shadowed by any inner scope.
+    ///
+    /// # Arguments
+    /// * `name` - Name of variable to look up
+    pub fn is_constant(&self, name: &str) -> bool {
+        match self.frames.split_first() {
+            Some((global, scopes)) => {
+                global.has_variable(name) && !scopes.iter().any(|f| f.has_variable(name))
+            }
+            None => false,
+        }
+    }
+
+    /// Gets the already computed value of a constant subexpression, counting the hit.
+    ///
+    /// # Arguments
+    /// * `key` - The subexpression as written, with the values of the constants it uses
+    pub fn cached_constant(&mut self, key: &str) -> Option<f64> {
+        let value = self.constant_cache.get(key).copied();
+        if value.is_some() {
+            self.constant_cache_hits += 1;
+        }
+        value
+    }
+
+    /// Returns how many times the value of a constant subexpression was reused
+    /// instead of being evaluated again.
+    pub fn constant_cache_hits(&self) -> usize {
+        self.constant_cache_hits
+    }
+
+    /// Stores the computed value of a constant subexpression, so that it doesn't
+    /// need to be evaluated again.
+    ///
+    /// # Arguments
+    /// * `key` - The subexpression as written, with the values of the constants it uses
+    /// * `value` - The number the subexpression evaluates to
+    pub fn cache_constant(&mut self, key: String, value: f64) {
+        self.constant_cache.insert(key, value);
+    }
+
+    /// Checks if a name is a family of domain variables, like `x` for `x_i` or `x[i]`,
+    /// that can be accessed by index like `x[2]`.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the family
+    pub fn is_indexed_domain_variable(&self, name: &str) -> bool {
+        self.indexed_domain.iter().any(|n| n == name)
+    }
+
     /// Gets the domain type of a variable.
     ///
     /// # Arguments
@@ -357,9 +568,19 @@ impl TransformerContext {
     /// * `Ok(())` if successful
     /// * `Err(TransformError)` if variable has no domain
     pub fn increment_domain_variable_usage(&mut self, name: &str) -> Result<(), TransformError> {
-        match self.domain.get_mut(name) {
-            Some(v) => {
-                v.increment_usage();
+        if let Some(v) = self.domain.get_mut(name) {
+            v.increment_usage();
+            return Ok(());
+        }
+        match self.config.default_domain.variable_type() {
+            Some(as_type) => {
+                self.add_warning(format!(
+                    "Variable \"{}\" was not declared, it was given the default domain {}",
+                    name, as_type
+                ));
+                let mut variable = DomainVariable::new(as_type, InputSpan::default());
+                variable.increment_usage();
+                self.domain.insert(name.to_string(), variable);
                 Ok(())
             }
             None => Err(TransformError::UndeclaredVariableDomain(name.to_string())),
@@ -482,12 +703,11 @@ impl TransformerContext {
     /// * `Err(TransformError)` if flattening fails
     pub fn flatten_compound_variable(
         &self,
-        name: &String,
+        name: &str,
         indexes: &[Primitive],
     ) -> Result<String, TransformError> {
-        let names: String = self.flatten_variable_name(indexes)?;
-        let name = format!("{}_{}", name, names);
-        Ok(name)
+        let indexes = self.flatten_indexes(indexes)?;
+        Ok(self.config.naming_strategy.format_name(name, &indexes))
     }
 
     /// Gets the value of an addressable variable access.
@@ -507,13 +727,39 @@ impl TransformerContext {
         //TODO add support for object access like G["a"] or g.a
         match self.value(&addressable_access.name) {
             Some(a) => {
-                let accesses = addressable_access
-                    .accesses
-                    .iter()
-                    .map(|access| access.as_usize_cast(self, fn_context))
-                    .collect::<Result<Vec<_>, TransformError>>()?;
-                let value = a.as_iterator()?.read(accesses)?;
-                Ok(value)
+                let mut value: Option<Primitive> = None;
+                let mut indexes = Vec::new();
+                for access in &addressable_access.accesses {
+                    match access {
+                        PointerAccess::Index(exp) => {
+                            indexes.push(exp.as_usize_cast(self, fn_context)?)
+                        }
+                        PointerAccess::Field(field) => {
+                            let current = read_indexes(
+                                value.as_ref().unwrap_or(a),
+                                &std::mem::take(&mut indexes),
+                            )?;
+                            value = Some(match current {
+                                Primitive::Tuple(t) => match t.get(*field) {
+                                    Some(v) => v.clone(),
+                                    None => {
+                                        return Err(TransformError::OutOfBounds(format!(
+                                            "cannot access field {} of {}",
+                                            field, t
+                                        )))
+                                    }
+                                },
+                                _ => {
+                                    return Err(TransformError::WrongArgument {
+                                        got: current.get_type(),
+                                        expected: PrimitiveKind::Tuple(vec![]),
+                                    })
+                                }
+                            });
+                        }
+                    }
+                }
+                read_indexes(value.as_ref().unwrap_or(a), &indexes)
             }
             None => Err(TransformError::UndeclaredVariable(
                 addressable_access.name.to_string(),
@@ -527,6 +773,27 @@ impl TransformerContext {
     }
 }
 
+/// Reads the element at the given indexes of an iterable or tuple. Consecutive indexes of nested
+/// arrays are read at once, without cloning the intermediate arrays, tuples are indexed one element at a time.
+fn read_indexes(value: &Primitive, indexes: &[usize]) -> Result<Primitive, TransformError> {
+    match value {
+        _ if indexes.is_empty() => Ok(value.clone()),
+        Primitive::Tuple(t) => match t.get(indexes[0]) {
+            Some(element) => read_indexes(element, &indexes[1..]),
+            None => Err(TransformError::OutOfBounds(format!(
+                "cannot access index {} of {}",
+                indexes[0], t
+            ))),
+        },
+        _ => {
+            let iterable = value.as_iterator()?;
+            let depth = iterable.depth().min(indexes.len());
+            let element = iterable.read(indexes[..depth].to_vec())?;
+            read_indexes(&element, &indexes[depth..])
+        }
+    }
+}
+
 /// Checks for duplicate variable declarations in a domain.
 ///
 /// # Arguments
@@ -7,22 +7,24 @@ use pest::Parser;
 use serde::Serialize;
 use std::fmt::Debug;
 
+use crate::math::OptimizationType;
 use crate::math::PreVariableType;
+use crate::parser::il::PreExp;
 use crate::parser::il::{PreConstraint, PreObjective};
 use crate::parser::model_transformer::assert_no_duplicates_in_domain;
 use crate::parser::model_transformer::TransformError;
-use crate::parser::model_transformer::{transform_parsed_problem, Model};
+use crate::parser::model_transformer::{transform_parsed_problem, Model, VariableNamingStrategy};
 use crate::primitives::Constant;
 #[cfg(target_arch = "wasm32")]
 use crate::runtime_builtin::JsFunction;
 use crate::runtime_builtin::{make_std, make_std_constants, RoocFunction};
-use crate::traits::ToLatex;
+use crate::traits::{escape_latex, FormatOptions, LatexOptions, ToLatex};
 use crate::type_checker::type_checker_context::{
     FunctionContext, TypeCheckable, TypeCheckerContext, TypedToken,
 };
 use crate::utils::{CompilationError, InputSpan, ParseError, Spanned};
 #[allow(unused)]
-use crate::{bail_missing_token, Primitive};
+use crate::{bail_missing_token, bail_semantic_error, Primitive};
 
 use super::domain_declaration::VariablesDomainDeclaration;
 use super::rules_parser::{
@@ -33,6 +35,10 @@ use super::rules_parser::{
 #[grammar = "parser/grammar.pest"]
 pub(crate) struct PLParser;
 
+/// Default maximum nesting depth of an expression, deeper expressions are rejected
+/// before being evaluated to avoid overflowing the stack
+pub const DEFAULT_MAX_EXPRESSION_DEPTH: usize = 128;
+
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 /// Represents a pre-transformed optimization model containing the objective,
 /// constraints, constants and domain declarations
@@ -40,6 +46,9 @@ pub(crate) struct PLParser;
 pub struct PreModel {
     /// Original source code if available
     source: Option<String>,
+    /// Name of the problem, declared with `problem "name"` before the objective
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
     /// Objective function to optimize
     objective: PreObjective,
     /// List of constraints that must be satisfied
@@ -48,6 +57,12 @@ pub struct PreModel {
     constants: Vec<Constant>,
     /// Domain declarations for variables
     domains: Vec<VariablesDomainDeclaration>,
+    /// Maximum nesting depth allowed for the expressions of the model
+    #[serde(skip)]
+    max_depth: usize,
+    /// How the indexes of compound variables are subscripted in LaTeX
+    #[serde(skip)]
+    naming_strategy: VariableNamingStrategy,
 }
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
@@ -55,6 +70,7 @@ pub struct PreModel {
 #[cfg(target_arch = "wasm32")]
 const IPreProblem: &'static str = r#"
 export type SerializedPreModel = {
+    name?: string,
     objective: SerializedPreObjective,
     constraints: SerializedPreConstraint[],
     constants: SerializedConstant[],
@@ -76,6 +92,9 @@ impl PreModel {
             constants,
             domains,
             source,
+            name: None,
+            max_depth: DEFAULT_MAX_EXPRESSION_DEPTH,
+            naming_strategy: VariableNamingStrategy::default(),
         }
     }
 
@@ -120,6 +139,97 @@ impl PreModel {
     pub fn source(&self) -> Option<String> {
         self.source.clone()
     }
+    /// Returns the name of the problem, if declared
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    /// Sets the name of the problem, it is kept in the transformed model and written as a title in LaTeX
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+    /// Serializes the parsed model to JSON, so that external tools can consume the syntax tree
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize the model to JSON")
+    }
+    /// Converts the model to LaTeX, writing numbers with full precision
+    pub fn to_latex(&self) -> String {
+        self.to_latex_with_options(&FormatOptions::default())
+    }
+    /// Converts the model to LaTeX, formatting the numbers it contains with the given options
+    pub fn to_latex_with_options(&self, options: &FormatOptions) -> String {
+        self.to_latex_with(&LatexOptions {
+            naming_strategy: self.naming_strategy,
+            numbers: *options,
+        })
+    }
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+    /// Sets the maximum nesting depth allowed for the expressions of the model
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+    /// Sets how the indexes of compound variables are subscripted in LaTeX,
+    /// like the names of the variables of the transformed model
+    pub fn set_naming_strategy(&mut self, naming_strategy: VariableNamingStrategy) {
+        self.naming_strategy = naming_strategy;
+    }
+    /// Checks that no expression of the model is nested deeper than the maximum depth,
+    /// as evaluating them could overflow the stack
+    pub fn check_depth(&self) -> Result<(), TransformError> {
+        match self.too_deep_expression() {
+            Some(exp) => Err(
+                TransformError::Other("expression too deeply nested".to_string())
+                    .add_span(exp.span()),
+            ),
+            None => Ok(()),
+        }
+    }
+    /// Finds the first expression of the model, including the domain declarations,
+    /// that is nested deeper than the maximum depth
+    fn too_deep_expression(&self) -> Option<&PreExp> {
+        std::iter::once(&self.objective.rhs)
+            .chain(self.constants.iter().map(|c| &c.value))
+            .chain(self.constraints.iter().flat_map(|c| {
+                [&c.lhs, &c.rhs]
+                    .into_iter()
+                    .chain(c.iteration.iter().map(|i| i.iterator.value()))
+            }))
+            .chain(self.domains.iter().flat_map(|d| d.expressions()))
+            .find(|exp| exp.depth() > self.max_depth)
+    }
+    /// Finds the spans of every occurrence of a variable or constant, both where it is
+    /// declared and where it is used, sorted by their position in the source.
+    ///
+    /// The objective, the constraints, the constants and the domain declarations are searched,
+    /// including the iteration variables they declare. For compound variables and
+    /// array accesses, like `x_i` or `c[i]`, the span covers only the name.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the variable or constant, like `x` for `x_i`
+    pub fn find_occurrences(&self, name: &str) -> Vec<InputSpan> {
+        let mut spans = Vec::new();
+        self.objective.rhs.find_occurrences(name, &mut spans);
+        for constraint in &self.constraints {
+            constraint.lhs.find_occurrences(name, &mut spans);
+            constraint.rhs.find_occurrences(name, &mut spans);
+            for iter in &constraint.iteration {
+                iter.find_occurrences(name, &mut spans);
+            }
+        }
+        for constant in &self.constants {
+            if constant.name.value() == name {
+                spans.push(constant.name.span().clone());
+            }
+            constant.value.find_occurrences(name, &mut spans);
+        }
+        for domain in &self.domains {
+            domain.find_occurrences(name, &mut spans);
+        }
+        spans.sort_by_key(|span| span.start);
+        spans.dedup_by_key(|span| span.start);
+        spans
+    }
     fn static_variables_domain(&self) -> Vec<(String, Spanned<PreVariableType>)> {
         self.domains
             .iter()
@@ -131,11 +241,18 @@ impl PreModel {
             })
             .collect::<Vec<_>>()
     }
+    fn indexed_variables_domain(&self) -> Vec<String> {
+        self.domains
+            .iter()
+            .flat_map(|d| d.compound_variable_names())
+            .collect()
+    }
     pub fn create_type_checker(
         &self,
         constants: &Vec<Constant>,
         fns: &IndexMap<String, Box<dyn RoocFunction>>,
     ) -> Result<(), TransformError> {
+        self.check_depth()?;
         let mut context = TypeCheckerContext::default();
         let domain = self.static_variables_domain();
         let std = make_std();
@@ -153,6 +270,7 @@ impl PreModel {
                 .collect::<Vec<_>>(),
         )?;
         context.set_static_domain(domain);
+        context.set_indexed_domain(self.indexed_variables_domain());
         for constant in make_std_constants() {
             constant.type_check(&mut context, &fn_context)?
         }
@@ -172,11 +290,15 @@ impl PreModel {
         constants: &Vec<Constant>,
         fns: &IndexMap<String, Box<dyn RoocFunction>>,
     ) -> IndexMap<u32, TypedToken> {
+        if self.check_depth().is_err() {
+            return IndexMap::new();
+        }
         let mut context = TypeCheckerContext::default();
         let domain = self.static_variables_domain();
         let std = make_std();
         let fn_context = FunctionContext::new(fns, &std);
         context.set_static_domain(domain);
+        context.set_indexed_domain(self.indexed_variables_domain());
         for constant in make_std_constants() {
             constant.populate_token_type_map(&mut context, &fn_context);
         }
@@ -225,13 +347,17 @@ impl TypeCheckable for PreModel {
 }
 
 impl ToLatex for PreModel {
-    fn to_latex(&self) -> String {
-        let mut s = self.objective.to_latex();
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        let mut s = match &self.name {
+            Some(name) => format!("\\textbf{{{}}}\\\\\n", escape_latex(name)),
+            None => String::new(),
+        };
+        s.push_str(&self.objective.to_latex_with(options));
         s.push_str("\\\\\n{s.t.}\\\\\n");
         let constraints = self
             .constraints
             .iter()
-            .map(|cond| format!("    \\quad {} \\quad", cond.to_latex()))
+            .map(|cond| format!("    \\quad {} \\quad", cond.to_latex_with(options)))
             .collect::<Vec<_>>()
             .join("\\\\\n");
         s.push_str(format!("\n\\begin{{align}}\n{}\n\\end{{align}}", constraints).as_str());
@@ -240,7 +366,7 @@ impl ToLatex for PreModel {
             let constants = self
                 .constants
                 .iter()
-                .map(|constant| format!("     \\quad {}", constant.to_latex()))
+                .map(|constant| format!("     \\quad {}", constant.to_latex_with(options)))
                 .collect::<Vec<_>>()
                 .join("\\\\\n");
             s.push_str(format!("\n\\begin{{align*}}\n{}\n\\end{{align*}}", constants).as_str());
@@ -250,7 +376,7 @@ impl ToLatex for PreModel {
             let domains = self
                 .domains
                 .iter()
-                .map(|domain| format!("     \\quad {}", domain.to_latex()))
+                .map(|domain| format!("     \\quad {}", domain.to_latex_with(options)))
                 .collect::<Vec<_>>()
                 .join("\\\\\n");
             s.push_str(format!("\n\\begin{{align*}}\n{}\n\\end{{align*}}", domains).as_str());
@@ -294,6 +420,9 @@ impl PreModel {
     pub fn serialize_wasm(&self) -> JsValue {
         serde_wasm_bindgen::to_value(&self).unwrap()
     }
+    pub fn to_json_wasm(&self) -> String {
+        self.to_json()
+    }
     pub fn format_wasm(&self) -> String {
         self.to_string()
     }
@@ -325,9 +454,16 @@ impl PreModel {
             .collect();
         serde_wasm_bindgen::to_value(&self.create_token_type_map(&constants, &fns)).unwrap()
     }
+    pub fn find_occurrences_wasm(&self, name: &str) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.find_occurrences(name)).unwrap()
+    }
     pub fn to_latex_wasm(&self) -> String {
         self.to_latex()
     }
+    pub fn to_latex_with_options_wasm(&self, options: JsValue) -> Result<String, JsValue> {
+        let options: FormatOptions = serde_wasm_bindgen::from_value(options)?;
+        Ok(self.to_latex_with_options(&options))
+    }
 
     pub fn wasm_get_source(&self) -> Option<String> {
         self.source.clone()
@@ -374,7 +510,11 @@ impl TransformErrorWrapper {
 
 impl fmt::Display for PreModel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut s = self.objective.to_string();
+        let mut s = match &self.name {
+            Some(name) => format!("problem \"{}\"\n", name),
+            None => String::new(),
+        };
+        s.push_str(&self.objective.to_string());
         s.push_str("\ns.t.\n");
         for cond in &self.constraints {
             s.push_str(&format!("    {}\n", cond));
@@ -405,6 +545,75 @@ impl fmt::Display for PreModel {
     }
 }
 
+/// Parses the source like [`parse_problem_source`], rejecting the expressions nested deeper
+/// than the maximum depth.
+///
+/// The parser is recursive, so the brackets are checked before parsing, and the expressions
+/// of the parsed model are checked before they are returned.
+pub(crate) fn parse_problem_source_with_max_depth(
+    source: &str,
+    max_depth: usize,
+) -> Result<PreModel, CompilationError> {
+    let too_deep = |span: InputSpan| {
+        CompilationError::new(
+            ParseError::SemanticError("expression too deeply nested".to_string()),
+            span,
+            source.to_string(),
+        )
+    };
+    if let Some(i) = find_too_deep_bracket(source, max_depth) {
+        let span = pest::Span::new(source, i, i + 1).unwrap();
+        return Err(too_deep(InputSpan::from_span(span)));
+    }
+    let mut problem = parse_problem_source(source)?;
+    problem.set_max_depth(max_depth);
+    match problem.too_deep_expression() {
+        Some(exp) => Err(too_deep(exp.span().clone())),
+        None => Ok(problem),
+    }
+}
+
+/// Returns the position of the first bracket nested deeper than the maximum depth,
+/// the brackets in strings and comments are ignored.
+fn find_too_deep_bracket(source: &str, max_depth: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut chars = source.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.next_if(|(_, c)| *c == '/').is_some() => {
+                while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+            }
+            '/' if chars.next_if(|(_, c)| *c == '*').is_some() => {
+                while let Some((_, c)) = chars.next() {
+                    if c == '*' && chars.next_if(|(_, c)| *c == '/').is_some() {
+                        break;
+                    }
+                }
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                if depth > max_depth {
+                    return Some(i);
+                }
+            }
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
 pub fn parse_problem_source(source: &str) -> Result<PreModel, CompilationError> {
     let problem = PLParser::parse(Rule::problem, source);
     match problem {
@@ -420,32 +629,20 @@ pub fn parse_problem_source(source: &str) -> Result<PreModel, CompilationError>
             let problem = problem.unwrap();
             parse_problem(problem, source)
         }
-        Err(err) => {
-            let location = &err.location;
-            let span = match location {
-                pest::error::InputLocation::Pos(pos) => InputSpan {
-                    start: *pos as u32,
-                    len: 1,
-                    start_line: 0,
-                    start_column: 0,
-                    tempered: false,
-                },
-                pest::error::InputLocation::Span((start, end)) => InputSpan {
-                    start: *start as u32,
-                    len: (end - start) as u32,
-                    start_line: 0,
-                    start_column: 0,
-                    tempered: false,
-                },
-            };
-            let kind = ParseError::UnexpectedToken(err.to_string());
-            Err(CompilationError::new(kind, span, source.to_string()))
-        }
+        Err(err) => Err(CompilationError::from_pest_error(err, source)),
     }
 }
 
 fn parse_problem(problem: Pair<Rule>, source: &str) -> Result<PreModel, CompilationError> {
     let pairs = problem.clone().into_inner();
+    let name = pairs
+        .find_first_tagged("name")
+        .and_then(|name| name.into_inner().next())
+        .map(|name| {
+            //remove quotes
+            let name = name.as_str();
+            name[1..name.len() - 1].to_string()
+        });
     let objective = pairs.find_first_tagged("objective").map(parse_objective);
     let constraints = pairs
         .find_first_tagged("constraints")
@@ -456,14 +653,31 @@ fn parse_problem(problem: Pair<Rule>, source: &str) -> Result<PreModel, Compilat
     let domain = pairs
         .find_first_tagged("define")
         .map(parse_domains_declaration);
-    match (objective, constraints) {
-        (Some(obj), Some(cond)) => Ok(PreModel::new(
-            obj?,
-            cond?,
-            consts.unwrap_or(Ok(Vec::new()))?,
-            domain.unwrap_or(Ok(Vec::new()))?,
-            Some(source.to_owned()),
-        )),
-        _ => bail_missing_token!("Objective and constraints are required", problem),
-    }
+    let constraints = match constraints {
+        Some(constraints) => constraints?,
+        None => return bail_missing_token!("Constraints are required", problem),
+    };
+    let objective = match objective {
+        Some(objective) => objective?,
+        //a model without objective only asks for a feasible solution
+        None if constraints.is_empty() => {
+            return bail_semantic_error!(
+                "A model without objective must have at least one constraint",
+                problem
+            )
+        }
+        None => PreObjective::new(
+            OptimizationType::Satisfy,
+            PreExp::Primitive(Spanned::new(Primitive::Number(0.0), InputSpan::default())),
+        ),
+    };
+    let mut model = PreModel::new(
+        objective,
+        constraints,
+        consts.unwrap_or(Ok(Vec::new()))?,
+        domain.unwrap_or(Ok(Vec::new()))?,
+        Some(source.to_owned()),
+    );
+    model.set_name(name);
+    Ok(model)
 }
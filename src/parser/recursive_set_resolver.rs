@@ -87,6 +87,10 @@ pub(crate) fn recursive_set_resolver<T>(
 
 /// Applies tuple values to variables in the context.
 ///
+/// Every variable of the tuple must receive a value, so an element with fewer values than
+/// variables is an error. An element with more values than variables only assigns the first
+/// ones and the rest are ignored, so `(i, j) in [[1, 2, 3]]` binds `i = 1` and `j = 2`.
+///
 /// # Arguments
 /// * `context` - Transformer context for variable management
 /// * `tuple` - Vector of variable names to assign values to
@@ -101,8 +105,14 @@ pub(crate) fn apply_tuple(
 ) -> Result<(), TransformError> {
     if tuple.len() > spreadable.len() {
         return Err(TransformError::Other(format!(
-            "Cannot destructure tuple of length {} in {} elements",
+            "Cannot destructure an element of {} values into the {} variables ({}), each element must have at least {} values",
             spreadable.len(),
+            tuple.len(),
+            tuple
+                .iter()
+                .map(|name| name.value().as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
             tuple.len()
         )));
     }
@@ -2,6 +2,7 @@ use pest::iterators::Pair;
 use pest::pratt_parser::PrattParser;
 
 use crate::parser::il::PreExp;
+use crate::primitives::Primitive;
 use crate::{
     err_unexpected_token,
     math::{BinOp, UnOp},
@@ -11,18 +12,28 @@ use crate::{
 
 use super::other_parser::{
     parse_array_access, parse_block_function, parse_block_scoped_function, parse_compound_variable,
-    parse_function_call, parse_primitive,
+    parse_function_call, parse_pre_graph, parse_primitive,
 };
 
 lazy_static::lazy_static! {
     static ref PRATT_PARSER: PrattParser<Rule> = {
         use pest::pratt_parser::{Assoc::*, Op};
         PrattParser::new()
+            .op(Op::infix(Rule::or, Left))
+            .op(Op::infix(Rule::and, Left))
+            .op(Op::infix(Rule::eq, Left)
+                | Op::infix(Rule::neq, Left)
+                | Op::infix(Rule::lt, Left)
+                | Op::infix(Rule::leq, Left)
+                | Op::infix(Rule::gt, Left)
+                | Op::infix(Rule::geq, Left)
+                | Op::infix(Rule::in_op, Left)
+                | Op::infix(Rule::not_in, Left))
             .op(Op::infix(Rule::add, Left) | Op::infix(Rule::sub, Left))
             .op(Op::infix(Rule::mul, Left) | Op::infix(Rule::div, Left))
             //.op(Op::infix(Rule::pow, Right)) TODO should i add this?
             //.op(Op::infix(Rule::fac, Left)) TODO should i add this?
-            .op(Op::prefix(Rule::neg))
+            .op(Op::prefix(Rule::neg) | Op::prefix(Rule::not))
     };
 }
 //TODO add implicit multiplication: 2x = 2 * x, should this be as a preprocessor? or part of the grammar?
@@ -36,6 +47,16 @@ pub(crate) fn parse_exp(exp_to_parse: Pair<Rule>) -> Result<PreExp, CompilationE
                 Rule::sub => BinOp::Sub,
                 Rule::mul => BinOp::Mul,
                 Rule::div => BinOp::Div,
+                Rule::and => BinOp::And,
+                Rule::or => BinOp::Or,
+                Rule::eq => BinOp::Equal,
+                Rule::neq => BinOp::NotEqual,
+                Rule::lt => BinOp::Less,
+                Rule::leq => BinOp::LessOrEqual,
+                Rule::gt => BinOp::Greater,
+                Rule::geq => BinOp::GreaterOrEqual,
+                Rule::in_op => BinOp::In,
+                Rule::not_in => BinOp::NotIn,
                 _ => return err_unexpected_token!("found {}, expected op", op),
             };
             Ok(PreExp::BinaryOperation(
@@ -48,6 +69,7 @@ pub(crate) fn parse_exp(exp_to_parse: Pair<Rule>) -> Result<PreExp, CompilationE
             let span = InputSpan::from_pair(&op);
             let op = match op.as_rule() {
                 Rule::neg => UnOp::Neg,
+                Rule::not => UnOp::Not,
                 _ => return err_unexpected_token!("found {}, expected op", op),
             };
             Ok(PreExp::UnaryOperation(
@@ -56,6 +78,61 @@ pub(crate) fn parse_exp(exp_to_parse: Pair<Rule>) -> Result<PreExp, CompilationE
             ))
         })
         .parse(exp_to_parse.into_inner())
+        .map(balance_associative_runs)
+}
+
+/// Rebuilds the runs of the same associative operator, like `a + b + c + d`, as balanced trees.
+///
+/// The parser builds a chain of operators as a left-deep tree, so a flat sum of n terms
+/// would be n levels deep, once balanced it is about log2(n) levels deep.
+fn balance_associative_runs(exp: PreExp) -> PreExp {
+    //the chain is the left spine of the tree, collected from the outermost operation
+    let mut spine = Vec::new();
+    let mut base = exp;
+    loop {
+        match base {
+            PreExp::BinaryOperation(op, lhs, rhs) => {
+                spine.push((op, balance_associative_runs(*rhs)));
+                base = *lhs;
+            }
+            exp => {
+                base = exp;
+                break;
+            }
+        }
+    }
+    let mut spine = spine.into_iter().rev().peekable();
+    let mut result = base;
+    while let Some((op, rhs)) = spine.next() {
+        if !matches!(*op, BinOp::Add | BinOp::Mul) {
+            result = PreExp::BinaryOperation(op, result.to_boxed(), rhs.to_boxed());
+            continue;
+        }
+        let mut operands = vec![result, rhs];
+        let mut operators = vec![op];
+        while let Some((op, rhs)) = spine.next_if(|(next, _)| **next == *operators[0]) {
+            operands.push(rhs);
+            operators.push(op);
+        }
+        result = balanced_operation(operands, operators);
+    }
+    result
+}
+
+/// Joins the operands with the operators between them as a balanced tree.
+fn balanced_operation(mut operands: Vec<PreExp>, mut operators: Vec<Spanned<BinOp>>) -> PreExp {
+    if operands.len() == 1 {
+        return operands.pop().unwrap();
+    }
+    let mid = operands.len() / 2;
+    let rhs = operands.split_off(mid);
+    let rhs_operators = operators.split_off(mid);
+    let op = operators.pop().unwrap();
+    PreExp::BinaryOperation(
+        op,
+        balanced_operation(operands, operators).to_boxed(),
+        balanced_operation(rhs, rhs_operators).to_boxed(),
+    )
 }
 
 pub(crate) fn parse_exp_leaf(exp: Pair<Rule>) -> Result<PreExp, CompilationError> {
@@ -80,6 +157,14 @@ pub(crate) fn parse_exp_leaf(exp: Pair<Rule>) -> Result<PreExp, CompilationError
         Rule::block_function => parse_block_function(&exp),
         Rule::block_scoped_function => parse_block_scoped_function(&exp),
         //also adding number since the implicit multiplication rule uses it without being part of the primitive
+        //graphs whose weights are expressions are evaluated when the model is transformed
+        Rule::primitive if exp.clone().into_inner().next().map(|p| p.as_rule()) == Some(Rule::graph) => {
+            let graph = parse_pre_graph(&exp.clone().into_inner().next().unwrap())?;
+            match graph.to_static_graph() {
+                Some(graph) => Ok(PreExp::Primitive(Spanned::new(Primitive::Graph(graph), span))),
+                None => Ok(PreExp::Graph(Spanned::new(graph, span))),
+            }
+        }
         Rule::primitive | Rule::float | Rule::integer => {
             let prim = parse_primitive(&exp)?;
             let spanned = Spanned::new(prim, span);
@@ -1,27 +1,31 @@
 use std::vec;
 
+use indexmap::IndexMap;
 use pest::iterators::{Pair, Pairs};
 
 use crate::math::{Comparison, OptimizationType, PreVariableType};
 use crate::parser::domain_declaration::{VariableToAssert, VariablesDomainDeclaration};
 use crate::parser::il::IterableSet;
 use crate::parser::il::PreExp;
-use crate::parser::il::{AddressableAccess, CompoundVariable, PreConstraint, PreObjective};
+use crate::parser::il::{
+    AddressableAccess, CompoundVariable, PointerAccess, PreConstraint, PreObjective,
+};
 use crate::parser::il::{
     BlockFunction, BlockFunctionKind, BlockScopedFunction, BlockScopedFunctionKind,
 };
-use crate::parser::iterable_utils::flatten_primitive_array_values;
+use crate::parser::il::{PreGraph, PreGraphEdge, PreGraphNode};
+use crate::parser::iterable_utils::{array_shape, flatten_primitive_array_values};
 use crate::parser::model_transformer::VariableKind;
 use crate::parser::pre_model::Rule;
 use crate::primitives::Constant;
 use crate::primitives::Primitive;
-use crate::primitives::{Graph, GraphEdge, GraphNode};
+use crate::primitives::{PrimitiveSet, Tuple};
 use crate::utils::{CompilationError, InputSpan, ParseError, Spanned};
 
 use super::exp_parser::parse_exp;
 
 use crate::runtime_builtin::FunctionCall;
-use crate::{bail_missing_token, err_unexpected_token};
+use crate::{bail_missing_token, bail_semantic_error, err_unexpected_token};
 
 pub fn parse_objective(objective: Pair<Rule>) -> Result<PreObjective, CompilationError> {
     match objective.as_rule() {
@@ -242,6 +246,19 @@ pub fn parse_variable_assertion(
             VariableToAssert::Variable(pair.as_str()[1..].to_string()),
             span,
         )),
+        Rule::array_access => {
+            let access = parse_array_access(pair)?;
+            match access.as_compound_variable() {
+                Some(compound_variable) => Ok(Spanned::new(
+                    VariableToAssert::CompoundVariable(compound_variable),
+                    span,
+                )),
+                None => bail_semantic_error!(
+                    "Only array indexes can be used to declare a family of variables",
+                    pair
+                ),
+            }
+        }
         _ => err_unexpected_token!("Expected variable but got: {}", pair),
     }
 }
@@ -271,77 +288,161 @@ pub fn parse_primitive(const_value: &Pair<Rule>) -> Result<Primitive, Compilatio
             let value = value[1..value.len() - 1].to_string();
             Ok(Primitive::String(value))
         }
-        Rule::array => {
+        Rule::tuple_literal => {
             let values = const_value
                 .clone()
                 .into_inner()
                 .map(|v| parse_primitive(&v))
                 .collect::<Result<Vec<_>, CompilationError>>()?;
-            Ok(Primitive::Iterable(flatten_primitive_array_values(values)))
+            Ok(Primitive::Tuple(Tuple::new(values)))
+        }
+        Rule::set => {
+            let values = const_value
+                .clone()
+                .into_inner()
+                .map(|v| parse_primitive(&v))
+                .collect::<Result<Vec<_>, CompilationError>>()?;
+            let elements = flatten_primitive_array_values(values);
+            Ok(Primitive::Set(PrimitiveSet::new(elements)))
         }
-        Rule::graph => {
-            let inner = const_value.clone().into_inner();
-            let body = inner.find_first_tagged("body");
-            match body {
-                Some(b) => {
-                    let inner = b
-                        .into_inner()
-                        .map(|n| parse_graph_node(&n))
-                        .collect::<Result<Vec<GraphNode>, CompilationError>>()?;
-                    let graph = Graph::new(inner);
-                    Ok(Primitive::Graph(graph))
+        Rule::array => {
+            let rows = const_value.clone().into_inner().collect::<Vec<_>>();
+            let values = rows
+                .iter()
+                .map(parse_primitive)
+                .collect::<Result<Vec<_>, CompilationError>>()?;
+            //multi dimensional arrays must be rectangular, the first row decides the shape
+            let expected = values.first().map(array_shape).unwrap_or_default();
+            if !expected.is_empty() {
+                for (i, (row, value)) in rows.iter().zip(values.iter()).enumerate().skip(1) {
+                    let shape = array_shape(value);
+                    if shape != expected {
+                        return bail_semantic_error!(
+                            "Ragged array, the row at index {i} has shape {shape:?} but the first row has shape {expected:?}",
+                            row
+                        );
+                    }
                 }
-                None => err_unexpected_token!("Expected graph but got: {}", const_value),
             }
+            Ok(Primitive::Iterable(flatten_primitive_array_values(values)))
         }
+        Rule::graph => match parse_pre_graph(const_value)?.to_static_graph() {
+            Some(graph) => Ok(Primitive::Graph(graph)),
+            None => bail_semantic_error!(
+                "Edge weights of a graph inside an array, set or tuple must be numbers, declare the graph as a constant to use expressions",
+                const_value
+            ),
+        },
         _ => err_unexpected_token!("Expected constant value but got: {}", const_value),
     }
 }
 
-pub fn parse_graph_node(node: &Pair<Rule>) -> Result<GraphNode, CompilationError> {
+/// Parses a graph whose edge weights are expressions, nodes declared more than once have their edges merged.
+pub fn parse_pre_graph(graph: &Pair<Rule>) -> Result<PreGraph, CompilationError> {
+    let inner = graph.clone().into_inner();
+    let body = inner.find_first_tagged("body");
+    match body {
+        Some(b) => {
+            let mut nodes: IndexMap<String, Vec<PreGraphEdge>> = IndexMap::new();
+            for pair in b.into_inner() {
+                let node = parse_graph_node(&pair)?;
+                let edges = nodes.entry(node.name.clone()).or_default();
+                for edge in node.edges {
+                    //the same node can be declared more than once with the edge list syntax
+                    assert_no_parallel_edge(edges, &node.name, &edge, &pair)?;
+                    edges.push(edge);
+                }
+            }
+            let nodes = nodes
+                .into_iter()
+                .map(|(name, edges)| PreGraphNode { name, edges })
+                .collect();
+            Ok(PreGraph::new(nodes))
+        }
+        None => err_unexpected_token!("Expected graph but got: {}", graph),
+    }
+}
+
+pub fn parse_graph_node(node: &Pair<Rule>) -> Result<PreGraphNode, CompilationError> {
     let inner = node.clone().into_inner();
     let name = inner.find_first_tagged("name");
-    let edges = inner.find_first_tagged("edges");
-    match (name, edges) {
-        (Some(name), Some(edges)) => {
+    let edges = inner.clone().find_first_tagged("edges");
+    let edge = inner.find_first_tagged("edge");
+    match (name, edges, edge) {
+        (Some(name), Some(edges), None) => {
             let name = name.as_str().to_string();
-            let edges = edges
-                .into_inner()
-                .map(|e| parse_graph_edge(&e, &name))
-                .collect::<Result<Vec<GraphEdge>, CompilationError>>()?;
-            Ok(GraphNode::new(name, edges))
+            let mut parsed: Vec<PreGraphEdge> = Vec::new();
+            for pair in edges.into_inner() {
+                let edge = parse_graph_edge(&pair)?;
+                assert_no_parallel_edge(&parsed, &name, &edge, &pair)?;
+                parsed.push(edge);
+            }
+            Ok(PreGraphNode {
+                name,
+                edges: parsed,
+            })
+        }
+        (Some(name), None, Some(edge)) => {
+            let name = name.as_str().to_string();
+            let edge = parse_graph_edge(&edge)?;
+            Ok(PreGraphNode {
+                name,
+                edges: vec![edge],
+            })
         }
-        (Some(name), None) => {
+        (Some(name), None, None) => {
             let name = name.as_str().to_string();
-            Ok(GraphNode::new(name, vec![]))
+            Ok(PreGraphNode {
+                name,
+                edges: vec![],
+            })
         }
         _ => err_unexpected_token!("Expected graph node but got: {}", node),
     }
 }
 
-pub fn parse_graph_edge(edge: &Pair<Rule>, from: &str) -> Result<GraphEdge, CompilationError> {
+/// Graphs are not multigraphs, so two edges between the same pair of nodes are rejected
+/// instead of silently keeping only one of them.
+fn assert_no_parallel_edge(
+    edges: &[PreGraphEdge],
+    from: &str,
+    edge: &PreGraphEdge,
+    pair: &Pair<Rule>,
+) -> Result<(), CompilationError> {
+    if edges.iter().any(|e| e.to == edge.to) {
+        let to = &edge.to;
+        return bail_semantic_error!(
+            "Parallel edges are not supported, the edge from \"{from}\" to \"{to}\" is declared more than once",
+            pair
+        );
+    }
+    Ok(())
+}
+
+pub fn parse_graph_edge(edge: &Pair<Rule>) -> Result<PreGraphEdge, CompilationError> {
     let inner = edge.clone().into_inner();
     let node = inner.find_first_tagged("node");
-    let cost = match inner.find_first_tagged("cost") {
+    let weight = match inner.find_first_tagged("cost") {
         Some(cost) => {
-            let parsed = cost.as_str().to_string().parse::<f64>();
-            if parsed.is_err() {
-                let error = ParseError::UnexpectedToken(format!(
-                    "Expected number but got: {}, error: {}",
-                    cost,
-                    parsed.unwrap_err()
-                ));
-                return Err(CompilationError::from_pair(error, &cost, false));
+            let weight = parse_exp(cost.clone())?;
+            if let PreExp::Primitive(p) = &weight {
+                if matches!(p.value().as_number_cast(), Ok(w) if !w.is_finite()) {
+                    let error = ParseError::SemanticError(format!(
+                        "Edge weight must be a finite number but got: {}",
+                        cost.as_str()
+                    ));
+                    return Err(CompilationError::from_pair(error, &cost, false));
+                }
             }
-            Some(parsed.unwrap())
+            Some(weight)
         }
         None => None,
     };
     match node {
-        Some(node) => {
-            let node = node.as_str().to_string();
-            Ok(GraphEdge::new(from.to_owned(), node, cost))
-        }
+        Some(node) => Ok(PreGraphEdge {
+            to: node.as_str().to_string(),
+            weight,
+        }),
         _ => err_unexpected_token!("Expected graph edge but got: {}", edge),
     }
 }
@@ -367,6 +468,15 @@ pub fn parse_constraint(constraint: &Pair<Rule>) -> Result<PreConstraint, Compil
             let relation = inner.find_first_tagged("relation");
             let rhs = inner.find_first_tagged("rhs");
             let iteration = inner.find_first_tagged("iteration");
+            if let Some(chain) = inner
+                .clone()
+                .find(|pair| pair.as_rule() == Rule::chained_relation)
+            {
+                return bail_semantic_error!(
+                    "Chained comparisons like \"a <= b <= c\" are not supported, write each comparison as its own constraint, like \"a <= b\" and \"b <= c\"",
+                    chain
+                );
+            }
             match (rhs, relation, lhs, iteration) {
                 (Some(rhs), Some(relation_type), Some(lhs), iteration) => {
                     let iteration = match iteration {
@@ -725,7 +835,13 @@ pub fn parse_array_access(
             let accesses = accesses.unwrap();
             let accesses = accesses
                 .into_inner()
-                .map(parse_parameter)
+                .map(|access| match access.as_rule() {
+                    Rule::field_access => match access.as_str()[1..].parse::<usize>() {
+                        Ok(field) => Ok(PointerAccess::Field(field)),
+                        Err(_) => bail_semantic_error!("Invalid tuple field", access),
+                    },
+                    _ => parse_parameter(access).map(PointerAccess::Index),
+                })
                 .collect::<Result<Vec<_>, CompilationError>>()?;
             Ok(AddressableAccess::new(name, accesses))
         }
@@ -213,7 +213,7 @@ pub enum PipeError {
     LinearizationError(LinearizationError),
     StandardizationError(SolverError),
     CanonicalizationError(CanonicalTransformError),
-    StepByStepSimplexError(SimplexError, Tableau),
+    StepByStepSimplexError(SimplexError, Box<Tableau>),
     SolverError(SolverError),
     Other(String),
 }
@@ -234,7 +234,7 @@ impl Pipeable for StepByStepSimplexPipe {
         let optimal_tableau = tableau.solve_step_by_step(1000);
         match optimal_tableau {
             Ok(optimal_tableau) => Ok(PipeableData::OptimalTableauWithSteps(optimal_tableau)),
-            Err(e) => Err(PipeError::StepByStepSimplexError(e, tableau)),
+            Err(e) => Err(PipeError::StepByStepSimplexError(e, Box::new(tableau))),
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::math::{BinOp, UnOp};
+use crate::math::{float_eq, float_ge, float_gt, float_le, float_lt, float_ne, BinOp, UnOp};
 use crate::parser::model_transformer::TransformError;
 
 use super::{
@@ -16,6 +16,13 @@ impl ApplyOp for String {
         match to {
             Primitive::String(s) => match op {
                 BinOp::Add => Ok(Primitive::String(format!("{}{}", self, s))),
+                //strings are compared in lexicographic order
+                BinOp::Equal => Ok(Primitive::Boolean(self == s)),
+                BinOp::NotEqual => Ok(Primitive::Boolean(self != s)),
+                BinOp::Less => Ok(Primitive::Boolean(self < s)),
+                BinOp::LessOrEqual => Ok(Primitive::Boolean(self <= s)),
+                BinOp::Greater => Ok(Primitive::Boolean(self > s)),
+                BinOp::GreaterOrEqual => Ok(Primitive::Boolean(self >= s)),
                 _ => Err(OperatorError::unsupported_bin_operation(
                     op,
                     PrimitiveKind::String,
@@ -34,8 +41,8 @@ impl ApplyOp for String {
             PrimitiveKind::String,
         ))
     }
-    fn can_apply_binary_op(_op: BinOp, to: Self::TargetType) -> bool {
-        matches!(to, PrimitiveKind::String)
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        (op == BinOp::Add || op.is_comparison()) && matches!(to, PrimitiveKind::String)
     }
     fn can_apply_unary_op(_op: UnOp) -> bool {
         false
@@ -46,23 +53,40 @@ impl ApplyOp for bool {
     type TargetType = PrimitiveKind;
     type Target = Primitive;
     type Error = OperatorError;
-    fn apply_binary_op(&self, op: BinOp, _to: &Primitive) -> Result<Primitive, OperatorError> {
-        Err(OperatorError::unsupported_bin_operation(
-            op,
-            PrimitiveKind::Boolean,
-        ))
+    fn apply_binary_op(&self, op: BinOp, to: &Primitive) -> Result<Primitive, OperatorError> {
+        match to {
+            Primitive::Boolean(b) => match op {
+                BinOp::And => Ok(Primitive::Boolean(*self && *b)),
+                BinOp::Or => Ok(Primitive::Boolean(*self || *b)),
+                BinOp::Equal => Ok(Primitive::Boolean(*self == *b)),
+                BinOp::NotEqual => Ok(Primitive::Boolean(*self != *b)),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Boolean,
+                )),
+            },
+            _ => Err(OperatorError::incompatible_type(
+                op,
+                PrimitiveKind::Boolean,
+                to.get_type(),
+            )),
+        }
     }
     fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
-        Err(OperatorError::unsupported_un_operation(
-            op,
-            PrimitiveKind::Boolean,
-        ))
+        match op {
+            UnOp::Not => Ok(Primitive::Boolean(!self)),
+            _ => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::Boolean,
+            )),
+        }
     }
-    fn can_apply_binary_op(_op: BinOp, _to: Self::TargetType) -> bool {
-        false
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        matches!(op, BinOp::And | BinOp::Or | BinOp::Equal | BinOp::NotEqual)
+            && matches!(to, PrimitiveKind::Boolean)
     }
-    fn can_apply_unary_op(_op: UnOp) -> bool {
-        false
+    fn can_apply_unary_op(op: UnOp) -> bool {
+        matches!(op, UnOp::Not)
     }
 }
 
@@ -71,30 +95,49 @@ impl ApplyOp for f64 {
     type Target = Primitive;
     type Error = OperatorError;
     fn apply_binary_op(&self, op: BinOp, to: &Primitive) -> Result<Primitive, OperatorError> {
+        if op.is_comparison() {
+            return compare_numbers(op, *self, to, PrimitiveKind::Number);
+        }
         match to {
             Primitive::Number(n) => match op {
                 BinOp::Add => Ok(Primitive::Number(self + n)),
                 BinOp::Sub => Ok(Primitive::Number(self - n)),
                 BinOp::Mul => Ok(Primitive::Number(self * n)),
                 BinOp::Div => Ok(Primitive::Number(self / n)),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Number,
+                )),
             },
             Primitive::Integer(n) => match op {
                 BinOp::Add => Ok(Primitive::Number(*self + (*n as f64))),
                 BinOp::Sub => Ok(Primitive::Number(*self - (*n as f64))),
                 BinOp::Mul => Ok(Primitive::Number(*self * (*n as f64))),
                 BinOp::Div => Ok(Primitive::Number(*self / (*n as f64))),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Number,
+                )),
             },
             Primitive::PositiveInteger(n) => match op {
                 BinOp::Add => Ok(Primitive::Number(*self + (*n as f64))),
                 BinOp::Sub => Ok(Primitive::Number(*self - (*n as f64))),
                 BinOp::Mul => Ok(Primitive::Number(*self * (*n as f64))),
                 BinOp::Div => Ok(Primitive::Number(*self / (*n as f64))),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Number,
+                )),
             },
             Primitive::Boolean(n) => match op {
                 BinOp::Add => Ok(Primitive::Number(*self + (*n as i8 as f64))),
                 BinOp::Sub => Ok(Primitive::Number(*self - (*n as i8 as f64))),
                 BinOp::Mul => Ok(Primitive::Number(*self * (*n as i8 as f64))),
                 BinOp::Div => Ok(Primitive::Number(*self / (*n as i8 as f64))),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Number,
+                )),
             },
             _ => Err(OperatorError::incompatible_type(
                 op,
@@ -106,16 +149,27 @@ impl ApplyOp for f64 {
     fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
         match op {
             UnOp::Neg => Ok(Primitive::Number(-self)),
+            UnOp::Not => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::Number,
+            )),
         }
     }
-    fn can_apply_binary_op(_op: BinOp, to: Self::TargetType) -> bool {
-        matches!(
-            to,
-            PrimitiveKind::Number
-                | PrimitiveKind::Integer
-                | PrimitiveKind::PositiveInteger
-                | PrimitiveKind::Boolean
-        )
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        if op.is_comparison() {
+            return matches!(
+                to,
+                PrimitiveKind::Number | PrimitiveKind::Integer | PrimitiveKind::PositiveInteger
+            );
+        }
+        !op.is_logical()
+            && matches!(
+                to,
+                PrimitiveKind::Number
+                    | PrimitiveKind::Integer
+                    | PrimitiveKind::PositiveInteger
+                    | PrimitiveKind::Boolean
+            )
     }
     fn can_apply_unary_op(op: UnOp) -> bool {
         matches!(op, UnOp::Neg)
@@ -127,30 +181,49 @@ impl ApplyOp for i64 {
     type Target = Primitive;
     type Error = OperatorError;
     fn apply_binary_op(&self, op: BinOp, to: &Primitive) -> Result<Primitive, OperatorError> {
+        if op.is_comparison() {
+            return compare_numbers(op, *self as f64, to, PrimitiveKind::Integer);
+        }
         match to {
             Primitive::Integer(n) => match op {
                 BinOp::Add => Ok(Primitive::Integer(self + n)),
                 BinOp::Sub => Ok(Primitive::Integer(self - n)),
                 BinOp::Mul => Ok(Primitive::Integer(self * n)),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as f64))),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Integer,
+                )),
             },
             Primitive::Number(n) => match op {
                 BinOp::Add => Ok(Primitive::Number((*self as f64) + n)),
                 BinOp::Sub => Ok(Primitive::Number((*self as f64) - n)),
                 BinOp::Mul => Ok(Primitive::Number((*self as f64) * n)),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / n)),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Integer,
+                )),
             },
             Primitive::PositiveInteger(n) => match op {
                 BinOp::Add => Ok(Primitive::Integer(*self + (*n as i64))),
                 BinOp::Sub => Ok(Primitive::Integer(*self - (*n as i64))),
                 BinOp::Mul => Ok(Primitive::Integer(*self * (*n as i64))),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as f64))),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Integer,
+                )),
             },
             Primitive::Boolean(n) => match op {
                 BinOp::Add => Ok(Primitive::Integer(*self + (*n as i64))),
                 BinOp::Sub => Ok(Primitive::Integer(*self - (*n as i64))),
                 BinOp::Mul => Ok(Primitive::Integer(*self)),
                 BinOp::Div => Ok(Primitive::Integer(*self)),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::Integer,
+                )),
             },
             _ => Err(OperatorError::incompatible_type(
                 op,
@@ -162,16 +235,27 @@ impl ApplyOp for i64 {
     fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
         match op {
             UnOp::Neg => Ok(Primitive::Integer(-self)),
+            UnOp::Not => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::Integer,
+            )),
         }
     }
-    fn can_apply_binary_op(_op: BinOp, to: Self::TargetType) -> bool {
-        matches!(
-            to,
-            PrimitiveKind::Number
-                | PrimitiveKind::Integer
-                | PrimitiveKind::PositiveInteger
-                | PrimitiveKind::Boolean
-        )
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        if op.is_comparison() {
+            return matches!(
+                to,
+                PrimitiveKind::Number | PrimitiveKind::Integer | PrimitiveKind::PositiveInteger
+            );
+        }
+        !op.is_logical()
+            && matches!(
+                to,
+                PrimitiveKind::Number
+                    | PrimitiveKind::Integer
+                    | PrimitiveKind::PositiveInteger
+                    | PrimitiveKind::Boolean
+            )
     }
     fn can_apply_unary_op(op: UnOp) -> bool {
         matches!(op, UnOp::Neg)
@@ -183,30 +267,49 @@ impl ApplyOp for u64 {
     type Target = Primitive;
     type Error = OperatorError;
     fn apply_binary_op(&self, op: BinOp, to: &Primitive) -> Result<Primitive, OperatorError> {
+        if op.is_comparison() {
+            return compare_numbers(op, *self as f64, to, PrimitiveKind::PositiveInteger);
+        }
         match to {
             Primitive::PositiveInteger(n) => match op {
                 BinOp::Add => Ok(Primitive::PositiveInteger(self + n)),
                 BinOp::Sub => Ok(Primitive::Integer((*self as i64) - (*n as i64))),
                 BinOp::Mul => Ok(Primitive::PositiveInteger(self * n)),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as f64))),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::PositiveInteger,
+                )),
             },
             Primitive::Integer(n) => match op {
                 BinOp::Add => Ok(Primitive::Integer((*self as i64) + n)),
                 BinOp::Sub => Ok(Primitive::Integer((*self as i64) - n)),
                 BinOp::Mul => Ok(Primitive::Integer((*self as i64) * n)),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / (*n as f64))),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::PositiveInteger,
+                )),
             },
             Primitive::Number(n) => match op {
                 BinOp::Add => Ok(Primitive::Number((*self as f64) + n)),
                 BinOp::Sub => Ok(Primitive::Number((*self as f64) - n)),
                 BinOp::Mul => Ok(Primitive::Number((*self as f64) * n)),
                 BinOp::Div => Ok(Primitive::Number((*self as f64) / n)),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::PositiveInteger,
+                )),
             },
             Primitive::Boolean(n) => match op {
                 BinOp::Add => Ok(Primitive::PositiveInteger(*self + (*n as u64))),
                 BinOp::Sub => Ok(Primitive::Integer((*self as i64) - (*n as i64))),
                 BinOp::Mul => Ok(Primitive::PositiveInteger(*self)),
                 BinOp::Div => Ok(Primitive::PositiveInteger(*self)),
+                _ => Err(OperatorError::unsupported_bin_operation(
+                    op,
+                    PrimitiveKind::PositiveInteger,
+                )),
             },
             _ => Err(OperatorError::incompatible_type(
                 op,
@@ -218,22 +321,58 @@ impl ApplyOp for u64 {
     fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
         match op {
             UnOp::Neg => Ok(Primitive::Integer(-(*self as i64))),
+            UnOp::Not => Err(OperatorError::unsupported_un_operation(
+                op,
+                PrimitiveKind::PositiveInteger,
+            )),
         }
     }
-    fn can_apply_binary_op(_op: BinOp, to: Self::TargetType) -> bool {
-        matches!(
-            to,
-            PrimitiveKind::Number
-                | PrimitiveKind::Integer
-                | PrimitiveKind::PositiveInteger
-                | PrimitiveKind::Boolean
-        )
+    fn can_apply_binary_op(op: BinOp, to: Self::TargetType) -> bool {
+        if op.is_comparison() {
+            return matches!(
+                to,
+                PrimitiveKind::Number | PrimitiveKind::Integer | PrimitiveKind::PositiveInteger
+            );
+        }
+        !op.is_logical()
+            && matches!(
+                to,
+                PrimitiveKind::Number
+                    | PrimitiveKind::Integer
+                    | PrimitiveKind::PositiveInteger
+                    | PrimitiveKind::Boolean
+            )
     }
     fn can_apply_unary_op(op: UnOp) -> bool {
         matches!(op, UnOp::Neg)
     }
 }
 
+/// Compares a number with any other numeric primitive, comparing any other type is an error
+fn compare_numbers(
+    op: BinOp,
+    lhs: f64,
+    to: &Primitive,
+    kind: PrimitiveKind,
+) -> Result<Primitive, OperatorError> {
+    let rhs = match to {
+        Primitive::Number(n) => *n,
+        Primitive::Integer(n) => *n as f64,
+        Primitive::PositiveInteger(n) => *n as f64,
+        _ => return Err(OperatorError::incompatible_type(op, kind, to.get_type())),
+    };
+    let result = match op {
+        BinOp::Equal => float_eq(lhs, rhs),
+        BinOp::NotEqual => float_ne(lhs, rhs),
+        BinOp::Less => float_lt(lhs, rhs),
+        BinOp::LessOrEqual => float_le(lhs, rhs),
+        BinOp::Greater => float_gt(lhs, rhs),
+        BinOp::GreaterOrEqual => float_ge(lhs, rhs),
+        _ => return Err(OperatorError::unsupported_bin_operation(op, kind)),
+    };
+    Ok(Primitive::Boolean(result))
+}
+
 /* --------- Spreadable --------- */
 
 impl Spreadable for f64 {
@@ -8,7 +8,7 @@ use super::primitive::{Primitive, PrimitiveKind};
 use crate::parser::il::PreExp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
-use crate::traits::ToLatex;
+use crate::traits::{variable_name_to_latex, LatexOptions, ToLatex};
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::utils::InputSpan;
 use crate::{
@@ -34,8 +34,12 @@ export type SerializedConstant = {
 "#;
 
 impl ToLatex for Constant {
-    fn to_latex(&self) -> String {
-        format!("{} &= {}", self.name.value(), self.value.to_latex())
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        format!(
+            "{} &= {}",
+            variable_name_to_latex(self.name.value()),
+            self.value.to_latex_with(options)
+        )
     }
 }
 
@@ -4,9 +4,9 @@ use core::fmt;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-use crate::math::{BinOp, UnOp};
+use crate::math::{float_ne, BinOp, UnOp};
 use crate::parser::model_transformer::TransformError;
-use crate::traits::{escape_latex, ToLatex};
+use crate::traits::{escape_latex, LatexOptions, ToLatex};
 
 use super::{
     primitive::{Primitive, PrimitiveKind},
@@ -38,9 +38,13 @@ impl GraphEdge {
 }
 
 impl ToLatex for GraphEdge {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         if let Some(w) = self.weight {
-            format!("\\text{{{}:{}}}", escape_latex(&self.to), w)
+            format!(
+                "\\text{{{}:{}}}",
+                escape_latex(&self.to),
+                options.numbers.format_number(w)
+            )
         } else {
             format!("\\text{{{}}}", escape_latex(&self.to))
         }
@@ -74,6 +78,8 @@ export type SerializedGraphNode = {
 "#;
 
 impl GraphNode {
+    /// Creates a node with its outgoing edges, graphs are not multigraphs so
+    /// if more than one edge goes to the same node, only the last one is kept.
     pub fn new(name: String, edges: Vec<GraphEdge>) -> Self {
         let edges = edges
             .into_iter()
@@ -84,17 +90,21 @@ impl GraphNode {
     pub fn to_edges(self) -> Vec<GraphEdge> {
         self.edges.into_values().collect()
     }
+    /// Returns the outgoing edges of the node, in the order they were declared.
+    pub fn edges(&self) -> Vec<&GraphEdge> {
+        self.edges.values().collect()
+    }
     pub fn name(&self) -> &String {
         &self.name
     }
 }
 
 impl ToLatex for GraphNode {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         let edges = self
             .edges
             .values()
-            .map(|edge| edge.to_latex())
+            .map(|edge| edge.to_latex_with(options))
             .collect::<Vec<_>>()
             .join(",\\ ");
         if edges.is_empty() {
@@ -144,6 +154,11 @@ impl Graph {
             .flat_map(|node| node.edges.into_values().collect::<Vec<_>>())
             .collect::<Vec<_>>()
     }
+    /// Returns the edges of the graph, grouped by node in the order the nodes and
+    /// their edges were declared.
+    pub fn edges(&self) -> Vec<&GraphEdge> {
+        self.vertices.iter().flat_map(|node| node.edges()).collect()
+    }
     pub fn nodes(&self) -> &Vec<GraphNode> {
         &self.vertices
     }
@@ -167,6 +182,238 @@ impl Graph {
             ))),
         }
     }
+    /// Returns the complement of the graph, with the same nodes and an unweighted edge from
+    /// every node to every other node it is not connected to, self loops are not added
+    pub fn complement(&self) -> Graph {
+        let vertices = self
+            .vertices
+            .iter()
+            .map(|node| {
+                let edges = self
+                    .vertices
+                    .iter()
+                    .filter(|other| {
+                        other.name != node.name && !node.edges.contains_key(&other.name)
+                    })
+                    .map(|other| GraphEdge::new(node.name.clone(), other.name.clone(), None))
+                    .collect();
+                GraphNode::new(node.name.clone(), edges)
+            })
+            .collect();
+        Graph::new(vertices)
+    }
+    /// Returns the length of the shortest path between every pair of nodes, computed with the
+    /// Floyd–Warshall algorithm. Rows and columns follow the order in which the nodes are declared,
+    /// unweighted edges have a length of 1.
+    ///
+    /// Pairs of nodes with no path between them have a distance of `f64::INFINITY` (the `Infinity` constant),
+    /// pairs whose path goes through a negative cycle have a distance of `f64::NEG_INFINITY`.
+    ///
+    /// # Arguments
+    /// * `error_on_negative_cycle` - Return an error instead if the graph has a negative cycle
+    pub fn shortest_paths(
+        &self,
+        error_on_negative_cycle: bool,
+    ) -> Result<Vec<Vec<f64>>, TransformError> {
+        let n = self.vertices.len();
+        let index = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.name.as_str(), i))
+            .collect::<IndexMap<_, _>>();
+        let mut distances = vec![vec![f64::INFINITY; n]; n];
+        for (i, node) in self.vertices.iter().enumerate() {
+            distances[i][i] = 0.0;
+            for edge in node.edges.values() {
+                let j = *index.get(edge.to.as_str()).ok_or_else(|| {
+                    TransformError::Other(format!("node {} not found in graph", edge.to))
+                })?;
+                distances[i][j] = distances[i][j].min(edge.weight.unwrap_or(1.0));
+            }
+        }
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    let through = distances[i][k] + distances[k][j];
+                    if through < distances[i][j] {
+                        distances[i][j] = through;
+                    }
+                }
+            }
+        }
+        let negative_cycle = (0..n)
+            .filter(|k| distances[*k][*k] < 0.0)
+            .collect::<Vec<_>>();
+        if let Some(k) = negative_cycle.first() {
+            if error_on_negative_cycle {
+                return Err(TransformError::Other(format!(
+                    "the graph has a negative cycle through node {}, the shortest paths are not defined",
+                    self.vertices[*k].name
+                )));
+            }
+            for i in 0..n {
+                for j in 0..n {
+                    let through_cycle = negative_cycle.iter().any(|k| {
+                        distances[i][*k] != f64::INFINITY && distances[*k][j] != f64::INFINITY
+                    });
+                    if through_cycle {
+                        distances[i][j] = f64::NEG_INFINITY;
+                    }
+                }
+            }
+        }
+        Ok(distances)
+    }
+    /// Returns the transitive closure of the graph, with the same nodes and an unweighted edge
+    /// from every node to every node reachable from it, computed with the Floyd–Warshall algorithm.
+    ///
+    /// # Arguments
+    /// * `include_self` - Add an edge from a node to itself when it lies on a cycle
+    pub fn transitive_closure(&self, include_self: bool) -> Result<Graph, TransformError> {
+        let n = self.vertices.len();
+        let index = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.name.as_str(), i))
+            .collect::<IndexMap<_, _>>();
+        let mut reachable = vec![vec![false; n]; n];
+        for (i, node) in self.vertices.iter().enumerate() {
+            for edge in node.edges.values() {
+                let j = *index.get(edge.to.as_str()).ok_or_else(|| {
+                    TransformError::Other(format!("node {} not found in graph", edge.to))
+                })?;
+                reachable[i][j] = true;
+            }
+        }
+        for k in 0..n {
+            let through = reachable[k].clone();
+            for row in reachable.iter_mut().filter(|row| row[k]) {
+                for (to, reached) in row.iter_mut().zip(&through) {
+                    *to |= reached;
+                }
+            }
+        }
+        let vertices = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, node)| {
+                let edges = self
+                    .vertices
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| reachable[i][*j] && (include_self || i != *j))
+                    .map(|(_, other)| GraphEdge::new(node.name.clone(), other.name.clone(), None))
+                    .collect();
+                GraphNode::new(node.name.clone(), edges)
+            })
+            .collect();
+        Ok(Graph::new(vertices))
+    }
+    /// Returns the value of the maximum flow from the source to the sink, computed with the
+    /// Edmonds–Karp algorithm, together with the flow going through each edge of the graph.
+    /// The weights of the edges are their capacities, unweighted edges have a capacity of 1.
+    ///
+    /// # Arguments
+    /// * `source` - The name of the node the flow starts from
+    /// * `sink` - The name of the node the flow arrives to
+    ///
+    /// # Returns
+    /// * `Ok((f64, Vec<GraphEdge>))` the value of the flow and the edges of the graph, weighted by their flow
+    /// * `Err(TransformError)` if the nodes are not in the graph, they are the same node, or a capacity is negative
+    pub fn max_flow(
+        &self,
+        source: &str,
+        sink: &str,
+    ) -> Result<(f64, Vec<GraphEdge>), TransformError> {
+        let n = self.vertices.len();
+        let index = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.name.as_str(), i))
+            .collect::<IndexMap<_, _>>();
+        let find = |name: &str| {
+            index
+                .get(name)
+                .copied()
+                .ok_or_else(|| TransformError::Other(format!("node {} not found in graph", name)))
+        };
+        let (s, t) = (find(source)?, find(sink)?);
+        if s == t {
+            return Err(TransformError::Other(format!(
+                "the source and the sink of a flow must be different nodes, got {} for both",
+                source
+            )));
+        }
+        let mut capacity = vec![vec![0.0; n]; n];
+        for (i, node) in self.vertices.iter().enumerate() {
+            for edge in node.edges.values() {
+                let weight = edge.weight.unwrap_or(1.0);
+                if weight < 0.0 {
+                    return Err(TransformError::Other(format!(
+                        "the capacity of the edge from {} to {} is negative: {}",
+                        node.name, edge.to, weight
+                    )));
+                }
+                let j = find(&edge.to)?;
+                if i != j {
+                    capacity[i][j] = weight;
+                }
+            }
+        }
+        //the flow is kept antisymmetric, so that flow[j][i] = -flow[i][j]
+        let mut flow = vec![vec![0.0; n]; n];
+        let mut value = 0.0;
+        loop {
+            //breadth first search of the shortest augmenting path in the residual graph
+            let mut previous = vec![None; n];
+            let mut queue = std::collections::VecDeque::from([s]);
+            previous[s] = Some(s);
+            while let Some(u) = queue.pop_front() {
+                for v in 0..n {
+                    let residual: f64 = capacity[u][v] - flow[u][v];
+                    if previous[v].is_none() && residual > 0.0 && float_ne(residual, 0.0) {
+                        previous[v] = Some(u);
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if previous[t].is_none() {
+                break;
+            }
+            let mut bottleneck = f64::INFINITY;
+            let mut v = t;
+            while let Some(u) = previous[v].filter(|_| v != s) {
+                bottleneck = bottleneck.min(capacity[u][v] - flow[u][v]);
+                v = u;
+            }
+            let mut v = t;
+            while let Some(u) = previous[v].filter(|_| v != s) {
+                flow[u][v] += bottleneck;
+                flow[v][u] -= bottleneck;
+                v = u;
+            }
+            value += bottleneck;
+        }
+        let edges = self
+            .vertices
+            .iter()
+            .enumerate()
+            .flat_map(|(i, node)| {
+                let flow = &flow;
+                let index = &index;
+                node.edges.values().map(move |edge| {
+                    let j = index[edge.to.as_str()];
+                    let flow = flow[i][j].max(0.0);
+                    GraphEdge::new(node.name.clone(), edge.to.clone(), Some(flow))
+                })
+            })
+            .collect();
+        Ok((value, edges))
+    }
     pub fn into_neighbours_of(self, node_name: &str) -> Result<Vec<GraphEdge>, TransformError> {
         let node = self
             .vertices
@@ -184,11 +431,11 @@ impl Graph {
 
 //TODO decide if this is a nice enough representation
 impl ToLatex for Graph {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         let nodes = self
             .vertices
             .iter()
-            .map(|node| node.to_latex())
+            .map(|node| node.to_latex_with(options))
             .collect::<Vec<_>>()
             .join("\\\\ ");
         if nodes.is_empty() {
@@ -12,10 +12,11 @@ use super::{
 };
 use crate::iterable_utils::flatten_primitive_array_values;
 use crate::parser::model_transformer::TransformError;
-use crate::traits::ToLatex;
+use crate::traits::{LatexOptions, ToLatex};
 use crate::{
-    check_bounds,
+    bail_wrong_argument, check_bounds,
     math::{BinOp, UnOp},
+    wrong_argument,
 };
 /// Represents different types of iterable collections in the system.
 ///
@@ -75,7 +76,112 @@ export type SerializedIterable =
     | { type: 'Anys', value: SerializedPrimitive[] }
 "#;
 
+/// Implements the owned conversion of an iterable to a vector of one of its variants,
+/// an empty iterable of unknown type converts to an empty vector.
+macro_rules! into_vec {
+    ($(#[$doc:meta])* $name:ident, $variant:ident, $item:ty, $kind:expr) => {
+        $(#[$doc])*
+        pub fn $name(self) -> Result<Vec<$item>, TransformError> {
+            match self {
+                IterableKind::$variant(v) => Ok(v),
+                IterableKind::Anys(v) if v.is_empty() => Ok(vec![]),
+                _ => bail_wrong_argument!(PrimitiveKind::Iterable(Box::new($kind)), self),
+            }
+        }
+    };
+}
+
 impl IterableKind {
+    /// Converts the iterable into a vector of numbers, converting integers to numbers.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<f64>)` - The numbers, if this is an iterable of numbers or integers
+    /// * `Err(TransformError::WrongArgument)` - With the actual type of the iterable otherwise
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::IterableKind;
+    /// let integers = IterableKind::Integers(vec![1, -2]);
+    /// assert_eq!(integers.into_numbers().unwrap(), vec![1.0, -2.0]);
+    /// let strings = IterableKind::Strings(vec!["a".to_string()]);
+    /// assert!(strings.into_numbers().is_err());
+    /// ```
+    pub fn into_numbers(self) -> Result<Vec<f64>, TransformError> {
+        match self {
+            IterableKind::Numbers(v) => Ok(v),
+            IterableKind::Integers(v) => Ok(v.into_iter().map(|n| n as f64).collect()),
+            IterableKind::PositiveIntegers(v) => Ok(v.into_iter().map(|n| n as f64).collect()),
+            IterableKind::Anys(v) if v.is_empty() => Ok(vec![]),
+            _ => bail_wrong_argument!(
+                PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+                self
+            ),
+        }
+    }
+    into_vec!(
+        /// Converts the iterable into a vector of integers, erroring with the actual type otherwise.
+        into_integers,
+        Integers,
+        i64,
+        PrimitiveKind::Integer
+    );
+    into_vec!(
+        /// Converts the iterable into a vector of positive integers, erroring with the actual type otherwise.
+        into_positive_integers,
+        PositiveIntegers,
+        u64,
+        PrimitiveKind::PositiveInteger
+    );
+    into_vec!(
+        /// Converts the iterable into a vector of strings, erroring with the actual type otherwise.
+        into_strings,
+        Strings,
+        String,
+        PrimitiveKind::String
+    );
+    into_vec!(
+        /// Converts the iterable into a vector of booleans, erroring with the actual type otherwise.
+        into_booleans,
+        Booleans,
+        bool,
+        PrimitiveKind::Boolean
+    );
+    into_vec!(
+        /// Converts the iterable into a vector of graph edges, erroring with the actual type otherwise.
+        into_edges,
+        Edges,
+        GraphEdge,
+        PrimitiveKind::GraphEdge
+    );
+    into_vec!(
+        /// Converts the iterable into a vector of graph nodes, erroring with the actual type otherwise.
+        into_nodes,
+        Nodes,
+        GraphNode,
+        PrimitiveKind::GraphNode
+    );
+    into_vec!(
+        /// Converts the iterable into a vector of graphs, erroring with the actual type otherwise.
+        into_graphs,
+        Graphs,
+        Graph,
+        PrimitiveKind::Graph
+    );
+    into_vec!(
+        /// Converts the iterable into a vector of tuples, erroring with the actual type otherwise.
+        into_tuples,
+        Tuples,
+        Tuple,
+        PrimitiveKind::Tuple(vec![])
+    );
+    into_vec!(
+        /// Converts the iterable into a vector of nested iterables, erroring with the actual type otherwise.
+        into_iterables,
+        Iterables,
+        IterableKind,
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any))
+    );
+
     /// Gets the primitive type of this iterable collection.
     ///
     /// # Returns
@@ -286,22 +392,22 @@ impl IterableKind {
     ///
     /// # Arguments
     /// * `include_block` - Whether to wrap the output in a matrix block
-    pub fn latexify(&self, include_block: bool) -> String {
+    pub fn latexify(&self, include_block: bool, options: &LatexOptions) -> String {
         match self {
-            IterableKind::Numbers(v) => latexify_vec(v, include_block),
-            IterableKind::Integers(v) => latexify_vec(v, include_block),
-            IterableKind::PositiveIntegers(v) => latexify_vec(v, include_block),
-            IterableKind::Anys(v) => latexify_vec(v, include_block),
-            IterableKind::Strings(v) => latexify_vec(v, include_block),
-            IterableKind::Edges(v) => latexify_vec(v, include_block),
-            IterableKind::Nodes(v) => latexify_vec(v, include_block),
-            IterableKind::Tuples(v) => latexify_vec(v, include_block),
-            IterableKind::Booleans(v) => latexify_vec(v, include_block),
-            IterableKind::Graphs(v) => latexify_vec(v, include_block),
+            IterableKind::Numbers(v) => latexify_vec(v, include_block, options),
+            IterableKind::Integers(v) => latexify_vec(v, include_block, options),
+            IterableKind::PositiveIntegers(v) => latexify_vec(v, include_block, options),
+            IterableKind::Anys(v) => latexify_vec(v, include_block, options),
+            IterableKind::Strings(v) => latexify_vec(v, include_block, options),
+            IterableKind::Edges(v) => latexify_vec(v, include_block, options),
+            IterableKind::Nodes(v) => latexify_vec(v, include_block, options),
+            IterableKind::Tuples(v) => latexify_vec(v, include_block, options),
+            IterableKind::Booleans(v) => latexify_vec(v, include_block, options),
+            IterableKind::Graphs(v) => latexify_vec(v, include_block, options),
             IterableKind::Iterables(v) => {
                 let s = v
                     .iter()
-                    .map(|i| i.to_latex())
+                    .map(|i| i.to_latex_with(options))
                     .collect::<Vec<_>>()
                     .join("\\\\");
                 if include_block {
@@ -313,13 +419,13 @@ impl IterableKind {
         }
     }
 }
-fn latexify_vec<T>(v: &[T], include_block: bool) -> String
+fn latexify_vec<T>(v: &[T], include_block: bool, options: &LatexOptions) -> String
 where
     T: ToLatex,
 {
     let values = v
         .iter()
-        .map(|e| e.to_latex())
+        .map(|e| e.to_latex_with(options))
         .collect::<Vec<_>>()
         .join(" & ");
     if include_block {
@@ -330,7 +436,7 @@ where
 }
 
 impl ToLatex for IterableKind {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         match self {
             IterableKind::Iterables(v) => {
                 let depth = self.depth();
@@ -338,33 +444,35 @@ impl ToLatex for IterableKind {
                     //try to prettify for 2d matrices
                     let items = v
                         .iter()
-                        .map(|i| i.latexify(false))
+                        .map(|i| i.latexify(false, options))
                         .collect::<Vec<_>>()
                         .join(" \\\\ ");
                     format!("\\begin{{bmatrix}} {} \\end{{bmatrix}}", items)
                 } else {
-                    self.latexify(true)
+                    self.latexify(true, options)
                 }
             }
-            _ => self.latexify(true),
+            _ => self.latexify(true, options),
         }
     }
 }
 
 impl fmt::Display for IterableKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        //TODO should i turn this into a self.to_primitive_set()  and then iterate and stringify?
+        //the elements are written like the primitives they convert to
         let s = match self {
-            IterableKind::Numbers(v) => format!("{:?}", v),
-            IterableKind::Integers(v) => format!("{:?}", v),
-            IterableKind::Anys(v) => format!("{:?}", v),
-            IterableKind::PositiveIntegers(v) => format!("{:?}", v),
-            IterableKind::Strings(v) => format!("{:?}", v),
-            IterableKind::Edges(v) => format!("{:?}", v),
-            IterableKind::Nodes(v) => format!("{:?}", v),
-            IterableKind::Tuples(v) => format!("{:?}", v),
-            IterableKind::Booleans(v) => format!("{:?}", v),
-            IterableKind::Graphs(v) => format!("{:?}", v),
+            IterableKind::Numbers(v) => display_vec(v),
+            IterableKind::Integers(v) => display_vec(v),
+            IterableKind::Anys(v) => display_vec(v),
+            IterableKind::PositiveIntegers(v) => display_vec(v),
+            IterableKind::Strings(v) => {
+                display_vec(&v.iter().map(|s| format!("\"{}\"", s)).collect::<Vec<_>>())
+            }
+            IterableKind::Edges(v) => display_vec(v),
+            IterableKind::Nodes(v) => display_vec(v),
+            IterableKind::Tuples(v) => display_vec(v),
+            IterableKind::Booleans(v) => display_vec(v),
+            IterableKind::Graphs(v) => display_vec(v),
             IterableKind::Iterables(v) => {
                 let result = v
                     .iter()
@@ -378,6 +486,16 @@ impl fmt::Display for IterableKind {
     }
 }
 
+fn display_vec<T: fmt::Display>(v: &[T]) -> String {
+    format!(
+        "[{}]",
+        v.iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
 impl ApplyOp for IterableKind {
     type TargetType = PrimitiveKind;
     type Target = Primitive;
@@ -4,6 +4,7 @@ pub mod graph;
 pub mod iterable;
 pub mod primitive;
 pub mod primitive_traits;
+pub mod set;
 pub mod tuple;
 
 #[allow(unused_imports)]
@@ -13,4 +14,5 @@ pub use graph::*;
 pub use iterable::*;
 pub use primitive::*;
 pub use primitive_traits::*;
+pub use set::*;
 pub use tuple::*;
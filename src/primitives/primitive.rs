@@ -8,11 +8,12 @@ use serde::{Deserialize, Serialize};
 use super::{
     graph::{Graph, GraphEdge, GraphNode},
     iterable::IterableKind,
+    set::PrimitiveSet,
     tuple::Tuple,
 };
 use crate::math::{float_lt, float_ne};
 use crate::parser::model_transformer::TransformError;
-use crate::traits::ToLatex;
+use crate::traits::{LatexOptions, ToLatex};
 use crate::{
     bail_wrong_argument, match_or_bail,
     math::{BinOp, UnOp},
@@ -54,6 +55,8 @@ pub enum Primitive {
     GraphNode(GraphNode),
     /// An ordered collection of primitives
     Tuple(Tuple),
+    /// A collection of unique values
+    Set(PrimitiveSet),
     /// A boolean value
     Boolean(bool),
     /// Represents an undefined value
@@ -74,6 +77,7 @@ export type SerializedPrimitive =
     | { type: 'GraphEdge', value: SerializedGraphEdge }
     | { type: 'GraphNode', value: SerializedGraphNode }
     | { type: 'Tuple', value: SerializedTuple }
+    | { type: 'Set', value: SerializedPrimitiveSet }
     | { type: 'Boolean', value: boolean }
     | { type: 'Undefined' }
 "#;
@@ -99,6 +103,8 @@ pub enum PrimitiveKind {
     GraphNode,
     /// Tuple type containing a sequence of primitive kinds
     Tuple(Vec<PrimitiveKind>),
+    /// Set type containing unique elements of the specified kind
+    Set(Box<PrimitiveKind>),
     /// Boolean type
     Boolean,
     /// Undefined type
@@ -121,6 +127,7 @@ export type SerializedPrimitiveKind =
     | { type: 'GraphEdge' }
     | { type: 'GraphNode' }
     | { type: 'Tuple', value: SerializedPrimitiveKind[] }
+    | { type: 'Set', value: SerializedPrimitiveKind }
     | { type: 'Boolean' }
     | { type: 'Undefined' }
     | { type: 'Any' }
@@ -145,6 +152,7 @@ impl PrimitiveKind {
             Primitive::GraphEdge(_) => PrimitiveKind::GraphEdge,
             Primitive::GraphNode(_) => PrimitiveKind::GraphNode,
             Primitive::Tuple(t) => t.get_type(),
+            Primitive::Set(s) => s.get_type(),
             Primitive::Boolean(_) => PrimitiveKind::Boolean,
             Primitive::Undefined => PrimitiveKind::Undefined,
         }
@@ -164,7 +172,7 @@ impl PrimitiveKind {
         matches!(self, PrimitiveKind::Any)
     }
     pub fn is_iterable(&self) -> bool {
-        matches!(self, PrimitiveKind::Iterable(_))
+        matches!(self, PrimitiveKind::Iterable(_) | PrimitiveKind::Set(_))
     }
 
     /// Returns the types that this primitive kind can be spread into.
@@ -186,6 +194,10 @@ impl PrimitiveKind {
     }
 
     pub fn can_apply_binary_op(&self, op: BinOp, to: PrimitiveKind) -> bool {
+        //any value can be looked up in a set
+        if op.is_membership() {
+            return matches!(to, PrimitiveKind::Set(_) | PrimitiveKind::Any);
+        }
         match self {
             PrimitiveKind::Any => true, //make it fail at runtime
             PrimitiveKind::Undefined => false,
@@ -198,6 +210,7 @@ impl PrimitiveKind {
             PrimitiveKind::GraphNode => GraphNode::can_apply_binary_op(op, to),
             PrimitiveKind::Tuple(_) => Tuple::can_apply_binary_op(op, to),
             PrimitiveKind::Iterable(_) => IterableKind::can_apply_binary_op(op, to),
+            PrimitiveKind::Set(_) => PrimitiveSet::can_apply_binary_op(op, to),
             PrimitiveKind::String => String::can_apply_binary_op(op, to),
         }
     }
@@ -214,6 +227,7 @@ impl PrimitiveKind {
             PrimitiveKind::GraphNode => GraphNode::can_apply_unary_op(op),
             PrimitiveKind::Tuple(_) => Tuple::can_apply_unary_op(op),
             PrimitiveKind::Iterable(_) => IterableKind::can_apply_unary_op(op),
+            PrimitiveKind::Set(_) => PrimitiveSet::can_apply_unary_op(op),
             PrimitiveKind::String => String::can_apply_unary_op(op),
         }
     }
@@ -227,6 +241,7 @@ impl Display for PrimitiveKind {
             PrimitiveKind::Integer => "Integer".to_string(),
             PrimitiveKind::PositiveInteger => "PositiveInteger".to_string(),
             PrimitiveKind::Iterable(i) => format!("{}[]", i),
+            PrimitiveKind::Set(i) => format!("{{{}}}", i),
             PrimitiveKind::Graph => "Graph".to_string(),
             PrimitiveKind::GraphEdge => "GraphEdge".to_string(),
             PrimitiveKind::GraphNode => "GraphNode".to_string(),
@@ -365,7 +380,15 @@ impl Primitive {
     pub fn as_iterator(&self) -> Result<&IterableKind, TransformError> {
         match_or_bail!(
             PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
-            Primitive::Iterable(i) => Ok(i);
+            Primitive::Iterable(i) => Ok(i),
+            Primitive::Set(s) => Ok(s.elements());
+            (self)
+        )
+    }
+    pub fn as_set(&self) -> Result<&PrimitiveSet, TransformError> {
+        match_or_bail!(
+            PrimitiveKind::Set(Box::new(PrimitiveKind::Any)),
+            Primitive::Set(s) => Ok(s);
             (self)
         )
     }
@@ -376,20 +399,210 @@ impl Primitive {
             (self)
         )
     }
+
+    /// Consumes the primitive and returns the owned string.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The string value
+    /// * `Err(TransformError)` - If the value is not a string
+    pub fn into_string(self) -> Result<String, TransformError> {
+        match self {
+            Primitive::String(s) => Ok(s),
+            _ => bail_wrong_argument!(PrimitiveKind::String, self),
+        }
+    }
+
+    /// Consumes the primitive and returns its values as numbers, converting integer arrays.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<f64>)` - The numeric values
+    /// * `Err(TransformError)` - If the value is not an array of numbers
+    pub fn into_vec_f64(self) -> Result<Vec<f64>, TransformError> {
+        match self {
+            Primitive::Iterable(i) => i.into_numbers(),
+            _ => bail_wrong_argument!(
+                PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+                self
+            ),
+        }
+    }
+
+    /// Consumes the primitive and returns its values as strings.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - The string values
+    /// * `Err(TransformError)` - If the value is not an array of strings
+    pub fn into_vec_string(self) -> Result<Vec<String>, TransformError> {
+        match self {
+            Primitive::Iterable(i) => i.into_strings(),
+            _ => bail_wrong_argument!(
+                PrimitiveKind::Iterable(Box::new(PrimitiveKind::String)),
+                self
+            ),
+        }
+    }
+
+    /// Consumes the primitive and returns its values as integers, converting positive integer arrays.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<i64>)` - The integer values
+    /// * `Err(TransformError)` - If the value is not an array of integers
+    pub fn into_vec_i64(self) -> Result<Vec<i64>, TransformError> {
+        match self {
+            Primitive::Iterable(IterableKind::PositiveIntegers(v)) => {
+                Ok(v.into_iter().map(|n| n as i64).collect())
+            }
+            Primitive::Iterable(i) => i.into_integers(),
+            _ => bail_wrong_argument!(
+                PrimitiveKind::Iterable(Box::new(PrimitiveKind::Integer)),
+                self
+            ),
+        }
+    }
+
+    /// Consumes the primitive and returns its values as booleans.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<bool>)` - The boolean values
+    /// * `Err(TransformError)` - If the value is not an array of booleans
+    pub fn into_vec_bool(self) -> Result<Vec<bool>, TransformError> {
+        match self {
+            Primitive::Iterable(i) => i.into_booleans(),
+            _ => bail_wrong_argument!(
+                PrimitiveKind::Iterable(Box::new(PrimitiveKind::Boolean)),
+                self
+            ),
+        }
+    }
+
+    /// Consumes the primitive and returns its rows as numbers, like the coefficients of a matrix.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Vec<f64>>)` - The rows, converting integer rows to numbers
+    /// * `Err(TransformError)` - If the value is not an array of arrays of numbers
+    pub fn into_matrix_f64(self) -> Result<Vec<Vec<f64>>, TransformError> {
+        match self {
+            Primitive::Iterable(i) => i
+                .into_iterables()?
+                .into_iter()
+                .map(|row| row.into_numbers())
+                .collect(),
+            _ => bail_wrong_argument!(
+                PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+                    PrimitiveKind::Number
+                )))),
+                self
+            ),
+        }
+    }
+
+    /// Consumes the primitive and returns its edges as `(from, to, weight)`,
+    /// edges without a weight have a weight of one.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(String, String, f64)>)` - The edges, if the value is a graph or an array of edges
+    /// * `Err(TransformError)` - Otherwise
+    pub fn into_vec_edges(self) -> Result<Vec<(String, String, f64)>, TransformError> {
+        let edges = match self {
+            Primitive::Graph(g) => g.to_edges(),
+            Primitive::Iterable(i) => i.into_edges()?,
+            _ => {
+                return bail_wrong_argument!(
+                    PrimitiveKind::Iterable(Box::new(PrimitiveKind::GraphEdge)),
+                    self
+                )
+            }
+        };
+        Ok(edges
+            .into_iter()
+            .map(|e| (e.from, e.to, e.weight.unwrap_or(1.0)))
+            .collect())
+    }
+}
+
+impl TryFrom<Primitive> for f64 {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.as_number_cast()
+    }
+}
+
+impl TryFrom<Primitive> for i64 {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.as_integer_cast()
+    }
+}
+
+impl TryFrom<Primitive> for bool {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.as_boolean()
+    }
+}
+
+impl TryFrom<Primitive> for String {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.into_string()
+    }
+}
+
+impl TryFrom<Primitive> for Vec<f64> {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.into_vec_f64()
+    }
+}
+
+impl TryFrom<Primitive> for Vec<String> {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.into_vec_string()
+    }
+}
+
+impl TryFrom<Primitive> for Vec<i64> {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.into_vec_i64()
+    }
+}
+
+impl TryFrom<Primitive> for Vec<bool> {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.into_vec_bool()
+    }
+}
+
+impl TryFrom<Primitive> for Vec<Vec<f64>> {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.into_matrix_f64()
+    }
+}
+
+impl TryFrom<Primitive> for Vec<(String, String, f64)> {
+    type Error = TransformError;
+    fn try_from(value: Primitive) -> Result<Self, Self::Error> {
+        value.into_vec_edges()
+    }
 }
 
 impl ToLatex for Primitive {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         match self {
-            Primitive::Number(n) => n.to_latex(),
-            Primitive::Integer(n) => n.to_latex(),
-            Primitive::PositiveInteger(n) => n.to_latex(),
-            Primitive::String(s) => s.to_latex(),
-            Primitive::Iterable(i) => i.to_latex(),
-            Primitive::Graph(g) => g.to_latex(),
-            Primitive::GraphEdge(e) => e.to_latex(),
-            Primitive::GraphNode(n) => n.to_latex(),
-            Primitive::Tuple(v) => v.to_latex(),
+            Primitive::Number(n) => n.to_latex_with(options),
+            Primitive::Integer(n) => n.to_latex_with(options),
+            Primitive::PositiveInteger(n) => n.to_latex_with(options),
+            Primitive::String(s) => s.to_latex_with(options),
+            Primitive::Iterable(i) => i.to_latex_with(options),
+            Primitive::Graph(g) => g.to_latex_with(options),
+            Primitive::GraphEdge(e) => e.to_latex_with(options),
+            Primitive::GraphNode(n) => n.to_latex_with(options),
+            Primitive::Tuple(v) => v.to_latex_with(options),
+            Primitive::Set(s) => s.to_latex_with(options),
             Primitive::Boolean(b) => b.to_string(),
             Primitive::Undefined => "undefined".to_string(),
         }
@@ -407,7 +620,8 @@ impl fmt::Display for Primitive {
             Primitive::Graph(g) => g.to_string(),
             Primitive::GraphEdge(e) => e.to_string(),
             Primitive::GraphNode(n) => n.to_string(),
-            Primitive::Tuple(v) => format!("{:?}", v),
+            Primitive::Tuple(v) => v.to_string(),
+            Primitive::Set(s) => s.to_string(),
             Primitive::Boolean(b) => b.to_string(),
             Primitive::Undefined => "undefined".to_string(),
         };
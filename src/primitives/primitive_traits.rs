@@ -124,10 +124,21 @@ impl ApplyOp for Primitive {
     type TargetType = PrimitiveKind;
     type Error = OperatorError;
     fn apply_binary_op(&self, op: BinOp, to: &Self::Target) -> Result<Primitive, OperatorError> {
+        if op.is_membership() {
+            return match to {
+                Primitive::Set(s) => Ok(Primitive::Boolean(s.contains(self) == (op == BinOp::In))),
+                _ => Err(OperatorError::incompatible_type(
+                    op,
+                    PrimitiveKind::Set(Box::new(PrimitiveKind::Any)),
+                    to.get_type(),
+                )),
+            };
+        }
         match self {
             Primitive::Boolean(b) => b.apply_binary_op(op, to),
             Primitive::String(s) => s.apply_binary_op(op, to),
             Primitive::Tuple(t) => t.apply_binary_op(op, to),
+            Primitive::Set(s) => s.apply_binary_op(op, to),
             Primitive::GraphNode(gn) => gn.apply_binary_op(op, to),
             Primitive::GraphEdge(ge) => ge.apply_binary_op(op, to),
             Primitive::Graph(g) => g.apply_binary_op(op, to),
@@ -149,6 +160,7 @@ impl ApplyOp for Primitive {
             Primitive::Boolean(b) => b.apply_unary_op(op),
             Primitive::String(s) => s.apply_unary_op(op),
             Primitive::Tuple(t) => t.apply_unary_op(op),
+            Primitive::Set(s) => s.apply_unary_op(op),
             Primitive::GraphNode(gn) => gn.apply_unary_op(op),
             Primitive::GraphEdge(ge) => ge.apply_unary_op(op),
             Primitive::Graph(g) => g.apply_unary_op(op),
@@ -167,6 +179,7 @@ impl Spreadable for Primitive {
             Primitive::Boolean(b) => b.to_primitive_set(),
             Primitive::String(s) => s.to_primitive_set(),
             Primitive::Tuple(t) => t.to_primitive_set(),
+            Primitive::Set(s) => s.to_primitive_set(),
             Primitive::GraphNode(gn) => gn.to_primitive_set(),
             Primitive::GraphEdge(ge) => ge.to_primitive_set(),
             Primitive::Graph(g) => g.to_primitive_set(),
@@ -0,0 +1,179 @@
+use core::fmt;
+use std::collections::HashSet;
+
+#[allow(unused_imports)]
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::math::{BinOp, UnOp};
+use crate::parser::iterable_utils::flatten_primitive_array_values;
+use crate::parser::model_transformer::TransformError;
+use crate::traits::{LatexOptions, ToLatex};
+
+use super::{
+    iterable::IterableKind,
+    primitive::{Primitive, PrimitiveKind},
+    primitive_traits::{ApplyOp, OperatorError, Spreadable},
+};
+
+/// A collection of unique primitives, with fast membership testing.
+///
+/// Duplicated elements are removed keeping their first occurrence, iterating over
+/// the set yields the elements in the order they were first inserted.
+///
+/// # Example
+/// ```rust
+/// use rooc::{IterableKind, Primitive, PrimitiveSet};
+///
+/// let cities = PrimitiveSet::new(IterableKind::Strings(vec![
+///     "Rome".to_string(),
+///     "Milan".to_string(),
+///     "Rome".to_string(),
+/// ]));
+/// assert_eq!(cities.len(), 2);
+/// assert!(cities.contains(&Primitive::String("Milan".to_string())));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "IterableKind", into = "IterableKind")]
+pub struct PrimitiveSet {
+    elements: IterableKind,
+    keys: HashSet<String>,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IPrimitiveSet: &'static str = r#"
+export type SerializedPrimitiveSet = SerializedIterable
+"#;
+
+/// Two primitives are the same element of a set if they are written the same way,
+/// so the number 1 and the integer 1 are the same element.
+fn element_key(element: &Primitive) -> String {
+    element.to_string()
+}
+
+impl PrimitiveSet {
+    /// Creates a new set from the elements of an iterable, removing duplicates.
+    ///
+    /// # Arguments
+    /// * `elements` - The elements of the set
+    pub fn new(elements: IterableKind) -> Self {
+        let mut keys = HashSet::new();
+        let unique = elements
+            .to_primitives()
+            .into_iter()
+            .filter(|e| keys.insert(element_key(e)))
+            .collect::<Vec<_>>();
+        Self {
+            elements: flatten_primitive_array_values(unique),
+            keys,
+        }
+    }
+
+    /// Checks if the value is an element of the set.
+    ///
+    /// # Arguments
+    /// * `value` - The value to look for
+    pub fn contains(&self, value: &Primitive) -> bool {
+        self.keys.contains(&element_key(value))
+    }
+
+    /// Returns the elements of the set, in insertion order.
+    pub fn elements(&self) -> &IterableKind {
+        &self.elements
+    }
+
+    /// Consumes the set and returns its elements, in insertion order.
+    pub fn into_elements(self) -> IterableKind {
+        self.elements
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Gets the type of the set, a `PrimitiveKind::Set` of the type of its elements.
+    pub fn get_type(&self) -> PrimitiveKind {
+        PrimitiveKind::Set(Box::new(self.elements.inner_type()))
+    }
+}
+
+impl From<IterableKind> for PrimitiveSet {
+    fn from(elements: IterableKind) -> Self {
+        Self::new(elements)
+    }
+}
+
+impl From<PrimitiveSet> for IterableKind {
+    fn from(set: PrimitiveSet) -> Self {
+        set.elements
+    }
+}
+
+impl PartialEq for PrimitiveSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.keys == other.keys
+    }
+}
+
+impl fmt::Display for PrimitiveSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = format!(
+            "{{{}}}",
+            self.elements
+                .clone()
+                .to_primitives()
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        f.write_str(&s)
+    }
+}
+
+impl ToLatex for PrimitiveSet {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        format!(
+            "\\{{{}\\}}",
+            self.elements
+                .clone()
+                .to_primitives()
+                .iter()
+                .map(|e| e.to_latex_with(options))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl ApplyOp for PrimitiveSet {
+    type TargetType = PrimitiveKind;
+    type Target = Primitive;
+    type Error = OperatorError;
+    fn apply_binary_op(&self, op: BinOp, _to: &Primitive) -> Result<Primitive, OperatorError> {
+        Err(OperatorError::unsupported_bin_operation(
+            op,
+            self.get_type(),
+        ))
+    }
+    fn apply_unary_op(&self, op: UnOp) -> Result<Self::Target, Self::Error> {
+        Err(OperatorError::unsupported_un_operation(op, self.get_type()))
+    }
+    fn can_apply_binary_op(_: BinOp, _: Self::TargetType) -> bool {
+        false
+    }
+    fn can_apply_unary_op(_: UnOp) -> bool {
+        false
+    }
+}
+
+impl Spreadable for PrimitiveSet {
+    fn to_primitive_set(self) -> Result<Vec<Primitive>, TransformError> {
+        Ok(self.elements.to_primitives())
+    }
+}
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::math::{BinOp, UnOp};
 use crate::parser::model_transformer::TransformError;
-use crate::traits::ToLatex;
+use crate::traits::{LatexOptions, ToLatex};
 
 use super::{
     primitive::{Primitive, PrimitiveKind},
@@ -106,12 +106,12 @@ impl Tuple {
 }
 
 impl ToLatex for Tuple {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         format!(
             "({})",
             self.0
                 .iter()
-                .map(|e| e.to_latex())
+                .map(|e| e.to_latex_with(options))
                 .collect::<Vec<_>>()
                 .join(",\\")
         )
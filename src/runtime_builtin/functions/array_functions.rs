@@ -1,6 +1,8 @@
 use serde::Serialize;
 
-use super::function_traits::{default_wrong_number_of_arguments, default_wrong_type, RoocFunction};
+use super::function_traits::{
+    default_wrong_number_of_arguments, default_wrong_type, FunctionSignature, RoocFunction,
+};
 use crate::parser::il::PreExp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
@@ -76,6 +78,17 @@ impl RoocFunction for EnumerateArray {
         }
     }
 
+    fn signature(&self) -> FunctionSignature {
+        FunctionSignature {
+            name: self.function_name(),
+            params: vec![PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any))],
+            returns: PrimitiveKind::Iterable(Box::new(PrimitiveKind::Tuple(vec![
+                PrimitiveKind::Any,
+                PrimitiveKind::PositiveInteger,
+            ]))),
+        }
+    }
+
     fn type_check(
         &self,
         args: &[PreExp],
@@ -152,7 +165,7 @@ impl RoocFunction for LenOfIterableFn {
         match args[..] {
             [ref of_iterable] => {
                 let arg_type = of_iterable.get_type(context, fn_context);
-                if !matches!(arg_type, PrimitiveKind::Iterable(_)) {
+                if !arg_type.is_iterable() {
                     return Err(TransformError::from_wrong_type(
                         PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any)),
                         arg_type,
@@ -251,6 +264,19 @@ impl RoocFunction for ZipArrays {
         "zip".to_string()
     }
 
+    //zip takes any number of iterables, the signature shows the usual two
+    fn signature(&self) -> FunctionSignature {
+        let iterable = PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any));
+        FunctionSignature {
+            name: self.function_name(),
+            params: vec![iterable.clone(), iterable],
+            returns: PrimitiveKind::Iterable(Box::new(PrimitiveKind::Tuple(vec![
+                PrimitiveKind::Any,
+                PrimitiveKind::Any,
+            ]))),
+        }
+    }
+
     fn type_check(
         &self,
         args: &[PreExp],
@@ -2,7 +2,7 @@ use crate::parser::il::PreExp;
 use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
 use crate::runtime_builtin::rooc_std::{std_fn_to_latex, std_fn_to_string};
-use crate::traits::{escape_latex, ToLatex};
+use crate::traits::{escape_latex, LatexOptions, ToLatex};
 use crate::type_checker::type_checker_context::{FunctionContext, TypeCheckerContext};
 use crate::{
     primitives::{Primitive, PrimitiveKind},
@@ -10,6 +10,7 @@ use crate::{
     utils::InputSpan,
 };
 use core::fmt;
+use indexmap::IndexMap;
 use pest::Span;
 use serde::Serialize;
 use std::fmt::Debug;
@@ -38,6 +39,15 @@ impl FunctionCall {
     }
 }
 
+/// The signature of a function, with the types of its parameters and of its result,
+/// used by tooling like autocompletion.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FunctionSignature {
+    pub name: String,
+    pub params: Vec<PrimitiveKind>,
+    pub returns: PrimitiveKind,
+}
+
 /// The default type check implementation, it performs type checking of function arguments against expected types.
 ///
 /// # Arguments
@@ -182,9 +192,9 @@ impl fmt::Display for FunctionCall {
 }
 
 impl ToLatex for FunctionCall {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
         //some builtin functions have
-        std_fn_to_latex(self).unwrap_or(default_rooc_function_to_latex(self))
+        std_fn_to_latex(self, options).unwrap_or(default_rooc_function_to_latex(self, options))
     }
 }
 
@@ -192,14 +202,15 @@ impl ToLatex for FunctionCall {
 ///
 /// # Arguments
 /// * `function` - The function call to convert
-pub fn default_rooc_function_to_latex(function: &FunctionCall) -> String {
+/// * `options` - The options passed down to the arguments
+pub fn default_rooc_function_to_latex(function: &FunctionCall, options: &LatexOptions) -> String {
     format!(
         "{}({})",
         escape_latex(&function.name),
         function
             .args
             .iter()
-            .map(|p| p.to_latex())
+            .map(|p| p.to_latex_with(options))
             .collect::<Vec<String>>()
             .join(",\\")
     )
@@ -286,4 +297,21 @@ pub trait RoocFunction: Debug {
             fn_context,
         )
     }
+
+    /// Returns the signature of the function when the types of its arguments are not known,
+    /// by default the type signature and return type of a call without arguments.
+    fn signature(&self) -> FunctionSignature {
+        let functions = IndexMap::new();
+        let fn_context = FunctionContext::new(&functions, &functions);
+        let context = TypeCheckerContext::default();
+        FunctionSignature {
+            name: self.function_name(),
+            params: self
+                .type_signature(&[], &context, &fn_context)
+                .into_iter()
+                .map(|(_, kind)| kind)
+                .collect(),
+            returns: self.return_type(&[], &context, &fn_context),
+        }
+    }
 }
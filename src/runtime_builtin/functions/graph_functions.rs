@@ -12,7 +12,7 @@ use crate::parser::model_transformer::TransformError;
 use crate::parser::model_transformer::TransformerContext;
 use crate::type_checker::type_checker_context::FunctionContext;
 use crate::{
-    primitives::{IterableKind, Primitive, PrimitiveKind},
+    primitives::{IterableKind, Primitive, PrimitiveKind, Tuple},
     type_checker::type_checker_context::{TypeCheckerContext, WithType},
 };
 
@@ -64,6 +64,63 @@ impl RoocFunction for EdgesOfGraphFn {
     }
 }
 
+/// Returns the edges of a graph as `(from, to, weight)` tuples, unweighted edges have a weight of 1
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct WeightedEdgesOfGraphFn {}
+impl RoocFunction for WeightedEdgesOfGraphFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_graph] => {
+                let graph = of_graph.as_graph(context, fn_context)?;
+                let edges = graph
+                    .to_edges()
+                    .into_iter()
+                    .map(|edge| {
+                        Tuple::new(vec![
+                            Primitive::String(edge.from),
+                            Primitive::String(edge.to),
+                            Primitive::Number(edge.weight.unwrap_or(1.0)),
+                        ])
+                    })
+                    .collect();
+                Ok(Primitive::Iterable(IterableKind::Tuples(edges)))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("of_graph".to_string(), PrimitiveKind::Graph)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::Tuple(vec![
+            PrimitiveKind::String,
+            PrimitiveKind::String,
+            PrimitiveKind::Number,
+        ])))
+    }
+
+    fn function_name(&self) -> String {
+        "weighted_edges".to_string()
+    }
+}
+
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 #[derive(Debug, Serialize, Clone)]
 pub struct NodesOfGraphFn {
@@ -254,3 +311,228 @@ impl RoocFunction for NeighboursOfNodeInGraphFn {
         }
     }
 }
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ComplementOfGraphFn {}
+impl RoocFunction for ComplementOfGraphFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_graph] => {
+                let graph = of_graph.as_graph(context, fn_context)?;
+                Ok(Primitive::Graph(graph.complement()))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![("of_graph".to_string(), PrimitiveKind::Graph)]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Graph
+    }
+
+    fn function_name(&self) -> String {
+        "complement".to_string()
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TransitiveClosureFn {}
+impl RoocFunction for TransitiveClosureFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        let (of_graph, include_self) = match args[..] {
+            [ref of_graph] => (of_graph, false),
+            [ref of_graph, ref include_self] => {
+                (of_graph, include_self.as_boolean(context, fn_context)?)
+            }
+            _ => return Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        };
+        let graph = of_graph.as_graph(context, fn_context)?;
+        let closure = graph
+            .transitive_closure(include_self)
+            .map_err(|e| e.add_span(of_graph.span()))?;
+        Ok(Primitive::Graph(closure))
+    }
+
+    //the flag to add an edge from a node to itself is optional
+    fn type_signature(
+        &self,
+        args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        let mut signature = vec![("of_graph".to_string(), PrimitiveKind::Graph)];
+        if args.len() == 2 {
+            signature.push(("include_self".to_string(), PrimitiveKind::Boolean));
+        }
+        signature
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Graph
+    }
+
+    fn function_name(&self) -> String {
+        "transitive_closure".to_string()
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AllPairsShortestFn {}
+impl RoocFunction for AllPairsShortestFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_graph, ref error_on_negative_cycle] => {
+                let graph = of_graph.as_graph(context, fn_context)?;
+                let error_on_negative_cycle =
+                    error_on_negative_cycle.as_boolean(context, fn_context)?;
+                let distances = graph
+                    .shortest_paths(error_on_negative_cycle)
+                    .map_err(|e| e.add_span(of_graph.span()))?;
+                Ok(Primitive::Iterable(IterableKind::Iterables(
+                    distances.into_iter().map(IterableKind::Numbers).collect(),
+                )))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("of_graph".to_string(), PrimitiveKind::Graph),
+            (
+                "error_on_negative_cycle".to_string(),
+                PrimitiveKind::Boolean,
+            ),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Iterable(Box::new(PrimitiveKind::Iterable(Box::new(
+            PrimitiveKind::Number,
+        ))))
+    }
+
+    fn function_name(&self) -> String {
+        "shortest_paths".to_string()
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MaxFlowFn {
+    pub edge_flows: bool,
+}
+impl RoocFunction for MaxFlowFn {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_graph, ref source, ref sink] => {
+                let graph = of_graph.as_graph(context, fn_context)?;
+                let source = source.as_string(context, fn_context)?;
+                let sink = sink.as_string(context, fn_context)?;
+                let (value, edges) = graph
+                    .max_flow(&source, &sink)
+                    .map_err(|e| e.add_span(of_graph.span()))?;
+                if !self.edge_flows {
+                    return Ok(Primitive::Number(value));
+                }
+                let flows = edges
+                    .into_iter()
+                    .map(|edge| {
+                        Tuple::new(vec![
+                            Primitive::String(edge.from),
+                            Primitive::String(edge.to),
+                            Primitive::Number(edge.weight.unwrap_or(0.0)),
+                        ])
+                    })
+                    .collect();
+                Ok(Primitive::Iterable(IterableKind::Tuples(flows)))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![
+            ("of_graph".to_string(), PrimitiveKind::Graph),
+            ("source".to_string(), PrimitiveKind::String),
+            ("sink".to_string(), PrimitiveKind::String),
+        ]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        if self.edge_flows {
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Tuple(vec![
+                PrimitiveKind::String,
+                PrimitiveKind::String,
+                PrimitiveKind::Number,
+            ])))
+        } else {
+            PrimitiveKind::Number
+        }
+    }
+
+    fn function_name(&self) -> String {
+        if self.edge_flows {
+            "max_flow_edges".to_string()
+        } else {
+            "max_flow".to_string()
+        }
+    }
+}
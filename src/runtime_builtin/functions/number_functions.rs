@@ -120,3 +120,85 @@ impl RoocFunction for NumericRange {
         }
     }
 }
+
+/// Computes the norm of a constant array of numbers, either the sum of the absolute
+/// values or the largest absolute value
+#[derive(Debug, Serialize, Clone)]
+pub struct ArrayNorm {
+    pub infinity_norm: bool,
+}
+
+impl RoocFunction for ArrayNorm {
+    fn call(
+        &self,
+        args: &[PreExp],
+        context: &TransformerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<Primitive, TransformError> {
+        match args[..] {
+            [ref of_array] => {
+                let values = of_array
+                    .as_primitive(context, fn_context)?
+                    .into_vec_f64()
+                    .map_err(|e| e.add_span(of_array.span()))?
+                    .into_iter()
+                    .map(f64::abs);
+                let norm = if self.infinity_norm {
+                    values.fold(0.0, f64::max)
+                } else {
+                    values.sum()
+                };
+                Ok(Primitive::Number(norm))
+            }
+            _ => Err(default_wrong_number_of_arguments(self, args, fn_context)),
+        }
+    }
+
+    fn type_signature(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> Vec<(String, PrimitiveKind)> {
+        vec![(
+            "of_array".to_string(),
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+        )]
+    }
+
+    fn return_type(
+        &self,
+        _args: &[PreExp],
+        _context: &TypeCheckerContext,
+        _fn_context: &FunctionContext,
+    ) -> PrimitiveKind {
+        PrimitiveKind::Number
+    }
+
+    fn function_name(&self) -> String {
+        if self.infinity_norm {
+            "norm_inf".to_string()
+        } else {
+            "norm1".to_string()
+        }
+    }
+
+    fn type_check(
+        &self,
+        args: &[PreExp],
+        context: &mut TypeCheckerContext,
+        fn_context: &FunctionContext,
+    ) -> Result<(), TransformError> {
+        match args[..] {
+            [ref of_array] => match of_array.get_type(context, fn_context) {
+                PrimitiveKind::Iterable(kind) if kind.is_numeric() => Ok(()),
+                arg_type => Err(TransformError::from_wrong_type(
+                    PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+                    arg_type,
+                    of_array.span().clone(),
+                )),
+            },
+            _ => Err(default_wrong_type(args, self, context, fn_context)),
+        }
+    }
+}
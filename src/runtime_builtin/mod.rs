@@ -4,4 +4,5 @@ mod rooc_std;
 
 pub use functions::*;
 pub(crate) use reserved_tokens::*;
+pub use rooc_std::available_functions;
 pub(crate) use rooc_std::*;
@@ -1,12 +1,13 @@
 use crate::functions::ZipArrays;
 use crate::parser::il::PreExp;
-use crate::runtime_builtin::functions::NumericRange;
 use crate::runtime_builtin::functions::{
-    EdgesOfGraphFn, NeighbourOfNodeFn, NeighboursOfNodeInGraphFn, NodesOfGraphFn,
+    AllPairsShortestFn, ComplementOfGraphFn, EdgesOfGraphFn, MaxFlowFn, NeighbourOfNodeFn,
+    NeighboursOfNodeInGraphFn, NodesOfGraphFn, TransitiveClosureFn, WeightedEdgesOfGraphFn,
 };
+use crate::runtime_builtin::functions::{ArrayNorm, NumericRange};
 use crate::runtime_builtin::functions::{EnumerateArray, LenOfIterableFn};
-use crate::runtime_builtin::functions::{FunctionCall, RoocFunction};
-use crate::traits::ToLatex;
+use crate::runtime_builtin::functions::{FunctionCall, FunctionSignature, RoocFunction};
+use crate::traits::{LatexOptions, ToLatex};
 use crate::{Constant, Primitive};
 use indexmap::IndexMap;
 
@@ -26,6 +27,10 @@ pub fn make_std() -> IndexMap<String, Box<dyn RoocFunction>> {
             shorthand_name: true,
         }),
     );
+    m.insert(
+        "weighted_edges".to_string(),
+        Box::new(WeightedEdgesOfGraphFn {}),
+    );
     m.insert("len".to_string(), Box::new(LenOfIterableFn {}));
     m.insert(
         "nodes".to_string(),
@@ -63,6 +68,23 @@ pub fn make_std() -> IndexMap<String, Box<dyn RoocFunction>> {
             shorthand_name: true,
         }),
     );
+    m.insert("complement".to_string(), Box::new(ComplementOfGraphFn {}));
+    m.insert(
+        "transitive_closure".to_string(),
+        Box::new(TransitiveClosureFn {}),
+    );
+    m.insert(
+        "shortest_paths".to_string(),
+        Box::new(AllPairsShortestFn {}),
+    );
+    m.insert(
+        "max_flow".to_string(),
+        Box::new(MaxFlowFn { edge_flows: false }),
+    );
+    m.insert(
+        "max_flow_edges".to_string(),
+        Box::new(MaxFlowFn { edge_flows: true }),
+    );
     m.insert(
         "enumerate".to_string(),
         Box::new(EnumerateArray {
@@ -80,9 +102,39 @@ pub fn make_std() -> IndexMap<String, Box<dyn RoocFunction>> {
     m.insert("difference".to_string(), Box::new(ArrayDifference {}));
     m.insert("union".to_string(), Box::new(ArrayUnion {}));
     m.insert("intersection".to_string(), Box::new(ArrayIntersection {}));
+    m.insert(
+        "norm1".to_string(),
+        Box::new(ArrayNorm {
+            infinity_norm: false,
+        }),
+    );
+    m.insert(
+        "norm_inf".to_string(),
+        Box::new(ArrayNorm {
+            infinity_norm: true,
+        }),
+    );
     m
 }
 
+/// Returns the signatures of the builtin functions, under the names they can be called with.
+///
+/// # Example
+/// ```rust
+/// use rooc::available_functions;
+/// let functions = available_functions();
+/// assert!(functions.iter().any(|f| f.name == "len"));
+/// ```
+pub fn available_functions() -> Vec<FunctionSignature> {
+    make_std()
+        .into_iter()
+        .map(|(name, f)| FunctionSignature {
+            name,
+            ..f.signature()
+        })
+        .collect()
+}
+
 pub fn make_std_constants() -> Vec<Constant> {
     vec![
         Constant::from_primitive("Infinity", Primitive::Number(f64::INFINITY)),
@@ -91,7 +143,7 @@ pub fn make_std_constants() -> Vec<Constant> {
     ]
 }
 
-pub fn std_fn_to_latex(fun: &FunctionCall) -> Option<String> {
+pub fn std_fn_to_latex(fun: &FunctionCall, options: &LatexOptions) -> Option<String> {
     match fun.name.as_str() {
         "range" => {
             if let [ref from, ref to, known_inclusive] = &fun.args[..] {
@@ -106,14 +158,14 @@ pub fn std_fn_to_latex(fun: &FunctionCall) -> Option<String> {
                         "\\dots"
                     };
                     let from = if from.is_leaf() {
-                        from.to_latex()
+                        from.to_latex_with(options)
                     } else {
-                        format!("({})", from.to_latex())
+                        format!("({})", from.to_latex_with(options))
                     };
                     let to = if to.is_leaf() {
-                        to.to_latex()
+                        to.to_latex_with(options)
                     } else {
-                        format!("({})", to.to_latex())
+                        format!("({})", to.to_latex_with(options))
                     };
                     Some(format!("\\left\\{{{},{},{}\\right\\}}", from, range, to))
                 } else {
@@ -127,8 +179,8 @@ pub fn std_fn_to_latex(fun: &FunctionCall) -> Option<String> {
             if let [ref first, ref second] = &fun.args[..] {
                 Some(format!(
                     "\\left\\{{{}\\setminus {}\\right\\}}",
-                    first.to_latex(),
-                    second.to_latex()
+                    first.to_latex_with(options),
+                    second.to_latex_with(options)
                 ))
             } else {
                 None
@@ -138,8 +190,8 @@ pub fn std_fn_to_latex(fun: &FunctionCall) -> Option<String> {
             if let [ref first, ref second] = &fun.args[..] {
                 Some(format!(
                     "\\left\\{{{}\\cup {}\\right\\}}",
-                    first.to_latex(),
-                    second.to_latex()
+                    first.to_latex_with(options),
+                    second.to_latex_with(options)
                 ))
             } else {
                 None
@@ -149,8 +201,35 @@ pub fn std_fn_to_latex(fun: &FunctionCall) -> Option<String> {
             if let [ref first, ref second] = &fun.args[..] {
                 Some(format!(
                     "\\left\\{{{}\\cap {}\\right\\}}",
-                    first.to_latex(),
-                    second.to_latex()
+                    first.to_latex_with(options),
+                    second.to_latex_with(options)
+                ))
+            } else {
+                None
+            }
+        }
+        "complement" => {
+            if let [ref of_graph] = &fun.args[..] {
+                Some(format!("\\overline{{{}}}", of_graph.to_latex_with(options)))
+            } else {
+                None
+            }
+        }
+        "norm1" => {
+            if let [ref of_array] = &fun.args[..] {
+                Some(format!(
+                    "\\left\\|{}\\right\\|_1",
+                    of_array.to_latex_with(options)
+                ))
+            } else {
+                None
+            }
+        }
+        "norm_inf" => {
+            if let [ref of_array] = &fun.args[..] {
+                Some(format!(
+                    "\\left\\|{}\\right\\|_\\infty",
+                    of_array.to_latex_with(options)
                 ))
             } else {
                 None
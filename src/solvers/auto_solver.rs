@@ -68,7 +68,7 @@ pub fn auto_solver(lp: &LinearModel) -> Result<LpSolution<MILPValue>, SolverErro
     }
 }
 
-fn bool_to_milp(val: LpSolution<bool>) -> LpSolution<MILPValue> {
+pub(crate) fn bool_to_milp(val: LpSolution<bool>) -> LpSolution<MILPValue> {
     let values = val
         .assignment()
         .iter()
@@ -80,7 +80,7 @@ fn bool_to_milp(val: LpSolution<bool>) -> LpSolution<MILPValue> {
     LpSolution::new(values, val.value())
 }
 
-fn int_bool_to_milp(val: LpSolution<IntOrBoolValue>) -> LpSolution<MILPValue> {
+pub(crate) fn int_bool_to_milp(val: LpSolution<IntOrBoolValue>) -> LpSolution<MILPValue> {
     let values = val
         .assignment()
         .iter()
@@ -98,14 +98,6 @@ fn int_bool_to_milp(val: LpSolution<IntOrBoolValue>) -> LpSolution<MILPValue> {
     LpSolution::new(values, val.value())
 }
 
-fn real_to_milp(val: LpSolution<f64>) -> LpSolution<MILPValue> {
-    let values = val
-        .assignment()
-        .iter()
-        .map(|v| Assignment {
-            value: MILPValue::Real(v.value),
-            name: v.name.clone(),
-        })
-        .collect();
-    LpSolution::new(values, val.value())
+pub(crate) fn real_to_milp(val: LpSolution<f64>) -> LpSolution<MILPValue> {
+    val.map_values(MILPValue::Real)
 }
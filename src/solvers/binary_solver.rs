@@ -34,6 +34,7 @@ use num_traits::ToPrimitive;
 /// let solution = solve_binary_lp_problem(&model).unwrap();
 /// ```
 pub fn solve_binary_lp_problem(lp: &LinearModel) -> Result<LpSolution<bool>, SolverError> {
+    lp.check_solvable()?;
     let non_binary_variables =
         find_invalid_variables(lp.domain(), |var| matches!(var, VariableType::Boolean));
     if !non_binary_variables.is_empty() {
@@ -1,15 +1,27 @@
 use crate::math::{Comparison, OptimizationType, VariableType};
 use crate::parser::model_transformer::DomainVariable;
+#[allow(unused_imports)]
+use crate::prelude::*;
+use crate::solvers::SolveStats;
+use crate::traits::{DisplayWithOptions, FormatOptions};
+use crate::transformers::LinearModel;
 use copper::views::{Times, ViewExt};
 use copper::{VarId, VarIdBinary};
 use indexmap::IndexMap;
 use num_traits::ToPrimitive;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 #[allow(unused)]
 use std::fmt::{write, Display, Formatter};
+use term_table::row::Row;
+use term_table::table_cell::TableCell;
+use term_table::Table;
 
 /// Represents errors that can occur during linear programming problem solving.
-#[derive(Debug)]
+///
+/// The `Display` implementation describes the error and, where possible, suggests a remedy,
+/// which is also available through [`SolverError::remedy`].
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", content = "value")]
 pub enum SolverError {
     /// Variables in the problem domain have invalid types.
     /// - `expected`: List of valid variable types
@@ -24,6 +36,11 @@ pub enum SolverError {
     /// - `value`: The value that was too large
     TooLarge { name: String, value: f64 },
 
+    /// A number of the model is NaN or infinite.
+    /// - `position`: Where the number appears in the model
+    /// - `value`: The non finite number
+    NumericalInstability { position: String, value: f64 },
+
     /// The solver failed to find a solution.
     DidNotSolve,
 
@@ -36,9 +53,21 @@ pub enum SolverError {
     /// A general error with a custom message.
     Other(String),
 
+    /// A value was given for a variable that is not in the model.
+    /// - `name`: Name of the variable
+    UnknownVariable(String),
+
     /// The solver reached its iteration limit before finding a solution.
     LimitReached,
 
+    /// The integer search ran out of its node or time budget before proving optimality or infeasibility.
+    /// - `incumbent`: The best feasible solution found so far, with its optimality gap, if any
+    /// - `nodes_explored`: Number of nodes of the search tree that were explored
+    IntegerSearchIncomplete {
+        incumbent: Option<Box<LpSolution<f64>>>,
+        nodes_explored: usize,
+    },
+
     /// The optimization type is not supported by the solver.
     /// - `expected`: List of supported optimization types
     /// - `got`: The unsupported optimization type that was used
@@ -56,25 +85,167 @@ pub enum SolverError {
     },
 }
 
-impl std::fmt::Display for SolverError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+pub const ISolverError: &'static str = r#"
+export type SerializedSolverError = {
+    type: "InvalidDomain",
+    value: {
+        expected: VariableType[],
+        got: [string, DomainVariable][]
+    }
+} | {
+    type: "TooLarge",
+    value: {
+        name: string,
+        value: number
+    }
+} | {
+    type: "NumericalInstability",
+    value: {
+        position: string,
+        value: number
+    }
+} | {
+    type: "DidNotSolve" | "Unbounded" | "Infisible" | "LimitReached"
+} | {
+    type: "Other" | "UnknownVariable",
+    value: string
+} | {
+    type: "IntegerSearchIncomplete",
+    value: {
+        incumbent: {
+            assignment: { name: string, value: number }[],
+            value: number,
+            gap: number
+        } | null,
+        nodes_explored: number
+    }
+} | {
+    type: "UnimplementedOptimizationType",
+    value: {
+        expected: OptimizationType[],
+        got: OptimizationType
+    }
+} | {
+    type: "UnavailableComparison",
+    value: {
+        got: Comparison,
+        expected: Comparison[]
+    }
+}
+"#;
+
+/// Returns true if the variable type only allows integer values
+fn is_integer_type(var_type: &VariableType) -> bool {
+    matches!(
+        var_type,
+        VariableType::Boolean | VariableType::IntegerRange(_, _)
+    )
+}
+
+impl SolverError {
+    /// Returns a suggestion on how to fix the error, if one is available.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{VariableType, Comparison, OptimizationType, solve_real_lp_problem_clarabel, LinearModel};
+    ///
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::IntegerRange(0, 10));
+    /// model.add_constraint(vec![1.0], Comparison::LessOrEqual, 5.0);
+    /// model.set_objective(vec![1.0], OptimizationType::Max);
+    ///
+    /// let error = solve_real_lp_problem_clarabel(&model).unwrap_err();
+    /// assert!(error.remedy().unwrap().contains("MILP"));
+    /// ```
+    pub fn remedy(&self) -> Option<String> {
+        match self {
+            SolverError::InvalidDomain { expected, got } => {
+                let supports_integers = expected.iter().any(is_integer_type);
+                let supports_reals = expected.iter().any(|t| !is_integer_type(t));
+                let has_integers = got.iter().any(|(_, v)| is_integer_type(v.get_type()));
+                let has_integer_ranges = got
+                    .iter()
+                    .any(|(_, v)| matches!(v.get_type(), VariableType::IntegerRange(_, _)));
+                if has_integers && !supports_integers {
+                    Some("This solver only supports continuous variables, use the MILP solver, or solve the continuous relaxation of the model".to_string())
+                } else if has_integer_ranges && supports_integers {
+                    Some("This solver only supports boolean variables, use the integer-binary or MILP solver".to_string())
+                } else if !supports_reals {
+                    Some("This solver does not support continuous variables, use the real or MILP solver".to_string())
+                } else {
+                    None
+                }
+            }
+            SolverError::TooLarge { .. } => Some(
+                "This solver only supports integer values that fit in 32 bits, rescale the model or use the MILP solver"
+                    .to_string(),
+            ),
+            SolverError::NumericalInstability { .. } => Some(
+                "Check the constants of the model for divisions by zero or values that overflow"
+                    .to_string(),
+            ),
+            SolverError::DidNotSolve => {
+                Some("Check that the constraints can all be satisfied at the same time".to_string())
+            }
+            SolverError::Unbounded => Some(
+                "Check that every variable that improves the objective is bounded by a constraint or by its domain"
+                    .to_string(),
+            ),
+            SolverError::Infisible => Some(
+                "Check for constraints that conflict with each other or with the domain of the variables"
+                    .to_string(),
+            ),
+            SolverError::Other(_) => None,
+            SolverError::UnknownVariable(_) => {
+                Some("Check the spelling of the variable, or remove its value".to_string())
+            }
+            SolverError::LimitReached => {
+                Some("Increase the iteration limit or simplify the model".to_string())
+            }
+            SolverError::IntegerSearchIncomplete { incumbent, .. } => match incumbent {
+                Some(_) => Some(
+                    "Increase the node or time limit, or accept the incumbent as a heuristic solution"
+                        .to_string(),
+                ),
+                None => Some("Increase the node limit".to_string()),
+            },
+            SolverError::UnimplementedOptimizationType { .. } => Some(
+                "Use the MILP solver, which supports every optimization type".to_string(),
+            ),
+            SolverError::UnavailableComparison { got, .. } => match got {
+                Comparison::Less | Comparison::Greater => Some(
+                    "Strict comparisons can be relaxed into non strict ones with the strict comparison mode of the transformer"
+                        .to_string(),
+                ),
+                _ => None,
+            },
+        }
+    }
+
+    fn describe(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SolverError::InvalidDomain { expected, got } => {
+                let expected = expected
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" or ");
                 let vars = got
                     .iter()
-                    .map(|(name, domain)| format!("    {}: {}", name, domain.get_type()))
+                    .map(|(name, domain)| {
+                        format!(
+                            "    {} is declared as {}, but this solver only supports {}",
+                            name,
+                            domain.get_type(),
+                            expected
+                        )
+                    })
                     .collect::<Vec<_>>()
                     .join("\n");
-                write!(
-                    f,
-                    "Invalid domain, the following variables are not {}: \n{}",
-                    expected
-                        .iter()
-                        .map(|t| t.to_string())
-                        .collect::<Vec<_>>()
-                        .join(" or "),
-                    vars
-                )
+                write!(f, "Invalid domain:\n{}", vars)
             }
             SolverError::Unbounded => {
                 write!(f, "The problem is unbounded")
@@ -82,9 +253,29 @@ impl std::fmt::Display for SolverError {
             SolverError::Other(s) => {
                 write!(f, "{}", s)
             }
+            SolverError::UnknownVariable(name) => {
+                write!(f, "The variable \"{}\" is not in the model", name)
+            }
             SolverError::LimitReached => {
                 write!(f, "The iteration limit was reached")
             }
+            SolverError::IntegerSearchIncomplete {
+                incumbent,
+                nodes_explored,
+            } => match incumbent {
+                Some(incumbent) => write!(
+                    f,
+                    "The search budget ran out after exploring {} nodes, the best solution found has value {} with a gap of {:.2}%",
+                    nodes_explored,
+                    incumbent.value(),
+                    incumbent.gap() * 100.0
+                ),
+                None => write!(
+                    f,
+                    "The search budget ran out after exploring {} nodes without finding a feasible solution",
+                    nodes_explored
+                ),
+            },
             SolverError::UnavailableComparison { got, expected } => {
                 write!(
                     f,
@@ -98,10 +289,13 @@ impl std::fmt::Display for SolverError {
                 )
             }
             SolverError::TooLarge { name, value } => {
-                write!(f, "The value of variable {} is too large: {}", name, value)
+                write!(f, "The value of {} is too large: {}", name, value)
+            }
+            SolverError::NumericalInstability { position, value } => {
+                write!(f, "The {} is not a finite number: {}", position, value)
             }
             SolverError::DidNotSolve => {
-                write!(f, "The problem was able to be solved")
+                write!(f, "The problem was not able to be solved")
             }
             SolverError::Infisible => {
                 write!(f, "The problem is infeasible")
@@ -109,17 +303,32 @@ impl std::fmt::Display for SolverError {
             SolverError::UnimplementedOptimizationType { expected, got } => {
                 write!(
                     f,
-                    "Expected optimization type to be one of {:?} but got {:?}",
-                    expected, got
+                    "The optimization type \"{}\" is not available in this solver, expected one of {}",
+                    got,
+                    expected
+                        .iter()
+                        .map(|t| t.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
                 )
             }
         }
     }
 }
 
+impl std::fmt::Display for SolverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.describe(f)?;
+        match self.remedy() {
+            Some(remedy) => write!(f, "\n{}", remedy),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Represents a variable assignment in a solution.
 /// - `T`: The type of the variable's value
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Assignment<T: Clone + Serialize + Copy + Display> {
     pub name: String,
     pub value: T,
@@ -127,30 +336,55 @@ pub struct Assignment<T: Clone + Serialize + Copy + Display> {
 
 impl<T: Clone + Serialize + Copy + Display> Display for Assignment<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} = {}", self.name, self.value)
+        f.write_str(&self.to_string_with_options(&FormatOptions::default()))
+    }
+}
+
+impl<T: Clone + Serialize + Copy + Display> DisplayWithOptions for Assignment<T> {
+    fn to_string_with_options(&self, options: &FormatOptions) -> String {
+        format!("{} = {}", self.name, options.format_value(&self.value))
     }
 }
 
 /// Represents a solution to a linear programming problem.
 /// - `T`: The type of the variables' values
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LpSolution<T: Clone + Serialize + Copy + Display> {
     assignment: Vec<Assignment<T>>,
     value: f64,
+    gap: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    slacks: Option<Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stats: Option<SolveStats>,
 }
 
 impl<T: Clone + Serialize + Copy + Display> Display for LpSolution<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Optimal value: {}\n\n", self.value)?;
-        write!(
-            f,
+        f.write_str(&self.to_string_with_options(&FormatOptions::default()))
+    }
+}
+
+impl<T: Clone + Serialize + Copy + Display> DisplayWithOptions for LpSolution<T> {
+    fn to_string_with_options(&self, options: &FormatOptions) -> String {
+        let mut s = format!("Optimal value: {}\n\n", options.format_number(self.value));
+        if self.gap > 0.0 {
+            s.push_str(&format!(
+                "Optimality gap: {}\n\n",
+                options.format_number(self.gap)
+            ));
+        }
+        s.push_str(&format!(
             "Variables:\n{}",
             self.assignment
                 .iter()
-                .map(|a| a.to_string())
+                .map(|a| a.to_string_with_options(options))
                 .collect::<Vec<_>>()
                 .join("\n")
-        )
+        ));
+        s
     }
 }
 
@@ -161,7 +395,87 @@ impl<T: Clone + Serialize + Copy + Display> LpSolution<T> {
     /// * `assignment` - Vector of variable assignments
     /// * `value` - The objective function value at this solution
     pub fn new(assignment: Vec<Assignment<T>>, value: f64) -> Self {
-        Self { assignment, value }
+        Self {
+            assignment,
+            value,
+            gap: 0.0,
+            slacks: None,
+            notes: Vec::new(),
+            stats: None,
+        }
+    }
+
+    /// Adds a note about how the solution was found, like an option the solver ignored.
+    ///
+    /// # Arguments
+    /// * `note` - The note to add
+    pub fn with_note(mut self, note: String) -> Self {
+        self.notes.push(note);
+        self
+    }
+
+    /// Returns the notes left by the solver about how the solution was found.
+    pub fn notes(&self) -> &Vec<String> {
+        &self.notes
+    }
+
+    /// Sets the counters collected by the simplex method while finding this solution.
+    ///
+    /// # Arguments
+    /// * `stats` - The counters of the solve
+    pub fn with_stats(mut self, stats: SolveStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Returns the counters collected by the simplex method while finding this solution,
+    /// the other solvers don't collect them.
+    pub fn stats(&self) -> Option<&SolveStats> {
+        self.stats.as_ref()
+    }
+
+    /// Converts the values of the assignment, keeping everything else about the solution.
+    pub(crate) fn map_values<U: Clone + Serialize + Copy + Display>(
+        self,
+        map: impl Fn(T) -> U,
+    ) -> LpSolution<U> {
+        LpSolution {
+            assignment: self
+                .assignment
+                .into_iter()
+                .map(|a| Assignment {
+                    name: a.name,
+                    value: map(a.value),
+                })
+                .collect(),
+            value: self.value,
+            gap: self.gap,
+            slacks: self.slacks,
+            notes: self.notes,
+            stats: self.stats,
+        }
+    }
+
+    /// Sets the relative gap between the value of this solution and the best bound
+    /// on the optimal value, for solutions that are not proven to be optimal.
+    ///
+    /// # Arguments
+    /// * `gap` - The relative optimality gap, zero if the solution is optimal
+    pub fn with_gap(mut self, gap: f64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Returns the relative gap between the value of this solution and the best bound
+    /// on the optimal value, it is zero for solutions that are proven to be optimal.
+    pub fn gap(&self) -> f64 {
+        self.gap
+    }
+
+    /// Returns the slack of each constraint of the model at this solution, in the order of the
+    /// constraints, if it was computed. See [`LinearModel::constraint_slacks`] for its sign.
+    pub fn slacks(&self) -> Option<&Vec<f64>> {
+        self.slacks.as_ref()
     }
 
     /// Returns a reference to the vector of variable assignments.
@@ -178,6 +492,129 @@ impl<T: Clone + Serialize + Copy + Display> LpSolution<T> {
     pub fn value(&self) -> f64 {
         self.value
     }
+
+    /// Returns true if the solution is proven to be optimal, that is, if its gap is zero.
+    pub fn is_optimal(&self) -> bool {
+        self.gap == 0.0
+    }
+
+    /// Renders the solution as a table, with the variable assignments followed by
+    /// the objective value, the status of the solution and, if it is not optimal, its gap.
+    ///
+    /// Numbers are rounded to 4 decimals, so that values like `1e-16` left by the solvers are
+    /// written as `0`, use [`LpSolution::to_table_with_options`] to choose how they are written.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Assignment, LpSolution};
+    ///
+    /// let solution = LpSolution::new(
+    ///     vec![Assignment { name: "x".to_string(), value: 2.0 }],
+    ///     4.0,
+    /// );
+    /// let table = solution.to_table();
+    /// assert!(table.contains("Objective value"));
+    /// assert!(table.contains("Optimal"));
+    /// ```
+    pub fn to_table(&self) -> String {
+        self.to_table_with_options(&FormatOptions::new().with_decimals(4))
+    }
+
+    /// Renders the solution as a table like [`LpSolution::to_table`], formatting the values
+    /// of the variables and the objective value with the given options.
+    ///
+    /// # Arguments
+    /// * `options` - How numbers should be written, [`FormatOptions::new`] keeps the full precision
+    pub fn to_table_with_options(&self, options: &FormatOptions) -> String {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![
+            TableCell::new("Variable"),
+            TableCell::new("Value"),
+        ]));
+        for assignment in &self.assignment {
+            table.add_row(Row::new(vec![
+                TableCell::new(&assignment.name),
+                TableCell::new(options.format_value(&assignment.value)),
+            ]));
+        }
+        table.add_row(Row::new(vec![
+            TableCell::new("Objective value"),
+            TableCell::new(options.format_number(self.value)),
+        ]));
+        let status = match self.is_optimal() {
+            true => "Optimal",
+            false => "Feasible",
+        };
+        table.add_row(Row::new(vec![
+            TableCell::new("Status"),
+            TableCell::new(status),
+        ]));
+        if !self.is_optimal() {
+            table.add_row(Row::new(vec![
+                TableCell::new("Gap"),
+                TableCell::new(format!("{:.2}%", self.gap * 100.0)),
+            ]));
+        }
+        table.render()
+    }
+
+    /// Creates a textual report of the solution, containing the model that was solved,
+    /// its statistics, the optimal value and the table of the solution.
+    ///
+    /// # Arguments
+    /// * `model` - The model this solution was found for
+    pub fn report(&self, model: &LinearModel) -> String {
+        format!(
+            "Model:\n{}\n\nStats:\n{}\n\nOptimal value: {}\n\nSolution:\n{}",
+            model,
+            model.stats(),
+            self.value,
+            self.to_table()
+        )
+    }
+}
+
+impl<T: Clone + Serialize + Copy + Display + Into<f64>> LpSolution<T> {
+    /// Computes the slack of each constraint of the model at this solution, so that
+    /// they can be read with [`LpSolution::slacks`].
+    ///
+    /// # Arguments
+    /// * `model` - The model this solution was found for
+    pub fn with_slacks(mut self, model: &LinearModel) -> Self {
+        let values = self
+            .assignment
+            .iter()
+            .map(|a| (a.name.clone(), a.value.into()))
+            .collect::<IndexMap<String, f64>>();
+        self.slacks = Some(model.constraint_slacks(&values));
+        self
+    }
+
+    /// Breaks down the objective value into the contribution (coefficient × value) of each variable,
+    /// the constant term of the objective is added under the `"__offset"` key.
+    ///
+    /// The sum of all the entries is equal to the objective value of the solution.
+    ///
+    /// # Arguments
+    /// * `model` - The model this solution was found for
+    pub fn objective_breakdown(&self, model: &LinearModel) -> IndexMap<String, f64> {
+        let values = self
+            .assignment
+            .iter()
+            .map(|a| (a.name.as_str(), a.value.into()))
+            .collect::<IndexMap<&str, f64>>();
+        let mut breakdown = model
+            .variables()
+            .iter()
+            .zip(model.objective().iter())
+            .map(|(name, coeff)| {
+                let value = values.get(name.as_str()).copied().unwrap_or(0.0);
+                (name.clone(), coeff * value)
+            })
+            .collect::<IndexMap<String, f64>>();
+        breakdown.insert("__offset".to_string(), model.objective_offset());
+        breakdown
+    }
 }
 
 /// Finds variables in a domain that don't satisfy a validation condition.
@@ -0,0 +1,142 @@
+use crate::math::{float_ne, VariableType};
+use crate::solvers::common::{LpSolution, SolverError};
+use crate::solvers::milp_solver::{
+    add_microlp_constraints, to_milp_assignment, to_solver_error, variable_bounds,
+};
+use crate::solvers::MILPValue;
+use crate::transformers::LinearModel;
+use crate::OptimizationType;
+use microlp::{Error, OptimizationDirection, Problem};
+
+/// Finds an integer solution of a mixed-integer linear programming problem with a diving heuristic,
+/// much faster than a full branch and bound on large models, but without proving that the solution is optimal.
+///
+/// The continuous relaxation of the model is solved, then the integer variable whose value is the most
+/// fractional is rounded to the nearest integer and fixed, and the relaxation is solved again.
+/// This is repeated until every integer variable has an integer value. If fixing a variable makes
+/// the relaxation infeasible, the variable is rounded in the other direction once before giving up.
+///
+/// The gap of the solution is measured against the value of the relaxation of the model,
+/// a gap of zero means that the solution is optimal.
+///
+/// # Arguments
+/// * `lp` - The mixed-integer linear programming model to solve
+/// * `max_dives` - The maximum number of variables that can be fixed
+///
+/// # Returns
+/// * `Ok(LpSolution<MILPValue>)` - A feasible solution, with its gap from the relaxation
+/// * `Err(SolverError::Infisible)` - If the relaxation of the model has no solution
+/// * `Err(SolverError::LimitReached)` - If more than `max_dives` variables had to be fixed
+/// * `Err(SolverError)` - If the dive could not reach a feasible solution, or other errors
+///
+/// # Example
+/// ```rust
+/// use rooc::{VariableType, Comparison, OptimizationType, solve_integer_heuristic, LinearModel};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::IntegerRange(0, 10));
+/// model.add_variable("y", VariableType::IntegerRange(0, 10));
+/// model.add_constraint(vec![2.0, 2.0], Comparison::LessOrEqual, 7.0);
+/// model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+///
+/// let solution = solve_integer_heuristic(&model, 10).unwrap();
+/// assert_eq!(solution.value(), 3.0);
+/// ```
+pub fn solve_integer_heuristic(
+    lp: &LinearModel,
+    max_dives: usize,
+) -> Result<LpSolution<MILPValue>, SolverError> {
+    lp.check_solvable()?;
+    let domain = lp.domain();
+    let types = lp
+        .variables()
+        .iter()
+        .map(|name| *domain.get(name).unwrap().get_type())
+        .collect::<Vec<_>>();
+    let mut bounds = variable_bounds(&types);
+    let mut relaxation_value = None;
+    //the last fixed variable, with the other rounding of its value if it was not tried yet
+    let mut last_fixed: Option<(usize, Option<f64>)> = None;
+    let mut dives = 0;
+    loop {
+        let Some((values, value)) = solve_relaxation(lp, &bounds)? else {
+            match last_fixed {
+                None => return Err(SolverError::Infisible),
+                Some((i, Some(other))) => {
+                    bounds[i] = (other, other);
+                    last_fixed = Some((i, None));
+                    continue;
+                }
+                Some((_, None)) => {
+                    return Err(SolverError::Other(
+                        "The diving heuristic could not find a feasible solution, use the MILP solver to search every solution"
+                            .to_string(),
+                    ))
+                }
+            }
+        };
+        let relaxation_value = *relaxation_value.get_or_insert(value);
+        let most_fractional = values
+            .iter()
+            .enumerate()
+            .filter(|(i, value)| {
+                !matches!(
+                    types[*i],
+                    VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _)
+                ) && float_ne(**value, value.round())
+            })
+            .map(|(i, value)| (i, *value, (value - value.floor() - 0.5).abs()))
+            .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+        let Some((i, fractional_value, _)) = most_fractional else {
+            let offset = lp.objective_offset();
+            let gap = match lp.optimization_type() {
+                OptimizationType::Satisfy => 0.0,
+                _ => (relaxation_value - value).abs() / (value + offset).abs().max(1e-9),
+            };
+            let assignment = to_milp_assignment(lp.variables(), &types, values);
+            return Ok(LpSolution::new(assignment, value + offset).with_gap(gap));
+        };
+        if dives == max_dives {
+            return Err(SolverError::LimitReached);
+        }
+        dives += 1;
+        let (min, max) = bounds[i];
+        let rounded = fractional_value.round().max(min.ceil()).min(max.floor());
+        let other = if rounded > fractional_value {
+            fractional_value.floor()
+        } else {
+            fractional_value.ceil()
+        };
+        bounds[i] = (rounded, rounded);
+        last_fixed = Some((i, Some(other).filter(|o| *o >= min && *o <= max)));
+    }
+}
+
+/// Solves the continuous relaxation of the model with the given bounds of the variables,
+/// returning the value of the variables and of the objective without the offset,
+/// or `None` if the relaxation is infeasible
+fn solve_relaxation(
+    lp: &LinearModel,
+    bounds: &[(f64, f64)],
+) -> Result<Option<(Vec<f64>, f64)>, SolverError> {
+    let direction = match lp.optimization_type() {
+        OptimizationType::Max => OptimizationDirection::Maximize,
+        OptimizationType::Min | OptimizationType::Satisfy => OptimizationDirection::Minimize,
+    };
+    let mut problem = Problem::new(direction);
+    let vars = lp
+        .objective()
+        .iter()
+        .zip(bounds.iter())
+        .map(|(coeff, bounds)| problem.add_var(*coeff, *bounds))
+        .collect::<Vec<_>>();
+    add_microlp_constraints(&mut problem, lp, &vars)?;
+    match problem.solve() {
+        Ok(solution) => {
+            let values = vars.iter().map(|v| *solution.var_value(*v)).collect();
+            Ok(Some((values, solution.objective())))
+        }
+        Err(Error::Infeasible) => Ok(None),
+        Err(e) => Err(to_solver_error(e)),
+    }
+}
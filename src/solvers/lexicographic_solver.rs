@@ -0,0 +1,279 @@
+use crate::math::{Comparison, OptimizationType, VariableType};
+use crate::solvers::common::{Assignment, LpSolution, SolverError};
+use crate::solvers::milp_solver::{variable_bounds, MILPValue};
+use crate::transformers::{LinearModel, SoftConstraint};
+
+/// How much the value of an already optimized objective is allowed to worsen while optimizing
+/// the next ones, relative to the magnitude of its optimal value (or absolute if it is below one).
+pub const LEXICOGRAPHIC_TOLERANCE: f64 = 1e-6;
+
+/// Solves a model with multiple objectives in lexicographic order.
+///
+/// The first objective is optimized, then its optimal value is fixed with a constraint (within
+/// [`LEXICOGRAPHIC_TOLERANCE`]) and the second objective is optimized, and so on, so each objective
+/// is only used to choose among the optimal solutions of the previous ones.
+/// The objective of the model is replaced by the given ones, while its offset is kept.
+///
+/// # Arguments
+/// * `model` - The model to solve, it is not modified
+/// * `objectives` - The objectives to optimize, in order of priority
+/// * `backend` - The solver used to solve each step, like [`crate::solve_real_lp_problem_clarabel`]
+///
+/// # Returns
+/// * `Ok(LpSolution<f64>)` - The solution of the last step, its value is the one of the last objective
+/// * `Err(SolverError)` - If there are no objectives or a step could not be solved
+///
+/// # Example
+/// ```rust
+/// use rooc::{solve_lexicographic, solve_real_lp_problem_clarabel, Comparison, LinearModel, OptimizationType, VariableType};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::non_negative_real());
+/// model.add_variable("y", VariableType::non_negative_real());
+/// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+/// model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 3.0);
+///
+/// let solution = solve_lexicographic(
+///     &model,
+///     vec![
+///         (OptimizationType::Max, vec![1.0, 1.0]),
+///         (OptimizationType::Min, vec![1.0, 0.0]),
+///     ],
+///     solve_real_lp_problem_clarabel,
+/// )
+/// .unwrap();
+/// ```
+pub fn solve_lexicographic<F>(
+    model: &LinearModel,
+    objectives: Vec<(OptimizationType, Vec<f64>)>,
+    backend: F,
+) -> Result<LpSolution<f64>, SolverError>
+where
+    F: Fn(&LinearModel) -> Result<LpSolution<f64>, SolverError>,
+{
+    let mut current = model.clone();
+    let mut solution = None;
+    for (optimization_type, objective) in objectives {
+        if objective.len() > current.variables().len() {
+            return Err(SolverError::Other(format!(
+                "Objective has {} coefficients while only {} variables were defined",
+                objective.len(),
+                current.variables().len()
+            )));
+        }
+        current.set_objective(objective.clone(), optimization_type.clone());
+        let step = backend(&current)?;
+        let value = objective
+            .iter()
+            .zip(step.assignment())
+            .map(|(coefficient, assignment)| coefficient * assignment.value)
+            .sum::<f64>();
+        let tolerance = LEXICOGRAPHIC_TOLERANCE * value.abs().max(1.0);
+        match optimization_type {
+            OptimizationType::Max => {
+                current.add_constraint(objective, Comparison::GreaterOrEqual, value - tolerance)
+            }
+            OptimizationType::Min => {
+                current.add_constraint(objective, Comparison::LessOrEqual, value + tolerance)
+            }
+            OptimizationType::Satisfy => {}
+        }
+        solution = Some(step);
+    }
+    solution.ok_or_else(|| SolverError::Other("No objective to optimize".to_string()))
+}
+
+/// Solves a model with soft constraints using preemptive goal programming.
+///
+/// The soft constraints are added to the model together with their deviation variables,
+/// then they are grouped in tiers by priority and, from the highest priority to the lowest,
+/// the weighted sum of the deviations of each tier is minimized with [`solve_lexicographic`],
+/// so a tier is only used to choose among the solutions that best satisfy the tiers above it.
+///
+/// # Arguments
+/// * `model` - The model with the hard constraints, it is not modified
+/// * `soft_constraints` - The constraints that should hold if possible
+/// * `backend` - The solver used to solve each tier, like [`crate::solve_real_lp_problem_clarabel`]
+///
+/// # Returns
+/// * `Ok(LpSolution<f64>)` - The solution after the last tier, the assignment includes the deviation
+///   variables and the value is the penalty of the lowest priority tier
+/// * `Err(SolverError)` - If there are no soft constraints, one of them is invalid or a tier could not be solved
+///
+/// # Example
+/// ```rust
+/// use rooc::{solve_preemptive_goals, solve_real_lp_problem_clarabel, Comparison, LinearConstraint, LinearModel, SoftConstraint, VariableType};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::non_negative_real());
+/// model.add_constraint(vec![1.0], Comparison::LessOrEqual, 10.0);
+///
+/// let solution = solve_preemptive_goals(
+///     &model,
+///     vec![
+///         SoftConstraint::new(LinearConstraint::new(vec![1.0], Comparison::GreaterOrEqual, 8.0))
+///             .with_priority(2),
+///         SoftConstraint::new(LinearConstraint::new(vec![1.0], Comparison::LessOrEqual, 5.0))
+///             .with_priority(1),
+///     ],
+///     solve_real_lp_problem_clarabel,
+/// )
+/// .unwrap();
+/// ```
+pub fn solve_preemptive_goals<F>(
+    model: &LinearModel,
+    soft_constraints: Vec<SoftConstraint>,
+    backend: F,
+) -> Result<LpSolution<f64>, SolverError>
+where
+    F: Fn(&LinearModel) -> Result<LpSolution<f64>, SolverError>,
+{
+    //the objective of the model is replaced by the penalties, so its offset is dropped
+    let (objective, optimization_type, _, constraints, variables, domain) =
+        model.clone().into_parts();
+    let mut goals = LinearModel::new_from_parts(
+        objective,
+        optimization_type,
+        0.0,
+        constraints,
+        variables,
+        domain,
+    );
+    //tiers of (priority, weighted deviations), from the highest priority to the lowest
+    let mut tiers: Vec<(u32, Vec<(String, f64)>)> = Vec::new();
+    for soft in soft_constraints {
+        let (priority, weight) = (soft.priority(), soft.weight());
+        let deviations = goals
+            .add_soft_constraint(soft)
+            .map_err(|e| SolverError::Other(format!("Invalid soft constraint: {}", e)))?;
+        let deviations = deviations.into_iter().map(|name| (name, weight));
+        match tiers.iter_mut().find(|(p, _)| *p == priority) {
+            Some((_, tier)) => tier.extend(deviations),
+            None => tiers.push((priority, deviations.collect())),
+        }
+    }
+    tiers.sort_by(|(a, _), (b, _)| b.cmp(a));
+    let objectives = tiers
+        .into_iter()
+        .map(|(_, deviations)| {
+            let mut objective = vec![0.0; goals.variables().len()];
+            for (name, weight) in deviations {
+                let index = goals.variables().iter().position(|v| *v == name).unwrap();
+                objective[index] += weight;
+            }
+            (OptimizationType::Min, objective)
+        })
+        .collect();
+    solve_lexicographic(&goals, objectives, backend)
+}
+
+/// Solves a model and, among its optimal solutions, looks for one with the fewest variables
+/// different from zero.
+///
+/// The model is solved once, then its optimal value is fixed with a constraint (within
+/// [`LEXICOGRAPHIC_TOLERANCE`]) and a boolean variable `$nz_name` is added for each variable,
+/// which must be true when the variable is not zero. The number of those booleans is then minimized.
+/// The bounds of each variable are used as the big-M of its boolean, so every variable must
+/// have a finite bound in the directions it can move away from zero.
+///
+/// The second step mixes the variables of the model with booleans, so it needs a backend
+/// that can solve mixed integer models, like [`crate::solve_milp_lp_problem`] or [`crate::auto_solver`].
+///
+/// # Arguments
+/// * `model` - The model to solve, it is not modified
+/// * `backend` - The solver used to solve each step
+///
+/// # Returns
+/// * `Ok(LpSolution<MILPValue>)` - The sparse solution, with only the variables of the model,
+///   its value is the one of the objective of the model
+/// * `Err(SolverError::Other)` - If a variable has no finite bound, assuming one could cut off the optimum
+/// * `Err(SolverError)` - If a step could not be solved
+///
+/// # Example
+/// ```rust
+/// use rooc::{solve_milp_lp_problem, solve_sparse, Comparison, LinearModel, OptimizationType, VariableType};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::NonNegativeReal(0.0, 4.0));
+/// model.add_variable("y", VariableType::NonNegativeReal(0.0, 4.0));
+/// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+/// model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+///
+/// let solution = solve_sparse(&model, solve_milp_lp_problem).unwrap();
+/// let nonzero = solution.assignment().iter().filter(|a| f64::from(a.value).abs() > 1e-6).count();
+/// assert_eq!(nonzero, 1);
+/// ```
+pub fn solve_sparse<F>(
+    model: &LinearModel,
+    backend: F,
+) -> Result<LpSolution<MILPValue>, SolverError>
+where
+    F: Fn(&LinearModel) -> Result<LpSolution<MILPValue>, SolverError>,
+{
+    let variables = model.variables().clone();
+    let types = variables
+        .iter()
+        .map(|name| *model.domain()[name].get_type())
+        .collect::<Vec<_>>();
+    let bounds = variable_bounds(&types);
+    if let Some((name, _)) = variables
+        .iter()
+        .zip(bounds.iter())
+        .find(|(_, (min, max))| min.is_infinite() || max.is_infinite())
+    {
+        return Err(SolverError::Other(format!(
+            "Variable \"{}\" has no finite bound, a sparse solution can only be searched when every variable is bounded, like NonNegativeReal(0, 100)",
+            name
+        )));
+    }
+    let objective = model.objective().clone();
+    let objective_value = |assignment: &[Assignment<MILPValue>]| {
+        objective
+            .iter()
+            .zip(assignment)
+            .map(|(coefficient, assignment)| coefficient * f64::from(assignment.value))
+            .sum::<f64>()
+    };
+    let mut sparse = model.clone();
+    let optimum = backend(model)?;
+    let value = objective_value(optimum.assignment());
+    let tolerance = LEXICOGRAPHIC_TOLERANCE * value.abs().max(1.0);
+    match model.optimization_type() {
+        OptimizationType::Max => sparse.add_constraint(
+            objective.clone(),
+            Comparison::GreaterOrEqual,
+            value - tolerance,
+        ),
+        OptimizationType::Min => sparse.add_constraint(
+            objective.clone(),
+            Comparison::LessOrEqual,
+            value + tolerance,
+        ),
+        OptimizationType::Satisfy => {}
+    }
+    let mut indicators = Vec::with_capacity(variables.len());
+    for (i, (name, (min, max))) in variables.iter().zip(bounds).enumerate() {
+        sparse.add_variable(&format!("$nz_{}", name), VariableType::Boolean);
+        let indicator = sparse.variables().len() - 1;
+        indicators.push(indicator);
+        let mut coefficients = vec![0.0; indicator + 1];
+        coefficients[i] = 1.0;
+        //x <= max * z and x >= min * z, so x is zero when z is false
+        coefficients[indicator] = -max.max(0.0);
+        sparse.add_constraint(coefficients.clone(), Comparison::LessOrEqual, 0.0);
+        coefficients[indicator] = -min.min(0.0);
+        sparse.add_constraint(coefficients, Comparison::GreaterOrEqual, 0.0);
+    }
+    let mut count = vec![0.0; sparse.variables().len()];
+    for indicator in indicators {
+        count[indicator] = 1.0;
+    }
+    sparse.set_objective(count, OptimizationType::Min);
+    let solution = backend(&sparse)?;
+    let assignment = solution.assignment()[..variables.len()].to_vec();
+    let value = match model.optimization_type() {
+        OptimizationType::Satisfy => 0.0,
+        _ => objective_value(&assignment) + model.objective_offset(),
+    };
+    Ok(LpSolution::new(assignment, value))
+}
@@ -27,6 +27,14 @@ impl Display for IntOrBoolValue {
         }
     }
 }
+impl From<IntOrBoolValue> for f64 {
+    fn from(value: IntOrBoolValue) -> Self {
+        match value {
+            IntOrBoolValue::Bool(b) => f64::from(b),
+            IntOrBoolValue::Int(i) => f64::from(i),
+        }
+    }
+}
 
 /// Solves a mixed integer-binary linear programming problem.
 ///
@@ -61,6 +69,7 @@ impl Display for IntOrBoolValue {
 pub fn solve_integer_binary_lp_problem(
     lp: &LinearModel,
 ) -> Result<LpSolution<IntOrBoolValue>, SolverError> {
+    lp.check_solvable()?;
     let invalid_variables = find_invalid_variables(lp.domain(), |var| {
         matches!(
             var,
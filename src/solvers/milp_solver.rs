@@ -1,12 +1,15 @@
+use crate::math::{float_gt, float_lt, float_ne};
 use crate::solvers::common::{LpSolution, SolverError};
 use crate::transformers::LinearModel;
 use crate::{Assignment, Comparison, OptimizationType, VariableType};
-use microlp::{ComparisonOp, Error, OptimizationDirection, Problem};
-use serde::Serialize;
+use microlp::{ComparisonOp, Error, OptimizationDirection, Problem, Variable};
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use web_time::Instant;
 
 /// Represents a variable value that can be either boolean or integer.
-#[derive(Debug, Clone, Serialize, Copy)]
+#[derive(Debug, Clone, Serialize, Deserialize, Copy)]
 #[serde(tag = "type", content = "value")]
 pub enum MILPValue {
     /// A boolean value (true/false)
@@ -25,6 +28,15 @@ impl Display for MILPValue {
         }
     }
 }
+impl From<MILPValue> for f64 {
+    fn from(value: MILPValue) -> Self {
+        match value {
+            MILPValue::Bool(b) => f64::from(b),
+            MILPValue::Int(i) => f64::from(i),
+            MILPValue::Real(r) => r,
+        }
+    }
+}
 /// Solves a mixed-integer linear programming problem using the MicroLP solver.
 ///
 /// Takes a linear model containing real, non-negative real, boolean, and integer variables and returns
@@ -64,6 +76,7 @@ impl Display for MILPValue {
 /// let solution = solve_milp_lp_problem(&model).unwrap();
 /// ```
 pub fn solve_milp_lp_problem(lp: &LinearModel) -> Result<LpSolution<MILPValue>, SolverError> {
+    lp.check_solvable()?;
     let variables = lp.variables();
     let domain = lp.domain();
     let objective = lp.objective();
@@ -86,6 +99,369 @@ pub fn solve_milp_lp_problem(lp: &LinearModel) -> Result<LpSolution<MILPValue>,
         microlp_vars.push(added_var);
     }
 
+    add_microlp_constraints(&mut problem, lp, &microlp_vars)?;
+    match problem.solve() {
+        Ok(s) => {
+            let assignment = microlp_vars
+                .iter()
+                .zip(variables)
+                .map(|(v, name)| {
+                    let value = s.var_value_rounded(*v);
+                    let var_domain = domain.get(name).unwrap();
+                    let value = match var_domain.get_type() {
+                        VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _) => {
+                            MILPValue::Real(value)
+                        }
+                        VariableType::IntegerRange(_, _) => MILPValue::Int(value as i32),
+                        VariableType::Boolean => MILPValue::Bool(value != 0.0),
+                    };
+                    Assignment {
+                        name: name.clone(),
+                        value,
+                    }
+                })
+                .collect();
+            Ok(LpSolution::new(assignment, s.objective() + lp.objective_offset()).with_slacks(lp))
+        }
+        Err(e) => Err(to_solver_error(e)),
+    }
+}
+
+/// A node of the branch and bound search tree, with the bounds of the variables
+/// and the value of the relaxation of its parent, which bounds the value of the node
+struct BranchNode {
+    bounds: Vec<(f64, f64)>,
+    parent_value: Option<f64>,
+}
+
+/// Solves a mixed-integer linear programming problem with a branch and bound over the continuous
+/// relaxations of the model, exploring at most `node_limit` nodes of the search tree.
+///
+/// If the limit is reached before the search is over, a `SolverError::IntegerSearchIncomplete` is returned,
+/// holding the best solution found so far (if any), together with the relative gap between its value
+/// and the best bound of the nodes left to explore. Callers can accept it as a heuristic answer.
+///
+/// # Arguments
+/// * `lp` - The mixed-integer linear programming model to solve
+/// * `node_limit` - The maximum number of nodes to explore
+///
+/// # Returns
+/// * `Ok(LpSolution<MILPValue>)` - The optimal solution, with a gap of zero
+/// * `Err(SolverError::Infisible)` - If the search proved that the problem has no solution
+/// * `Err(SolverError::IntegerSearchIncomplete)` - If the node limit was reached before the search was over
+/// * `Err(SolverError)` - Various error conditions that prevented finding a solution
+///
+/// # Example
+/// ```rust
+/// use rooc::{VariableType, Comparison, OptimizationType, solve_milp_lp_problem_with_node_limit, LinearModel};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::IntegerRange(0, 10));
+/// model.add_variable("y", VariableType::IntegerRange(0, 10));
+/// model.add_constraint(vec![2.0, 2.0], Comparison::LessOrEqual, 7.0);
+/// model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+///
+/// let solution = solve_milp_lp_problem_with_node_limit(&model, 100).unwrap();
+/// assert_eq!(solution.value(), 3.0);
+/// assert_eq!(solution.gap(), 0.0);
+/// ```
+pub fn solve_milp_lp_problem_with_node_limit(
+    lp: &LinearModel,
+    node_limit: usize,
+) -> Result<LpSolution<MILPValue>, SolverError> {
+    solve_milp_lp_problem_with_budget(lp, SearchBudget::new().with_node_limit(node_limit))
+}
+
+/// The limits shared by the whole branch and bound search tree, the search stops as soon
+/// as any of them is reached. A budget without limits explores the whole tree.
+///
+/// # Example
+/// ```rust
+/// use rooc::SearchBudget;
+/// use std::time::Duration;
+///
+/// let budget = SearchBudget::new()
+///     .with_node_limit(1000)
+///     .with_time_limit(Duration::from_secs(2));
+/// assert_eq!(budget.node_limit, Some(1000));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearchBudget {
+    /// The maximum number of nodes to explore
+    pub node_limit: Option<usize>,
+    /// The maximum time the whole search can take, checked after every node
+    pub time_limit: Option<Duration>,
+    /// The relative gap between the incumbent and the best bound at which the search stops
+    /// and the incumbent is accepted, checked after every node
+    pub gap_tolerance: Option<f64>,
+}
+
+impl SearchBudget {
+    /// Creates a budget without limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of nodes of the search tree that are explored.
+    ///
+    /// # Arguments
+    /// * `node_limit` - The maximum number of nodes to explore
+    pub fn with_node_limit(mut self, node_limit: usize) -> Self {
+        self.node_limit = Some(node_limit);
+        self
+    }
+
+    /// Limits the time spent by the whole search, the relaxation of a node is never interrupted
+    /// so the search can run slightly longer than the limit.
+    ///
+    /// # Arguments
+    /// * `time_limit` - The maximum time the search can take
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Stops the search as soon as the relative gap `|incumbent - bound| / |incumbent|` between the
+    /// best solution found and the best bound of the nodes left to explore is at most `gap_tolerance`,
+    /// the incumbent is then returned as a solution together with its gap.
+    ///
+    /// # Arguments
+    /// * `gap_tolerance` - The accepted relative gap, like `0.01` for 1%
+    pub fn with_gap_tolerance(mut self, gap_tolerance: f64) -> Self {
+        self.gap_tolerance = Some(gap_tolerance);
+        self
+    }
+}
+
+/// Solves a mixed-integer linear programming problem with a branch and bound over the continuous
+/// relaxations of the model, stopping when any limit of the search budget is reached.
+///
+/// The budget is shared by the whole search tree: every node counts towards the node limit,
+/// and the time limit is measured from the start of the search. As with
+/// [`solve_milp_lp_problem_with_node_limit`], if the budget runs out a
+/// `SolverError::IntegerSearchIncomplete` is returned with the best solution found so far.
+/// If the budget has a gap tolerance, the search stops early once the incumbent is close enough
+/// to the best bound, and the incumbent is returned with its gap.
+///
+/// # Arguments
+/// * `lp` - The mixed-integer linear programming model to solve
+/// * `budget` - The limits of the search
+///
+/// # Returns
+/// * `Ok(LpSolution<MILPValue>)` - The optimal solution with a gap of zero, or a solution within the gap tolerance
+/// * `Err(SolverError::Infisible)` - If the search proved that the problem has no solution
+/// * `Err(SolverError::IntegerSearchIncomplete)` - If the budget ran out before the search was over
+/// * `Err(SolverError)` - Various error conditions that prevented finding a solution
+///
+/// # Example
+/// ```rust
+/// use rooc::{VariableType, Comparison, OptimizationType, solve_milp_lp_problem_with_budget, LinearModel, SearchBudget};
+/// use std::time::Duration;
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::IntegerRange(0, 10));
+/// model.add_variable("y", VariableType::IntegerRange(0, 10));
+/// model.add_constraint(vec![2.0, 2.0], Comparison::LessOrEqual, 7.0);
+/// model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+///
+/// let budget = SearchBudget::new().with_time_limit(Duration::from_secs(10));
+/// let solution = solve_milp_lp_problem_with_budget(&model, budget).unwrap();
+/// assert_eq!(solution.value(), 3.0);
+/// ```
+pub fn solve_milp_lp_problem_with_budget(
+    lp: &LinearModel,
+    budget: SearchBudget,
+) -> Result<LpSolution<MILPValue>, SolverError> {
+    let start = Instant::now();
+    lp.check_solvable()?;
+    let variables = lp.variables();
+    let domain = lp.domain();
+    let objective = lp.objective();
+    let opt_type = match lp.optimization_type() {
+        OptimizationType::Max => OptimizationDirection::Maximize,
+        OptimizationType::Min => OptimizationDirection::Minimize,
+        OptimizationType::Satisfy => OptimizationDirection::Minimize,
+    };
+    let is_better = |value: f64, than: f64| match lp.optimization_type() {
+        OptimizationType::Max => float_gt(value, than),
+        _ => float_lt(value, than),
+    };
+    let types = variables
+        .iter()
+        .map(|name| *domain.get(name).unwrap().get_type())
+        .collect::<Vec<_>>();
+    let bounds = variable_bounds(&types);
+    let mut open = vec![BranchNode {
+        bounds,
+        parent_value: None,
+    }];
+    let offset = lp.objective_offset();
+    //nodes whose parent relaxation cannot improve the incumbent would be pruned anyway
+    let gap_to_open_nodes = |open: &[BranchNode], value: f64| {
+        open.iter()
+            .filter_map(|node| node.parent_value)
+            .filter(|bound| is_better(*bound, value))
+            .reduce(|best, bound| if is_better(bound, best) { bound } else { best })
+            .map(|best_bound| {
+                ((best_bound + offset) - (value + offset)).abs() / (value + offset).abs().max(1e-9)
+            })
+    };
+    let mut incumbent: Option<(Vec<f64>, f64)> = None;
+    let mut explored = 0;
+    loop {
+        let out_of_nodes = budget.node_limit.is_some_and(|limit| explored >= limit);
+        let out_of_time = budget
+            .time_limit
+            .is_some_and(|limit| start.elapsed() >= limit);
+        let within_gap = match (budget.gap_tolerance, &incumbent) {
+            (Some(tolerance), Some((_, value))) => {
+                gap_to_open_nodes(&open, *value).is_some_and(|gap| gap <= tolerance)
+            }
+            _ => false,
+        };
+        if out_of_nodes || out_of_time || within_gap {
+            break;
+        }
+        let Some(node) = open.pop() else {
+            break;
+        };
+        explored += 1;
+        if let (Some(parent_value), Some((_, best))) = (node.parent_value, &incumbent) {
+            if !is_better(parent_value, *best) {
+                continue;
+            }
+        }
+        let mut problem = Problem::new(opt_type);
+        let microlp_vars = objective
+            .iter()
+            .zip(node.bounds.iter())
+            .map(|(coeff, bounds)| problem.add_var(*coeff, *bounds))
+            .collect::<Vec<_>>();
+        add_microlp_constraints(&mut problem, lp, &microlp_vars)?;
+        let solution = match problem.solve() {
+            Ok(s) => s,
+            Err(Error::Infeasible) => continue,
+            Err(e) => return Err(to_solver_error(e)),
+        };
+        let value = solution.objective();
+        if let Some((_, best)) = &incumbent {
+            if !is_better(value, *best) {
+                continue;
+            }
+        }
+        let values = microlp_vars
+            .iter()
+            .map(|v| *solution.var_value(*v))
+            .collect::<Vec<_>>();
+        //branch on the first integer variable that has a fractional value
+        let fractional = values.iter().enumerate().find(|(i, value)| {
+            !matches!(
+                types[*i],
+                VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _)
+            ) && float_ne(**value, value.round())
+        });
+        match fractional {
+            Some((i, fractional_value)) => {
+                let mut up = node.bounds.clone();
+                up[i].0 = fractional_value.ceil();
+                let mut down = node.bounds;
+                down[i].1 = fractional_value.floor();
+                open.push(BranchNode {
+                    bounds: up,
+                    parent_value: Some(value),
+                });
+                open.push(BranchNode {
+                    bounds: down,
+                    parent_value: Some(value),
+                });
+            }
+            None => incumbent = Some((values, value)),
+        }
+    }
+    let Some((values, value)) = incumbent else {
+        return match open.is_empty() {
+            true => Err(SolverError::Infisible),
+            false => Err(SolverError::IntegerSearchIncomplete {
+                incumbent: None,
+                nodes_explored: explored,
+            }),
+        };
+    };
+    let assignment = to_milp_assignment(variables, &types, values);
+    match gap_to_open_nodes(&open, value) {
+        None => Ok(LpSolution::new(assignment, value + offset).with_slacks(lp)),
+        Some(gap)
+            if budget
+                .gap_tolerance
+                .is_some_and(|tolerance| gap <= tolerance) =>
+        {
+            Ok(LpSolution::new(assignment, value + offset)
+                .with_gap(gap)
+                .with_slacks(lp))
+        }
+        Some(gap) => {
+            let assignment = assignment
+                .into_iter()
+                .map(|a| Assignment {
+                    name: a.name,
+                    value: a.value.into(),
+                })
+                .collect();
+            Err(SolverError::IntegerSearchIncomplete {
+                incumbent: Some(Box::new(
+                    LpSolution::new(assignment, value + offset).with_gap(gap),
+                )),
+                nodes_explored: explored,
+            })
+        }
+    }
+}
+
+/// Returns the lower and upper bound of each variable type, booleans are bounded in `[0, 1]`
+pub(crate) fn variable_bounds(types: &[VariableType]) -> Vec<(f64, f64)> {
+    types
+        .iter()
+        .map(|var_type| match var_type {
+            VariableType::Real(min, max) | VariableType::NonNegativeReal(min, max) => (*min, *max),
+            VariableType::IntegerRange(min, max) => (*min as f64, *max as f64),
+            VariableType::Boolean => (0.0, 1.0),
+        })
+        .collect()
+}
+
+/// Converts the values of the variables of a relaxation into an assignment,
+/// rounding the values of the integer and boolean variables.
+pub(crate) fn to_milp_assignment(
+    variables: &[String],
+    types: &[VariableType],
+    values: Vec<f64>,
+) -> Vec<Assignment<MILPValue>> {
+    variables
+        .iter()
+        .zip(types.iter())
+        .zip(values)
+        .map(|((name, var_type), value)| {
+            let value = match var_type {
+                VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _) => {
+                    MILPValue::Real(value)
+                }
+                VariableType::IntegerRange(_, _) => MILPValue::Int(value.round() as i32),
+                VariableType::Boolean => MILPValue::Bool(value.round() != 0.0),
+            };
+            Assignment {
+                name: name.clone(),
+                value,
+            }
+        })
+        .collect()
+}
+
+/// Adds the constraints of the model to a MicroLP problem, using the given MicroLP variables
+pub(crate) fn add_microlp_constraints(
+    problem: &mut Problem,
+    lp: &LinearModel,
+    microlp_vars: &[Variable],
+) -> Result<(), SolverError> {
     for constraint in lp.constraints() {
         let coeffs = constraint.coefficients();
         let rhs = constraint.rhs();
@@ -112,33 +488,13 @@ pub fn solve_milp_lp_problem(lp: &LinearModel) -> Result<LpSolution<MILPValue>,
             .collect::<Vec<_>>();
         problem.add_constraint(microlp_coeffs, microlp_comparison_type, rhs);
     }
-    match problem.solve() {
-        Ok(s) => {
-            let assignment = microlp_vars
-                .iter()
-                .zip(variables)
-                .map(|(v, name)| {
-                    let value = s.var_value_rounded(*v);
-                    let var_domain = domain.get(name).unwrap();
-                    let value = match var_domain.get_type() {
-                        VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _) => {
-                            MILPValue::Real(value)
-                        }
-                        VariableType::IntegerRange(_, _) => MILPValue::Int(value as i32),
-                        VariableType::Boolean => MILPValue::Bool(value != 0.0),
-                    };
-                    Assignment {
-                        name: name.clone(),
-                        value,
-                    }
-                })
-                .collect();
-            Ok(LpSolution::new(assignment, s.objective()))
-        }
-        Err(e) => Err(match e {
-            Error::InternalError(s) => SolverError::Other(s),
-            Error::Unbounded => SolverError::Unbounded,
-            Error::Infeasible => SolverError::Infisible,
-        }),
+    Ok(())
+}
+
+pub(crate) fn to_solver_error(error: Error) -> SolverError {
+    match error {
+        Error::InternalError(s) => SolverError::Other(s),
+        Error::Unbounded => SolverError::Unbounded,
+        Error::Infeasible => SolverError::Infisible,
     }
 }
@@ -1,15 +1,31 @@
+//! The solvers of linear models and their entry points.
+//!
+//! [`solve`] together with [`SolverConfig`] and [`SolverBackend`] is the single entry point
+//! to solve a [`crate::LinearModel`] with any of the solvers, the functions of each solver
+//! ([`auto_solver`], [`solve_real_lp_problem_clarabel`], [`solve_real_lp_problem_slow_simplex`],
+//! [`solve_binary_lp_problem`], [`solve_integer_binary_lp_problem`], [`solve_milp_lp_problem`]...)
+//! are re-exported here as well, so they can be imported from `rooc::solvers` regardless
+//! of which module they are implemented in.
 mod auto_solver;
 pub mod binary_solver;
 pub mod common;
+mod diving_solver;
+mod lexicographic_solver;
 pub mod linear_integer_binary_solver;
 mod milp_solver;
+mod product_solver;
 pub mod real_solver;
 pub mod simplex;
+mod solver_api;
 
 pub use auto_solver::*;
 pub use binary_solver::*;
 pub use common::*;
+pub use diving_solver::*;
+pub use lexicographic_solver::*;
 pub use linear_integer_binary_solver::*;
 pub use milp_solver::*;
+pub use product_solver::*;
 pub use real_solver::*;
 pub use simplex::*;
+pub use solver_api::*;
@@ -0,0 +1,262 @@
+use crate::math::{Comparison, VariableType};
+use crate::solvers::{
+    find_invalid_variables, Assignment, LpSolution, SolverBackend, SolverConfig, SolverError,
+};
+use crate::transformers::LinearModel;
+use clarabel::algebra::CscMatrix;
+use clarabel::solver::{
+    DefaultSettingsBuilder, DefaultSolver, IPSolver, SolverStatus, SupportedConeT,
+};
+
+/// A model that maximizes the product of some of its variables, subject to the constraints
+/// and domains of a [`LinearModel`].
+///
+/// The product of strictly positive variables is maximized where the sum of their logarithms is,
+/// so the model is solved as the maximization of `t_1 + ... + t_n` with `t_i <= log(x_i)`,
+/// each of which is an exponential cone constraint `(t_i, 1, x_i)` solved by Clarabel.
+/// The linear solvers cannot represent the logarithm, solving with them fails with an error.
+///
+/// # Example
+/// ```rust
+/// use rooc::{Comparison, LinearModel, VariableType};
+/// use rooc::solvers::{SolverBackend, SolverConfig};
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::non_negative_real());
+/// model.add_variable("y", VariableType::non_negative_real());
+/// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 10.0);
+///
+/// let product = LinearModel::from_product_objective(model, &["x", "y"]).unwrap();
+/// let solution = product.solve(&SolverConfig::new(SolverBackend::Real)).unwrap();
+/// assert!((solution.value() - 25.0).abs() < 1e-4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProductObjectiveModel {
+    model: LinearModel,
+    factors: Vec<String>,
+}
+
+impl LinearModel {
+    /// Creates a model that maximizes the product of the given variables, keeping the variables,
+    /// domains and constraints of the model, its objective is ignored.
+    ///
+    /// # Arguments
+    /// * `model` - The model whose constraints the product is subject to, with only real variables
+    /// * `factors` - The variables to multiply, each at most once
+    ///
+    /// # Returns
+    /// * `Ok(ProductObjectiveModel)` the log transformed model
+    /// * `Err(SolverError::InvalidDomain)` if a variable of the model is not real
+    /// * `Err(SolverError::Other)` if there are no factors, a factor is not a variable of the model,
+    ///   is repeated or cannot be strictly positive
+    pub fn from_product_objective(
+        model: LinearModel,
+        factors: &[&str],
+    ) -> Result<ProductObjectiveModel, SolverError> {
+        model.check_solvable()?;
+        if factors.is_empty() {
+            return Err(SolverError::Other(
+                "The product objective needs at least one variable to multiply".to_string(),
+            ));
+        }
+        let invalid_variables = find_invalid_variables(model.domain(), |var| {
+            matches!(
+                var,
+                VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _)
+            )
+        });
+        if !invalid_variables.is_empty() {
+            return Err(SolverError::InvalidDomain {
+                expected: vec![
+                    VariableType::Real(f64::NEG_INFINITY, f64::INFINITY),
+                    VariableType::NonNegativeReal(0.0, f64::INFINITY),
+                ],
+                got: invalid_variables,
+            });
+        }
+        let mut names: Vec<String> = Vec::with_capacity(factors.len());
+        for factor in factors {
+            if names.iter().any(|n| n == factor) {
+                return Err(SolverError::Other(format!(
+                    "The variable \"{}\" is repeated in the product objective, use it once",
+                    factor
+                )));
+            }
+            let max = match model.domain().get(*factor).map(|v| v.get_type()) {
+                Some(VariableType::Real(_, max)) | Some(VariableType::NonNegativeReal(_, max)) => {
+                    *max
+                }
+                _ => {
+                    return Err(SolverError::Other(format!(
+                        "The variable \"{}\" of the product objective is not in the model",
+                        factor
+                    )))
+                }
+            };
+            if max <= 0.0 {
+                return Err(SolverError::Other(format!(
+                    "The variable \"{}\" of the product objective must be able to be strictly positive, but its upper bound is {}",
+                    factor, max
+                )));
+            }
+            names.push(factor.to_string());
+        }
+        Ok(ProductObjectiveModel {
+            model,
+            factors: names,
+        })
+    }
+}
+
+impl ProductObjectiveModel {
+    /// Returns the model whose constraints the product is subject to.
+    pub fn model(&self) -> &LinearModel {
+        &self.model
+    }
+
+    /// Returns the variables whose product is maximized.
+    pub fn factors(&self) -> &Vec<String> {
+        &self.factors
+    }
+
+    /// Maximizes the product of the factors.
+    ///
+    /// # Arguments
+    /// * `config` - The solver to use, only [`SolverBackend::Real`] and [`SolverBackend::Auto`]
+    ///   support the exponential cones of the transformed model
+    ///
+    /// # Returns
+    /// * `Ok(LpSolution<f64>)` the optimal values of the variables, the value is the product of the factors
+    /// * `Err(SolverError::Other)` if the backend only solves linear objectives
+    /// * `Err(SolverError)` if the model is infeasible, unbounded or could not be solved
+    pub fn solve(&self, config: &SolverConfig) -> Result<LpSolution<f64>, SolverError> {
+        match config.backend {
+            SolverBackend::Auto | SolverBackend::Real => self.solve_conic(),
+            backend => Err(SolverError::Other(format!(
+                "The {:?} solver only supports linear objectives, maximizing a product needs the exponential cones of the Real solver, use SolverBackend::Real",
+                backend
+            ))),
+        }
+    }
+
+    fn solve_conic(&self) -> Result<LpSolution<f64>, SolverError> {
+        let variables = self.model.variables();
+        let n = variables.len();
+        //the columns are the variables of the model followed by one logarithm per factor
+        let columns = n + self.factors.len();
+        let mut rows: Vec<usize> = Vec::new();
+        let mut cols: Vec<usize> = Vec::new();
+        let mut values: Vec<f64> = Vec::new();
+        let mut b: Vec<f64> = Vec::new();
+        let mut cones: Vec<SupportedConeT<f64>> = Vec::new();
+        //each row is a constraint b - A x in the cone
+        let mut push_row = |terms: &[(usize, f64)], rhs: f64| {
+            let row = b.len();
+            for (col, value) in terms {
+                rows.push(row);
+                cols.push(*col);
+                values.push(*value);
+            }
+            b.push(rhs);
+        };
+        for constraint in self.model.constraints() {
+            let terms = constraint
+                .coefficients()
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| **c != 0.0)
+                .map(|(i, c)| (i, *c))
+                .collect::<Vec<_>>();
+            match constraint.constraint_type() {
+                Comparison::LessOrEqual => {
+                    push_row(&terms, constraint.rhs());
+                    cones.push(SupportedConeT::NonnegativeConeT(1));
+                }
+                Comparison::GreaterOrEqual => {
+                    let negated = terms.iter().map(|(i, c)| (*i, -c)).collect::<Vec<_>>();
+                    push_row(&negated, -constraint.rhs());
+                    cones.push(SupportedConeT::NonnegativeConeT(1));
+                }
+                Comparison::Equal => {
+                    push_row(&terms, constraint.rhs());
+                    cones.push(SupportedConeT::ZeroConeT(1));
+                }
+                c => {
+                    return Err(SolverError::UnavailableComparison {
+                        got: *c,
+                        expected: vec![
+                            Comparison::LessOrEqual,
+                            Comparison::GreaterOrEqual,
+                            Comparison::Equal,
+                        ],
+                    })
+                }
+            }
+        }
+        for (i, name) in variables.iter().enumerate() {
+            let (min, max) = match self.model.domain().get(name).map(|v| v.get_type()) {
+                Some(VariableType::Real(min, max))
+                | Some(VariableType::NonNegativeReal(min, max)) => (*min, *max),
+                _ => (f64::NEG_INFINITY, f64::INFINITY),
+            };
+            if min.is_finite() {
+                push_row(&[(i, -1.0)], -min);
+                cones.push(SupportedConeT::NonnegativeConeT(1));
+            }
+            if max.is_finite() {
+                push_row(&[(i, 1.0)], max);
+                cones.push(SupportedConeT::NonnegativeConeT(1));
+            }
+        }
+        for (k, factor) in self.factors.iter().enumerate() {
+            let i = variables.iter().position(|v| v == factor).unwrap();
+            //(t_k, 1, x_i) in the exponential cone, so that t_k <= log(x_i)
+            push_row(&[(n + k, -1.0)], 0.0);
+            push_row(&[], 1.0);
+            push_row(&[(i, -1.0)], 0.0);
+            cones.push(SupportedConeT::ExponentialConeT());
+        }
+        let m = b.len();
+        let a = CscMatrix::new_from_triplets(m, columns, rows, cols, values);
+        let p = CscMatrix::zeros((columns, columns));
+        let mut q = vec![0.0; columns];
+        for t in q.iter_mut().skip(n) {
+            *t = -1.0;
+        }
+        let settings = DefaultSettingsBuilder::default()
+            .verbose(false)
+            .build()
+            .map_err(|e| SolverError::Other(e.to_string()))?;
+        let mut solver = DefaultSolver::new(&p, &q, &a, &b, &cones, settings);
+        solver.solve();
+        match solver.solution.status {
+            SolverStatus::Solved | SolverStatus::AlmostSolved => {
+                let x = &solver.solution.x;
+                let assignment = variables
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| Assignment {
+                        name: name.clone(),
+                        value: x[i],
+                    })
+                    .collect::<Vec<_>>();
+                let value = self
+                    .factors
+                    .iter()
+                    .map(|f| x[variables.iter().position(|v| v == f).unwrap()])
+                    .product();
+                Ok(LpSolution::new(assignment, value).with_slacks(&self.model))
+            }
+            SolverStatus::PrimalInfeasible | SolverStatus::AlmostPrimalInfeasible => {
+                Err(SolverError::Infisible)
+            }
+            SolverStatus::DualInfeasible | SolverStatus::AlmostDualInfeasible => {
+                Err(SolverError::Unbounded)
+            }
+            SolverStatus::MaxIterations => Err(SolverError::LimitReached),
+            status => Err(SolverError::Other(format!(
+                "The conic solver did not solve the model: {:?}",
+                status
+            ))),
+        }
+    }
+}
@@ -8,6 +8,7 @@ use good_lp::{
     VariableDefinition,
 };
 use indexmap::IndexMap;
+use std::collections::HashMap;
 
 /// Solves a linear programming problem with real variables using the Clarabel solver.
 ///
@@ -38,7 +39,57 @@ use indexmap::IndexMap;
 /// let solution = solve_real_lp_problem_clarabel(&model).unwrap();
 /// ```
 pub fn solve_real_lp_problem_clarabel(lp: &LinearModel) -> Result<LpSolution<f64>, SolverError> {
+    solve_real_lp_problem_clarabel_with_warm_start(lp, &HashMap::new())
+}
+
+/// Solves a linear programming problem with real variables using the Clarabel solver,
+/// starting from the given values of the variables.
+///
+/// The starting values are only used as a hint, they don't need to be feasible.
+/// The interior point method of Clarabel does not accept a starting point, so the values
+/// are validated and then ignored, the solution carries a note saying so, see [`LpSolution::notes`].
+///
+/// # Arguments
+/// * `lp` - The linear programming model to solve, must contain only real or non-negative real variables
+/// * `warm_start` - The starting value of some of the variables of the model
+///
+/// # Returns
+/// * `Ok(LpSolution<f64>)` - The optimal solution if found
+/// * `Err(SolverError::UnknownVariable)` - If a starting value refers to a variable that is not in the model
+/// * `Err(SolverError::Other)` - If a starting value is not finite
+/// * `Err(SolverError)` - Various error conditions that prevented finding a solution
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashMap;
+/// use rooc::{VariableType, Comparison, OptimizationType, solve_real_lp_problem_clarabel_with_warm_start, LinearModel};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x1", VariableType::non_negative_real());
+/// model.add_variable("x2", VariableType::non_negative_real());
+/// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 5.0);
+/// model.set_objective(vec![1.0, 2.0], OptimizationType::Max);
+///
+/// let warm_start = HashMap::from([("x2".to_string(), 4.0)]);
+/// let solution = solve_real_lp_problem_clarabel_with_warm_start(&model, &warm_start).unwrap();
+/// ```
+pub fn solve_real_lp_problem_clarabel_with_warm_start(
+    lp: &LinearModel,
+    warm_start: &HashMap<String, f64>,
+) -> Result<LpSolution<f64>, SolverError> {
+    lp.check_solvable()?;
     let domain = lp.domain();
+    for (name, value) in warm_start {
+        if !domain.contains_key(name) {
+            return Err(SolverError::UnknownVariable(name.clone()));
+        }
+        if !value.is_finite() {
+            return Err(SolverError::Other(format!(
+                "The warm start value of variable \"{}\" must be finite, got {}",
+                name, value
+            )));
+        }
+    }
     let invalid_variables = find_invalid_variables(domain, |var| {
         matches!(
             var,
@@ -74,13 +125,15 @@ pub fn solve_real_lp_problem_clarabel(lp: &LinearModel) -> Result<LpSolution<f64
     let vars = lp.variables();
     let obj_exp = match lp.optimization_type() {
         OptimizationType::Satisfy => 0.into(),
-        OptimizationType::Max | OptimizationType::Min => vars.iter().zip(lp.objective()).fold(
-            Expression::from(lp.objective_offset()),
-            |acc, (name, coeff)| {
-                let var = created_vars.get(name).unwrap();
-                acc + (*coeff) * (*var)
-            },
-        ),
+        //the offset does not change the optimum, it is added once to the value of the solution
+        OptimizationType::Max | OptimizationType::Min => {
+            vars.iter()
+                .zip(lp.objective())
+                .fold(Expression::from(0.0), |acc, (name, coeff)| {
+                    let var = created_vars.get(name).unwrap();
+                    acc + (*coeff) * (*var)
+                })
+        }
     };
     let objective = variables.optimise(opt_type, obj_exp.clone());
     let mut model = objective.using(clarabel);
@@ -129,7 +182,15 @@ pub fn solve_real_lp_problem_clarabel(lp: &LinearModel) -> Result<LpSolution<f64
                 .fold(lp.objective_offset(), |acc, (i, a)| {
                     acc + a.value * coeffs[i]
                 });
-            Ok(LpSolution::new(vars, value + lp.objective_offset()))
+            let solution = LpSolution::new(vars, value).with_slacks(lp);
+            if warm_start.is_empty() {
+                Ok(solution)
+            } else {
+                Ok(solution.with_note(
+                    "The warm start was ignored, the interior point method of the real solver does not accept a starting point"
+                        .to_string(),
+                ))
+            }
         }
         Err(e) => match e {
             ResolutionError::Unbounded => Err(SolverError::Unbounded),
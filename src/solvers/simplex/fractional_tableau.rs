@@ -1,30 +1,129 @@
 use crate::solvers::simplex::Tableau;
-use num_rational::Rational64;
-use num_traits::cast::FromPrimitive;
 
+/// The largest denominator used when approximating a number with a fraction
+pub const MAX_PRETTY_DENOMINATOR: i64 = 10000;
+
+/// The maximum relative error allowed when approximating a number with a fraction
+pub const PRETTY_FRACTION_TOLERANCE: f64 = 1e-9;
+
+/// A number formatted as a fraction with a small denominator, or as a decimal
+/// when no such fraction approximates it closely enough.
 pub struct PrettyFraction {
     numerator: i64,
     denominator: i64,
+    decimal: Option<f64>,
 }
 
 impl PrettyFraction {
-    fn new(num: f64) -> PrettyFraction {
-        //TODO make it use precision for smaller numbers
-        let f = Rational64::from_f64(num).unwrap();
-
-        PrettyFraction {
-            numerator: *f.numer(),
-            denominator: *f.denom(),
+    /// Approximates the number with the continued fraction expansion, stopping before
+    /// the denominator exceeds `MAX_PRETTY_DENOMINATOR`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::simplex::PrettyFraction;
+    ///
+    /// assert_eq!(PrettyFraction::new(0.1).pretty(), "1/10");
+    /// assert_eq!(PrettyFraction::new(-2.0 / 3.0).pretty(), "-2/3");
+    /// assert_eq!(PrettyFraction::new(std::f64::consts::PI).pretty(), "3.141592653589793");
+    /// ```
+    pub fn new(num: f64) -> PrettyFraction {
+        Self::with_tolerance(num, PRETTY_FRACTION_TOLERANCE)
+    }
+    /// Approximates the number with the continued fraction expansion, returning the fraction
+    /// with the smallest denominator whose relative error is within the tolerance.
+    ///
+    /// # Arguments
+    /// * `num` - The number to approximate
+    /// * `tolerance` - The maximum relative error, or absolute error for numbers smaller than one
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::simplex::PrettyFraction;
+    ///
+    /// assert_eq!(PrettyFraction::with_tolerance(0.3334, 1e-3).pretty(), "1/3");
+    /// assert_eq!(PrettyFraction::new(0.3334).pretty(), "1667/5000");
+    /// ```
+    pub fn with_tolerance(num: f64, tolerance: f64) -> PrettyFraction {
+        let decimal = PrettyFraction {
+            numerator: 0,
+            denominator: 1,
+            decimal: Some(num),
+        };
+        if !num.is_finite() || num.abs() >= i64::MAX as f64 {
+            return decimal;
+        }
+        //convergents h/k of the continued fraction, starting from 1/0 and 0/1
+        let (mut h, mut h_prev) = (1i64, 0i64);
+        let (mut k, mut k_prev) = (0i64, 1i64);
+        let mut rest = num;
+        loop {
+            let a = rest.floor();
+            let (Some(next_h), Some(next_k)) = (
+                (a as i64)
+                    .checked_mul(h)
+                    .and_then(|v| v.checked_add(h_prev)),
+                (a as i64)
+                    .checked_mul(k)
+                    .and_then(|v| v.checked_add(k_prev)),
+            ) else {
+                break;
+            };
+            if next_k > MAX_PRETTY_DENOMINATOR {
+                break;
+            }
+            (h_prev, h) = (h, next_h);
+            (k_prev, k) = (k, next_k);
+            let error = (h as f64 / k as f64 - num).abs();
+            if error <= tolerance * num.abs().max(1.0) {
+                return PrettyFraction {
+                    numerator: h,
+                    denominator: k,
+                    decimal: None,
+                };
+            }
+            rest = 1.0 / (rest - a);
+            if !rest.is_finite() {
+                break;
+            }
         }
+        decimal
     }
     #[allow(unused)]
     fn to_f64(&self) -> f64 {
-        self.numerator as f64 / self.denominator as f64
+        match self.decimal {
+            Some(decimal) => decimal,
+            None => self.numerator as f64 / self.denominator as f64,
+        }
+    }
+    /// Returns true if the number was approximated by a fraction, false if it is kept as a decimal.
+    pub fn is_fraction(&self) -> bool {
+        self.decimal.is_none()
+    }
+    /// Returns the numerator of the fraction, zero if the number is kept as a decimal.
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+    /// Returns the denominator of the fraction, one if the number is kept as a decimal.
+    pub fn denominator(&self) -> i64 {
+        self.denominator
+    }
+    /// Formats the number as `\frac{numerator}{denominator}`, as an integer, or as a decimal.
+    pub fn pretty_latex(&self) -> String {
+        match (self.decimal, self.denominator) {
+            (Some(decimal), _) => format!("{}", decimal),
+            (None, 1) => format!("{}", self.numerator),
+            (None, _) if self.numerator < 0 => {
+                format!("-\\frac{{{}}}{{{}}}", -self.numerator, self.denominator)
+            }
+            (None, _) => format!("\\frac{{{}}}{{{}}}", self.numerator, self.denominator),
+        }
     }
-    fn pretty(&self) -> String {
-        match self.denominator {
-            1 => format!("{}", self.numerator),
-            _ => format!("{}/{}", self.numerator, self.denominator),
+    /// Formats the number as `numerator/denominator`, as an integer, or as a decimal.
+    pub fn pretty(&self) -> String {
+        match (self.decimal, self.denominator) {
+            (Some(decimal), _) => format!("{}", decimal),
+            (None, 1) => format!("{}", self.numerator),
+            (None, _) => format!("{}/{}", self.numerator, self.denominator),
         }
     }
 }
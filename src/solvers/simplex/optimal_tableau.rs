@@ -2,6 +2,7 @@
 use crate::prelude::*;
 use crate::solvers::{LpSolution, Tableau};
 use core::fmt;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[derive(Debug, Clone)]
@@ -26,11 +27,44 @@ impl OptimalTableau {
     }
     pub fn optimal_value(&self) -> f64 {
         let flip = if self.flip_result { -1.0 } else { 1.0 };
-        ((self.tableau.current_value() + self.tableau.value_offset()) * -1.0) * flip
+        //the tableau holds the negated value of the minimized objective, without its offset
+        (self.tableau.value_offset() - self.tableau.current_value()) * flip
     }
     pub fn tableau(&self) -> &Tableau {
         &self.tableau
     }
+    pub fn stats(&self) -> SolveStats {
+        self.tableau.stats()
+    }
+    /// Returns the indices of the constraints whose slack is zero (within `tolerance`) at the optimum,
+    /// equality constraints are always binding.
+    ///
+    /// The indices refer to the constraints of the model in the order they were defined,
+    /// followed by the variable bounds that were turned into constraints in the standard form
+    pub fn binding_constraints(&self, tolerance: f64) -> Vec<usize> {
+        self.tableau
+            .slack_columns()
+            .iter()
+            .enumerate()
+            .filter(|(_, slack)| match slack {
+                Some(column) => self.values[*column].abs() <= tolerance,
+                None => true,
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns other optimal vertices of the problem when its optimal solution is not unique,
+    /// each with the values of every variable of the tableau, like [`OptimalTableau::variables_values`].
+    ///
+    /// They are found by pivoting on the non basic variables whose reduced cost is zero,
+    /// the returned vertices are different from each other and from the current solution.
+    ///
+    /// # Arguments
+    /// * `max` - The maximum number of vertices to return
+    pub fn alternate_optima(&self, max: usize) -> Vec<Vec<f64>> {
+        self.tableau.alternate_optima(max)
+    }
 
     pub fn as_lp_solution(&self) -> LpSolution<f64> {
         let values = self.variables_values().clone();
@@ -61,6 +95,63 @@ impl OptimalTableau {
     pub fn wasm_get_tableau(&self) -> Tableau {
         self.tableau.clone()
     }
+    pub fn wasm_binding_constraints(&self, tolerance: f64) -> Vec<usize> {
+        self.binding_constraints(tolerance)
+    }
+    pub fn wasm_alternate_optima(&self, max: usize) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.alternate_optima(max)).unwrap()
+    }
+}
+
+/// Counters collected while solving a tableau with the simplex method
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub struct SolveStats {
+    iterations: usize,
+    degenerate_pivots: usize,
+    phase1_iterations: usize,
+}
+
+impl SolveStats {
+    pub fn new(iterations: usize, degenerate_pivots: usize, phase1_iterations: usize) -> Self {
+        SolveStats {
+            iterations,
+            degenerate_pivots,
+            phase1_iterations,
+        }
+    }
+    /// Number of pivots done to reach the optimal solution, excluding the first phase
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+    /// Number of pivots, in both phases, that did not move to a different vertex
+    pub fn degenerate_pivots(&self) -> usize {
+        self.degenerate_pivots
+    }
+    /// Number of pivots done by the first phase to find a feasible basis
+    pub fn phase1_iterations(&self) -> usize {
+        self.phase1_iterations
+    }
+    pub(crate) fn record_pivot(&mut self, degenerate: bool) {
+        self.iterations += 1;
+        if degenerate {
+            self.degenerate_pivots += 1;
+        }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+#[cfg(target_arch = "wasm32")]
+impl SolveStats {
+    pub fn wasm_get_iterations(&self) -> usize {
+        self.iterations
+    }
+    pub fn wasm_get_degenerate_pivots(&self) -> usize {
+        self.degenerate_pivots
+    }
+    pub fn wasm_get_phase1_iterations(&self) -> usize {
+        self.phase1_iterations
+    }
 }
 
 impl Display for OptimalTableau {
@@ -14,11 +14,25 @@ pub enum StepAction {
     Finished,
 }
 
+/// The rule used to pick the variable that enters the basis at each simplex step
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub enum PivotRule {
+    /// Picks the variable with the most negative reduced cost
+    #[default]
+    Dantzig,
+    /// Picks the variable with the steepest edge, approximated with the
+    /// Devex reference framework weights
+    SteepestEdge,
+}
+
 #[derive(Debug)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub enum SimplexError {
     Unbounded,
     IterationLimitReached,
+    Infeasible,
+    InvalidIndex,
     Other,
 }
 impl Display for SimplexError {
@@ -26,6 +40,8 @@ impl Display for SimplexError {
         let s = match self {
             SimplexError::Unbounded => "Unbounded Problem",
             SimplexError::IterationLimitReached => "Iteration Limit Reached",
+            SimplexError::Infeasible => "Infeasible Problem",
+            SimplexError::InvalidIndex => "Invalid Constraint Index",
             SimplexError::Other => "Other",
         };
         f.write_str(s)
@@ -1,5 +1,7 @@
 use crate::math::{Comparison, OptimizationType, VariableType};
-use crate::solvers::{find_invalid_variables, Assignment, LpSolution, SimplexError, SolverError};
+use crate::solvers::{
+    find_invalid_variables, Assignment, LpSolution, SimplexError, SolveStats, SolverError,
+};
 use crate::transformers::LinearModel;
 use microlp::{OptimizationDirection, Problem};
 
@@ -13,7 +15,7 @@ use microlp::{OptimizationDirection, Problem};
 /// * `limit` - Maximum number of iterations before giving up
 ///
 /// # Returns
-/// * `Ok(LpSolution<f64>)` - The optimal solution if found
+/// * `Ok(LpSolution<f64>)` - The optimal solution if found, with the counters of the solve in [`LpSolution::stats`]
 /// * `Err(SolverError)` - Various error conditions that prevented finding a solution
 ///
 /// # Example
@@ -37,18 +39,59 @@ pub fn solve_real_lp_problem_slow_simplex(
     lp: &LinearModel,
     limit: i64,
 ) -> Result<LpSolution<f64>, SolverError> {
+    solve_real_lp_problem_slow_simplex_with_stats(lp, limit)
+        .map(|(solution, stats)| solution.with_stats(stats))
+}
+
+/// Solves a linear programming problem with real variables using a basic simplex algorithm,
+/// returning the counters of the solve alongside the solution.
+///
+/// # Arguments
+/// * `lp` - The linear programming model to solve
+/// * `limit` - Maximum number of iterations before giving up
+///
+/// # Returns
+/// * `Ok((LpSolution<f64>, SolveStats))` - The optimal solution and how many pivots it took
+/// * `Err(SolverError)` - Various error conditions that prevented finding a solution
+///
+/// # Example
+/// ```rust
+/// use rooc::{VariableType, Comparison, OptimizationType, solve_real_lp_problem_slow_simplex_with_stats, LinearModel};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x1", VariableType::non_negative_real());
+/// model.add_variable("x2", VariableType::non_negative_real());
+///
+/// // Add constraints: x1 + x2 = 2 and x1 - x2 <= 1
+/// model.add_constraint(vec![1.0, 1.0], Comparison::Equal, 2.0);
+/// model.add_constraint(vec![1.0, -1.0], Comparison::LessOrEqual, 1.0);
+///
+/// // Set objective: minimize x1 + 3*x2
+/// model.set_objective(vec![1.0, 3.0], OptimizationType::Min);
+///
+/// let (solution, stats) = solve_real_lp_problem_slow_simplex_with_stats(&model, 1000).unwrap();
+/// // the equality constraint has no slack variable, so a first phase is needed
+/// assert!(stats.phase1_iterations() > 0);
+/// ```
+pub fn solve_real_lp_problem_slow_simplex_with_stats(
+    lp: &LinearModel,
+    limit: i64,
+) -> Result<(LpSolution<f64>, SolveStats), SolverError> {
     let standard = lp.clone().into_standard_form()?;
     let mut canonical_form = standard
         .into_tableau()
         .map_err(|e| SolverError::Other(e.to_string()))?;
 
-    let solution = canonical_form.solve(limit);
+    let solution = canonical_form.solve_with_stats(limit);
     match solution {
-        Ok(optimal_tableau) => Ok(optimal_tableau.as_lp_solution()),
+        Ok((optimal_tableau, stats)) => Ok((optimal_tableau.as_lp_solution(), stats)),
         Err(e) => match e {
             SimplexError::IterationLimitReached => Err(SolverError::LimitReached),
             SimplexError::Unbounded => Err(SolverError::Unbounded),
-            SimplexError::Other => Err(SolverError::Other("An error occoured".to_string())),
+            SimplexError::Infeasible => Err(SolverError::Infisible),
+            SimplexError::InvalidIndex | SimplexError::Other => {
+                Err(SolverError::Other("An error occoured".to_string()))
+            }
         },
     }
 }
@@ -82,6 +125,7 @@ pub fn solve_real_lp_problem_slow_simplex(
 /// let solution = solve_real_lp_problem_micro_lp(&model).unwrap();
 /// ```
 pub fn solve_real_lp_problem_micro_lp(lp: &LinearModel) -> Result<LpSolution<f64>, SolverError> {
+    lp.check_solvable()?;
     let domain = lp.domain();
     let invalid_variables = find_invalid_variables(domain, |var| {
         matches!(
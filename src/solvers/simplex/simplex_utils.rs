@@ -3,3 +3,13 @@ pub fn divide_matrix_row_by(matrix: &mut [Vec<f64>], row: usize, value: f64) {
         matrix[row][i] /= value;
     }
 }
+
+pub(crate) fn identity_matrix(size: usize) -> Vec<Vec<f64>> {
+    (0..size)
+        .map(|row| {
+            let mut values = vec![0.0; size];
+            values[row] = 1.0;
+            values
+        })
+        .collect()
+}
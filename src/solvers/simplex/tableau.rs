@@ -1,9 +1,9 @@
-use crate::math::{float_ge, float_gt, float_le, float_lt};
+use crate::math::{float_eq, float_ge, float_gt, float_le, float_lt, float_ne};
 #[allow(unused_imports)]
 use crate::prelude::*;
 use crate::solvers::{
-    FractionalTableau, OptimalTableau, OptimalTableauWithSteps, SimplexError, SimplexStep,
-    StepAction,
+    FractionalTableau, OptimalTableau, OptimalTableauWithSteps, PivotRule, SimplexError,
+    SimplexStep, SolveStats, StepAction,
 };
 use core::fmt;
 use std::fmt::Display;
@@ -22,6 +22,13 @@ pub struct Tableau {
     in_basis: Vec<usize>,
     current_value: f64,
     value_offset: f64,
+    pivot_rule: PivotRule,
+    reference_weights: Vec<f64>,
+    stats: SolveStats,
+    original_b: Vec<f64>,
+    basis_inverse: Vec<Vec<f64>>,
+    basis_inverse_costs: Vec<f64>,
+    slack_columns: Vec<Option<usize>>,
 }
 
 impl Display for Tableau {
@@ -70,11 +77,33 @@ impl Tableau {
     pub fn wasm_get_value_offset(&self) -> f64 {
         self.value_offset
     }
+    pub fn wasm_get_pivot_rule(&self) -> PivotRule {
+        self.pivot_rule
+    }
+    pub fn wasm_set_pivot_rule(&mut self, pivot_rule: PivotRule) {
+        self.set_pivot_rule(pivot_rule)
+    }
+    pub fn wasm_get_stats(&self) -> SolveStats {
+        self.stats
+    }
 
     pub fn wasm_step(&mut self, variables_to_avoid: Vec<usize>) -> Result<JsValue, SimplexError> {
         self.step(&variables_to_avoid)
             .map(|action| serde_wasm_bindgen::to_value(&action).unwrap())
     }
+    pub fn wasm_update_rhs(
+        &mut self,
+        constraint_index: usize,
+        new_value: f64,
+    ) -> Result<(), SimplexError> {
+        self.update_rhs(constraint_index, new_value)
+    }
+    pub fn wasm_reoptimize(&mut self, limit: i64) -> Result<OptimalTableau, SimplexError> {
+        self.reoptimize(limit)
+    }
+    pub fn wasm_solve_dual(&mut self, limit: i64) -> Result<OptimalTableau, SimplexError> {
+        self.solve_dual(limit)
+    }
     pub fn wasm_to_string(&self) -> String {
         self.to_string()
     }
@@ -92,6 +121,15 @@ impl Tableau {
         variables: Vec<String>,
         flip_result: bool,
     ) -> Tableau {
+        let reference_weights = vec![1.0; c.len()];
+        //by default the starting basis is assumed to be the identity, made of the slack variables
+        let original_b = b.clone();
+        let basis_inverse = a
+            .iter()
+            .map(|row| in_basis.iter().map(|&column| row[column]).collect())
+            .collect();
+        let basis_inverse_costs = in_basis.iter().map(|&column| c[column]).collect();
+        let slack_columns = in_basis.iter().map(|&column| Some(column)).collect();
         Tableau {
             c,
             a,
@@ -101,15 +139,58 @@ impl Tableau {
             value_offset,
             variables,
             flip_result,
+            pivot_rule: PivotRule::default(),
+            reference_weights,
+            stats: SolveStats::default(),
+            original_b,
+            basis_inverse,
+            basis_inverse_costs,
+            slack_columns,
         }
     }
 
+    /// Sets the rule used to pick the entering variable, this also resets the
+    /// reference framework of the steepest edge weights to the current non basic variables
+    pub fn set_pivot_rule(&mut self, pivot_rule: PivotRule) {
+        self.pivot_rule = pivot_rule;
+        self.reference_weights = vec![1.0; self.c.len()];
+    }
+    pub fn with_pivot_rule(mut self, pivot_rule: PivotRule) -> Self {
+        self.set_pivot_rule(pivot_rule);
+        self
+    }
+    pub fn pivot_rule(&self) -> PivotRule {
+        self.pivot_rule
+    }
+    /// Returns the counters of the pivots done so far
+    pub fn stats(&self) -> SolveStats {
+        self.stats
+    }
+    //adds the counters of a first phase that was needed to find a feasible basis
+    pub(crate) fn add_phase1_stats(&mut self, phase1: SolveStats) {
+        self.stats = SolveStats::new(
+            self.stats.iterations(),
+            self.stats.degenerate_pivots() + phase1.degenerate_pivots(),
+            self.stats.phase1_iterations() + phase1.iterations(),
+        );
+    }
+
     pub fn flip_result(&self) -> bool {
         self.flip_result
     }
     pub fn solve(&mut self, limit: i64) -> Result<OptimalTableau, SimplexError> {
         self.solve_avoiding(limit, &[])
     }
+    /// Solves the tableau, returning the counters of the solve alongside the optimal tableau
+    pub fn solve_with_stats(
+        &mut self,
+        limit: i64,
+    ) -> Result<(OptimalTableau, SolveStats), SimplexError> {
+        self.solve(limit).map(|optimal| {
+            let stats = optimal.stats();
+            (optimal, stats)
+        })
+    }
     pub fn variables(&self) -> &Vec<String> {
         &self.variables
     }
@@ -178,20 +259,205 @@ impl Tableau {
                 match t {
                     None => Err(SimplexError::Unbounded),
                     Some((t, ratio)) => match self.pivot(t, h) {
-                        Ok(()) => Ok(StepAction::Pivot {
+                        Ok(()) => {
+                            self.stats.record_pivot(float_eq(ratio, 0.0));
+                            Ok(StepAction::Pivot {
+                                entering: h,
+                                leaving: t,
+                                ratio,
+                            })
+                        }
+                        Err(_) => Err(SimplexError::Other),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Changes the right hand side of a constraint, keeping the current basis.
+    ///
+    /// The constraint index refers to the constraints of the standard form the tableau was
+    /// created from, and the new value replaces their original right hand side, before any
+    /// scaling or first phase. The values in the basis are updated accordingly and might become
+    /// negative, call `reoptimize` afterwards to find the new optimal solution.
+    pub fn update_rhs(
+        &mut self,
+        constraint_index: usize,
+        new_value: f64,
+    ) -> Result<(), SimplexError> {
+        if constraint_index >= self.original_b.len() {
+            return Err(SimplexError::InvalidIndex);
+        }
+        let delta = new_value - self.original_b[constraint_index];
+        //the values in the basis are the inverse of the basis times the original right hand side
+        for (b, row) in self.b.iter_mut().zip(self.basis_inverse.iter()) {
+            *b += delta * row[constraint_index];
+        }
+        self.current_value += delta * self.basis_inverse_costs[constraint_index];
+        self.original_b[constraint_index] = new_value;
+        Ok(())
+    }
+
+    /// Finds the optimal solution after the right hand side was changed with `update_rhs`.
+    ///
+    /// If the tableau was optimal, the dual simplex is used to restore feasibility while keeping
+    /// optimality, otherwise a first phase is run from the current basis before solving again.
+    pub fn reoptimize(&mut self, limit: i64) -> Result<OptimalTableau, SimplexError> {
+        if self.is_optimal() {
+            return self.solve_dual(limit);
+        }
+        if self.find_infeasible_row().is_some() {
+            self.restore_feasibility(limit)?;
+        }
+        self.solve(limit)
+    }
+
+    /// Solves the tableau with the dual simplex method.
+    ///
+    /// The tableau must be dual feasible, that is all the reduced costs must be non negative,
+    /// while the values in the basis can be negative. Each step keeps the tableau dual
+    /// feasible and moves it towards primal feasibility, which makes it the right method to
+    /// warm start after adding a cut or tightening a bound.
+    pub fn solve_dual(&mut self, limit: i64) -> Result<OptimalTableau, SimplexError> {
+        if !self.is_optimal() {
+            return Err(SimplexError::Other);
+        }
+        let mut iteration = 0;
+        while iteration <= limit {
+            match self.dual_step() {
+                Ok(StepAction::Pivot { .. }) => {
+                    iteration += 1;
+                }
+                Ok(StepAction::Finished) => {
+                    return Ok(OptimalTableau::new(self.variables_values(), self.clone()));
+                }
+                Err(e) => {
+                    return Err(e);
+                }
+            }
+        }
+        Err(SimplexError::IterationLimitReached)
+    }
+
+    /// Performs a single step of the dual simplex method, see `solve_dual`.
+    pub fn dual_step(&mut self) -> Result<StepAction, SimplexError> {
+        match self.find_infeasible_row() {
+            None => Ok(StepAction::Finished),
+            Some(t) => {
+                let h = self.find_dual_h(t).ok_or(SimplexError::Infeasible)?;
+                let ratio = self.c[h] / -self.a[t][h];
+                match self.pivot(t, h) {
+                    Ok(()) => {
+                        self.stats.record_pivot(float_eq(ratio, 0.0));
+                        Ok(StepAction::Pivot {
                             entering: h,
                             leaving: t,
                             ratio,
-                        }),
-                        Err(_) => Err(SimplexError::Other),
-                    },
+                        })
+                    }
+                    Err(_) => Err(SimplexError::Other),
                 }
             }
         }
     }
 
+    //finds the row with the most negative value in the basis
+    fn find_infeasible_row(&self) -> Option<usize> {
+        self.b
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| float_lt(**b, 0.0))
+            .min_by(|(_, b1), (_, b2)| b1.partial_cmp(b2).unwrap())
+            .map(|(i, _)| i)
+    }
+
+    //finds the variable that will enter the basis in the dual simplex, keeping the costs non negative
+    fn find_dual_h(&self, t: usize) -> Option<usize> {
+        self.a[t]
+            .iter()
+            .enumerate()
+            .filter(|(j, a)| !self.in_basis.contains(j) && float_lt(**a, 0.0))
+            .map(|(j, a)| (j, self.c[j] / -a))
+            .min_by(|(_, r1), (_, r2)| r1.partial_cmp(r2).unwrap())
+            .map(|(j, _)| j)
+    }
+
+    //finds a feasible basis starting from the current one, by adding an artificial variable
+    //to each infeasible row and minimizing their sum
+    fn restore_feasibility(&mut self, limit: i64) -> Result<(), SimplexError> {
+        let number_of_variables = self.c.len();
+        let infeasible_rows = (0..self.b.len())
+            .filter(|i| float_lt(self.b[*i], 0.0))
+            .collect::<Vec<_>>();
+        let total_variables = number_of_variables + infeasible_rows.len();
+        let mut a = self.a.clone();
+        let mut b = self.b.clone();
+        let mut basis = self.in_basis.clone();
+        let mut variables = self.variables.clone();
+        let mut c = vec![0.0; total_variables];
+        let mut value = 0.0;
+        for row in a.iter_mut() {
+            row.resize(total_variables, 0.0);
+        }
+        let mut basis_inverse = self.basis_inverse.clone();
+        for (i, &row) in infeasible_rows.iter().enumerate() {
+            //flip the row so that the artificial variable starts with a positive value
+            for coefficient in a[row].iter_mut().chain(basis_inverse[row].iter_mut()) {
+                *coefficient = -*coefficient;
+            }
+            b[row] = -b[row];
+            a[row][number_of_variables + i] = 1.0;
+            basis[row] = number_of_variables + i;
+            variables.push(format!("$a_{}", i));
+            c[number_of_variables + i] = 1.0;
+            for (j, coefficient) in a[row].iter().enumerate() {
+                c[j] -= coefficient;
+            }
+            value -= b[row];
+        }
+        let artificial_variables = (number_of_variables..total_variables).collect::<Vec<_>>();
+        let mut phase1 = Tableau::new(c, a, b, basis, value, 0.0, variables, false);
+        phase1.basis_inverse = basis_inverse;
+        let optimal = phase1.solve_avoiding(limit, &artificial_variables)?;
+        let phase1 = optimal.tableau();
+        if float_ne(phase1.current_value(), 0.0)
+            || phase1.in_basis().iter().any(|&i| i >= number_of_variables)
+        {
+            return Err(SimplexError::Infeasible);
+        }
+        self.a = phase1.a_matrix().clone();
+        for row in self.a.iter_mut() {
+            row.truncate(number_of_variables);
+        }
+        self.b = phase1.b_vec().clone();
+        self.in_basis = phase1.in_basis().clone();
+        self.basis_inverse = phase1.basis_inverse.clone();
+        //put the objective function back in canonical form for the new basis
+        for (row, &variable) in self.in_basis.iter().enumerate() {
+            let coefficient = self.c[variable];
+            for (c, a) in self.c.iter_mut().zip(self.a[row].iter()) {
+                *c -= coefficient * a;
+            }
+            for (c, a) in self
+                .basis_inverse_costs
+                .iter_mut()
+                .zip(self.basis_inverse[row].iter())
+            {
+                *c -= coefficient * a;
+            }
+            self.current_value -= coefficient * self.b[row];
+        }
+        self.add_phase1_stats(optimal.stats());
+        Ok(())
+    }
+
+    //the reduced costs of the variables in the basis are zero, but can drift to tiny
+    //negative values after many pivots, so only the other variables are checked
     fn is_optimal(&self) -> bool {
-        self.c.iter().all(|c| float_ge(*c, 0.0))
+        self.c
+            .iter()
+            .enumerate()
+            .all(|(i, c)| self.in_basis.contains(&i) || float_ge(*c, 0.0))
     }
 
     #[allow(unused)]
@@ -202,14 +468,40 @@ impl Tableau {
     //finds the variable that will enter the basis
     #[allow(unused)]
     fn find_h(&self, variables_to_avoid: &[usize]) -> Option<usize> {
-        //uses the Bland's rule for anti-cycling
-        let min = self
+        let candidates = self
             .c
             .iter()
             .enumerate()
-            .filter(|(i, c)| !self.in_basis.contains(i) && float_lt(**c, 0.0))
-            .min_by(|(_, c1), (_, c2)| c1.partial_cmp(c2).unwrap());
-        min.map(|(i, _)| i)
+            .filter(|(i, c)| !self.in_basis.contains(i) && float_lt(**c, 0.0));
+        match self.pivot_rule {
+            //uses the Bland's rule for anti-cycling
+            PivotRule::Dantzig => candidates
+                .min_by(|(_, c1), (_, c2)| c1.partial_cmp(c2).unwrap())
+                .map(|(i, _)| i),
+            //picks the largest reduced cost relative to the approximate norm of the edge
+            PivotRule::SteepestEdge => candidates
+                .map(|(i, c)| (i, c * c / self.reference_weights[i]))
+                .fold(None, |best: Option<(usize, f64)>, (i, score)| match best {
+                    Some((_, best_score)) if score <= best_score => best,
+                    _ => Some((i, score)),
+                })
+                .map(|(i, _)| i),
+        }
+    }
+
+    //updates the Devex weights before variable h enters the basis in row t
+    fn update_reference_weights(&mut self, t: usize, h: usize) {
+        let row = &self.a[t];
+        let pivot = row[h];
+        let entering_weight = self.reference_weights[h];
+        for (j, weight) in self.reference_weights.iter_mut().enumerate() {
+            if j != h {
+                let ratio = row[j] / pivot;
+                *weight = weight.max(ratio * ratio * entering_weight);
+            }
+        }
+        let leaving = self.in_basis[t];
+        self.reference_weights[leaving] = (entering_weight / (pivot * pivot)).max(1.0);
     }
 
     //finds the variable that will leave the basis, prioritize variabls_to_prefer
@@ -244,6 +536,48 @@ impl Tableau {
         }
     }
 
+    /// Enumerates the optimal vertices reachable from an optimal tableau, other than its own.
+    ///
+    /// Each non basic variable with a zero reduced cost can enter the basis without changing
+    /// the objective value, the bases reached this way are explored until `max` vertices are found
+    pub(crate) fn alternate_optima(&self, max: usize) -> Vec<Vec<f64>> {
+        let current = self.variables_values();
+        let mut found: Vec<Vec<f64>> = Vec::new();
+        let mut visited = vec![sorted_basis(&self.in_basis)];
+        let mut to_visit = vec![self.clone()];
+        while let Some(tableau) = to_visit.pop() {
+            for h in 0..tableau.c.len() {
+                if found.len() >= max {
+                    return found;
+                }
+                if tableau.in_basis.contains(&h) || !float_eq(tableau.c[h], 0.0) {
+                    continue;
+                }
+                //an unbounded ray of optimal solutions has no other vertex in that direction
+                let Some((t, _)) = tableau.find_t(h, &[]) else {
+                    continue;
+                };
+                let mut next = tableau.clone();
+                if next.pivot(t, h).is_err() {
+                    continue;
+                }
+                let basis = sorted_basis(&next.in_basis);
+                if visited.contains(&basis) {
+                    continue;
+                }
+                visited.push(basis);
+                let values = next.variables_values();
+                let is_new =
+                    |v: &Vec<f64>| v.iter().zip(values.iter()).any(|(a, b)| float_ne(*a, *b));
+                if is_new(&current) && found.iter().all(is_new) {
+                    found.push(values);
+                }
+                to_visit.push(next);
+            }
+        }
+        found
+    }
+
     fn variables_values(&self) -> Vec<f64> {
         let mut values = vec![0.0; self.c.len()];
         for (i, &j) in self.in_basis.iter().enumerate() {
@@ -253,10 +587,15 @@ impl Tableau {
     }
     //performs the pivot operation where variable h enters the basis and variable B(t) leaves the basis
     fn pivot(&mut self, t: usize, h: usize) -> Result<(), ()> {
+        if self.pivot_rule == PivotRule::SteepestEdge {
+            self.update_reference_weights(t, h);
+        }
         let in_basis = &mut self.in_basis;
         let a = &mut self.a;
         let b = &mut self.b;
         let c = &mut self.c;
+        let inverse = &mut self.basis_inverse;
+        let inverse_costs = &mut self.basis_inverse_costs;
         let pivot = a[t][h];
 
         //normalize the pivot column
@@ -266,6 +605,9 @@ impl Tableau {
                 for j in 0..a[i].len() {
                     a[i][j] -= factor * a[t][j];
                 }
+                for j in 0..inverse[i].len() {
+                    inverse[i][j] -= factor * inverse[t][j];
+                }
                 b[i] -= factor * b[t];
             }
         }
@@ -274,11 +616,17 @@ impl Tableau {
         for (i, row) in c.iter_mut().enumerate() {
             *row -= factor * a[t][i];
         }
+        for (i, cost) in inverse_costs.iter_mut().enumerate() {
+            *cost -= factor * inverse[t][i];
+        }
         self.current_value -= factor * b[t];
         //normalize the pivot row
         for i in 0..a[t].len() {
             a[t][i] /= pivot;
         }
+        for i in 0..inverse[t].len() {
+            inverse[t][i] /= pivot;
+        }
         //normalize the pivot's row value
         b[t] /= pivot;
         //update the basis
@@ -303,4 +651,34 @@ impl Tableau {
     pub fn in_basis(&self) -> &Vec<usize> {
         &self.in_basis
     }
+    /// Returns, for each constraint, the column of its slack variable, or `None` if
+    /// the constraint is an equality and has no slack variable
+    pub fn slack_columns(&self) -> &Vec<Option<usize>> {
+        &self.slack_columns
+    }
+    /// Sets the original right hand side of the constraints, with the columns of the inverse of
+    /// the starting basis and their reduced costs, used by `update_rhs`.
+    ///
+    /// Column `i` of the inverse is how the tableau was derived from constraint `i`, for a
+    /// constraint with a slack variable it is the column of the slack.
+    pub(crate) fn set_original_rhs(
+        &mut self,
+        original_b: Vec<f64>,
+        basis_inverse: Vec<Vec<f64>>,
+        basis_inverse_costs: Vec<f64>,
+    ) {
+        self.original_b = original_b;
+        self.basis_inverse = basis_inverse;
+        self.basis_inverse_costs = basis_inverse_costs;
+    }
+    //by default the starting basis is assumed to be made of the slack variables
+    pub(crate) fn set_slack_columns(&mut self, slack_columns: Vec<Option<usize>>) {
+        self.slack_columns = slack_columns;
+    }
+}
+
+fn sorted_basis(basis: &[usize]) -> Vec<usize> {
+    let mut basis = basis.to_vec();
+    basis.sort();
+    basis
 }
@@ -0,0 +1,206 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::solvers::auto_solver::{bool_to_milp, int_bool_to_milp, real_to_milp};
+use crate::solvers::{
+    auto_solver, solve_binary_lp_problem, solve_integer_binary_lp_problem, solve_milp_lp_problem,
+    solve_milp_lp_problem_with_budget, solve_real_lp_problem_clarabel_with_warm_start,
+    solve_real_lp_problem_slow_simplex, solve_sparse, LpSolution, MILPValue, SearchBudget,
+    SolverError,
+};
+use crate::transformers::LinearModel;
+
+/// The solvers that can be used to solve a [`LinearModel`] with [`solve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum SolverBackend {
+    /// Picks the solver based on the types of the variables of the model
+    #[default]
+    Auto,
+    /// Interior point solver for models with only real variables
+    Real,
+    /// Simplex solver for models with only real variables
+    Simplex,
+    /// Solver for models with only boolean variables
+    Binary,
+    /// Solver for models with only boolean and integer variables
+    IntegerBinary,
+    /// Branch and bound solver for models with real, integer and boolean variables
+    Milp,
+}
+
+/// Configuration of [`solve`], choosing the solver and its limits.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SolverConfig {
+    /// The solver to use
+    pub backend: SolverBackend,
+    /// The maximum number of iterations of the simplex solver
+    pub iteration_limit: i64,
+    /// The maximum number of nodes explored by the branch and bound of the MILP solver,
+    /// if set and reached, the search is stopped with [`SolverError::IntegerSearchIncomplete`]
+    pub node_limit: Option<usize>,
+    /// The maximum time spent by the branch and bound of the MILP solver, shared by the
+    /// whole search tree, if set and reached, the search is stopped like with the node limit
+    pub time_limit: Option<Duration>,
+    /// The relative optimality gap at which the branch and bound of the MILP solver stops
+    /// and returns its best solution, see [`SearchBudget::with_gap_tolerance`]
+    pub gap_tolerance: Option<f64>,
+    /// The starting values of the variables, checked by the real solver and ignored by the others,
+    /// the solution carries a note when they are ignored
+    pub warm_start: HashMap<String, f64>,
+    /// Whether to look, among the optimal solutions, for one with the fewest variables different
+    /// from zero with [`solve_sparse`], it needs a solver that can handle boolean variables
+    pub prefer_sparse: bool,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            backend: SolverBackend::default(),
+            iteration_limit: 10000,
+            node_limit: None,
+            time_limit: None,
+            gap_tolerance: None,
+            warm_start: HashMap::new(),
+            prefer_sparse: false,
+        }
+    }
+}
+
+impl SolverConfig {
+    /// Creates a configuration that uses the given solver with the default limits.
+    ///
+    /// # Arguments
+    /// * `backend` - The solver to use
+    pub fn new(backend: SolverBackend) -> Self {
+        Self {
+            backend,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the maximum number of iterations of the simplex solver.
+    pub fn with_iteration_limit(mut self, iteration_limit: i64) -> Self {
+        self.iteration_limit = iteration_limit;
+        self
+    }
+
+    /// Sets the maximum number of nodes explored by the MILP solver.
+    pub fn with_node_limit(mut self, node_limit: usize) -> Self {
+        self.node_limit = Some(node_limit);
+        self
+    }
+
+    /// Sets the maximum time spent by the MILP solver.
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    /// Sets the relative optimality gap at which the MILP solver stops searching.
+    pub fn with_gap_tolerance(mut self, gap_tolerance: f64) -> Self {
+        self.gap_tolerance = Some(gap_tolerance);
+        self
+    }
+
+    /// Sets the starting values of the variables, the solvers that don't support
+    /// a starting point ignore them and leave a note in the solution.
+    pub fn with_warm_start(mut self, warm_start: HashMap<String, f64>) -> Self {
+        self.warm_start = warm_start;
+        self
+    }
+
+    /// Looks for an optimal solution with the fewest variables different from zero,
+    /// by solving the model a second time with [`solve_sparse`].
+    ///
+    /// The second step adds boolean variables to the model, so it can only be used with
+    /// the solvers that support them, the real and simplex solvers return an error.
+    pub fn with_prefer_sparse(mut self) -> Self {
+        self.prefer_sparse = true;
+        self
+    }
+}
+
+/// Solves a model with the solver chosen in the configuration.
+///
+/// The values of the solution are converted to [`MILPValue`] so that every solver
+/// returns the same type of solution, together with the slack of each constraint.
+/// The simplex solver also returns the counters of the solve, see [`LpSolution::stats`].
+///
+/// # Arguments
+/// * `model` - The model to solve
+/// * `config` - Which solver to use and its limits
+///
+/// # Returns
+/// * `Ok(LpSolution<MILPValue>)` - The solution found by the solver
+/// * `Err(SolverError)` - If the model can't be solved by the chosen solver
+///
+/// # Example
+/// ```rust
+/// use rooc::solvers::{solve, SolverBackend, SolverConfig};
+/// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+///
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::non_negative_real());
+/// model.add_variable("y", VariableType::non_negative_real());
+/// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+/// model.set_objective(vec![1.0, 2.0], OptimizationType::Max);
+///
+/// let solution = solve(&model, &SolverConfig::new(SolverBackend::Real)).unwrap();
+/// ```
+pub fn solve(
+    model: &LinearModel,
+    config: &SolverConfig,
+) -> Result<LpSolution<MILPValue>, SolverError> {
+    if config.prefer_sparse {
+        if matches!(config.backend, SolverBackend::Real | SolverBackend::Simplex) {
+            return Err(SolverError::Other(format!(
+                "Preferring sparse solutions needs a solver that supports boolean variables, but got {:?}",
+                config.backend
+            )));
+        }
+        let step = SolverConfig {
+            prefer_sparse: false,
+            ..config.clone()
+        };
+        return solve_sparse(model, |model| solve(model, &step))
+            .map(|solution| solution.with_slacks(model));
+    }
+    let solution = match config.backend {
+        SolverBackend::Real => {
+            return solve_real_lp_problem_clarabel_with_warm_start(model, &config.warm_start)
+                .map(|solution| real_to_milp(solution).with_slacks(model))
+        }
+        SolverBackend::Auto => auto_solver(model),
+        SolverBackend::Simplex => {
+            let solution = solve_real_lp_problem_slow_simplex(model, config.iteration_limit)?;
+            Ok(real_to_milp(solution))
+        }
+        SolverBackend::Binary => solve_binary_lp_problem(model).map(bool_to_milp),
+        SolverBackend::IntegerBinary => {
+            solve_integer_binary_lp_problem(model).map(int_bool_to_milp)
+        }
+        SolverBackend::Milp => match (config.node_limit, config.time_limit, config.gap_tolerance) {
+            (None, None, None) => solve_milp_lp_problem(model),
+            (node_limit, time_limit, gap_tolerance) => {
+                let budget = SearchBudget {
+                    node_limit,
+                    time_limit,
+                    gap_tolerance,
+                };
+                solve_milp_lp_problem_with_budget(model, budget)
+            }
+        },
+    };
+    solution.map(|solution| {
+        let solution = solution.with_slacks(model);
+        if config.warm_start.is_empty() {
+            solution
+        } else {
+            solution.with_note(format!(
+                "The warm start was ignored, the {:?} solver does not accept a starting point",
+                config.backend
+            ))
+        }
+    })
+}
@@ -0,0 +1,196 @@
+#[allow(unused_imports)]
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use crate::solvers::simplex::PrettyFraction;
+
+/// Options to format the numbers written when displaying a model or a solution,
+/// either as text or as LaTeX. Only the output is changed, the values stored in
+/// the model or solution stay exact.
+///
+/// # Example
+/// ```rust
+/// use rooc::{Comparison, DisplayWithOptions, FormatOptions, LinearModel, VariableType};
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::non_negative_real());
+/// model.add_constraint(vec![0.25000000001], Comparison::LessOrEqual, 1.0 / 3.0);
+///
+/// let options = FormatOptions::new().with_significant_digits(3);
+/// assert!(model.to_string_with_options(&options).contains("0.25x <= 0.333"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FormatOptions {
+    significant_digits: Option<u8>,
+    latex_fraction_tolerance: Option<f64>,
+    decimals: Option<u8>,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const IFormatOptions: &'static str = r#"
+export type SerializedFormatOptions = {
+    significant_digits?: number
+    latex_fraction_tolerance?: number
+    decimals?: number
+}
+"#;
+
+impl FormatOptions {
+    /// Creates options that write numbers with full precision.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes numbers with at most the given number of significant digits,
+    /// the integer part of a number is rounded too when it has more digits.
+    ///
+    /// # Arguments
+    /// * `digits` - The number of significant digits, at least one
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::FormatOptions;
+    /// let options = FormatOptions::new().with_significant_digits(3);
+    /// assert_eq!(options.format_number(2.0 / 3.0), "0.667");
+    /// assert_eq!(options.format_number(12345.678), "12300");
+    /// ```
+    pub fn with_significant_digits(mut self, digits: u8) -> Self {
+        self.significant_digits = Some(digits.max(1));
+        self
+    }
+
+    /// Writes numbers with at most the given number of decimals, trailing zeros are dropped
+    /// and numbers that round to zero, like `1e-16`, are written as `0`.
+    ///
+    /// # Arguments
+    /// * `decimals` - The number of decimals
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::FormatOptions;
+    /// let options = FormatOptions::new().with_decimals(4);
+    /// assert_eq!(options.format_number(2.0 / 3.0), "0.6667");
+    /// assert_eq!(options.format_number(1.5), "1.5");
+    /// assert_eq!(options.format_number(-1e-16), "0");
+    /// ```
+    pub fn with_decimals(mut self, decimals: u8) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Writes numbers in LaTeX as `\\frac{}{}` when they are close to a simple fraction, like `1/3`,
+    /// numbers that are not close to any fraction are written as decimals.
+    ///
+    /// # Arguments
+    /// * `tolerance` - The maximum relative error between the number and the fraction,
+    ///   the larger it is, the simpler the fractions that are found
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::FormatOptions;
+    /// let options = FormatOptions::new().with_latex_fractions(1e-9);
+    /// assert_eq!(options.format_latex_number(-2.0 / 3.0), "-\\frac{2}{3}");
+    /// assert_eq!(options.format_latex_number(0.5), "\\frac{1}{2}");
+    /// assert_eq!(options.format_latex_number(std::f64::consts::PI), "3.141592653589793");
+    /// ```
+    pub fn with_latex_fractions(mut self, tolerance: f64) -> Self {
+        self.latex_fraction_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Returns the number of significant digits numbers are written with, if limited.
+    pub fn significant_digits(&self) -> Option<u8> {
+        self.significant_digits
+    }
+
+    /// Returns the number of decimals numbers are written with, if limited.
+    pub fn decimals(&self) -> Option<u8> {
+        self.decimals
+    }
+
+    /// Returns the tolerance used to write numbers as fractions in LaTeX, if enabled.
+    pub fn latex_fraction_tolerance(&self) -> Option<f64> {
+        self.latex_fraction_tolerance
+    }
+
+    /// Formats a single number.
+    ///
+    /// # Arguments
+    /// * `value` - The number to format
+    pub fn format_number(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        let mut rounded = value;
+        if let Some(digits) = self.significant_digits.filter(|_| value != 0.0) {
+            let magnitude = value.abs().log10().floor() as i32;
+            let decimals = digits as i32 - 1 - magnitude;
+            rounded = if decimals >= 0 {
+                round_to_decimals(rounded, decimals as usize)
+            } else {
+                let scale = 10f64.powi(-decimals);
+                (rounded / scale).round() * scale
+            };
+        }
+        if let Some(decimals) = self.decimals {
+            rounded = round_to_decimals(rounded, decimals as usize);
+            //a tiny negative number would otherwise be written as -0
+            if rounded == 0.0 {
+                return "0".to_string();
+            }
+        }
+        rounded.to_string()
+    }
+
+    /// Formats a single number to be written in LaTeX, as a fraction if enabled
+    /// and the number is close to a simple fraction.
+    ///
+    /// # Arguments
+    /// * `value` - The number to format
+    pub fn format_latex_number(&self, value: f64) -> String {
+        let Some(tolerance) = self.latex_fraction_tolerance else {
+            return self.format_number(value);
+        };
+        let fraction = PrettyFraction::with_tolerance(value, tolerance);
+        //a small number should not be approximated by zero
+        if fraction.is_fraction() && (fraction.numerator() != 0 || value == 0.0) {
+            fraction.pretty_latex()
+        } else {
+            self.format_number(value)
+        }
+    }
+
+    /// Formats a value that is written as a number, like the value of a variable in a solution,
+    /// values that are not numbers, like booleans, are written as they are.
+    ///
+    /// # Arguments
+    /// * `value` - The value to format
+    pub(crate) fn format_value<T: Display>(&self, value: &T) -> String {
+        let value = value.to_string();
+        match value.parse::<f64>() {
+            Ok(number) => self.format_number(number),
+            Err(_) => value,
+        }
+    }
+}
+
+/// Rounds a number to the given number of decimals
+fn round_to_decimals(value: f64, decimals: usize) -> f64 {
+    format!("{:.*}", decimals, value)
+        .parse::<f64>()
+        .unwrap_or(value)
+}
+
+/// Converts a value to a string, formatting the numbers it contains with the given options.
+///
+/// It is implemented by the models and solutions, their `Display` implementation
+/// writes numbers with the default options, keeping the full precision.
+pub trait DisplayWithOptions: Display {
+    /// Converts the value to a string, formatting its numbers with the given options.
+    ///
+    /// # Arguments
+    /// * `options` - How numbers should be written
+    fn to_string_with_options(&self, options: &FormatOptions) -> String;
+}
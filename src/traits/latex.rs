@@ -1,35 +1,47 @@
+use crate::parser::model_transformer::VariableNamingStrategy;
+use crate::traits::FormatOptions;
 use std::fmt::Debug;
 
+/// Options that change how a model is written in LaTeX
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LatexOptions {
+    /// How the indexes of compound variables are subscripted
+    pub naming_strategy: VariableNamingStrategy,
+    /// How the numbers are written
+    pub numbers: FormatOptions,
+}
+
 pub trait ToLatex: Debug {
-    fn to_latex(&self) -> String;
+    /// Converts to LaTeX, passing the options down to every nested element
+    fn to_latex_with(&self, options: &LatexOptions) -> String;
 }
 
 impl ToLatex for String {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         format!("\\text{{\"{}\"}}", escape_latex(self))
     }
 }
 
 impl ToLatex for f64 {
-    fn to_latex(&self) -> String {
-        format!("{}", self)
+    fn to_latex_with(&self, options: &LatexOptions) -> String {
+        options.numbers.format_latex_number(*self)
     }
 }
 
 impl ToLatex for i64 {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         format!("{}", self)
     }
 }
 
 impl ToLatex for u64 {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         format!("{}", self)
     }
 }
 
 impl ToLatex for bool {
-    fn to_latex(&self) -> String {
+    fn to_latex_with(&self, _options: &LatexOptions) -> String {
         format!("{}", self)
     }
 }
@@ -53,3 +65,59 @@ pub fn escape_latex(string: &str) -> String {
     }
     result
 }
+
+/// The names of the Greek letters that have a LaTeX command, like `\alpha` or `\Delta`
+const GREEK_LETTERS: [&str; 35] = [
+    "alpha",
+    "beta",
+    "gamma",
+    "delta",
+    "epsilon",
+    "zeta",
+    "eta",
+    "theta",
+    "iota",
+    "kappa",
+    "lambda",
+    "mu",
+    "nu",
+    "xi",
+    "pi",
+    "rho",
+    "sigma",
+    "tau",
+    "upsilon",
+    "phi",
+    "chi",
+    "psi",
+    "omega",
+    "Gamma",
+    "Delta",
+    "Theta",
+    "Lambda",
+    "Xi",
+    "Pi",
+    "Sigma",
+    "Upsilon",
+    "Phi",
+    "Psi",
+    "Omega",
+    "varepsilon",
+];
+
+/// Converts the name of a variable to LaTeX, names of Greek letters are written as the letter,
+/// and trailing digits of any name are written as a subscript (`x1` becomes `x_{1}` and
+/// `alpha1` becomes `\alpha_{1}`). The rest of the name is escaped as it is.
+pub fn variable_name_to_latex(name: &str) -> String {
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = &name[base.len()..];
+    let latex_base = match GREEK_LETTERS.contains(&base) {
+        true => format!("\\{}", base),
+        false => escape_latex(base),
+    };
+    //names made only of digits, or already subscripted like x_1, are kept as they are
+    if digits.is_empty() || base.is_empty() || base.ends_with('_') {
+        return format!("{}{}", latex_base, escape_latex(digits));
+    }
+    format!("{}_{{{}}}", latex_base, digits)
+}
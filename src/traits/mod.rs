@@ -1,3 +1,5 @@
+mod format;
 mod latex;
 
+pub use format::*;
 pub use latex::*;
@@ -0,0 +1,118 @@
+#[allow(unused_imports)]
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::parser::model_transformer::VariableIndexes;
+use crate::solvers::{LpSolution, MILPValue};
+use crate::transformers::{LinearModel, LinearModelError};
+
+/// A linear model bundled with, optionally, a solution of the model, serialized as a single
+/// JSON document so that a viewer can render the model and show the value of each indexed
+/// variable like `x[1][2]`.
+///
+/// The JSON has the following shape, described by `SerializedCompiledProblem` in TypeScript:
+/// - `model`: the model, as written by [`LinearModel::to_json`], which includes the base name and
+///   indexes of each flattened variable, like `["x", [1, 2]]` for `x_1_2`
+/// - `solution`: the solution, if any, with the value of each variable tagged with its type
+///
+/// # Example
+/// ```rust
+/// use rooc::{Comparison, CompiledProblem, LinearModel, OptimizationType, VariableType};
+/// use rooc::solvers::{solve, SolverBackend, SolverConfig};
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::IntegerRange(0, 10));
+/// model.add_constraint(vec![2.0], Comparison::LessOrEqual, 7.0);
+/// model.set_objective(vec![1.0], OptimizationType::Max);
+///
+/// let solution = solve(&model, &SolverConfig::new(SolverBackend::Milp)).unwrap();
+/// let json = CompiledProblem::new(model, Some(solution)).to_json();
+/// let read = CompiledProblem::from_json(&json).unwrap();
+/// assert_eq!(read.solution().unwrap().value(), 3.0);
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledProblem {
+    model: LinearModel,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    solution: Option<LpSolution<MILPValue>>,
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const ICompiledProblem: &'static str = r#"
+export type SerializedCompiledProblem = {
+    model: SerializedLinearModel,
+    solution?: {
+        assignment: {
+            name: string,
+            value: { type: "Int", value: number } | { type: "Bool", value: boolean } | { type: "Real", value: number }
+        }[],
+        value: number,
+        gap: number,
+        slacks?: number[]
+    }
+}
+"#;
+
+impl CompiledProblem {
+    /// Bundles a model with its solution.
+    ///
+    /// # Arguments
+    /// * `model` - The model to export
+    /// * `solution` - A solution of the model, if it was solved
+    pub fn new(model: LinearModel, solution: Option<LpSolution<MILPValue>>) -> Self {
+        Self { model, solution }
+    }
+
+    /// Returns the model.
+    pub fn model(&self) -> &LinearModel {
+        &self.model
+    }
+
+    /// Returns the base name and indexes of each flattened variable of the model.
+    pub fn variable_indexes(&self) -> &VariableIndexes {
+        self.model.variable_indexes()
+    }
+
+    /// Returns the solution of the model, if any.
+    pub fn solution(&self) -> Option<&LpSolution<MILPValue>> {
+        self.solution.as_ref()
+    }
+
+    /// Serializes the model and its solution to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize the problem to JSON")
+    }
+
+    /// Reads a problem serialized with [`CompiledProblem::to_json`].
+    ///
+    /// # Arguments
+    /// * `json` - The serialized problem
+    ///
+    /// # Returns
+    /// * `Ok(CompiledProblem)` if the problem is valid
+    /// * `Err(LinearModelError::InvalidJson)` if the JSON is not a problem, the model is not valid
+    ///   like in [`LinearModel::from_json`], or the solution assigns a variable that is not in the model
+    pub fn from_json(json: &str) -> Result<CompiledProblem, LinearModelError> {
+        let problem: CompiledProblem =
+            serde_json::from_str(json).map_err(|e| LinearModelError::InvalidJson(e.to_string()))?;
+        problem
+            .model
+            .validate()
+            .map_err(|problems| LinearModelError::InvalidJson(problems.join(", ")))?;
+        if let Some(solution) = &problem.solution {
+            let variables = problem.model.variables();
+            if let Some(assignment) = solution
+                .assignment()
+                .iter()
+                .find(|a| !variables.contains(&a.name))
+            {
+                return Err(LinearModelError::InvalidJson(format!(
+                    "The solution assigns \"{}\" which is not a variable of the model",
+                    assignment.name
+                )));
+            }
+        }
+        Ok(problem)
+    }
+}
@@ -0,0 +1,192 @@
+use crate::math::{Comparison, VariableType};
+use crate::transformers::{LinearConstraint, LinearModel, LinearModelError};
+
+/// A set of linear constraints of which at least `at_least` must hold.
+///
+/// When added to a [`LinearModel`], each constraint gets a binary indicator variable
+/// which enables it through a big-M term, the big-M is derived from the bounds of the variables.
+///
+/// # Example
+/// ```rust
+/// use rooc::{Comparison, DisjunctiveConstraintSet, LinearConstraint, LinearModel, VariableType};
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::NonNegativeReal(0.0, 10.0));
+/// // x <= 2 or x >= 8
+/// let disjunction = DisjunctiveConstraintSet::new(
+///     vec![
+///         LinearConstraint::new(vec![1.0], Comparison::LessOrEqual, 2.0),
+///         LinearConstraint::new(vec![1.0], Comparison::GreaterOrEqual, 8.0),
+///     ],
+///     1,
+/// );
+/// model.add_disjunctive_constraints(disjunction).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DisjunctiveConstraintSet {
+    constraints: Vec<LinearConstraint>,
+    at_least: usize,
+}
+
+impl DisjunctiveConstraintSet {
+    /// Creates a new disjunction where at least `at_least` of the constraints must hold.
+    ///
+    /// # Arguments
+    /// * `constraints` - The constraints of the disjunction
+    /// * `at_least` - How many constraints must hold
+    pub fn new(constraints: Vec<LinearConstraint>, at_least: usize) -> Self {
+        Self {
+            constraints,
+            at_least,
+        }
+    }
+
+    /// Returns the constraints of the disjunction.
+    pub fn constraints(&self) -> &Vec<LinearConstraint> {
+        &self.constraints
+    }
+
+    /// Returns how many constraints must hold.
+    pub fn at_least(&self) -> usize {
+        self.at_least
+    }
+}
+
+/// Returns the lower and upper bound of a variable type
+fn variable_bounds(var_type: &VariableType) -> (f64, f64) {
+    match var_type {
+        VariableType::Boolean => (0.0, 1.0),
+        VariableType::NonNegativeReal(min, max) => (*min, *max),
+        VariableType::Real(min, max) => (*min, *max),
+        VariableType::IntegerRange(min, max) => (*min as f64, *max as f64),
+    }
+}
+
+impl LinearModel {
+    /// Returns the smallest and largest values the left hand side of a constraint
+    /// can have within the bounds of the variables, used to derive big-M values.
+    ///
+    /// # Returns
+    /// * `Ok((min, max))` if all the variables used by the constraint have finite bounds
+    /// * `Err(LinearModelError)` if the constraint has too many coefficients or
+    ///   uses a variable without finite bounds
+    pub(crate) fn activity_range(
+        &self,
+        constraint: &LinearConstraint,
+    ) -> Result<(f64, f64), LinearModelError> {
+        if constraint.coefficients().len() > self.variables().len() {
+            return Err(LinearModelError::TooManyCoefficients);
+        }
+        let mut min_activity = 0.0;
+        let mut max_activity = 0.0;
+        for (i, c) in constraint.coefficients().iter().enumerate() {
+            if *c == 0.0 {
+                continue;
+            }
+            let name = &self.variables()[i];
+            let (lower, upper) = self
+                .domain()
+                .get(name)
+                .map(|d| variable_bounds(d.get_type()))
+                .unwrap_or((f64::NEG_INFINITY, f64::INFINITY));
+            if !lower.is_finite() || !upper.is_finite() {
+                return Err(LinearModelError::UnboundedVariable(name.clone()));
+            }
+            let (low, high) = if *c > 0.0 {
+                (c * lower, c * upper)
+            } else {
+                (c * upper, c * lower)
+            };
+            min_activity += low;
+            max_activity += high;
+        }
+        Ok((min_activity, max_activity))
+    }
+
+    /// Adds a disjunction of constraints to the model, at least `at_least` of them will hold.
+    ///
+    /// Each constraint gets a binary indicator variable named `$dis_i` which, when zero,
+    /// relaxes the constraint by a big-M computed from the variable bounds.
+    ///
+    /// # Arguments
+    /// * `disjunction` - The set of constraints to add
+    ///
+    /// # Returns
+    /// * `Ok(())` if successful
+    /// * `Err(LinearModelError)` if a constraint has too many coefficients, a variable
+    ///   used in the disjunction has no finite bounds or `at_least` is too large
+    pub fn add_disjunctive_constraints(
+        &mut self,
+        disjunction: DisjunctiveConstraintSet,
+    ) -> Result<(), LinearModelError> {
+        let DisjunctiveConstraintSet {
+            constraints,
+            at_least,
+        } = disjunction;
+        if at_least > constraints.len() {
+            return Err(LinearModelError::InvalidDisjunction {
+                at_least,
+                constraints: constraints.len(),
+            });
+        }
+        let mut relaxed = Vec::new();
+        for constraint in constraints {
+            let (min_activity, max_activity) = self.activity_range(&constraint)?;
+            relaxed.push((constraint, min_activity, max_activity));
+        }
+        let first_index = self
+            .variables()
+            .iter()
+            .filter(|v| v.starts_with("$dis_"))
+            .count();
+        let mut indicators = Vec::new();
+        for (i, (constraint, min_activity, max_activity)) in relaxed.into_iter().enumerate() {
+            let indicator = format!("$dis_{}", first_index + i);
+            self.add_variable(&indicator, VariableType::Boolean);
+            let indicator_index = self.variables().len() - 1;
+            let (coefficients, constraint_type, rhs) = constraint.into_parts();
+            let upper = match constraint_type {
+                Comparison::LessOrEqual | Comparison::Less | Comparison::Equal => {
+                    Some(max_activity - rhs)
+                }
+                _ => None,
+            };
+            let lower = match constraint_type {
+                Comparison::GreaterOrEqual | Comparison::Greater | Comparison::Equal => {
+                    Some(rhs - min_activity)
+                }
+                _ => None,
+            };
+            // lhs <= rhs + M * (1 - indicator)
+            if let Some(big_m) = upper {
+                let big_m = big_m.max(0.0);
+                let mut coefficients = coefficients.clone();
+                coefficients.resize(indicator_index, 0.0);
+                coefficients.push(big_m);
+                let constraint_type = match constraint_type {
+                    Comparison::Less => Comparison::Less,
+                    _ => Comparison::LessOrEqual,
+                };
+                self.add_constraint(coefficients, constraint_type, rhs + big_m);
+            }
+            // lhs >= rhs - M * (1 - indicator)
+            if let Some(big_m) = lower {
+                let big_m = big_m.max(0.0);
+                let mut coefficients = coefficients.clone();
+                coefficients.resize(indicator_index, 0.0);
+                coefficients.push(-big_m);
+                let constraint_type = match constraint_type {
+                    Comparison::Greater => Comparison::Greater,
+                    _ => Comparison::GreaterOrEqual,
+                };
+                self.add_constraint(coefficients, constraint_type, rhs - big_m);
+            }
+            indicators.push(indicator_index);
+        }
+        let mut selector = vec![0.0; self.variables().len()];
+        for index in indicators {
+            selector[index] = 1.0;
+        }
+        self.add_constraint(selector, Comparison::GreaterOrEqual, at_least as f64);
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use crate::math::{Comparison, VariableType};
+use crate::transformers::{LinearModel, LinearModelError};
+use crate::OptimizationType;
+
+/// A row of the primal model, either a constraint or a finite bound of a variable
+struct PrimalRow {
+    name: String,
+    coefficients: Vec<f64>,
+    constraint_type: Comparison,
+    rhs: f64,
+}
+
+impl LinearModel {
+    /// Builds the dual of a linear programming model with real variables.
+    ///
+    /// Each constraint of the model becomes a variable of the dual, named like the constraint
+    /// in [`LinearModel::iter_constraints_named`] (`c0`, `c1`, ...), and each variable of the model
+    /// becomes a constraint of the dual, in the same order. A maximization becomes a minimization
+    /// and the other way around, a model to satisfy is treated as a minimization of zero.
+    ///
+    /// The sign of a dual variable depends on the comparison of its constraint: for a maximization,
+    /// `<=` constraints give non negative variables, `>=` constraints give non positive variables and
+    /// equality constraints give free variables, the opposite holds for a minimization. Non negative
+    /// variables of the model give inequality constraints, free variables give equality constraints.
+    /// Finite bounds of the variables other than zero are added as constraints before building the dual,
+    /// their dual variables are named `$lb_name` and `$ub_name`. The objective offset is kept.
+    ///
+    /// # Returns
+    /// * `Ok(LinearModel)` the dual model, with the same optimal value as the model if it has one
+    /// * `Err(LinearModelError::NotReal)` if a variable is not real
+    /// * `Err(LinearModelError::StrictComparison)` if a constraint uses `<` or `>`
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_variable("y", VariableType::non_negative_real());
+    /// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+    /// model.set_objective(vec![2.0, 3.0], OptimizationType::Max);
+    ///
+    /// let dual = model.dual().unwrap();
+    /// assert_eq!(dual.variables(), &vec!["c0".to_string()]);
+    /// assert_eq!(dual.objective(), &vec![4.0]);
+    /// assert_eq!(dual.optimization_type(), &OptimizationType::Min);
+    /// ```
+    pub fn dual(&self) -> Result<LinearModel, LinearModelError> {
+        let variables = self.variables();
+        let mut rows = Vec::with_capacity(self.constraints().len());
+        for (i, constraint) in self.constraints().iter().enumerate() {
+            if matches!(
+                constraint.constraint_type(),
+                Comparison::Less | Comparison::Greater
+            ) {
+                return Err(LinearModelError::StrictComparison(i));
+            }
+            let mut coefficients = constraint.coefficients().clone();
+            coefficients.resize(variables.len(), 0.0);
+            rows.push(PrimalRow {
+                name: format!("c{}", i),
+                coefficients,
+                constraint_type: *constraint.constraint_type(),
+                rhs: constraint.rhs(),
+            });
+        }
+        let mut is_free = Vec::with_capacity(variables.len());
+        for (i, name) in variables.iter().enumerate() {
+            let (min, max, lower_bound) = match self.domain().get(name).map(|d| d.get_type()) {
+                Some(VariableType::Real(min, max)) => (*min, *max, f64::NEG_INFINITY),
+                Some(VariableType::NonNegativeReal(min, max)) => (*min, *max, 0.0),
+                _ => return Err(LinearModelError::NotReal(name.clone())),
+            };
+            is_free.push(lower_bound == f64::NEG_INFINITY);
+            let mut coefficients = vec![0.0; variables.len()];
+            coefficients[i] = 1.0;
+            if min != lower_bound {
+                rows.push(PrimalRow {
+                    name: format!("$lb_{}", name),
+                    coefficients: coefficients.clone(),
+                    constraint_type: Comparison::GreaterOrEqual,
+                    rhs: min,
+                });
+            }
+            if max != f64::INFINITY {
+                rows.push(PrimalRow {
+                    name: format!("$ub_{}", name),
+                    coefficients,
+                    constraint_type: Comparison::LessOrEqual,
+                    rhs: max,
+                });
+            }
+        }
+        let is_max = *self.optimization_type() == OptimizationType::Max;
+        let mut dual = LinearModel::new();
+        for row in &rows {
+            let domain = match (row.constraint_type, is_max) {
+                (Comparison::Equal, _) => VariableType::real(),
+                (Comparison::LessOrEqual, true) | (Comparison::GreaterOrEqual, false) => {
+                    VariableType::non_negative_real()
+                }
+                _ => VariableType::Real(f64::NEG_INFINITY, 0.0),
+            };
+            dual.add_variable(&row.name, domain);
+        }
+        let objective = match self.optimization_type() {
+            OptimizationType::Satisfy => vec![0.0; variables.len()],
+            _ => self.objective().clone(),
+        };
+        for (i, free) in is_free.iter().enumerate() {
+            let coefficients = rows.iter().map(|row| row.coefficients[i]).collect();
+            let constraint_type = match (free, is_max) {
+                (true, _) => Comparison::Equal,
+                (false, true) => Comparison::GreaterOrEqual,
+                (false, false) => Comparison::LessOrEqual,
+            };
+            dual.add_constraint(coefficients, constraint_type, objective[i]);
+        }
+        let optimization_type = match is_max {
+            true => OptimizationType::Min,
+            false => OptimizationType::Max,
+        };
+        dual.set_objective(rows.iter().map(|row| row.rhs).collect(), optimization_type);
+        let (objective, optimization_type, _, constraints, variables, domain) = dual.into_parts();
+        Ok(LinearModel::new_from_parts(
+            objective,
+            optimization_type,
+            self.objective_offset(),
+            constraints,
+            variables,
+            domain,
+        ))
+    }
+}
@@ -0,0 +1,151 @@
+use crate::math::{Comparison, VariableType};
+use crate::transformers::{LinearConstraint, LinearModel, LinearModelError};
+
+/// A linear constraint that only has to hold when a boolean indicator variable is one.
+///
+/// When added to a [`LinearModel`], the constraint is linked to the indicator with a big-M term:
+/// `lhs <= rhs + M * (1 - y)` for `<=` constraints and `lhs >= rhs - M * (1 - y)` for `>=` constraints,
+/// equality constraints get both. When the indicator is zero the constraint is relaxed by `M`,
+/// so `M` must be at least as large as the largest violation of the constraint within the
+/// feasible region, a smaller `M` cuts off valid solutions while a larger one weakens the relaxation.
+///
+/// If no big-M is given, it is derived from the bounds of the variables used by the constraint.
+///
+/// # Example
+/// ```rust
+/// use rooc::{Comparison, IndicatorConstraint, LinearConstraint, LinearModel, VariableType};
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::NonNegativeReal(0.0, 10.0));
+/// model.add_variable("y", VariableType::Boolean);
+/// // if y then x >= 5
+/// let indicator = IndicatorConstraint::new(
+///     "y",
+///     LinearConstraint::new(vec![1.0], Comparison::GreaterOrEqual, 5.0),
+/// );
+/// model.add_indicator_constraint(indicator).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndicatorConstraint {
+    indicator: String,
+    constraint: LinearConstraint,
+    big_m: Option<f64>,
+}
+
+impl IndicatorConstraint {
+    /// Creates a new constraint that holds when the `indicator` variable is one,
+    /// the big-M is derived from the bounds of the variables.
+    ///
+    /// # Arguments
+    /// * `indicator` - The name of the boolean indicator variable
+    /// * `constraint` - The constraint to enforce when the indicator is one
+    pub fn new(indicator: &str, constraint: LinearConstraint) -> Self {
+        Self {
+            indicator: indicator.to_string(),
+            constraint,
+            big_m: None,
+        }
+    }
+
+    /// Sets the big-M used to relax the constraint instead of deriving it from the variable bounds.
+    ///
+    /// # Arguments
+    /// * `big_m` - An upper bound of how much the constraint can be violated
+    pub fn with_big_m(mut self, big_m: f64) -> Self {
+        self.big_m = Some(big_m);
+        self
+    }
+
+    /// Returns the name of the indicator variable.
+    pub fn indicator(&self) -> &String {
+        &self.indicator
+    }
+
+    /// Returns the constraint enforced when the indicator is one.
+    pub fn constraint(&self) -> &LinearConstraint {
+        &self.constraint
+    }
+
+    /// Returns the big-M given by the user, if any.
+    pub fn big_m(&self) -> Option<f64> {
+        self.big_m
+    }
+}
+
+impl LinearModel {
+    /// Adds a constraint that only holds when its indicator variable is one.
+    ///
+    /// # Arguments
+    /// * `indicator` - The indicator constraint to add
+    ///
+    /// # Returns
+    /// * `Ok(())` if successful
+    /// * `Err(LinearModelError)` if the indicator is not a boolean variable of the model,
+    ///   the constraint has too many coefficients or, when no big-M is given, a variable
+    ///   used in the constraint has no finite bounds
+    pub fn add_indicator_constraint(
+        &mut self,
+        indicator: IndicatorConstraint,
+    ) -> Result<(), LinearModelError> {
+        let IndicatorConstraint {
+            indicator,
+            constraint,
+            big_m,
+        } = indicator;
+        let indicator_index = match self.variables().iter().position(|v| *v == indicator) {
+            Some(index) => index,
+            None => return Err(LinearModelError::UnknownVariable(indicator)),
+        };
+        let is_boolean = self
+            .domain()
+            .get(&indicator)
+            .is_some_and(|d| *d.get_type() == VariableType::Boolean);
+        if !is_boolean {
+            return Err(LinearModelError::InvalidIndicator(indicator));
+        }
+        if constraint.coefficients().len() > self.variables().len() {
+            return Err(LinearModelError::TooManyCoefficients);
+        }
+        let (upper, lower) = match big_m {
+            Some(big_m) => (big_m, big_m),
+            None => {
+                let (min_activity, max_activity) = self.activity_range(&constraint)?;
+                (
+                    max_activity - constraint.rhs(),
+                    constraint.rhs() - min_activity,
+                )
+            }
+        };
+        let (coefficients, constraint_type, rhs) = constraint.into_parts();
+        // lhs + M * y <= rhs + M
+        if matches!(
+            constraint_type,
+            Comparison::LessOrEqual | Comparison::Less | Comparison::Equal
+        ) {
+            let big_m = upper.max(0.0);
+            let mut coefficients = coefficients.clone();
+            coefficients.resize(self.variables().len(), 0.0);
+            coefficients[indicator_index] += big_m;
+            let constraint_type = match constraint_type {
+                Comparison::Less => Comparison::Less,
+                _ => Comparison::LessOrEqual,
+            };
+            self.add_constraint(coefficients, constraint_type, rhs + big_m);
+        }
+        // lhs - M * y >= rhs - M
+        if matches!(
+            constraint_type,
+            Comparison::GreaterOrEqual | Comparison::Greater | Comparison::Equal
+        ) {
+            let big_m = lower.max(0.0);
+            let mut coefficients = coefficients;
+            coefficients.resize(self.variables().len(), 0.0);
+            coefficients[indicator_index] -= big_m;
+            let constraint_type = match constraint_type {
+                Comparison::Greater => Comparison::Greater,
+                _ => Comparison::GreaterOrEqual,
+            };
+            self.add_constraint(coefficients, constraint_type, rhs - big_m);
+        }
+        Ok(())
+    }
+}
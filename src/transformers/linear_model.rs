@@ -3,17 +3,22 @@
 use crate::prelude::*;
 use indexmap::IndexMap;
 use num_traits::Zero;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 use crate::domain_declaration::format_domain;
-use crate::math::{float_lt, VariableType};
-use crate::parser::model_transformer::DomainVariable;
-use crate::solvers::SolverError;
-use crate::transformers::standard_linear_model::{format_var, StandardLinearModel};
+use crate::math::{float_eq, float_ge, float_gt, float_le, float_lt, VariableType};
+use crate::parser::model_transformer::{DomainVariable, VariableIndexes};
+use crate::solvers::{solve_real_lp_problem_clarabel, LpSolution, MILPValue, SolverError};
+use crate::traits::{DisplayWithOptions, FormatOptions};
+use crate::transformers::standard_linear_model::{format_var_with_options, StandardLinearModel};
+#[cfg(target_arch = "wasm32")]
+use crate::transformers::CompiledProblem;
 use crate::utils::{remove_many, InputSpan};
 use crate::{
     math::{Comparison, OptimizationType},
     transformers::standardizer::to_standard_form,
+    Assignment,
 };
 
 /// Represents a linear constraint in the form: coefficients * variables comparison_operator rhs
@@ -22,12 +27,18 @@ use crate::{
 /// - coefficients: [2.0, 3.0]
 /// - constraint_type: LessOrEqual
 /// - rhs: 5.0
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub struct LinearConstraint {
     coefficients: Vec<f64>,
     rhs: f64,
     constraint_type: Comparison,
+    /// The variable this constraint fixes, if it was added by [`LinearModel::fix_variable`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fixes: Option<String>,
+    /// The name given to the constraint with [`LinearModel::add_named_constraint`], if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
 }
 
 impl LinearConstraint {
@@ -42,9 +53,54 @@ impl LinearConstraint {
             coefficients,
             rhs,
             constraint_type,
+            fixes: None,
+            name: None,
         }
     }
 
+    /// Creates a new linear constraint from named terms, placing each coefficient at the
+    /// position of its variable in `variable_order` and filling the missing ones with zeros.
+    ///
+    /// Terms that refer to the same variable are summed together.
+    ///
+    /// # Arguments
+    /// * `terms` - Pairs of variable name and coefficient, in any order
+    /// * `constraint_type` - Type of comparison operator (e.g., <=, =, >=)
+    /// * `rhs` - Right-hand side constant value
+    /// * `variable_order` - The variables of the model, in the order of the coefficients
+    ///
+    /// # Returns
+    /// * `Ok(LinearConstraint)` if successful
+    /// * `Err(LinearModelError)` if a term refers to a variable not in `variable_order`
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearConstraint};
+    /// let order = vec!["x".to_string(), "y".to_string(), "z".to_string()];
+    /// let constraint = LinearConstraint::from_terms(
+    ///     &[("z".to_string(), 2.0), ("x".to_string(), 1.0)],
+    ///     Comparison::LessOrEqual,
+    ///     10.0,
+    ///     &order,
+    /// ).unwrap();
+    /// assert_eq!(constraint.coefficients(), &vec![1.0, 0.0, 2.0]);
+    /// ```
+    pub fn from_terms(
+        terms: &[(String, f64)],
+        constraint_type: Comparison,
+        rhs: f64,
+        variable_order: &[String],
+    ) -> Result<LinearConstraint, LinearModelError> {
+        let mut coefficients = vec![0.0; variable_order.len()];
+        for (name, coefficient) in terms {
+            match variable_order.iter().position(|v| v == name) {
+                Some(index) => coefficients[index] += coefficient,
+                None => return Err(LinearModelError::UnknownVariable(name.clone())),
+            }
+        }
+        Ok(LinearConstraint::new(coefficients, constraint_type, rhs))
+    }
+
     /// Returns a reference to the coefficient vector.
     pub fn coefficients(&self) -> &Vec<f64> {
         &self.coefficients
@@ -73,6 +129,11 @@ impl LinearConstraint {
         &self.constraint_type
     }
 
+    /// Returns the name given to the constraint, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     /// Decomposes the constraint into its constituent parts.
     ///
     /// # Returns
@@ -122,7 +183,7 @@ impl LinearConstraint {
 /// // Add constraint: x + y <= 10
 /// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 10.0);
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
 pub struct LinearModel {
     variables: Vec<String>,
@@ -131,8 +192,38 @@ pub struct LinearModel {
     optimization_type: OptimizationType,
     objective: Vec<f64>,
     constraints: Vec<LinearConstraint>,
+    #[serde(default)]
+    variable_indexes: VariableIndexes,
+    #[serde(default)]
+    fixed: IndexMap<String, f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    warnings: Vec<String>,
 }
 
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(typescript_custom_section))]
+#[allow(non_upper_case_globals)]
+#[cfg(target_arch = "wasm32")]
+const ILinearModel: &'static str = r#"
+export type SerializedLinearConstraint = {
+    coefficients: number[],
+    rhs: number,
+    constraint_type: Comparison,
+    fixes?: string,
+    name?: string
+}
+export type SerializedLinearModel = {
+    variables: string[],
+    domain: Record<string, DomainVariable>,
+    objective_offset: number,
+    optimization_type: OptimizationType,
+    objective: number[],
+    constraints: SerializedLinearConstraint[],
+    variable_indexes?: Record<string, [string, SerializedPrimitive[]]>,
+    fixed?: Record<string, number>,
+    warnings?: string[]
+}
+"#;
+
 impl Default for LinearModel {
     fn default() -> Self {
         LinearModel {
@@ -142,6 +233,9 @@ impl Default for LinearModel {
             optimization_type: OptimizationType::Min,
             objective: vec![],
             constraints: vec![],
+            variable_indexes: IndexMap::new(),
+            fixed: IndexMap::new(),
+            warnings: Vec::new(),
         }
     }
 }
@@ -151,6 +245,184 @@ impl Default for LinearModel {
 pub enum LinearModelError {
     /// Indicates that the number of coefficients provided exceeds the number of variables in the model.
     TooManyCoefficients,
+    /// Indicates that a variable has no finite bounds, so a big-M value or a search space can't be derived from it.
+    UnboundedVariable(String),
+    /// Indicates that more constraints are required to hold than how many there are in a disjunction.
+    InvalidDisjunction { at_least: usize, constraints: usize },
+    /// Indicates that a term refers to a variable that is not part of the model.
+    UnknownVariable(String),
+    /// Indicates that a variable is declared more than once.
+    DuplicateVariable(String),
+    /// Indicates that two constraints have the same name.
+    DuplicateConstraint(String),
+    /// Indicates that a number of the model is NaN or infinite, the position says where it appears.
+    NotFinite { position: String, value: f64 },
+    /// Indicates that a model read from JSON is malformed or inconsistent.
+    InvalidJson(String),
+    /// Indicates that the objective, or the constraint at the given index, doesn't have one coefficient per variable.
+    WrongCoefficientCount {
+        constraint: Option<usize>,
+        expected: usize,
+        got: usize,
+    },
+    /// Indicates that the indicator of a constraint is not a boolean variable.
+    InvalidIndicator(String),
+    /// Indicates that a variable is not an integer or boolean, so its values can't be enumerated.
+    NotInteger(String),
+    /// Indicates that there are more points to enumerate than the given limit.
+    SearchSpaceTooLarge { size: usize, limit: usize },
+    /// Indicates that a variable is not real, so the model has no linear dual.
+    NotReal(String),
+    /// Indicates that the constraint at the given index uses a strict comparison, which has no linear dual.
+    StrictComparison(usize),
+    /// Indicates that the equality constraints give the variable a value outside of its domain,
+    /// or a different value than the other constraints.
+    ContradictoryEquality(String),
+}
+
+impl Display for LinearModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinearModelError::TooManyCoefficients => {
+                write!(f, "There are more coefficients than variables in the model")
+            }
+            LinearModelError::UnboundedVariable(name) => {
+                write!(f, "Variable \"{}\" has no finite bounds", name)
+            }
+            LinearModelError::InvalidDisjunction {
+                at_least,
+                constraints,
+            } => write!(
+                f,
+                "At least {} constraints must hold but the disjunction has {}",
+                at_least, constraints
+            ),
+            LinearModelError::UnknownVariable(name) => {
+                write!(f, "Variable \"{}\" is not in the model", name)
+            }
+            LinearModelError::DuplicateVariable(name) => {
+                write!(f, "Variable \"{}\" is declared more than once", name)
+            }
+            LinearModelError::DuplicateConstraint(name) => {
+                write!(f, "Constraint \"{}\" is declared more than once", name)
+            }
+            LinearModelError::NotFinite { position, value } => {
+                write!(f, "The {} is {}", position, value)
+            }
+            LinearModelError::InvalidJson(reason) => write!(f, "Invalid model JSON: {}", reason),
+            LinearModelError::WrongCoefficientCount {
+                constraint,
+                expected,
+                got,
+            } => {
+                let position = match constraint {
+                    Some(i) => format!("constraint {}", i),
+                    None => "objective".to_string(),
+                };
+                write!(
+                    f,
+                    "The {} has {} coefficients, expected {}",
+                    position, got, expected
+                )
+            }
+            LinearModelError::InvalidIndicator(name) => {
+                write!(f, "The indicator \"{}\" is not a boolean variable", name)
+            }
+            LinearModelError::NotInteger(name) => {
+                write!(f, "Variable \"{}\" is not an integer or boolean", name)
+            }
+            LinearModelError::SearchSpaceTooLarge { size, limit } => write!(
+                f,
+                "There are {} points to enumerate, more than the limit of {}",
+                size, limit
+            ),
+            LinearModelError::NotReal(name) => write!(f, "Variable \"{}\" is not real", name),
+            LinearModelError::StrictComparison(i) => {
+                write!(f, "Constraint {} uses a strict comparison", i)
+            }
+            LinearModelError::ContradictoryEquality(name) => write!(
+                f,
+                "The equality constraints give variable \"{}\" a value outside of its domain, or conflicting values",
+                name
+            ),
+        }
+    }
+}
+
+/// A problem found by [`LinearModel::validate`].
+#[derive(Debug, Clone)]
+enum ModelProblem {
+    NotFinite {
+        position: String,
+        value: f64,
+    },
+    WrongCoefficientCount {
+        constraint: Option<usize>,
+        expected: usize,
+        got: usize,
+    },
+    NoVariables(usize),
+    MissingDomain(String),
+    DuplicateConstraint(String),
+}
+
+impl Display for ModelProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelProblem::NotFinite { position, value } => {
+                write!(f, "The {} is {}", position, value)
+            }
+            ModelProblem::WrongCoefficientCount {
+                constraint: None,
+                expected,
+                got,
+            } => write!(
+                f,
+                "The objective has {} coefficients but there are {} variables",
+                got, expected
+            ),
+            ModelProblem::WrongCoefficientCount {
+                constraint: Some(i),
+                expected,
+                got,
+            } => write!(
+                f,
+                "Constraint {} has {} coefficients but there are {} variables",
+                i, got, expected
+            ),
+            ModelProblem::NoVariables(constraints) => write!(
+                f,
+                "The model has {} constraints but no variables",
+                constraints
+            ),
+            ModelProblem::MissingDomain(name) => write!(f, "Variable \"{}\" has no domain", name),
+            ModelProblem::DuplicateConstraint(name) => {
+                write!(f, "Constraint \"{}\" is declared more than once", name)
+            }
+        }
+    }
+}
+
+impl From<ModelProblem> for LinearModelError {
+    fn from(problem: ModelProblem) -> Self {
+        match problem {
+            ModelProblem::NotFinite { position, value } => {
+                LinearModelError::NotFinite { position, value }
+            }
+            ModelProblem::WrongCoefficientCount {
+                constraint,
+                expected,
+                got,
+            } => LinearModelError::WrongCoefficientCount {
+                constraint,
+                expected,
+                got,
+            },
+            ModelProblem::MissingDomain(name) => LinearModelError::UnknownVariable(name),
+            ModelProblem::DuplicateConstraint(name) => LinearModelError::DuplicateConstraint(name),
+            ModelProblem::NoVariables(_) => LinearModelError::InvalidJson(problem.to_string()),
+        }
+    }
 }
 
 impl LinearModel {
@@ -178,6 +450,76 @@ impl LinearModel {
             variables,
             objective_offset,
             domain,
+            variable_indexes: IndexMap::new(),
+            fixed: IndexMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Creates a new LinearModel from its constituent parts like [`LinearModel::new_from_parts`],
+    /// checking them with [`LinearModel::validate`] and returning the first problem found.
+    ///
+    /// # Arguments
+    /// * `objective` - Vector of objective function coefficients
+    /// * `optimization_type` - Whether to minimize or maximize
+    /// * `objective_offset` - Constant term in objective function
+    /// * `constraints` - Vector of linear constraints
+    /// * `variables` - Vector of variable names
+    /// * `domain` - Map of variable domains
+    /// * `pad` - Whether the objective and constraints with fewer coefficients than variables
+    ///   are padded with zeros instead of rejected
+    ///
+    /// # Returns
+    /// * `Ok(LinearModel)` if the parts are consistent
+    /// * `Err(LinearModelError::NotFinite)` if a number of the model is NaN or infinite
+    /// * `Err(LinearModelError::WrongCoefficientCount)` if the objective or a constraint has more
+    ///   coefficients than variables, or fewer and `pad` is false
+    /// * `Err(LinearModelError::UnknownVariable)` if a variable has no domain
+    /// * `Err(LinearModelError::DuplicateConstraint)` if two constraints have the same name
+    ///
+    /// # Example
+    /// ```rust
+    /// use indexmap::IndexMap;
+    /// use rooc::{Comparison, LinearConstraint, LinearModel, OptimizationType};
+    /// let constraints = vec![LinearConstraint::new(vec![1.0, 1.0, 1.0], Comparison::LessOrEqual, 1.0)];
+    /// let model = LinearModel::try_new(
+    ///     vec![1.0],
+    ///     OptimizationType::Max,
+    ///     0.0,
+    ///     constraints,
+    ///     vec!["x".to_string()],
+    ///     IndexMap::new(),
+    ///     true,
+    /// );
+    /// assert!(model.is_err());
+    /// ```
+    pub fn try_new(
+        mut objective: Vec<f64>,
+        optimization_type: OptimizationType,
+        objective_offset: f64,
+        mut constraints: Vec<LinearConstraint>,
+        variables: Vec<String>,
+        domain: IndexMap<String, DomainVariable>,
+        pad: bool,
+    ) -> Result<LinearModel, LinearModelError> {
+        if pad {
+            let expected = variables.len();
+            std::iter::once(&mut objective)
+                .chain(constraints.iter_mut().map(|c| &mut c.coefficients))
+                .filter(|coefficients| coefficients.len() < expected)
+                .for_each(|coefficients| coefficients.resize(expected, 0.0));
+        }
+        let model = LinearModel::new_from_parts(
+            objective,
+            optimization_type,
+            objective_offset,
+            constraints,
+            variables,
+            domain,
+        );
+        match model.problems().into_iter().next() {
+            Some(problem) => Err(problem.into()),
+            None => Ok(model),
         }
     }
 
@@ -212,6 +554,28 @@ impl LinearModel {
         )
     }
 
+    /// Gets the base name and indexes of each flattened compound variable of the model,
+    /// useful to rebuild the structure of a solution.
+    pub fn variable_indexes(&self) -> &VariableIndexes {
+        &self.variable_indexes
+    }
+
+    /// Sets the base name and indexes of each flattened compound variable.
+    pub fn set_variable_indexes(&mut self, variable_indexes: VariableIndexes) {
+        self.variable_indexes = variable_indexes;
+    }
+
+    /// Gets the warnings about parts of the source model that were changed or kept as they are
+    /// even though some solvers can't handle them, carried over from [`Model::warnings`].
+    pub fn warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
+    /// Sets the warnings about parts of the source model.
+    pub fn set_warnings(&mut self, warnings: Vec<String>) {
+        self.warnings = warnings;
+    }
+
     /// Ensures all vectors in the model have consistent sizes.
     fn ensure_sizes(&mut self) {
         self.constraints
@@ -264,6 +628,129 @@ impl LinearModel {
             .push(LinearConstraint::new(coefficients, constraint_type, rhs));
     }
 
+    /// Adds a constraint with a name, so that it can be told apart from the other constraints
+    /// when the model is edited, like in [`LinearModel::diff`].
+    ///
+    /// # Arguments
+    /// * `name` - Name of the constraint, unique in the model
+    /// * `coefficients` - Vector of coefficients for each variable, missing ones are zero
+    /// * `constraint_type` - Type of comparison operator (e.g., <=, =, >=)
+    /// * `rhs` - Right-hand side constant value
+    ///
+    /// # Returns
+    /// * `Ok(())` if the constraint was added
+    /// * `Err(LinearModelError::DuplicateConstraint)` if another constraint has the same name
+    /// * `Err(LinearModelError::TooManyCoefficients)` if there are more coefficients than variables
+    pub fn add_named_constraint(
+        &mut self,
+        name: &str,
+        mut coefficients: Vec<f64>,
+        constraint_type: Comparison,
+        rhs: f64,
+    ) -> Result<(), LinearModelError> {
+        if (0..self.constraints.len()).any(|i| self.constraint_name(i) == name) {
+            return Err(LinearModelError::DuplicateConstraint(name.to_string()));
+        }
+        if coefficients.len() > self.variables.len() {
+            return Err(LinearModelError::TooManyCoefficients);
+        }
+        coefficients.resize(self.variables.len(), 0.0);
+        let mut constraint = LinearConstraint::new(coefficients, constraint_type, rhs);
+        constraint.name = Some(name.to_string());
+        self.constraints.push(constraint);
+        Ok(())
+    }
+
+    /// Returns the name of the constraint at the given index, the constraints added without
+    /// a name are named `c` followed by their index.
+    ///
+    /// # Arguments
+    /// * `index` - Index of the constraint
+    pub fn constraint_name(&self, index: usize) -> String {
+        match self.constraints.get(index).and_then(|c| c.name()) {
+            Some(name) => name.to_string(),
+            None => format!("c{}", index),
+        }
+    }
+
+    /// Fixes a variable to a value by adding the constraint `name = value`, useful to
+    /// re-solve the model with a what-if scenario without editing the other constraints.
+    ///
+    /// Fixing a variable that is already fixed replaces its value.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the variable to fix
+    /// * `value` - The value the variable must take
+    ///
+    /// # Returns
+    /// * `Ok(())` if the variable was fixed
+    /// * `Err(LinearModelError::UnknownVariable)` if the variable is not part of the model
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_constraint(vec![1.0], Comparison::LessOrEqual, 10.0);
+    /// model.set_objective(vec![1.0], OptimizationType::Max);
+    ///
+    /// model.fix_variable("x", 4.0).unwrap();
+    /// assert_eq!(model.constraints().len(), 2);
+    /// model.unfix_variable("x").unwrap();
+    /// assert_eq!(model.constraints().len(), 1);
+    /// ```
+    pub fn fix_variable(&mut self, name: &str, value: f64) -> Result<(), LinearModelError> {
+        let index = self
+            .variables
+            .iter()
+            .position(|v| v == name)
+            .ok_or_else(|| LinearModelError::UnknownVariable(name.to_string()))?;
+        if let Some(constraint) = self.fixing_constraint(name) {
+            self.constraints[constraint].rhs = value;
+        } else {
+            let mut coefficients = vec![0.0; self.variables.len()];
+            coefficients[index] = 1.0;
+            let mut constraint = LinearConstraint::new(coefficients, Comparison::Equal, value);
+            constraint.fixes = Some(name.to_string());
+            self.constraints.push(constraint);
+        }
+        self.fixed.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    /// Removes the constraint added by [`LinearModel::fix_variable`], does nothing if the
+    /// variable is not fixed.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the variable to unfix
+    ///
+    /// # Returns
+    /// * `Ok(())` if the variable is not fixed anymore
+    /// * `Err(LinearModelError::UnknownVariable)` if the variable is not part of the model
+    pub fn unfix_variable(&mut self, name: &str) -> Result<(), LinearModelError> {
+        if !self.variables.iter().any(|v| v == name) {
+            return Err(LinearModelError::UnknownVariable(name.to_string()));
+        }
+        if let Some(constraint) = self.fixing_constraint(name) {
+            self.constraints.remove(constraint);
+        }
+        self.fixed.shift_remove(name);
+        Ok(())
+    }
+
+    /// Returns the variables fixed with [`LinearModel::fix_variable`] and their value.
+    pub fn fixed_variables(&self) -> &IndexMap<String, f64> {
+        &self.fixed
+    }
+
+    /// Finds the constraint added by [`LinearModel::fix_variable`] to fix a variable,
+    /// constraints written like it by the user are never matched.
+    fn fixing_constraint(&self, name: &str) -> Option<usize> {
+        self.constraints
+            .iter()
+            .position(|c| c.fixes.as_deref() == Some(name))
+    }
+
     /// Sets the objective function of the model.
     ///
     /// # Arguments
@@ -288,6 +775,81 @@ impl LinearModel {
         self.optimization_type = optimization_type;
     }
 
+    /// Sets the objective function of the model from named terms, terms that refer
+    /// to the same variable are summed together and missing variables get a zero coefficient.
+    ///
+    /// # Arguments
+    /// * `terms` - Pairs of variable name and coefficient, in any order
+    /// * `optimization_type` - Whether to minimize or maximize
+    ///
+    /// # Returns
+    /// * `Ok(())` if successful
+    /// * `Err(LinearModelError::UnknownVariable)` if a term refers to a variable not in the model,
+    ///   the objective is left unchanged
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_variable("y", VariableType::non_negative_real());
+    /// let terms = [("x".to_string(), 2.0), ("y".to_string(), 1.0), ("x".to_string(), 3.0)];
+    /// model.set_objective_from_terms(&terms, OptimizationType::Max).unwrap();
+    /// assert_eq!(model.objective(), &vec![5.0, 1.0]);
+    /// ```
+    pub fn set_objective_from_terms(
+        &mut self,
+        terms: &[(String, f64)],
+        optimization_type: OptimizationType,
+    ) -> Result<(), LinearModelError> {
+        let mut objective = vec![0.0; self.variables.len()];
+        for (name, coefficient) in terms {
+            match self.variables.iter().position(|v| v == name) {
+                Some(index) => objective[index] += coefficient,
+                None => return Err(LinearModelError::UnknownVariable(name.clone())),
+            }
+        }
+        self.set_objective(objective, optimization_type);
+        Ok(())
+    }
+
+    /// Serializes the model to JSON, the variables and their domains keep their order.
+    ///
+    /// Infinite bounds of the variables are written as `null`.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize the model to JSON")
+    }
+
+    /// Reads a model serialized with [`LinearModel::to_json`].
+    ///
+    /// # Arguments
+    /// * `json` - The serialized model
+    ///
+    /// # Returns
+    /// * `Ok(LinearModel)` if the model is valid
+    /// * `Err(LinearModelError::InvalidJson)` if the JSON is not a model, or lists the problems
+    ///   found by [`LinearModel::validate`]
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_constraint(vec![1.0], Comparison::LessOrEqual, 10.0);
+    /// model.set_objective(vec![1.0], OptimizationType::Max);
+    ///
+    /// let read = LinearModel::from_json(&model.to_json()).unwrap();
+    /// assert_eq!(read.to_string(), model.to_string());
+    /// ```
+    pub fn from_json(json: &str) -> Result<LinearModel, LinearModelError> {
+        let model: LinearModel =
+            serde_json::from_str(json).map_err(|e| LinearModelError::InvalidJson(e.to_string()))?;
+        model
+            .validate()
+            .map_err(|problems| LinearModelError::InvalidJson(problems.join(", ")))?;
+        Ok(model)
+    }
+
     /// Returns the optimization type (minimize/maximize).
     pub fn optimization_type(&self) -> &OptimizationType {
         &self.optimization_type
@@ -295,6 +857,7 @@ impl LinearModel {
 
     /// Converts the model to standard form.
     pub fn into_standard_form(self) -> Result<StandardLinearModel, SolverError> {
+        self.check_solvable()?;
         to_standard_form(self)
     }
 
@@ -322,10 +885,1112 @@ impl LinearModel {
     pub fn domain(&self) -> &IndexMap<String, DomainVariable> {
         &self.domain
     }
+
+    /// Checks that the model is consistent, returning every problem found.
+    ///
+    /// The model is consistent if every number is finite, the objective and every constraint have
+    /// one coefficient per variable, there are variables if there are constraints, every variable
+    /// has a domain and no two constraints have the same name. NaN or infinite values usually come
+    /// from a division by zero in the source model and make the solvers return meaningless results.
+    ///
+    /// The solvers, [`LinearModel::try_new`] and [`LinearModel::from_json`] run this check.
+    ///
+    /// # Returns
+    /// * `Ok(())` if the model is consistent
+    /// * `Err(Vec<String>)` a description of each problem, in the order they were found
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_constraint(vec![f64::NAN], Comparison::LessOrEqual, f64::INFINITY);
+    /// model.set_objective(vec![1.0], OptimizationType::Max);
+    ///
+    /// let problems = model.validate().unwrap_err();
+    /// assert_eq!(problems, vec![
+    ///     "The coefficient of x in constraint 0 is NaN".to_string(),
+    ///     "The right hand side of constraint 0 is inf".to_string(),
+    /// ]);
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let problems = self.problems();
+        match problems.is_empty() {
+            true => Ok(()),
+            false => Err(problems.iter().map(|p| p.to_string()).collect()),
+        }
+    }
+
+    /// Runs [`LinearModel::validate`] before solving, returning the first problem found,
+    /// a number that is not finite is reported as [`SolverError::NumericalInstability`].
+    pub(crate) fn check_solvable(&self) -> Result<(), SolverError> {
+        match self.problems().into_iter().next() {
+            Some(ModelProblem::NotFinite { position, value }) => {
+                Err(SolverError::NumericalInstability { position, value })
+            }
+            Some(problem) => Err(SolverError::Other(problem.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn problems(&self) -> Vec<ModelProblem> {
+        let mut problems = Vec::new();
+        let len = self.variables.len();
+        let name = |i: usize| {
+            self.variables
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("#{}", i))
+        };
+        let mut check = |value: f64, position: &dyn Fn() -> String| {
+            if !value.is_finite() {
+                problems.push(ModelProblem::NotFinite {
+                    position: position(),
+                    value,
+                });
+            }
+        };
+        for (i, coefficient) in self.objective.iter().enumerate() {
+            check(*coefficient, &|| {
+                format!("objective coefficient of {}", name(i))
+            });
+        }
+        check(self.objective_offset, &|| "objective offset".to_string());
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            for (j, coefficient) in constraint.coefficients.iter().enumerate() {
+                check(*coefficient, &|| {
+                    format!("coefficient of {} in constraint {}", name(j), i)
+                });
+            }
+            check(constraint.rhs, &|| {
+                format!("right hand side of constraint {}", i)
+            });
+        }
+        let rows = std::iter::once((None, &self.objective)).chain(
+            self.constraints
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (Some(i), &c.coefficients)),
+        );
+        for (constraint, coefficients) in rows {
+            if coefficients.len() != len {
+                problems.push(ModelProblem::WrongCoefficientCount {
+                    constraint,
+                    expected: len,
+                    got: coefficients.len(),
+                });
+            }
+        }
+        if len == 0 && !self.constraints.is_empty() {
+            problems.push(ModelProblem::NoVariables(self.constraints.len()));
+        }
+        for name in &self.variables {
+            if !self.domain.contains_key(name) {
+                problems.push(ModelProblem::MissingDomain(name.clone()));
+            }
+        }
+        let mut names = Vec::new();
+        for constraint in &self.constraints {
+            if let Some(name) = constraint.name() {
+                if names.contains(&name) {
+                    problems.push(ModelProblem::DuplicateConstraint(name.to_string()));
+                }
+                names.push(name);
+            }
+        }
+        problems
+    }
+
+    /// Returns a copy of the model where the integrality of every variable is relaxed,
+    /// booleans become reals in `[0, 1]` and integers become reals with the same bounds.
+    pub fn relaxed(&self) -> LinearModel {
+        let mut relaxed = self.clone();
+        for var in relaxed.domain.values_mut() {
+            let as_type = match *var.get_type() {
+                VariableType::Boolean => VariableType::NonNegativeReal(0.0, 1.0),
+                VariableType::IntegerRange(min, max) if min >= 0 => {
+                    VariableType::NonNegativeReal(min as f64, max as f64)
+                }
+                VariableType::IntegerRange(min, max) => VariableType::Real(min as f64, max as f64),
+                as_type => as_type,
+            };
+            *var = DomainVariable::new(as_type, var.span().clone());
+        }
+        relaxed
+    }
+
+    /// Solves the continuous relaxation of the model and returns its objective value,
+    /// which bounds the optimal value of the model (from above when maximizing, from below when minimizing).
+    ///
+    /// The model itself is left untouched.
+    pub fn relaxation_bound(&self) -> Result<f64, SolverError> {
+        solve_real_lp_problem_clarabel(&self.relaxed()).map(|solution| solution.value())
+    }
+
+    /// Presolves equality constraints that only involve one variable, like `2x = 10`,
+    /// by fixing the bounds of the variable and removing the constraint.
+    ///
+    /// A constraint is only converted when the fixed value can be expressed by the domain
+    /// of the variable: it must be within its current bounds and, for integers, be an integer.
+    /// Boolean variables and constraints that make the model infeasible are left untouched,
+    /// so that the solver can report them. Variables fixed with [`LinearModel::fix_variable`]
+    /// keep their constraint, so that they can be unfixed.
+    ///
+    /// # Returns
+    /// The number of constraints that were converted into bounds
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_variable("y", VariableType::non_negative_real());
+    /// model.add_constraint(vec![2.0, 0.0], Comparison::Equal, 10.0);
+    /// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 8.0);
+    /// model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+    ///
+    /// assert_eq!(model.presolve_fixed_variables(), 1);
+    /// assert_eq!(model.constraints().len(), 1);
+    /// assert_eq!(
+    ///     *model.domain().get("x").unwrap().get_type(),
+    ///     VariableType::NonNegativeReal(5.0, 5.0)
+    /// );
+    /// ```
+    pub fn presolve_fixed_variables(&mut self) -> usize {
+        let mut converted = Vec::new();
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            if constraint.constraint_type != Comparison::Equal {
+                continue;
+            }
+            let mut non_zero = constraint
+                .coefficients
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| !c.is_zero());
+            let (index, coefficient) = match (non_zero.next(), non_zero.next()) {
+                (Some(term), None) => term,
+                _ => continue,
+            };
+            let value = constraint.rhs / coefficient;
+            let name = &self.variables[index];
+            //keep the constraint of fixed variables so that they can be unfixed
+            if self.fixed.contains_key(name) {
+                continue;
+            }
+            let Some(variable) = self.domain.get_mut(name) else {
+                continue;
+            };
+            let fixed = match *variable.get_type() {
+                VariableType::Real(min, max) if min <= value && value <= max => {
+                    VariableType::Real(value, value)
+                }
+                VariableType::NonNegativeReal(min, max) if min <= value && value <= max => {
+                    VariableType::NonNegativeReal(value, value)
+                }
+                VariableType::IntegerRange(min, max)
+                    if float_eq(value, value.round())
+                        && min as f64 <= value.round()
+                        && value.round() <= max as f64 =>
+                {
+                    let value = value.round() as i32;
+                    VariableType::IntegerRange(value, value)
+                }
+                _ => continue,
+            };
+            *variable = DomainVariable::new(fixed, variable.span().clone());
+            converted.push(i);
+        }
+        remove_many(&mut self.constraints, &converted);
+        converted.len()
+    }
+
+    /// Removes the variables that are fixed by an equality constraint, either to another variable
+    /// with `x = y` (or any `a·x - a·y = 0`) or to a constant with `x = c` (or any `a·x = b`).
+    ///
+    /// For `x = y` the variable that comes later in the model is replaced by the earlier one
+    /// everywhere, and the domain of the kept variable becomes the intersection of both domains.
+    /// Variables with domains of a different kind, like an integer and a real, are not merged.
+    /// For `x = c` the variable is replaced by the constant, moving its terms to the right-hand side
+    /// of the constraints and to the objective offset. Substitutions are repeated until no equality
+    /// is left to remove, so chains like `x = y, y = z` collapse into a single variable. Constraints
+    /// left without variables are removed. Variables fixed with [`LinearModel::fix_variable`] are kept.
+    ///
+    /// # Returns
+    /// * `Ok(EqualitySubstitutions)` the removed variables, used to reconstruct a solution of the original model
+    /// * `Err(LinearModelError::ContradictoryEquality)` if the equalities give a variable a value outside of its
+    ///   domain, or leave a constraint without variables that can't be satisfied, like `x = y, x = 1, y = 2`.
+    ///   The model is left untouched
+    ///
+    /// # Example
+    /// ```rust
+    /// use indexmap::IndexMap;
+    /// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_variable("y", VariableType::non_negative_real());
+    /// model.add_variable("z", VariableType::non_negative_real());
+    /// model.add_constraint(vec![1.0, -1.0, 0.0], Comparison::Equal, 0.0);
+    /// model.add_constraint(vec![0.0, 0.0, 1.0], Comparison::Equal, 2.0);
+    /// model.add_constraint(vec![1.0, 1.0, 1.0], Comparison::LessOrEqual, 10.0);
+    /// model.set_objective(vec![1.0, 1.0, 1.0], OptimizationType::Max);
+    ///
+    /// let substitutions = model.substitute_equalities().unwrap();
+    /// assert_eq!(model.variables(), &vec!["x".to_string()]);
+    /// assert_eq!(model.constraints()[0].coefficients(), &vec![2.0]);
+    /// assert_eq!(model.constraints()[0].rhs(), 8.0);
+    /// assert_eq!(model.objective_offset(), 2.0);
+    ///
+    /// let values = substitutions.reconstruct(&IndexMap::from([("x".to_string(), 4.0)]));
+    /// assert_eq!(values.values().copied().collect::<Vec<_>>(), vec![4.0, 4.0, 2.0]);
+    /// ```
+    pub fn substitute_equalities(&mut self) -> Result<EqualitySubstitutions, LinearModelError> {
+        let mut model = self.clone();
+        let mut substitutions = EqualitySubstitutions {
+            original_variables: self.variables().clone(),
+            ..EqualitySubstitutions::default()
+        };
+        while let Some((constraint, equality)) = model.find_substitutable_equality() {
+            model.constraints.remove(constraint);
+            let removed = match equality {
+                Equality::Variables(kept, removed) => {
+                    let kept_name = model.variables[kept].clone();
+                    let removed_name = model.variables[removed].clone();
+                    let merged = intersect_types(
+                        model.domain[&kept_name].get_type(),
+                        model.domain[&removed_name].get_type(),
+                    )
+                    .flatten()
+                    .ok_or_else(|| LinearModelError::ContradictoryEquality(removed_name.clone()))?;
+                    let span = model.domain[&kept_name].span().clone();
+                    model.domain[&kept_name] = DomainVariable::new(merged, span);
+                    for coefficients in model.coefficient_rows_mut() {
+                        coefficients[kept] += coefficients[removed];
+                    }
+                    substitutions.substituted.insert(
+                        removed_name.clone(),
+                        SubstitutedVariable::Variable(kept_name),
+                    );
+                    removed
+                }
+                Equality::Constant(removed, value) => {
+                    let removed_name = model.variables[removed].clone();
+                    let var_type = *model.domain[&removed_name].get_type();
+                    if !accepts_value(&var_type, value) {
+                        return Err(LinearModelError::ContradictoryEquality(removed_name));
+                    }
+                    for constraint in model.constraints.iter_mut() {
+                        constraint.rhs -= constraint.coefficients[removed] * value;
+                    }
+                    model.objective_offset += model.objective[removed] * value;
+                    substitutions
+                        .substituted
+                        .insert(removed_name.clone(), SubstitutedVariable::Constant(value));
+                    substitutions.types.insert(removed_name, var_type);
+                    removed
+                }
+            };
+            let name = model.variables.remove(removed);
+            model.domain.shift_remove(&name);
+            model.variable_indexes.shift_remove(&name);
+            for coefficients in model.coefficient_rows_mut() {
+                coefficients.remove(removed);
+            }
+            model.remove_empty_constraints(&name)?;
+        }
+        *self = model;
+        Ok(substitutions)
+    }
+
+    /// Iterates over the coefficients of the objective and of every constraint
+    fn coefficient_rows_mut(&mut self) -> impl Iterator<Item = &mut Vec<f64>> {
+        std::iter::once(&mut self.objective)
+            .chain(self.constraints.iter_mut().map(|c| &mut c.coefficients))
+    }
+
+    /// Finds the first equality constraint that can be removed by a substitution
+    fn find_substitutable_equality(&self) -> Option<(usize, Equality)> {
+        self.constraints
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.constraint_type == Comparison::Equal)
+            .find_map(|(i, constraint)| {
+                let mut non_zero = constraint
+                    .coefficients
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| !c.is_zero());
+                let is_fixed = |index: usize| self.fixed.contains_key(&self.variables[index]);
+                let equality = match (non_zero.next(), non_zero.next(), non_zero.next()) {
+                    (Some((index, coefficient)), None, None) if !is_fixed(index) => {
+                        Equality::Constant(index, constraint.rhs / coefficient)
+                    }
+                    (Some((kept, a)), Some((removed, b)), None)
+                        if constraint.rhs.is_zero()
+                            && float_eq(*a, -*b)
+                            && !is_fixed(kept)
+                            && !is_fixed(removed)
+                            && intersect_types(
+                                self.domain[&self.variables[kept]].get_type(),
+                                self.domain[&self.variables[removed]].get_type(),
+                            )
+                            .is_some() =>
+                    {
+                        Equality::Variables(kept, removed)
+                    }
+                    _ => return None,
+                };
+                Some((i, equality))
+            })
+    }
+
+    /// Removes the constraints that have no variables left, failing if one of them is not satisfied
+    fn remove_empty_constraints(&mut self, substituted: &str) -> Result<(), LinearModelError> {
+        let mut satisfied = true;
+        self.constraints.retain(|constraint| {
+            if constraint.coefficients.iter().any(|c| !c.is_zero()) {
+                return true;
+            }
+            let rhs = constraint.rhs;
+            satisfied &= match constraint.constraint_type {
+                Comparison::LessOrEqual => float_le(0.0, rhs),
+                Comparison::GreaterOrEqual => float_ge(0.0, rhs),
+                Comparison::Equal => float_eq(0.0, rhs),
+                Comparison::Less => 0.0 < rhs,
+                Comparison::Greater => 0.0 > rhs,
+            };
+            false
+        });
+        match satisfied {
+            true => Ok(()),
+            false => Err(LinearModelError::ContradictoryEquality(
+                substituted.to_string(),
+            )),
+        }
+    }
+
+    /// Checks which constraints are violated by an assignment of the variables,
+    /// variables missing from the assignment are considered to be zero.
+    ///
+    /// # Arguments
+    /// * `assignment` - The value of each variable, by name
+    ///
+    /// # Returns
+    /// The indexes of the violated constraints, empty if the assignment is feasible
+    pub fn check_feasibility(&self, assignment: &IndexMap<String, f64>) -> Vec<usize> {
+        let values = self
+            .variables
+            .iter()
+            .map(|v| assignment.get(v).copied().unwrap_or(0.0))
+            .collect::<Vec<_>>();
+        self.constraints
+            .iter()
+            .enumerate()
+            .filter(|(_, constraint)| {
+                let lhs = constraint
+                    .coefficients
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(c, v)| c * v)
+                    .sum::<f64>();
+                let rhs = constraint.rhs;
+                !match constraint.constraint_type {
+                    Comparison::LessOrEqual => float_le(lhs, rhs),
+                    Comparison::GreaterOrEqual => float_ge(lhs, rhs),
+                    Comparison::Equal => float_eq(lhs, rhs),
+                    Comparison::Less => float_lt(lhs, rhs),
+                    Comparison::Greater => float_gt(lhs, rhs),
+                }
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Computes how much room each constraint has left at an assignment of the variables,
+    /// variables missing from the assignment are considered to be zero.
+    ///
+    /// The slack of a `<=` or `<` constraint is `rhs - lhs` and the slack of a `>=` or `>` constraint
+    /// is `lhs - rhs`, so it is positive when the constraint is satisfied with room to spare, zero when
+    /// it is binding and negative when it is violated. The slack of an `=` constraint is `lhs - rhs`.
+    ///
+    /// # Arguments
+    /// * `assignment` - The value of each variable, by name
+    ///
+    /// # Returns
+    /// The slack of each constraint, in the order of [`LinearModel::constraints`]
+    ///
+    /// # Example
+    /// ```rust
+    /// use indexmap::IndexMap;
+    /// use rooc::{Comparison, LinearModel, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_constraint(vec![1.0], Comparison::LessOrEqual, 4.0);
+    /// model.add_constraint(vec![1.0], Comparison::GreaterOrEqual, 1.0);
+    ///
+    /// let assignment = IndexMap::from([("x".to_string(), 3.0)]);
+    /// assert_eq!(model.constraint_slacks(&assignment), vec![1.0, 2.0]);
+    /// ```
+    pub fn constraint_slacks(&self, assignment: &IndexMap<String, f64>) -> Vec<f64> {
+        let values = self
+            .variables
+            .iter()
+            .map(|v| assignment.get(v).copied().unwrap_or(0.0))
+            .collect::<Vec<_>>();
+        self.constraints
+            .iter()
+            .map(|constraint| {
+                let lhs = constraint
+                    .coefficients
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(c, v)| c * v)
+                    .sum::<f64>();
+                match constraint.constraint_type {
+                    Comparison::LessOrEqual | Comparison::Less => constraint.rhs - lhs,
+                    Comparison::GreaterOrEqual | Comparison::Greater | Comparison::Equal => {
+                        lhs - constraint.rhs
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Enumerates every feasible assignment of a model with only integer and boolean variables,
+    /// by trying every point within the bounds of the variables.
+    ///
+    /// This is only meant for tiny models, like the ones used for teaching or to verify other solvers.
+    ///
+    /// # Arguments
+    /// * `limit` - The maximum number of points to try
+    ///
+    /// # Returns
+    /// * `Ok(Vec<IndexMap<String, f64>>)` the feasible assignments, in lexicographic order of the variables
+    /// * `Err(LinearModelError)` if a variable is not an integer or boolean, or the bounds
+    ///   of the variables contain more than `limit` points
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::Boolean);
+    /// model.add_variable("y", VariableType::IntegerRange(0, 2));
+    /// model.add_constraint(vec![1.0, 1.0], Comparison::Equal, 2.0);
+    ///
+    /// let solutions = model.enumerate_integer_solutions(100).unwrap();
+    /// assert_eq!(solutions.len(), 2);
+    /// assert_eq!(solutions[0]["y"], 2.0);
+    /// ```
+    pub fn enumerate_integer_solutions(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<IndexMap<String, f64>>, LinearModelError> {
+        let mut ranges = Vec::with_capacity(self.variables.len());
+        for name in &self.variables {
+            let range = match self.domain.get(name).map(|d| *d.get_type()) {
+                Some(VariableType::Boolean) => (0, 1),
+                Some(VariableType::IntegerRange(min, max)) => {
+                    if min == i32::MIN || max == i32::MAX {
+                        return Err(LinearModelError::UnboundedVariable(name.clone()));
+                    }
+                    (min as i64, max as i64)
+                }
+                _ => return Err(LinearModelError::NotInteger(name.clone())),
+            };
+            ranges.push(range);
+        }
+        let size = ranges.iter().fold(1usize, |size, (min, max)| {
+            size.saturating_mul((max - min + 1).max(0) as usize)
+        });
+        if size > limit {
+            return Err(LinearModelError::SearchSpaceTooLarge { size, limit });
+        }
+        let mut solutions = Vec::new();
+        if size == 0 {
+            return Ok(solutions);
+        }
+        let mut point = ranges.iter().map(|(min, _)| *min).collect::<Vec<_>>();
+        loop {
+            let assignment = self
+                .variables
+                .iter()
+                .zip(point.iter())
+                .map(|(name, value)| (name.clone(), *value as f64))
+                .collect::<IndexMap<_, _>>();
+            if self.check_feasibility(&assignment).is_empty() {
+                solutions.push(assignment);
+            }
+            //advance to the next point, the last variable changes first
+            let next = (0..point.len()).rev().find(|i| point[*i] < ranges[*i].1);
+            match next {
+                Some(i) => {
+                    point[i] += 1;
+                    for (j, value) in point.iter_mut().enumerate().skip(i + 1) {
+                        *value = ranges[j].0;
+                    }
+                }
+                None => break,
+            }
+        }
+        Ok(solutions)
+    }
+
+    /// Computes basic statistics about the size and shape of the model.
+    pub fn stats(&self) -> ModelStats {
+        let count_type = |f: fn(&VariableType) -> bool| {
+            self.variables
+                .iter()
+                .filter(|v| {
+                    self.domain
+                        .get(*v)
+                        .map(|d| f(d.get_type()))
+                        .unwrap_or(false)
+                })
+                .count()
+        };
+        ModelStats {
+            variables: self.variables.len(),
+            constraints: self.constraints.len(),
+            boolean_variables: count_type(|t| matches!(t, VariableType::Boolean)),
+            integer_variables: count_type(|t| matches!(t, VariableType::IntegerRange(_, _))),
+            real_variables: count_type(|t| {
+                matches!(
+                    t,
+                    VariableType::Real(_, _) | VariableType::NonNegativeReal(_, _)
+                )
+            }),
+            free_variables: count_type(
+                |t| matches!(t, VariableType::Real(min, max) if min.is_infinite() && max.is_infinite()),
+            ),
+            non_zero_coefficients: self
+                .constraints
+                .iter()
+                .map(|c| c.coefficients.iter().filter(|c| !c.is_zero()).count())
+                .sum(),
+        }
+    }
+
+    /// Exports the model as the dense matrices used by most solvers, so that the model is
+    /// `optimize c·x + offset` subject to `A[i]·x senses[i] b[i]`.
+    ///
+    /// Rows of `A`, `b` and `senses` follow the order of [`LinearModel::constraints`], columns of `A`
+    /// and entries of `c` follow the order of [`LinearModel::variables`]. Rows shorter than the
+    /// number of variables are padded with zeros. The optimization type and the domain of the
+    /// variables are not included.
+    ///
+    /// # Returns
+    /// The tuple `(A, b, c, senses, offset)`
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_variable("y", VariableType::non_negative_real());
+    /// model.add_constraint(vec![1.0], Comparison::LessOrEqual, 4.0);
+    /// model.add_constraint(vec![1.0, -1.0], Comparison::GreaterOrEqual, 0.0);
+    /// model.set_objective(vec![2.0, 3.0], OptimizationType::Max);
+    ///
+    /// let (a, b, c, senses, offset) = model.to_matrices();
+    /// assert_eq!(a, vec![vec![1.0, 0.0], vec![1.0, -1.0]]);
+    /// assert_eq!(b, vec![4.0, 0.0]);
+    /// assert_eq!(c, vec![2.0, 3.0]);
+    /// assert_eq!(senses, vec![Comparison::LessOrEqual, Comparison::GreaterOrEqual]);
+    /// assert_eq!(offset, 0.0);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn to_matrices(&self) -> (Vec<Vec<f64>>, Vec<f64>, Vec<f64>, Vec<Comparison>, f64) {
+        let columns = self.variables.len();
+        let a = self
+            .constraints
+            .iter()
+            .map(|constraint| {
+                let mut row = constraint.coefficients.clone();
+                row.resize(columns, 0.0);
+                row
+            })
+            .collect();
+        let b = self.constraints.iter().map(|c| c.rhs).collect();
+        let mut c = self.objective.clone();
+        c.resize(columns, 0.0);
+        let senses = self.constraints.iter().map(|c| c.constraint_type).collect();
+        (a, b, c, senses, self.objective_offset)
+    }
+
+    /// Returns the constraints in which a variable appears, the column of the variable
+    /// in the matrix of the constraints.
+    ///
+    /// # Arguments
+    /// * `variable` - The name of the variable
+    ///
+    /// # Returns
+    /// * `Some(Vec<(usize, f64)>)` the index of each constraint where the variable has a
+    ///   coefficient different from zero, with the coefficient
+    /// * `None` if the variable is not in the model
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_variable("y", VariableType::non_negative_real());
+    /// model.add_constraint(vec![1.0, 2.0], Comparison::LessOrEqual, 4.0);
+    /// model.add_constraint(vec![0.0, 3.0], Comparison::GreaterOrEqual, 1.0);
+    ///
+    /// assert_eq!(model.column("x"), Some(vec![(0, 1.0)]));
+    /// assert_eq!(model.column("y"), Some(vec![(0, 2.0), (1, 3.0)]));
+    /// assert_eq!(model.column("z"), None);
+    /// ```
+    pub fn column(&self, variable: &str) -> Option<Vec<(usize, f64)>> {
+        let index = self.variables.iter().position(|v| v == variable)?;
+        Some(
+            self.constraints
+                .iter()
+                .enumerate()
+                .filter_map(|(i, constraint)| {
+                    constraint
+                        .coefficients
+                        .get(index)
+                        .filter(|c| !c.is_zero())
+                        .map(|c| (i, *c))
+                })
+                .collect(),
+        )
+    }
+
+    /// Iterates over the constraints of the model with their terms referenced by variable name,
+    /// instead of by the position of the coefficient.
+    ///
+    /// The constraints are named like in [`LinearModel::constraint_name`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_variable("y", VariableType::non_negative_real());
+    /// model.add_constraint(vec![0.0, 2.0], Comparison::LessOrEqual, 4.0);
+    ///
+    /// let view = model.iter_constraints_named().next().unwrap();
+    /// assert_eq!(view.name, "c0");
+    /// assert_eq!(view.terms.get("y"), Some(&2.0));
+    /// assert!(!view.terms.contains_key("x"));
+    /// ```
+    pub fn iter_constraints_named(&self) -> impl Iterator<Item = ConstraintView> + '_ {
+        self.constraints
+            .iter()
+            .enumerate()
+            .map(|(i, constraint)| ConstraintView {
+                name: self.constraint_name(i),
+                terms: constraint
+                    .coefficients
+                    .iter()
+                    .zip(self.variables.iter())
+                    .filter(|(c, _)| !c.is_zero())
+                    .map(|(c, name)| (name.clone(), *c))
+                    .collect(),
+                constraint_type: constraint.constraint_type,
+                rhs: constraint.rhs,
+            })
+    }
+
+    /// Returns the coefficient of a variable, or zero if the variable is not in the model.
+    fn coefficient_of(&self, coefficients: &[f64], name: &str) -> f64 {
+        self.variables
+            .iter()
+            .position(|v| v == name)
+            .and_then(|i| coefficients.get(i).copied())
+            .unwrap_or(0.0)
+    }
+
+    fn named_constraints(&self) -> IndexMap<String, &LinearConstraint> {
+        (0..self.constraints.len())
+            .map(|i| (self.constraint_name(i), &self.constraints[i]))
+            .collect()
+    }
+
+    /// Computes what changed from this model to another one.
+    ///
+    /// Coefficients are compared by variable name, so adding or removing a variable does not
+    /// mark every constraint as changed. Constraints are matched by their name, see
+    /// [`LinearModel::constraint_name`], the unnamed ones are then matched by their index.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{Comparison, LinearModel, OptimizationType, VariableType};
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::non_negative_real());
+    /// model.add_named_constraint("capacity", vec![1.0], Comparison::LessOrEqual, 10.0).unwrap();
+    /// model.set_objective(vec![1.0], OptimizationType::Max);
+    ///
+    /// let mut other = model.clone();
+    /// other.add_variable("y", VariableType::non_negative_real());
+    /// other.add_named_constraint("demand", vec![1.0, 1.0], Comparison::LessOrEqual, 5.0).unwrap();
+    ///
+    /// let diff = model.diff(&other);
+    /// assert_eq!(diff.added_variables, vec!["y".to_string()]);
+    /// assert_eq!(diff.added_constraints, vec!["demand".to_string()]);
+    /// assert!(diff.changed_constraints.is_empty());
+    /// ```
+    pub fn diff(&self, other: &LinearModel) -> ModelDiff {
+        let added_variables = other
+            .variables
+            .iter()
+            .filter(|v| !self.variables.contains(v))
+            .cloned()
+            .collect::<Vec<_>>();
+        let removed_variables = self
+            .variables
+            .iter()
+            .filter(|v| !other.variables.contains(v))
+            .cloned()
+            .collect::<Vec<_>>();
+        let all_variables = self
+            .variables
+            .iter()
+            .chain(added_variables.iter())
+            .collect::<Vec<_>>();
+        let changed_domains = self
+            .domain
+            .iter()
+            .filter_map(|(name, before)| {
+                let after = other.domain.get(name)?;
+                if before.get_type() == after.get_type() {
+                    None
+                } else {
+                    Some((name.clone(), *before.get_type(), *after.get_type()))
+                }
+            })
+            .collect();
+        let changed_objective = all_variables
+            .iter()
+            .filter_map(|name| {
+                let before = self.coefficient_of(&self.objective, name);
+                let after = other.coefficient_of(&other.objective, name);
+                if float_eq(before, after) {
+                    None
+                } else {
+                    Some((name.to_string(), before, after))
+                }
+            })
+            .collect();
+        let changed_optimization_type = if self.optimization_type == other.optimization_type {
+            None
+        } else {
+            Some((
+                self.optimization_type.clone(),
+                other.optimization_type.clone(),
+            ))
+        };
+        let changed_objective_offset = if float_eq(self.objective_offset, other.objective_offset) {
+            None
+        } else {
+            Some((self.objective_offset, other.objective_offset))
+        };
+        let before_constraints = self.named_constraints();
+        let after_constraints = other.named_constraints();
+        let added_constraints = after_constraints
+            .keys()
+            .filter(|name| !before_constraints.contains_key(*name))
+            .cloned()
+            .collect();
+        let removed_constraints = before_constraints
+            .keys()
+            .filter(|name| !after_constraints.contains_key(*name))
+            .cloned()
+            .collect();
+        let changed_constraints = before_constraints
+            .iter()
+            .filter(|(name, before)| match after_constraints.get(*name) {
+                Some(after) => {
+                    before.constraint_type != after.constraint_type
+                        || !float_eq(before.rhs, after.rhs)
+                        || all_variables.iter().any(|name| {
+                            !float_eq(
+                                self.coefficient_of(&before.coefficients, name),
+                                other.coefficient_of(&after.coefficients, name),
+                            )
+                        })
+                }
+                None => false,
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        ModelDiff {
+            added_variables,
+            removed_variables,
+            changed_domains,
+            changed_optimization_type,
+            changed_objective,
+            changed_objective_offset,
+            added_constraints,
+            removed_constraints,
+            changed_constraints,
+        }
+    }
+}
+
+/// Basic statistics about a linear model.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelStats {
+    /// Total number of variables
+    pub variables: usize,
+    /// Total number of constraints
+    pub constraints: usize,
+    /// Number of boolean variables
+    pub boolean_variables: usize,
+    /// Number of integer variables
+    pub integer_variables: usize,
+    /// Number of real and non negative real variables
+    pub real_variables: usize,
+    /// Number of real variables without bounds, like the undeclared variables
+    /// of a model transformed with [`crate::parser::model_transformer::DefaultDomain::Real`]
+    pub free_variables: usize,
+    /// Number of non zero coefficients in the constraints
+    pub non_zero_coefficients: usize,
+}
+
+impl Display for ModelStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Variables: {}", self.variables)?;
+        writeln!(f, "    Boolean: {}", self.boolean_variables)?;
+        writeln!(f, "    Integer: {}", self.integer_variables)?;
+        writeln!(f, "    Real: {}", self.real_variables)?;
+        writeln!(f, "        Free: {}", self.free_variables)?;
+        writeln!(f, "Constraints: {}", self.constraints)?;
+        write!(f, "Non zero coefficients: {}", self.non_zero_coefficients)
+    }
+}
+
+/// A constraint of a linear model with its terms referenced by variable name,
+/// see [`LinearModel::iter_constraints_named`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConstraintView {
+    /// Name of the constraint
+    pub name: String,
+    /// Non zero coefficients of the constraint, by variable name, in the order of the variables
+    pub terms: IndexMap<String, f64>,
+    /// Comparison between the left and right hand side
+    pub constraint_type: Comparison,
+    /// Right hand side of the constraint
+    pub rhs: f64,
+}
+
+/// The changes between two versions of a linear model.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelDiff {
+    /// Variables that are only in the new model
+    pub added_variables: Vec<String>,
+    /// Variables that are only in the old model
+    pub removed_variables: Vec<String>,
+    /// Variables in both models whose domain or bounds changed, as (variable, old type, new type)
+    pub changed_domains: Vec<(String, VariableType, VariableType)>,
+    /// The old and new optimization type, if it changed
+    pub changed_optimization_type: Option<(OptimizationType, OptimizationType)>,
+    /// Objective coefficients that changed, as (variable, old value, new value)
+    pub changed_objective: Vec<(String, f64, f64)>,
+    /// The old and new constant added to the objective, if it changed
+    pub changed_objective_offset: Option<(f64, f64)>,
+    /// Names of the constraints that are only in the new model
+    pub added_constraints: Vec<String>,
+    /// Names of the constraints that are only in the old model
+    pub removed_constraints: Vec<String>,
+    /// Names of the constraints that are in both models but differ
+    pub changed_constraints: Vec<String>,
+}
+
+impl ModelDiff {
+    /// Returns true if the two models are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added_variables.is_empty()
+            && self.removed_variables.is_empty()
+            && self.changed_domains.is_empty()
+            && self.changed_optimization_type.is_none()
+            && self.changed_objective.is_empty()
+            && self.changed_objective_offset.is_none()
+            && self.added_constraints.is_empty()
+            && self.removed_constraints.is_empty()
+            && self.changed_constraints.is_empty()
+    }
+}
+
+impl Display for ModelDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = |values: &Vec<String>| values.join(", ");
+        let domains = self
+            .changed_domains
+            .iter()
+            .map(|(name, before, after)| format!("{}: {} -> {}", name, before, after))
+            .collect::<Vec<_>>();
+        let objective = self
+            .changed_objective
+            .iter()
+            .map(|(name, before, after)| format!("{}: {} -> {}", name, before, after))
+            .collect::<Vec<_>>();
+        writeln!(f, "Added variables: {}", list(&self.added_variables))?;
+        writeln!(f, "Removed variables: {}", list(&self.removed_variables))?;
+        writeln!(f, "Changed domains: {}", list(&domains))?;
+        if let Some((before, after)) = &self.changed_optimization_type {
+            writeln!(f, "Changed optimization type: {} -> {}", before, after)?;
+        }
+        writeln!(f, "Changed objective: {}", list(&objective))?;
+        if let Some((before, after)) = &self.changed_objective_offset {
+            writeln!(f, "Changed objective offset: {} -> {}", before, after)?;
+        }
+        writeln!(f, "Added constraints: {}", list(&self.added_constraints))?;
+        writeln!(
+            f,
+            "Removed constraints: {}",
+            list(&self.removed_constraints)
+        )?;
+        write!(
+            f,
+            "Changed constraints: {}",
+            list(&self.changed_constraints)
+        )
+    }
+}
+
+/// How a variable removed by [`LinearModel::substitute_equalities`] is computed from the variables left in the model.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubstitutedVariable {
+    /// The variable is equal to another variable, which can itself have been substituted
+    Variable(String),
+    /// The variable is equal to a constant
+    Constant(f64),
+}
+
+/// The variables removed by [`LinearModel::substitute_equalities`], so that a solution of the
+/// reduced model can be read in terms of the variables of the original model.
+#[derive(Debug, Clone, Default)]
+pub struct EqualitySubstitutions {
+    original_variables: Vec<String>,
+    substituted: IndexMap<String, SubstitutedVariable>,
+    types: IndexMap<String, VariableType>,
+}
+
+impl EqualitySubstitutions {
+    /// Returns the removed variables with what they were replaced by, in the order they were removed.
+    pub fn substituted(&self) -> &IndexMap<String, SubstitutedVariable> {
+        &self.substituted
+    }
+
+    /// Computes the value of every variable of the original model from the values of the reduced model.
+    ///
+    /// # Arguments
+    /// * `values` - The value of each variable of the reduced model, missing values are zero
+    ///
+    /// # Returns
+    /// The value of each variable, in the order of the original model
+    pub fn reconstruct(&self, values: &IndexMap<String, f64>) -> IndexMap<String, f64> {
+        let mut values = values.clone();
+        //a variable can only be replaced by one that is removed later, so go backwards
+        for (name, substitution) in self.substituted.iter().rev() {
+            let value = match substitution {
+                SubstitutedVariable::Variable(other) => values.get(other).copied().unwrap_or(0.0),
+                SubstitutedVariable::Constant(value) => *value,
+            };
+            values.insert(name.clone(), value);
+        }
+        self.original_variables
+            .iter()
+            .map(|name| (name.clone(), values.get(name).copied().unwrap_or(0.0)))
+            .collect()
+    }
+
+    /// Adds the removed variables to a solution of the reduced model, the objective value is kept
+    /// as the constants were already moved to the objective offset.
+    ///
+    /// # Arguments
+    /// * `solution` - A solution of the reduced model
+    pub fn reconstruct_solution(&self, solution: &LpSolution<MILPValue>) -> LpSolution<MILPValue> {
+        let mut values = solution
+            .assignment()
+            .iter()
+            .map(|a| (a.name.clone(), a.value))
+            .collect::<IndexMap<_, _>>();
+        for (name, substitution) in self.substituted.iter().rev() {
+            let value = match substitution {
+                SubstitutedVariable::Variable(other) => {
+                    values.get(other).copied().unwrap_or(MILPValue::Real(0.0))
+                }
+                SubstitutedVariable::Constant(value) => match self.types.get(name) {
+                    Some(VariableType::Boolean) => MILPValue::Bool(*value != 0.0),
+                    Some(VariableType::IntegerRange(_, _)) => MILPValue::Int(value.round() as i32),
+                    _ => MILPValue::Real(*value),
+                },
+            };
+            values.insert(name.clone(), value);
+        }
+        let assignment = self
+            .original_variables
+            .iter()
+            .filter_map(|name| {
+                values.get(name).map(|value| Assignment {
+                    name: name.clone(),
+                    value: *value,
+                })
+            })
+            .collect();
+        LpSolution::new(assignment, solution.value()).with_gap(solution.gap())
+    }
+}
+
+/// Returns the domain allowed by both variable types, `None` if the types are of a different kind,
+/// so the variables can't be merged, and `Some(None)` if the domains don't overlap.
+fn intersect_types(a: &VariableType, b: &VariableType) -> Option<Option<VariableType>> {
+    let merged = match (a, b) {
+        (VariableType::Boolean, VariableType::Boolean) => VariableType::Boolean,
+        (VariableType::IntegerRange(a_min, a_max), VariableType::IntegerRange(b_min, b_max)) => {
+            VariableType::IntegerRange(*a_min.max(b_min), *a_max.min(b_max))
+        }
+        (VariableType::Real(a_min, a_max), VariableType::Real(b_min, b_max)) => {
+            VariableType::Real(a_min.max(*b_min), a_max.min(*b_max))
+        }
+        (
+            VariableType::Real(a_min, a_max) | VariableType::NonNegativeReal(a_min, a_max),
+            VariableType::Real(b_min, b_max) | VariableType::NonNegativeReal(b_min, b_max),
+        ) => VariableType::NonNegativeReal(a_min.max(*b_min), a_max.min(*b_max)),
+        _ => return None,
+    };
+    let is_empty = match merged {
+        VariableType::IntegerRange(min, max) => min > max,
+        VariableType::Real(min, max) | VariableType::NonNegativeReal(min, max) => min > max,
+        VariableType::Boolean => false,
+    };
+    Some((!is_empty).then_some(merged))
+}
+
+/// Returns true if a variable of the given type can take the value
+fn accepts_value(var_type: &VariableType, value: f64) -> bool {
+    match var_type {
+        VariableType::Boolean => float_eq(value, 0.0) || float_eq(value, 1.0),
+        VariableType::IntegerRange(min, max) => {
+            float_eq(value, value.round())
+                && float_ge(value, *min as f64)
+                && float_le(value, *max as f64)
+        }
+        VariableType::Real(min, max) | VariableType::NonNegativeReal(min, max) => {
+            float_ge(value, *min) && float_le(value, *max)
+        }
+    }
+}
+
+/// An equality that can be removed by substituting one of its variables
+enum Equality {
+    /// The variable at the first index is equal to the one at the second index
+    Variables(usize, usize),
+    /// The variable at the index is equal to the constant
+    Constant(usize, f64),
 }
 
 impl Display for LinearModel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_string_with_options(&FormatOptions::default()))
+    }
+}
+
+impl DisplayWithOptions for LinearModel {
+    fn to_string_with_options(&self, options: &FormatOptions) -> String {
         let constraints = self.constraints.iter().map(|c| {
             let mut is_first = true;
             let coefficients = c
@@ -336,7 +2001,8 @@ impl Display for LinearModel {
                     if c.is_zero() {
                         None
                     } else {
-                        let var = format_var(&self.variables[i], *c, is_first);
+                        let var =
+                            format_var_with_options(&self.variables[i], *c, is_first, options);
                         is_first = false;
                         Some(var)
                     }
@@ -351,7 +2017,7 @@ impl Display for LinearModel {
             let rhs = if c.rhs.is_zero() {
                 "0".to_string()
             } else {
-                c.rhs.to_string()
+                options.format_number(c.rhs)
             };
             format!("    {} {} {}", lhs, c.constraint_type, rhs)
         });
@@ -366,7 +2032,7 @@ impl Display for LinearModel {
                 if c.is_zero() {
                     None
                 } else {
-                    let var = format_var(&self.variables[i], *c, is_first);
+                    let var = format_var_with_options(&self.variables[i], *c, is_first, options);
                     is_first = false;
                     Some(var)
                 }
@@ -381,9 +2047,9 @@ impl Display for LinearModel {
         let offset = if self.objective_offset.is_zero() {
             "".to_string()
         } else if float_lt(self.objective_offset, 0.0) {
-            format!(" - {}", self.objective_offset.abs())
+            format!(" - {}", options.format_number(self.objective_offset.abs()))
         } else {
-            format!(" + {}", self.objective_offset)
+            format!(" + {}", options.format_number(self.objective_offset))
         };
         let objective = format!("{}{}", objective, offset);
         let domain: String = if !self.domain.is_empty() {
@@ -397,8 +2063,7 @@ impl Display for LinearModel {
         } else {
             "".to_string()
         };
-        write!(
-            f,
+        format!(
             "{} {}\ns.t.\n{}{}",
             self.optimization_type, objective, constraints, domain
         )
@@ -423,8 +2088,28 @@ impl LinearModel {
     pub fn wasm_get_optimization_type(&self) -> OptimizationType {
         self.optimization_type.clone()
     }
+    pub fn wasm_get_variable_indexes(&self) -> Result<JsValue, JsValue> {
+        serialize_json_compatible(&self.variable_indexes).map_err(JsValue::from)
+    }
+    pub fn wasm_get_warnings(&self) -> Vec<String> {
+        self.warnings.clone()
+    }
 
     pub fn wasm_to_string(&self) -> String {
         format!("{}", self)
     }
+    pub fn wasm_to_json(&self) -> String {
+        self.to_json()
+    }
+    pub fn wasm_from_json(json: &str) -> Result<LinearModel, String> {
+        LinearModel::from_json(json).map_err(|e| e.to_string())
+    }
+    pub fn wasm_to_compiled_json(&self, solution: JsValue) -> Result<String, JsValue> {
+        let solution: Option<LpSolution<MILPValue>> = serde_wasm_bindgen::from_value(solution)?;
+        Ok(CompiledProblem::new(self.clone(), solution).to_json())
+    }
+    pub fn wasm_to_string_with_options(&self, options: JsValue) -> Result<String, JsValue> {
+        let options: FormatOptions = serde_wasm_bindgen::from_value(options)?;
+        Ok(self.to_string_with_options(&options))
+    }
 }
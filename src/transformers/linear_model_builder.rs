@@ -0,0 +1,146 @@
+use crate::math::{Comparison, OptimizationType, VariableType};
+use crate::transformers::{LinearConstraint, LinearModel, LinearModelError};
+
+/// A constraint added to the builder, kept by variable name until the model is built
+struct PendingConstraint {
+    terms: Vec<(String, f64)>,
+    constraint_type: Comparison,
+    rhs: f64,
+}
+
+/// Builds a [`LinearModel`] by variable name instead of by position, checking that
+/// the model is consistent only when it is built.
+///
+/// Variables are added to the model in the order they are declared, terms of the objective
+/// and of the constraints can be written in any order and terms that refer to the same
+/// variable are summed together.
+///
+/// # Example
+/// ```rust
+/// use rooc::{Comparison, LinearModelBuilder, OptimizationType, VariableType};
+/// let model = LinearModelBuilder::new()
+///     .maximize()
+///     .variable("x", VariableType::non_negative_real())
+///     .variable("y", VariableType::non_negative_real())
+///     .objective_term("x", 2.0)
+///     .objective_term("y", 3.0)
+///     .constraint(&[("x", 1.0), ("y", 1.0)], Comparison::LessOrEqual, 4.0)
+///     .build()
+///     .unwrap();
+/// assert_eq!(model.objective(), &vec![2.0, 3.0]);
+/// assert_eq!(model.optimization_type(), &OptimizationType::Max);
+/// assert_eq!(model.constraints()[0].coefficients(), &vec![1.0, 1.0]);
+/// ```
+pub struct LinearModelBuilder {
+    optimization_type: OptimizationType,
+    variables: Vec<(String, VariableType)>,
+    objective: Vec<(String, f64)>,
+    constraints: Vec<PendingConstraint>,
+}
+
+impl Default for LinearModelBuilder {
+    fn default() -> Self {
+        LinearModelBuilder {
+            optimization_type: OptimizationType::Min,
+            variables: vec![],
+            objective: vec![],
+            constraints: vec![],
+        }
+    }
+}
+
+impl LinearModelBuilder {
+    /// Creates an empty builder for a minimization.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Minimizes the objective, this is the default.
+    pub fn minimize(mut self) -> Self {
+        self.optimization_type = OptimizationType::Min;
+        self
+    }
+
+    /// Maximizes the objective.
+    pub fn maximize(mut self) -> Self {
+        self.optimization_type = OptimizationType::Max;
+        self
+    }
+
+    /// Only looks for a solution that satisfies the constraints, the objective is ignored.
+    pub fn satisfy(mut self) -> Self {
+        self.optimization_type = OptimizationType::Satisfy;
+        self
+    }
+
+    /// Declares a variable of the model.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the variable, must be unique
+    /// * `domain` - Type/domain of the variable (e.g., Boolean, Integer, etc.)
+    pub fn variable(mut self, name: &str, domain: VariableType) -> Self {
+        self.variables.push((name.to_string(), domain));
+        self
+    }
+
+    /// Adds a term to the objective.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the variable, it can be declared after the term is added
+    /// * `coefficient` - The coefficient of the variable in the objective
+    pub fn objective_term(mut self, name: &str, coefficient: f64) -> Self {
+        self.objective.push((name.to_string(), coefficient));
+        self
+    }
+
+    /// Adds a constraint to the model.
+    ///
+    /// # Arguments
+    /// * `terms` - Pairs of variable name and coefficient, in any order
+    /// * `constraint_type` - Type of comparison operator
+    /// * `rhs` - Right-hand side value
+    pub fn constraint(
+        mut self,
+        terms: &[(&str, f64)],
+        constraint_type: Comparison,
+        rhs: f64,
+    ) -> Self {
+        self.constraints.push(PendingConstraint {
+            terms: terms
+                .iter()
+                .map(|(name, coefficient)| (name.to_string(), *coefficient))
+                .collect(),
+            constraint_type,
+            rhs,
+        });
+        self
+    }
+
+    /// Builds the model.
+    ///
+    /// # Returns
+    /// * `Ok(LinearModel)` if every variable used in the objective and constraints is declared
+    /// * `Err(LinearModelError::DuplicateVariable)` if a variable is declared more than once
+    /// * `Err(LinearModelError::UnknownVariable)` if a term refers to a variable that is not declared
+    pub fn build(self) -> Result<LinearModel, LinearModelError> {
+        let mut model = LinearModel::new();
+        for (name, domain) in self.variables {
+            if model.variables().contains(&name) {
+                return Err(LinearModelError::DuplicateVariable(name));
+            }
+            model.add_variable(&name, domain);
+        }
+        for constraint in self.constraints {
+            let (coefficients, constraint_type, rhs) = LinearConstraint::from_terms(
+                &constraint.terms,
+                constraint.constraint_type,
+                constraint.rhs,
+                model.variables(),
+            )?
+            .into_parts();
+            model.add_constraint(coefficients, constraint_type, rhs);
+        }
+        model.set_objective_from_terms(&self.objective, self.optimization_type)?;
+        Ok(model)
+    }
+}
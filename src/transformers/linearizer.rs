@@ -61,6 +61,21 @@ impl Exp {
                             )));
                         }
                     }
+                    //logical and comparison operators are only allowed on constants, they should never reach the model
+                    BinOp::And
+                    | BinOp::Or
+                    | BinOp::Equal
+                    | BinOp::NotEqual
+                    | BinOp::Less
+                    | BinOp::LessOrEqual
+                    | BinOp::Greater
+                    | BinOp::GreaterOrEqual
+                    | BinOp::In
+                    | BinOp::NotIn => {
+                        return Err(LinearizationError::UnimplementedExpression(Box::new(
+                            self.clone(),
+                        )))
+                    }
                 };
                 Ok(context)
             }
@@ -70,6 +85,9 @@ impl Exp {
                     context.mul_by(-1.0);
                     Ok(context)
                 }
+                UnOp::Not => Err(LinearizationError::UnimplementedExpression(Box::new(
+                    self.clone(),
+                ))),
             },
             Exp::Number(num) => Ok(LinearizationContext::from_rhs(*num)),
             Exp::Variable(name) => Ok(LinearizationContext::from_var(name.clone(), 1.0)),
@@ -107,9 +125,21 @@ impl Exp {
                 )?;
                 Ok(LinearizationContext::from_var(var_name, 1.0))
             }
-            Exp::Abs(_) => Err(LinearizationError::UnimplementedExpression(Box::new(
-                self.clone(),
-            ))),
+            Exp::Abs(exp) => {
+                //like max, the auxiliary variable is bounded from below by both exp and -exp
+                let var_name = format!("$abs_{}", linearizer_context.abs_count);
+                linearizer_context.abs_count += 1;
+                for bound in [*exp.clone(), Exp::UnOp(UnOp::Neg, exp.clone())] {
+                    linearizer_context.add_constraint(Constraint::new(
+                        Exp::Variable(var_name.clone()),
+                        Comparison::GreaterOrEqual,
+                        bound,
+                    ));
+                }
+                linearizer_context
+                    .declare_variable(var_name.clone(), VariableType::non_negative_real())?;
+                Ok(LinearizationContext::from_var(var_name, 1.0))
+            }
         }
     }
 }
@@ -197,6 +227,7 @@ pub struct Linearizer {
     slack_count: u32,
     min_count: u32,
     max_count: u32,
+    abs_count: u32,
     domain: IndexMap<String, DomainVariable>,
 }
 
@@ -277,6 +308,8 @@ impl Linearizer {
     /// * `Ok(LinearModel)` - The linearized model
     /// * `Err(LinearizationError)` - If linearization fails
     pub fn linearize(model: Model) -> Result<LinearModel, LinearizationError> {
+        let variable_indexes = model.variable_indexes().clone();
+        let warnings = model.warnings().clone();
         let (objective, constraints, domain) = model.into_components();
         let mut context = Linearizer::new_from(constraints, domain);
         let mut linear_constraints: Vec<MidLinearConstraint> = Vec::new();
@@ -310,14 +343,21 @@ impl Linearizer {
             .collect();
         let objective_coeffs = extract_coeffs(&linearized_objective.current_vars, &vars_indexes);
         let objective_offset = linearized_objective.current_rhs;
-        Ok(LinearModel::new_from_parts(
+        let variable_indexes = variable_indexes
+            .into_iter()
+            .filter(|(name, _)| vars.contains(name))
+            .collect();
+        let mut linear_model = LinearModel::new_from_parts(
             objective_coeffs,
             objective_type,
             objective_offset,
             linear_constraints,
             vars,
             domain,
-        ))
+        );
+        linear_model.set_variable_indexes(variable_indexes);
+        linear_model.set_warnings(warnings);
+        Ok(linear_model)
     }
 }
 
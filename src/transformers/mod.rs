@@ -1,9 +1,20 @@
+pub mod compiled_problem;
+pub mod disjunctive_constraints;
+pub mod dual;
+pub mod indicator_constraints;
 pub mod linear_model;
+pub mod linear_model_builder;
 pub mod linearizer;
+pub mod soft_constraints;
 pub mod standard_linear_model;
 pub mod standardizer;
 
+pub use compiled_problem::*;
+pub use disjunctive_constraints::*;
+pub use indicator_constraints::*;
 pub use linear_model::*;
+pub use linear_model_builder::*;
 pub use linearizer::*;
+pub use soft_constraints::*;
 pub use standard_linear_model::*;
 pub use standardizer::*;
@@ -0,0 +1,128 @@
+use crate::math::{Comparison, VariableType};
+use crate::transformers::{LinearConstraint, LinearModel, LinearModelError};
+
+/// A linear constraint that is allowed to be violated, at the cost of a penalty.
+///
+/// When added to a [`LinearModel`], non negative deviation variables are added to the constraint:
+/// `lhs - over <= rhs` for `<=` constraints, `lhs + under >= rhs` for `>=` constraints and
+/// `lhs + under - over = rhs` for equality constraints. The penalty of the constraint is its
+/// weight times the sum of its deviations.
+///
+/// Soft constraints are grouped in tiers by their priority, constraints with a higher priority
+/// are satisfied as much as possible before the ones with a lower priority are considered,
+/// see [`crate::solve_preemptive_goals`].
+///
+/// # Example
+/// ```rust
+/// use rooc::{Comparison, LinearConstraint, LinearModel, SoftConstraint, VariableType};
+/// let mut model = LinearModel::new();
+/// model.add_variable("x", VariableType::non_negative_real());
+/// // try to keep x at least 5, with priority 1
+/// let soft = SoftConstraint::new(LinearConstraint::new(vec![1.0], Comparison::GreaterOrEqual, 5.0))
+///     .with_priority(1);
+/// let deviations = model.add_soft_constraint(soft).unwrap();
+/// assert_eq!(deviations, vec!["$under_0".to_string()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SoftConstraint {
+    constraint: LinearConstraint,
+    weight: f64,
+    priority: u32,
+}
+
+impl SoftConstraint {
+    /// Creates a new soft constraint with a weight of one and a priority of zero.
+    ///
+    /// # Arguments
+    /// * `constraint` - The constraint that should hold if possible
+    pub fn new(constraint: LinearConstraint) -> Self {
+        Self {
+            constraint,
+            weight: 1.0,
+            priority: 0,
+        }
+    }
+
+    /// Sets how much a unit of violation of the constraint is penalized within its priority tier.
+    ///
+    /// # Arguments
+    /// * `weight` - The penalty of a unit of violation
+    pub fn with_weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the priority tier of the constraint, higher priorities are satisfied first.
+    ///
+    /// # Arguments
+    /// * `priority` - The priority tier of the constraint
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the constraint that should hold if possible.
+    pub fn constraint(&self) -> &LinearConstraint {
+        &self.constraint
+    }
+
+    /// Returns the penalty of a unit of violation of the constraint.
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// Returns the priority tier of the constraint.
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+}
+
+impl LinearModel {
+    /// Adds a constraint that is allowed to be violated, together with the variables
+    /// measuring how much it is violated.
+    ///
+    /// The deviation variables are named `$over_i` and `$under_i`, where `i` is the index
+    /// of the constraint in the model.
+    ///
+    /// # Arguments
+    /// * `soft` - The soft constraint to add
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` the names of the deviation variables that measure the violation
+    /// * `Err(LinearModelError)` if the constraint has too many coefficients
+    pub fn add_soft_constraint(
+        &mut self,
+        soft: SoftConstraint,
+    ) -> Result<Vec<String>, LinearModelError> {
+        if soft.constraint.coefficients().len() > self.variables().len() {
+            return Err(LinearModelError::TooManyCoefficients);
+        }
+        let index = self.constraints().len();
+        let (mut coefficients, constraint_type, rhs) = soft.constraint.into_parts();
+        coefficients.resize(self.variables().len(), 0.0);
+        // lhs - over <= rhs
+        let mut deviations = Vec::new();
+        if matches!(
+            constraint_type,
+            Comparison::LessOrEqual | Comparison::Less | Comparison::Equal
+        ) {
+            let name = format!("$over_{}", index);
+            self.add_variable(&name, VariableType::non_negative_real());
+            coefficients.push(-1.0);
+            deviations.push(name);
+        }
+        // lhs + under >= rhs
+        if matches!(
+            constraint_type,
+            Comparison::GreaterOrEqual | Comparison::Greater | Comparison::Equal
+        ) {
+            let name = format!("$under_{}", index);
+            self.add_variable(&name, VariableType::non_negative_real());
+            coefficients.resize(self.variables().len() - 1, 0.0);
+            coefficients.push(1.0);
+            deviations.push(name);
+        }
+        self.add_constraint(coefficients, constraint_type, rhs);
+        Ok(deviations)
+    }
+}
@@ -5,7 +5,8 @@ use std::fmt::Display;
 
 use crate::math::{float_gt, float_lt, float_ne};
 use crate::solvers::SolverError;
-use crate::solvers::{divide_matrix_row_by, CanonicalTransformError, Tableau};
+use crate::solvers::{divide_matrix_row_by, identity_matrix, CanonicalTransformError, Tableau};
+use crate::traits::FormatOptions;
 use crate::transformers::linear_model::LinearModel;
 use crate::transformers::standardizer::to_standard_form;
 use crate::utils::remove_many;
@@ -49,8 +50,12 @@ impl StandardLinearModel {
     /// * `Err(CanonicalTransformError)` - If the model cannot be converted to a tableau
     pub fn into_tableau(self) -> Result<Tableau, CanonicalTransformError> {
         let mut usable_independent_vars: Vec<IndependentVariable> = Vec::new();
-        //find independent variables by checking if the column has a single value, and if so, add it to the independent list
-        for column in 0..self.variables.len() {
+        let objective = self.c_vec();
+        //find independent variables by checking if the column has a single value, and if so, add it to the independent list,
+        //the ones that are not in the objective function (like slacks) are tried first so the objective stays unchanged
+        let mut columns = (0..self.variables.len()).collect::<Vec<_>>();
+        columns.sort_by_key(|column| float_ne(objective[*column], 0.0));
+        for column in columns {
             let mut independent_count = 0;
             let mut independent_row = 0;
             let mut independent_value = 0.0;
@@ -62,8 +67,14 @@ impl StandardLinearModel {
                     independent_value = constraint.coefficient(column);
                 }
             }
-            //only positive values are allowed, as the B column must be all positive
-            if independent_count == 1 && float_gt(independent_value, 0.0) {
+            //only positive values are allowed, as the B column must be all positive,
+            //and only one variable per row can be in the basis
+            if independent_count == 1
+                && float_gt(independent_value, 0.0)
+                && !usable_independent_vars
+                    .iter()
+                    .any(|v| v.row == independent_row)
+            {
                 usable_independent_vars.push(IndependentVariable {
                     row: independent_row,
                     column,
@@ -77,25 +88,34 @@ impl StandardLinearModel {
             let mut b = self.b_vec();
             let mut c = self.c_vec();
             let mut value = 0.0;
+            //the inverse of the basis starts as the identity and goes through the same row operations
+            let mut inverse = identity_matrix(self.constraints.len());
+            let mut inverse_costs = vec![0.0; self.constraints.len()];
             //normalize the rows of the independent variables
             for independent_variable in usable_independent_vars.iter() {
                 divide_matrix_row_by(&mut a, independent_variable.row, independent_variable.value);
+                divide_matrix_row_by(
+                    &mut inverse,
+                    independent_variable.row,
+                    independent_variable.value,
+                );
                 b[independent_variable.row] /= independent_variable.value;
                 let amount = c[independent_variable.column];
                 for (index, coefficient) in a[independent_variable.row].iter().enumerate() {
                     c[index] -= amount * coefficient;
                 }
-                value += amount * b[independent_variable.row];
+                for (index, coefficient) in inverse[independent_variable.row].iter().enumerate() {
+                    inverse_costs[index] -= amount * coefficient;
+                }
+                value -= amount * b[independent_variable.row];
             }
 
-            let mut basis = usable_independent_vars
-                .iter()
-                .map(|i| i.column)
-                .collect::<Vec<_>>();
-            //we only need as many basis variables as there are constraints
-            basis.resize(self.constraints.len(), 0);
-            Ok(Tableau::new(
-                self.c_vec(),
+            let mut basis = vec![0; self.constraints.len()];
+            for independent_variable in usable_independent_vars.iter() {
+                basis[independent_variable.row] = independent_variable.column;
+            }
+            let mut tableau = Tableau::new(
+                c,
                 a,
                 b,
                 basis,
@@ -103,7 +123,10 @@ impl StandardLinearModel {
                 self.objective_offset(),
                 self.variables(),
                 self.flip_objective,
-            ))
+            );
+            tableau.set_original_rhs(self.b_vec(), inverse, inverse_costs);
+            tableau.set_slack_columns(self.slack_columns());
+            Ok(tableau)
         } else {
             //use the 2 phase method to find a canonical tableau by adding artificial variables to the constraints and solving the tableau
             let mut a = self.a_matrix();
@@ -158,10 +181,13 @@ impl StandardLinearModel {
                     if new_basis.iter().all(|&i| i < number_of_variables) {
                         //restore the original objective function
                         let mut new_a = tableau.a_matrix().clone();
-                        //remove the artificial variables from the tableau
-                        for row in new_a.iter_mut() {
-                            row.resize(number_of_variables, 0.0);
-                        }
+                        //remove the artificial variables from the tableau, their columns started as
+                        //the identity so they are the inverse of the new basis
+                        let inverse = new_a
+                            .iter_mut()
+                            .map(|row| row.split_off(number_of_variables))
+                            .collect::<Vec<_>>();
+                        let mut inverse_costs = vec![0.0; number_of_artificial_variables];
                         let mut value = 0.0;
                         let mut new_c = self.c_vec();
                         let new_b = tableau.b_vec().clone();
@@ -173,10 +199,13 @@ impl StandardLinearModel {
                             for (index, c) in new_c.iter_mut().enumerate() {
                                 *c -= coefficient * new_a[row_index][index];
                             }
+                            for (index, c) in inverse_costs.iter_mut().enumerate() {
+                                *c -= coefficient * inverse[row_index][index];
+                            }
                             value -= coefficient * new_b[row_index];
                         }
 
-                        Ok(Tableau::new(
+                        let mut canonical = Tableau::new(
                             new_c,
                             new_a,
                             new_b,
@@ -185,7 +214,11 @@ impl StandardLinearModel {
                             self.objective_offset(),
                             self.variables(),
                             self.flip_objective,
-                        ))
+                        );
+                        canonical.add_phase1_stats(optimal_tableau.stats());
+                        canonical.set_original_rhs(self.b_vec(), inverse, inverse_costs);
+                        canonical.set_slack_columns(self.slack_columns());
+                        Ok(canonical)
                     } else {
                         Err(CanonicalTransformError::InvalidBasis(format!(
                             "Invalid basis: {:?}",
@@ -399,6 +432,16 @@ impl Display for StandardLinearModel {
 /// * `value` - Coefficient value
 /// * `is_first` - Whether this is the first term in an expression
 pub fn format_var(name: &str, value: f64, is_first: bool) -> String {
+    format_var_with_options(name, value, is_first, &FormatOptions::default())
+}
+
+/// Formats a variable term like [`format_var`], writing the coefficient with the given options.
+pub(crate) fn format_var_with_options(
+    name: &str,
+    value: f64,
+    is_first: bool,
+    options: &FormatOptions,
+) -> String {
     let sign = if float_lt(value, 0.0) {
         "- "
     } else if is_first {
@@ -409,7 +452,7 @@ pub fn format_var(name: &str, value: f64, is_first: bool) -> String {
     let num = if value == 1.0 || value == -1.0 {
         "".to_string()
     } else {
-        value.abs().to_string()
+        options.format_number(value.abs())
     };
     format!("{}{}{}", sign, num, name)
 }
@@ -433,6 +476,21 @@ impl StandardLinearModel {
             .collect()
     }
 
+    /// Returns, for each constraint, the column of the slack or surplus variable that was added
+    /// to turn it into an equality, or `None` if the constraint was already an equality.
+    fn slack_columns(&self) -> Vec<Option<usize>> {
+        self.constraints
+            .iter()
+            .map(|constraint| {
+                (0..self.variables.len()).find(|&column| {
+                    let name = &self.variables[column];
+                    (name.starts_with("$su_") || name.starts_with("$sl_"))
+                        && float_ne(constraint.coefficient(column), 0.0)
+                })
+            })
+            .collect()
+    }
+
     /// Returns a clone of the variable names vector.
     fn variables(&self) -> Vec<String> {
         self.variables.clone()
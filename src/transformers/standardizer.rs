@@ -1,4 +1,5 @@
-use crate::math::float_eq;
+use indexmap::IndexMap;
+
 use crate::math::{Comparison, OptimizationType, VariableType};
 use crate::parser::model_transformer::DomainVariable;
 use crate::solvers::{find_invalid_variables, SolverError};
@@ -41,6 +42,87 @@ use crate::utils::{remove_many, InputSpan};
 ///
 /// ```
 pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, SolverError> {
+    to_standard_form_with_map(problem).map(|(standard, _)| standard)
+}
+
+/// How a variable of a model is written with the variables of its standard form.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StandardizedVariable {
+    /// The variable is kept as the column at the given index
+    Column(usize),
+    /// A free variable, replaced by the difference of two non negative columns
+    Split { positive: usize, negative: usize },
+}
+
+/// Maps the variables of a model to the columns of its standard form, so that a solution
+/// of the standard form can be read in terms of the original variables.
+#[derive(Debug, Clone)]
+pub struct StandardizationMap {
+    variables: IndexMap<String, StandardizedVariable>,
+}
+
+impl StandardizationMap {
+    /// Returns how each variable of the original model is written in the standard form,
+    /// in the order of the original model.
+    pub fn variables(&self) -> &IndexMap<String, StandardizedVariable> {
+        &self.variables
+    }
+
+    /// Computes the value of the original variables from the values of the variables of the standard form,
+    /// the slack and surplus variables are dropped.
+    ///
+    /// # Arguments
+    /// * `standard_values` - The value of each variable of the standard form, missing values are zero
+    ///
+    /// # Example
+    /// ```rust
+    /// use rooc::{
+    ///     to_standard_form_with_map, Comparison, LinearModel, OptimizationType, StandardizedVariable,
+    ///     VariableType,
+    /// };
+    /// let mut model = LinearModel::new();
+    /// model.add_variable("x", VariableType::real());
+    /// model.add_variable("y", VariableType::non_negative_real());
+    /// model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 10.0);
+    /// model.set_objective(vec![1.0, 2.0], OptimizationType::Min);
+    ///
+    /// let (_, map) = to_standard_form_with_map(model).unwrap();
+    /// // y, the positive and negative part of x, then the slack of the constraint
+    /// let x = StandardizedVariable::Split { positive: 1, negative: 2 };
+    /// assert_eq!(map.variables().get("x"), Some(&x));
+    /// let values = map.reconstruct(&[3.0, 1.0, 5.0, 11.0]);
+    /// assert_eq!(values.get("x"), Some(&-4.0));
+    /// assert_eq!(values.get("y"), Some(&3.0));
+    /// ```
+    pub fn reconstruct(&self, standard_values: &[f64]) -> IndexMap<String, f64> {
+        let value = |column: usize| standard_values.get(column).copied().unwrap_or(0.0);
+        self.variables
+            .iter()
+            .map(|(name, variable)| {
+                let v = match variable {
+                    StandardizedVariable::Column(column) => value(*column),
+                    StandardizedVariable::Split { positive, negative } => {
+                        value(*positive) - value(*negative)
+                    }
+                };
+                (name.clone(), v)
+            })
+            .collect()
+    }
+}
+
+/// Converts a linear programming model into standard form like [`to_standard_form`],
+/// also returning how the variables of the model are written in the standard form.
+///
+/// # Arguments
+/// * `problem` - The linear model to convert
+///
+/// # Returns
+/// * `Ok((StandardLinearModel, StandardizationMap))` - The model in standard form and the mapping of its variables
+/// * `Err(SolverError)` - If the model cannot be converted
+pub fn to_standard_form_with_map(
+    problem: LinearModel,
+) -> Result<(StandardLinearModel, StandardizationMap), SolverError> {
     let (
         mut objective,
         optimization_type,
@@ -76,6 +158,11 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
         match domain_type {
             VariableType::Real(min, max) => match (*min, *max) {
                 (f64::NEG_INFINITY, f64::INFINITY) => continue,
+                (min, max) if min == max => {
+                    let mut coeffs = vec![0.0; variables.len()];
+                    coeffs[i] = 1.0;
+                    constraints.push(LinearConstraint::new(coeffs, Comparison::Equal, min));
+                }
                 (min, max) if min != f64::NEG_INFINITY || max != f64::INFINITY => {
                     let mut coeffs = vec![0.0; variables.len()];
                     coeffs[i] = 1.0;
@@ -98,6 +185,11 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
             },
             VariableType::NonNegativeReal(min, max) => match (*min, *max) {
                 (0.0, f64::INFINITY) => continue,
+                (min, max) if min == max => {
+                    let mut coeffs = vec![0.0; variables.len()];
+                    coeffs[i] = 1.0;
+                    constraints.push(LinearConstraint::new(coeffs, Comparison::Equal, min));
+                }
                 (min, max) if min != 0.0 || max != f64::INFINITY => {
                     let mut coeffs = vec![0.0; variables.len()];
                     coeffs[i] = 1.0;
@@ -121,6 +213,14 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
             _ => (),
         }
     }
+    //the original variables and whether they are free, to map them to the standard form at the end
+    let original_variables = variables
+        .iter()
+        .map(|v| {
+            let is_free = matches!(domain.get(v).unwrap().get_type(), VariableType::Real(_, _));
+            (v.clone(), is_free)
+        })
+        .collect::<Vec<_>>();
     //we now need to replace all free variables with positive variables
     let free_variables = variables
         .iter()
@@ -156,17 +256,12 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
             ),
         );
         context.total_variables += 1; //we add two variables, but one is removed, so only one is added
+                                      //zero coefficients are pushed too, so that the columns of the next free variables stay aligned
         constraints.iter_mut().for_each(|c| {
             let original_coefficient = c.coefficients()[*i];
-            if float_eq(original_coefficient, 0.0) {
-                return;
-            }
             c.coefficients_mut().push(original_coefficient);
             c.coefficients_mut().push(original_coefficient * -1.0);
         });
-        if float_eq(objective[*i], 0.0) {
-            continue;
-        }
         objective.push(objective[*i]);
         objective.push(objective[*i] * -1.0);
     }
@@ -209,27 +304,41 @@ pub fn to_standard_form(problem: LinearModel) -> Result<StandardLinearModel, Sol
     constraints
         .iter_mut()
         .for_each(|c| c.ensure_size(context.total_variables));
+    let column = |name: &str| variables.iter().position(|v| v == name).unwrap();
+    let map = StandardizationMap {
+        variables: original_variables
+            .iter()
+            .map(|(name, is_free)| {
+                let variable = match is_free {
+                    true => StandardizedVariable::Split {
+                        positive: column(&format!("$p{}", name)),
+                        negative: column(&format!("$m{}", name)),
+                    },
+                    false => StandardizedVariable::Column(column(name)),
+                };
+                (name.clone(), variable)
+            })
+            .collect(),
+    };
     let (objective_offset, objective, flip_objective) = match optimization_type {
+        //the whole objective is negated, offset included
         OptimizationType::Max => (
-            objective_offset,
+            -objective_offset,
             objective.iter().map(|c| c * -1.0).collect(),
             true,
         ),
         OptimizationType::Min => (objective_offset, objective.clone(), false),
-        _ => {
-            return Err(SolverError::UnimplementedOptimizationType {
-                expected: vec![OptimizationType::Max, OptimizationType::Min],
-                got: optimization_type,
-            })
-        }
+        //any feasible point is a solution, so the first phase of the simplex is enough
+        OptimizationType::Satisfy => (0.0, vec![0.0; objective.len()], false),
     };
-    Ok(StandardLinearModel::new(
+    let standard = StandardLinearModel::new(
         objective,
         constraints,
         variables,
         objective_offset,
         flip_objective,
-    ))
+    );
+    Ok((standard, map))
 }
 
 /// Context for tracking the normalization process of converting constraints to standard form.
@@ -4,8 +4,8 @@ use indexmap::IndexMap;
 use serde::Serialize;
 
 use crate::math::PreVariableType;
-use crate::parser::il::AddressableAccess;
 use crate::parser::il::PreExp;
+use crate::parser::il::{AddressableAccess, PointerAccess};
 use crate::parser::model_transformer::Frame;
 use crate::parser::model_transformer::TransformError;
 use crate::runtime_builtin::RoocFunction;
@@ -154,12 +154,22 @@ impl<'a> FunctionContext<'a> {
             None => self.functions.get(name).map(|f| f.as_ref()),
         }
     }
+
+    /// Checks if a function is built-in, built-in functions always return the same
+    /// value when called with the same arguments.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the function
+    pub fn is_builtin(&self, name: &str) -> bool {
+        self.builtin_functions.contains_key(name)
+    }
 }
 
 /// Main context for type checking, maintaining variable scopes and type information.
 pub struct TypeCheckerContext {
     frames: Vec<Frame<PrimitiveKind>>,
     static_domain: IndexMap<String, StaticVariableType>,
+    indexed_domain: Vec<String>,
     token_map: IndexMap<u32, TypedToken>,
 }
 
@@ -189,6 +199,7 @@ impl TypeCheckerContext {
             frames: vec![frame],
             token_map,
             static_domain,
+            indexed_domain: Vec::new(),
         }
     }
 
@@ -272,6 +283,23 @@ impl TypeCheckerContext {
         self.static_domain.get(name)
     }
 
+    /// Sets the names of the families of domain variables, like `x` for `x_i` or `x[i]`,
+    /// that can be accessed by index like `x[2]`.
+    ///
+    /// # Arguments
+    /// * `names` - Names of the families of domain variables
+    pub fn set_indexed_domain(&mut self, names: Vec<String>) {
+        self.indexed_domain = names;
+    }
+
+    /// Checks if a name is a family of domain variables that can be accessed by index.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the family
+    pub fn is_indexed_domain_variable(&self, name: &str) -> bool {
+        self.indexed_domain.iter().any(|n| n == name)
+    }
+
     /// Removes and returns the top scope frame.
     ///
     /// # Returns
@@ -396,6 +424,26 @@ impl TypeCheckerContext {
             Some(v) => {
                 let mut last_value = v;
                 for access in addressable_access.accesses.iter() {
+                    let access = match access {
+                        PointerAccess::Index(exp) => exp,
+                        PointerAccess::Field(field) => {
+                            last_value = match last_value {
+                                PrimitiveKind::Tuple(kinds) => match kinds.get(*field) {
+                                    Some(kind) => kind,
+                                    None => return Err(TransformError::OutOfBounds(format!(
+                                        "cannot access field {} of a tuple of type \"{}\", check the definition of \"{}\"",
+                                        field, last_value, addressable_access
+                                    ))),
+                                },
+                                PrimitiveKind::Any => last_value,
+                                _ => return Err(TransformError::Other(format!(
+                                    "Expected value of type \"Tuple\" to access field {}, got \"{}\", check the definition of \"{}\"",
+                                    field, last_value, addressable_access
+                                ))),
+                            };
+                            continue;
+                        }
+                    };
                     if !access.get_type(self, fn_context).is_numeric() {
                         //TODO this is a relaxed check, the runtime will check for the exact type
                         return Err(TransformError::Other(format!(
@@ -408,6 +456,29 @@ impl TypeCheckerContext {
                         PrimitiveKind::Iterable(i) => {
                             last_value = i
                         }
+                        //tuples are indexed like their fields when the index is known
+                        PrimitiveKind::Tuple(kinds) => {
+                            let index = match access {
+                                PreExp::Primitive(p) => p.value().as_usize_cast().ok(),
+                                _ => None,
+                            };
+                            last_value = match index {
+                                Some(index) => match kinds.get(index) {
+                                    Some(kind) => kind,
+                                    None => return Err(TransformError::OutOfBounds(format!(
+                                        "cannot access index {} of a tuple of type \"{}\", check the definition of \"{}\"",
+                                        index, last_value, addressable_access
+                                    )).add_span(access.span())),
+                                },
+                                None => match kinds.first() {
+                                    Some(first) if kinds.iter().all(|k| k == first) => first,
+                                    _ => return Err(TransformError::Other(format!(
+                                        "Tuples of type \"{}\" can only be indexed by a constant, check the definition of \"{}\"",
+                                        last_value, addressable_access
+                                    )).add_span(access.span())),
+                                },
+                            }
+                        }
                         _ => return Err(TransformError::Other(format!(
                             "Expected value of type \"Iterable\" to index, got \"{}\", check the definition of \"{}\"",
                             last_value,
@@ -417,6 +488,16 @@ impl TypeCheckerContext {
                 }
                 Ok(last_value.clone())
             }
+            //indexed domain variables like x[i] are numbers once flattened
+            None if self.is_indexed_domain_variable(&addressable_access.name) => {
+                match addressable_access.as_compound_variable() {
+                    Some(_) => Ok(PrimitiveKind::Number),
+                    None => Err(TransformError::Other(format!(
+                        "Domain variable \"{}\" can only be accessed by index, check the definition of \"{}\"",
+                        addressable_access.name, addressable_access
+                    ))),
+                }
+            }
             None => Err(TransformError::UndeclaredVariable(
                 addressable_access.name.to_string(),
             )),
@@ -77,6 +77,18 @@ impl InputSpan {
         }
         Ok(&text[start..end])
     }
+
+    /// Returns the span of the first `len` characters of this span, like the name
+    /// at the start of `x_i` or `c[i]`.
+    ///
+    /// # Arguments
+    /// * `len` - The length of the prefix, it is capped to the length of the span
+    pub fn prefix(&self, len: u32) -> Self {
+        Self {
+            len: len.min(self.len),
+            ..self.clone()
+        }
+    }
 }
 
 /// A wrapper type that associates a value with its location in source code.
@@ -211,6 +223,61 @@ impl CompilationError {
         Self::new(kind, span, text)
     }
 
+    /// Creates a CompilationError from an error of the Pest parser, listing the
+    /// grammar rules that were expected where the parsing failed.
+    ///
+    /// # Arguments
+    /// * `error` - The error returned by the Pest parser
+    /// * `source` - The source text that was being parsed
+    pub fn from_pest_error(error: pest::error::Error<Rule>, source: &str) -> Self {
+        let (start, len) = match error.location {
+            pest::error::InputLocation::Pos(pos) => (pos, 1),
+            pest::error::InputLocation::Span((start, end)) => (start, end - start),
+        };
+        let (start_line, start_column) = match error.line_col {
+            pest::error::LineColLocation::Pos(pos) => pos,
+            pest::error::LineColLocation::Span(start, _) => start,
+        };
+        let span = InputSpan {
+            start_line: start_line as u32,
+            start_column: start_column as u32,
+            start: start as u32,
+            len: len as u32,
+            tempered: false,
+        };
+        let kind = match error.variant {
+            pest::error::ErrorVariant::ParsingError { positives, .. } => {
+                let rest = &source[start.min(source.len())..];
+                let found = match rest.split_whitespace().next() {
+                    _ if rest.starts_with(['\n', '\r']) => "Unexpected new line".to_string(),
+                    Some(token) => format!("Unexpected \"{}\"", token),
+                    None => "Unexpected end of input".to_string(),
+                };
+                let mut expected: Vec<String> = Vec::new();
+                for rule in positives {
+                    let name = readable_rule_name(rule);
+                    if !expected.contains(&name) {
+                        expected.push(name);
+                    }
+                }
+                ParseError::UnexpectedToken {
+                    message: found,
+                    expected,
+                }
+            }
+            pest::error::ErrorVariant::CustomError { message } => ParseError::UnexpectedToken {
+                message,
+                expected: Vec::new(),
+            },
+        };
+        Self::new(kind, span, source.to_string())
+    }
+
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> &ParseError {
+        &self.kind
+    }
+
     /// Formats the error message using the original source text.
     ///
     /// # Arguments
@@ -230,6 +297,33 @@ impl CompilationError {
     }
 }
 
+/// Returns the name of a grammar rule as it should be shown to the user.
+fn readable_rule_name(rule: Rule) -> String {
+    match rule {
+        Rule::number | Rule::integer | Rule::float | Rule::signed_number | Rule::implicit_mul => {
+            "number".to_string()
+        }
+        Rule::variable
+        | Rule::simple_variable
+        | Rule::compound_variable
+        | Rule::escaped_compound_variable => "variable".to_string(),
+        Rule::parenthesis => "'('".to_string(),
+        Rule::modulo => "'|'".to_string(),
+        Rule::neg => "'-'".to_string(),
+        Rule::not => "'not'".to_string(),
+        Rule::in_op => "'in'".to_string(),
+        Rule::not_in => "'not in'".to_string(),
+        Rule::primitive => "value".to_string(),
+        Rule::function | Rule::function_name => "function".to_string(),
+        Rule::comparison | Rule::comparison_op => "comparison".to_string(),
+        Rule::objective_type => "min or max".to_string(),
+        Rule::nl => "new line".to_string(),
+        Rule::comma => "','".to_string(),
+        Rule::EOI => "end of input".to_string(),
+        rule => format!("{:?}", rule).replace('_', " "),
+    }
+}
+
 impl std::fmt::Debug for CompilationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = format!(
@@ -264,8 +358,12 @@ impl CompilationError {
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", content = "value")]
 pub enum ParseError {
-    /// Indicates an unexpected token was encountered during parsing
-    UnexpectedToken(String),
+    /// Indicates an unexpected token was encountered during parsing,
+    /// `expected` lists what the grammar would have accepted instead, when known
+    UnexpectedToken {
+        message: String,
+        expected: Vec<String>,
+    },
     /// Indicates a required token was not found during parsing
     MissingToken(String),
     /// Indicates a semantic error in the parsed code
@@ -278,7 +376,10 @@ pub enum ParseError {
 pub const IParseError: &'static str = r#"
 export type ParseError = {
     type: "UnexpectedToken",
-    value: string
+    value: {
+        message: string,
+        expected: string[]
+    }
 } | {
     type: "MissingToken",
     value: string
@@ -298,7 +399,14 @@ export type ParseError = {
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
-            Self::UnexpectedToken(s) => format!("[Unexpected token] {}", s),
+            Self::UnexpectedToken { message, expected } => match expected.is_empty() {
+                true => format!("[Unexpected token] {}", message),
+                false => format!(
+                    "[Unexpected token] {}, expected one of: {}",
+                    message,
+                    expected.join(", ")
+                ),
+            },
             Self::MissingToken(s) => format!("[Missing token] {}", s),
             Self::SemanticError(s) => format!("[Semantic error] {}", s),
         };
@@ -3,6 +3,7 @@ mod math_tests {
     use rooc::{
         math_enums::{Comparison, OptimizationType},
         operators::{BinOp, UnOp},
+        RoocParser,
     };
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test;
@@ -50,4 +51,36 @@ mod math_tests {
             );
         }
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_math_enums_round_trip() {
+        let comparisons = [
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("=", Comparison::Equal),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+        ];
+        for (op, comparison) in comparisons {
+            assert_eq!(op.parse::<Comparison>(), Ok(comparison));
+            assert_eq!(comparison.to_string(), op);
+            let source = format!("min x\ns.t.\n    x {} 1\ndefine\n    x as Real", op);
+            let parsed = RoocParser::new(source).parse().expect("Failed to parse");
+            assert_eq!(parsed.constraints()[0].constraint_type, comparison);
+        }
+        assert!("=>".parse::<Comparison>().is_err());
+        let optimization_types = [
+            ("min", OptimizationType::Min),
+            ("max", OptimizationType::Max),
+            ("solve", OptimizationType::Satisfy),
+        ];
+        for (name, optimization_type) in optimization_types {
+            assert_eq!(
+                name.parse::<OptimizationType>(),
+                Ok(optimization_type.clone())
+            );
+            assert_eq!(optimization_type.to_string(), name);
+        }
+    }
 }
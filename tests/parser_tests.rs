@@ -1,7 +1,16 @@
 #[cfg(test)]
 mod parser_tests {
     use indexmap::IndexMap;
-    use rooc::RoocParser;
+    use rooc::common::{Assignment, LpSolution};
+    use rooc::model_transformer::{
+        DefaultDomain, StrictComparisonMode, TransformError, TransformerContext,
+        VariableNamingStrategy,
+    };
+    use rooc::type_checker::type_checker_context::{FunctionContext, TypeCheckerContext};
+    use rooc::{DisplayWithOptions, FormatOptions};
+    use rooc::{Linearizer, PreExp, Primitive, PrimitiveKind, RoocFunction, RoocParser};
+    use std::cell::Cell;
+    use std::rc::Rc;
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test;
 
@@ -586,4 +595,1855 @@ define
             .type_check(&vec![], &IndexMap::new())
             .expect("Failed to typecheck");
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_multi_dimensional_arrays() {
+        let input = "
+        min 1
+        s.t.
+            M[i][j] + T[i][j][k] >= 1 for i in 0..len(M), j in 0..len(M[i]), k in 0..2
+        where
+            let M = [
+                [1, 2],
+                [3, 4]
+            ]
+            let T = [
+                [[1, 2], [3, 4]],
+                [[5, 6], [7, 8]]
+            ]
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_ragged_arrays() {
+        let input = "
+        min 1
+        s.t.
+            1 >= 1
+        where
+            let M = [
+                [1, 2],
+                [3]
+            ]
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse()
+            .expect_err("Failed to detect ragged array");
+        let err = err.to_string_from_source(input);
+        assert!(err.contains("Ragged array"));
+        assert!(err.contains("([3])"));
+        let input = "
+        min 1
+        s.t.
+            1 >= 1
+        where
+            let T = [
+                [[1, 2], [3, 4]],
+                [[5, 6], [7]]
+            ]
+        ";
+        RoocParser::new(input.to_string())
+            .parse()
+            .expect_err("Failed to detect ragged array");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_boolean_operators() {
+        let input = "
+        min 1
+        s.t.
+            x * a + y * b + z * c <= 1
+        where
+            let t = true
+            let a = t and not false
+            let b = false or t and false
+            let c = not (t or false)
+        define
+            x, y, z as Boolean
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.to_string(),
+            "min 1\ns.t.\n    x * 1 + y * 0 + z * 0 <= 1\ndefine\n    x, y, z as Boolean"
+        );
+        RoocParser::new(input.to_string())
+            .type_check(&vec![], &IndexMap::new())
+            .expect("Failed to type check problem");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_boolean_operators_errors() {
+        let input = "
+        min 1
+        s.t.
+            x <= a
+        where
+            let a = 1 and true
+        define
+            x as Boolean
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&vec![], &IndexMap::new())
+            .expect_err("Failed to detect non boolean operand");
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect non boolean operand");
+        let input = "
+        min 1
+        s.t.
+            not x <= 1
+        define
+            x as Boolean
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect logical operator on a variable");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_comparison_operators() {
+        let input = "
+        min 1
+        s.t.
+            x * a + y * b + z * c + w * d <= 1
+            x * e + y * f + z * g + w * h >= 0
+        where
+            let a = 1 < 2
+            let b = 2.5 >= 3
+            let c = 1 + 1 == 2 and 3 != 2
+            let d = 2 <= 2.0 or 1 > 2
+            let e = \"apple\" < \"banana\"
+            let f = \"b\" <= \"abc\"
+            let g = \"rooc\" == \"rooc\"
+            let h = true != false
+        define
+            x, y, z, w as Boolean
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.to_string(),
+            "min 1\ns.t.\n    x * 1 + y * 0 + z * 1 + w * 1 <= 1\n    x * 1 + y * 0 + z * 1 + w * 1 >= 0\ndefine\n    x, y, z, w as Boolean"
+        );
+        RoocParser::new(input.to_string())
+            .type_check(&vec![], &IndexMap::new())
+            .expect("Failed to type check problem");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_comparison_operators_errors() {
+        for value in ["1 < \"a\"", "\"a\" == 1", "true < false", "1 == true"] {
+            let input = format!(
+                "
+            min 1
+            s.t.
+                x <= a
+            where
+                let a = {}
+            define
+                x as Boolean
+            ",
+                value
+            );
+            RoocParser::new(input.clone())
+                .type_check(&vec![], &IndexMap::new())
+                .expect_err("Failed to detect comparison between different types");
+            RoocParser::new(input)
+                .parse_and_transform(vec![], &IndexMap::new())
+                .expect_err("Failed to detect comparison between different types");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_deeply_nested_expression() {
+        let make_input = |exp: String| {
+            format!(
+                "
+            min 1
+            s.t.
+                x <= {}
+            define
+                x as Boolean
+            ",
+                exp
+            )
+        };
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        let subtractions = |terms: usize| vec!["1"; terms].join(" - ");
+        let err = RoocParser::new(make_input(nested(5000)))
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect deeply nested expression");
+        assert!(err.contains("expression too deeply nested"));
+        let err = RoocParser::new(make_input(nested(5000)))
+            .type_check(&vec![], &IndexMap::new())
+            .expect_err("Failed to detect deeply nested expression");
+        assert!(err.contains("expression too deeply nested"));
+        let err = RoocParser::new(make_input(subtractions(5000)))
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect deeply nested expression");
+        assert!(err.contains("expression too deeply nested"));
+        let domain = format!(
+            "
+            min 1
+            s.t.
+                x <= 1
+            define
+                x as IntegerRange(0, {})
+            ",
+            subtractions(5000)
+        );
+        let err = RoocParser::new(domain)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect deeply nested domain");
+        assert!(err.contains("expression too deeply nested"));
+        RoocParser::new(make_input(subtractions(50)))
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        RoocParser::new(make_input(subtractions(50)))
+            .with_max_depth(10)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to respect the configured depth");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_flat_sum_is_not_nested() {
+        let input = format!(
+            "
+            min 1
+            s.t.
+                x <= {}
+            define
+                x as Boolean
+            ",
+            vec!["1"; 1000].join(" + ")
+        );
+        let model = RoocParser::new(input)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize problem");
+        assert_eq!(linear.constraints()[0].rhs(), 1000.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_variable_naming_strategy() {
+        let input = "
+        min sum(i in 0..2, j in 0..2) { x_i_j }
+        s.t.
+            x_0_1 + x_1_0 >= 1
+        define
+            x_i_j as Boolean for i in 0..2, j in 0..2
+        ";
+        let model = RoocParser::new(input.to_string())
+            .with_naming_strategy(VariableNamingStrategy::Bracketed)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.to_string(),
+            "min x[0,0] + x[0,1] + x[1,0] + x[1,1]\ns.t.\n    x[0,1] + x[1,0] >= 1\ndefine\n    x[0,0], x[0,1], x[1,0], x[1,1] as Boolean"
+        );
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.to_string(),
+            "min x_0_0 + x_0_1 + x_1_0 + x_1_1\ns.t.\n    x_0_1 + x_1_0 >= 1\ndefine\n    x_0_0, x_0_1, x_1_0, x_1_1 as Boolean"
+        );
+
+        //the naming strategy also decides how the indexes are subscripted in LaTeX
+        let latex = RoocParser::new(input.to_string())
+            .with_naming_strategy(VariableNamingStrategy::Bracketed)
+            .parse()
+            .expect("Failed to parse problem")
+            .to_latex_with_options(&FormatOptions::new());
+        assert!(latex.contains("x_{i,j}"));
+        assert!(latex.contains("x_{0,1} + x_{1,0}"));
+        let latex = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .to_latex_with_options(&FormatOptions::new());
+        assert!(latex.contains("x_{ij}"));
+        assert!(latex.contains("x_{01} + x_{10}"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_model_to_json() {
+        let input = "
+        max 2x + y
+        s.t.
+            x + y <= 4 for i in 0..2
+        where
+            let a = 3
+        define
+            x, y as Real(0, 10)
+        ";
+        let json = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .to_json();
+        let value: serde_json::Value =
+            serde_json::from_str(&json).expect("Failed to read back the JSON");
+        assert_eq!(value["objective"]["objective_type"]["type"], "Max");
+        assert!(value["objective"]["rhs"]["BinaryOperation"].is_array());
+        let constraints = value["constraints"].as_array().unwrap();
+        assert_eq!(constraints.len(), 1);
+        assert_eq!(constraints[0]["constraint_type"]["type"], "LessOrEqual");
+        assert_eq!(constraints[0]["iteration"].as_array().unwrap().len(), 1);
+        assert_eq!(value["constants"][0]["name"]["value"], "a");
+        let domain = &value["domains"][0];
+        assert_eq!(domain["as_type"]["type"], "Real");
+        assert_eq!(domain["variables"].as_array().unwrap().len(), 2);
+        assert_eq!(domain["variables"][1]["value"]["value"], "y");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_norm_functions() {
+        let input = "
+        min norm1(v) + norm_inf(v) * x + norm1 { 2, -3 }
+        s.t.
+            x >= norm_inf(w)
+        where
+            let v = [3, 4, 1]
+            let w = [2.5, 1.0]
+        define
+            x as Real
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.to_string(),
+            "min 8 + 4 * x + |2| + |-3|\ns.t.\n    x >= 2.5\ndefine\n    x as Real"
+        );
+        let input = "
+        min norm1(v)
+        s.t.
+            x >= 0
+        where
+            let v = [\"a\", \"b\"]
+        define
+            x as Real
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject a norm of strings");
+    }
+
+    fn parse_graph_constant(graph: &str) -> rooc::Graph {
+        let input = format!(
+            "
+        min 1
+        s.t.
+            1 <= 1
+        where
+            let G = {graph}
+        "
+        );
+        let model = RoocParser::new(input)
+            .parse()
+            .expect("Failed to parse problem");
+        match &model.constants()[0].value {
+            rooc::PreExp::Primitive(p) => match p.value() {
+                rooc::Primitive::Graph(g) => g.clone(),
+                _ => panic!("Expected a graph constant"),
+            },
+            _ => panic!("Expected a primitive constant"),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_graph_edge_syntaxes() {
+        let node_centric = parse_graph_constant(
+            "Graph {
+                A -> [B:3, C],
+                B -> [C:-1.5],
+                C
+            }",
+        );
+        let edge_list = parse_graph_constant(
+            "Graph {
+                A -> B:3,
+                A -> C,
+                B -> C:-1.5,
+                C
+            }",
+        );
+        assert_eq!(node_centric, edge_list);
+        let edges = edge_list.to_edges();
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges[0].weight, Some(3.0));
+        assert_eq!(edges[1].weight, None);
+        assert_eq!(edges[2].weight, Some(-1.5));
+        let huge = "9".repeat(400);
+        RoocParser::new(format!(
+            "min 1\ns.t.\n    1 <= 1\nwhere\n    let G = Graph {{ A -> B:{huge} }}"
+        ))
+        .parse()
+        .expect_err("Failed to reject a non finite edge weight");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_weighted_edges_round_trip() {
+        let graph = parse_graph_constant(
+            "Graph {
+                A -> [B:3, C:0.25, D],
+                B -> C:-1.5,
+                B -> D,
+                C -> [],
+                D
+            }",
+        );
+        let edges = graph.clone().to_edges();
+        let weights = edges.iter().map(|e| e.weight).collect::<Vec<_>>();
+        assert_eq!(weights, vec![Some(3.0), Some(0.25), None, Some(-1.5), None]);
+        assert_eq!(
+            edges.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+            vec!["B:3", "C:0.25", "D", "C:-1.5", "D"]
+        );
+        let source = graph.to_string();
+        assert_eq!(
+            source,
+            "Graph {\n    A -> [ B:3, C:0.25, D ],\n    B -> [ C:-1.5, D ],\n    C,\n    D\n}"
+        );
+        assert_eq!(parse_graph_constant(&source), graph);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_parallel_edges_and_self_loops() {
+        for graph in [
+            "Graph { A -> [B, B:2] }",
+            "Graph { A -> B:1, A -> B:2, B }",
+            "Graph { A -> [B:1], B, A -> [C, B] }",
+        ] {
+            let input = format!("min 1\ns.t.\n    1 <= 1\nwhere\n    let G = {graph}");
+            let err = RoocParser::new(input.clone())
+                .parse()
+                .expect_err("Failed to reject parallel edges");
+            assert!(err
+                .to_string_from_source(&input)
+                .contains("the edge from \"A\" to \"B\" is declared more than once"));
+        }
+        let graph = parse_graph_constant("Graph { A -> [A:2, B], B -> B }");
+        let edges = graph
+            .to_edges()
+            .into_iter()
+            .map(|e| (e.from, e.to, e.weight))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                ("A".to_string(), "A".to_string(), Some(2.0)),
+                ("A".to_string(), "B".to_string(), None),
+                ("B".to_string(), "B".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_strict_comparisons() {
+        let input = "
+        max x + y
+        s.t.
+            x + y < 4
+            x > 1
+        define
+            x, y as NonNegativeReal
+        ";
+        let err = RoocParser::new(input.to_string())
+            .with_strict_comparison(StrictComparisonMode::Reject)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject a strict comparison");
+        assert!(err.contains("The strict comparison \"<\" in \"x + y < 4\""));
+
+        let model = RoocParser::new(input.to_string())
+            .with_strict_comparison(StrictComparisonMode::Relax { epsilon: 0.5 })
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to relax strict comparisons");
+        assert_eq!(
+            model.to_string(),
+            "max x + y\ns.t.\n    x + y <= 3.5\n    x >= 1.5\ndefine\n    x, y as NonNegativeReal"
+        );
+        assert_eq!(model.warnings().len(), 2);
+        assert!(model.warnings()[0].contains("was relaxed to \"<=\" with a margin of 0.5"));
+
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to keep strict comparisons");
+        assert_eq!(model.warnings().len(), 2);
+        assert!(model.warnings()[1].contains("The strict comparison \">\" in \"x > 1\" was kept"));
+        let linear = Linearizer::linearize(model.clone()).expect("Failed to linearize");
+        assert_eq!(linear.warnings(), model.warnings());
+        assert_eq!(
+            model.to_string(),
+            "max x + y\ns.t.\n    x + y < 4\n    x > 1\ndefine\n    x, y as NonNegativeReal"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_objective_less_model() {
+        let input = "
+        s.t.
+            x + y <= 1
+            x >= 1
+        define
+            x, y as Boolean
+        ";
+        let parser = RoocParser::new(input.to_string());
+        let pre_model = parser
+            .parse()
+            .expect("Failed to parse a model without objective");
+        assert_eq!(
+            pre_model.objective().objective_type,
+            rooc::OptimizationType::Satisfy
+        );
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to transform a model without objective");
+        assert_eq!(
+            model.objective().objective_type,
+            rooc::OptimizationType::Satisfy
+        );
+        assert_eq!(model.constraints().len(), 2);
+
+        let input = "
+        s.t.
+        define
+            x as Boolean
+        ";
+        RoocParser::new(input.to_string())
+            .parse()
+            .expect_err("Failed to reject a model without objective and constraints");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_syntax_error_lists_expected_tokens() {
+        let input = "
+        max x +
+        s.t.
+            x <= 1
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse()
+            .expect_err("Failed to detect syntax error");
+        match err.kind() {
+            rooc::ParseError::UnexpectedToken { message, expected } => {
+                assert_eq!(message, "Unexpected new line");
+                for token in ["number", "variable", "'('"] {
+                    assert!(expected.contains(&token.to_string()));
+                }
+            }
+            kind => panic!("Expected an unexpected token error, got {}", kind),
+        }
+        assert!(err
+            .to_string_from_source(input)
+            .contains("expected one of: number"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_boolean_and_tuple_constants() {
+        let input = "
+        max x
+        s.t.
+            sum((a, b) in pairs) { a * x } <= 8
+        where
+            let enabled = true
+            let bounds = (2, 8)
+            let pairs = [(1, \"a\"), (3, \"b\")]
+        define
+            x as NonNegativeReal
+        ";
+        let parser = RoocParser::new(input.to_string());
+        let pre_model = parser.parse().expect("Failed to parse problem");
+        let source = pre_model.to_string();
+        assert!(source.contains("let enabled = true"));
+        assert!(source.contains("let bounds = (2, 8)"));
+        assert!(source.contains("let pairs = [(1, \"a\"), (3, \"b\")]"));
+        parser
+            .type_check(&vec![], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(model.constraints()[0].to_string(), "1 * x + 3 * x <= 8");
+    }
+
+    #[derive(Debug)]
+    struct CountedWeight {
+        calls: Rc<Cell<usize>>,
+    }
+
+    impl RoocFunction for CountedWeight {
+        fn call(
+            &self,
+            args: &[PreExp],
+            context: &TransformerContext,
+            fn_context: &FunctionContext,
+        ) -> Result<Primitive, TransformError> {
+            self.calls.set(self.calls.get() + 1);
+            let i = args[0].as_number_cast(context, fn_context)?;
+            Ok(Primitive::Number(i + 1.0))
+        }
+
+        fn type_signature(
+            &self,
+            _args: &[PreExp],
+            _context: &TypeCheckerContext,
+            _fn_context: &FunctionContext,
+        ) -> Vec<(String, PrimitiveKind)> {
+            vec![("i".to_string(), PrimitiveKind::Number)]
+        }
+
+        fn return_type(
+            &self,
+            _args: &[PreExp],
+            _context: &TypeCheckerContext,
+            _fn_context: &FunctionContext,
+        ) -> PrimitiveKind {
+            PrimitiveKind::Number
+        }
+
+        fn function_name(&self) -> String {
+            "weight".to_string()
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_subexpressions_are_evaluated_once() {
+        let input = "
+        min x_0
+        s.t.
+            x_j >= sum(i in 0..len(W)) { W[i] * len(W) } for j in 0..2
+            x_j <= sum(i in 0..len(W)) { W[i] * j } for j in 0..2
+        where
+            let W = [1, 2, 3]
+        define
+            x_j as NonNegativeReal for j in 0..2
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let constraints = model
+            .constraints()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        // the constant sum is folded to a number, the one depending on j is not
+        assert_eq!(
+            constraints,
+            vec![
+                "x_0 >= 18",
+                "x_1 >= 18",
+                "x_0 <= 1 * 0 + 2 * 0 + 3 * 0",
+                "x_1 <= 1 * 1 + 2 * 1 + 3 * 1",
+            ]
+        );
+
+        let input = "
+        min x
+        s.t.
+            x >= sum(i in 0..3) { weight(i) }
+            y >= sum(i in 0..3) { weight(i) }
+            z_j >= sum(i in 0..3) { weight(i) } for j in 0..2
+        define
+            x, y as NonNegativeReal
+            z_j as NonNegativeReal for j in 0..2
+        ";
+        let calls = Rc::new(Cell::new(0));
+        let mut fns: IndexMap<String, Box<dyn RoocFunction>> = IndexMap::new();
+        fns.insert(
+            "weight".to_string(),
+            Box::new(CountedWeight {
+                calls: calls.clone(),
+            }),
+        );
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &fns)
+            .expect("Failed to parse and transform problem");
+        // user functions might not return the same value every time, they are always called
+        assert_eq!(calls.get(), 4 * 3);
+        assert_eq!(model.constraints()[3].to_string(), "z_1 >= 1 + 2 + 3");
+
+        // the same constant sum written in different constraints is evaluated once
+        let input = "
+        min x
+        s.t.
+            x >= sum(w in W) { w * 2 }
+            y <= sum(w in W) { w * 2 }
+            x + y >= sum(w in W) { w * 3 }
+        where
+            let W = [1, 2, 3]
+        define
+            x, y as NonNegativeReal
+        ";
+        let pre_model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        let (fns, std) = (IndexMap::new(), IndexMap::new());
+        let fn_context = FunctionContext::new(&fns, &std);
+        let mut context = TransformerContext::new_from_constants(
+            pre_model.constants().clone(),
+            pre_model.domains().clone(),
+            &fn_context,
+        )
+        .expect("Failed to create the context");
+        let values = pre_model
+            .constraints()
+            .iter()
+            .map(|c| {
+                c.rhs
+                    .into_exp(&mut context, &fn_context)
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec!["12", "12", "18"]);
+        assert_eq!(context.constant_cache_hits(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_set_constants() {
+        let input = "
+        max sum(c in Cities) { x_c * (c not in Hubs) }
+        s.t.
+            x_c <= a for c in Cities
+            y * b <= len(Hubs)
+        where
+            let Cities = {\"Rome\", \"Milan\", \"Rome\", \"Turin\"}
+            let Hubs = {
+                \"Milan\"
+            }
+            let a = \"Milan\" in Hubs
+            let b = 1 not in {1, 2.0}
+        define
+            x_c as Boolean for c in Cities
+            y as Boolean
+        ";
+        let parser = RoocParser::new(input.to_string());
+        let pre_model = parser.parse().expect("Failed to parse problem");
+        assert!(pre_model
+            .to_string()
+            .contains("let Cities = {\"Rome\", \"Milan\", \"Turin\"}"));
+        parser
+            .type_check(&vec![], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.to_string(),
+            "max x_Rome * 1 + x_Milan * 0 + x_Turin * 1\ns.t.\n    x_Rome <= 1\n    x_Milan <= 1\n    x_Turin <= 1\n    y * 0 <= 1\ndefine\n    x_Rome, x_Milan, x_Turin, y as Boolean"
+        );
+
+        let input = "
+        min 1
+        s.t.
+            x <= a
+        where
+            let a = 1 in [1, 2]
+        define
+            x as Boolean
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&vec![], &IndexMap::new())
+            .expect_err("Failed to detect membership in an array");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_trailing_commas_and_empty_collections() {
+        let input = "
+        max sum(i in A){ x_i } + sum(s in S){ y_s } + sum(e in None){ e }
+        s.t.
+            x_i <= len(None) + len(Empty) + 1 for i in A
+            y_s <= 1 for s in S
+        where
+            let A = [1, 2, 3,]
+            let M = [
+                [1, 2,],
+                [3, 4,],
+            ]
+            let S = {\"a\", \"b\",}
+            let None = []
+            let Empty = {}
+        define
+            x_i as Boolean for i in A
+            y_s as Boolean for s in S
+        ";
+        let pre_model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        let source = pre_model.to_string();
+        assert!(source.contains("A = [1, 2, 3]"));
+        assert!(source.contains("S = {\"a\", \"b\"}"));
+        assert!(source.contains("None = []"));
+        assert!(source.contains("Empty = {}"));
+        let model = pre_model
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem");
+        assert_eq!(
+            model.to_string(),
+            "max x_1 + x_2 + x_3 + y_a + y_b + 0\ns.t.\n    x_1 <= 0 + 0 + 1\n    x_2 <= 0 + 0 + 1\n    x_3 <= 0 + 0 + 1\n    y_a <= 1\n    y_b <= 1\ndefine\n    x_1, x_2, x_3, y_a, y_b as Boolean"
+        );
+        RoocParser::new("min 1\ns.t.\n    1 >= 1\nwhere\n    let A = [,]".to_string())
+            .parse()
+            .expect_err("A lone comma is not an array");
+    }
+
+    fn type_check_source(source: &str) -> Result<(), TransformError> {
+        RoocParser::new(source.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .create_type_checker(&vec![], &IndexMap::new())
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_iteration_variable_types() {
+        let constants = "
+        where
+            let G = Graph { a -> [b:2], b -> [a:3] }
+            let T = [(1, \"a\"), (2, \"b\")]
+            let Mixed = [(1, \"a\"), (2, 3)]
+            let Rows = {[1, 2], [3, 4]}
+        define
+            x as Real
+        ";
+        //the endpoints of an edge are strings, its weight a number
+        let err = type_check_source(&format!(
+            "min sum((u, v) in edges(G)){{ u * x }}\ns.t.\n    x >= 1{constants}"
+        ))
+        .expect_err("Failed to detect arithmetic on an edge endpoint");
+        assert!(matches!(
+            err.base_error(),
+            TransformError::BinOpError {
+                lhs: PrimitiveKind::String,
+                ..
+            }
+        ));
+        type_check_source(&format!(
+            "min sum((u, v, w) in edges(G)){{ w * x }}\ns.t.\n    x >= 1{constants}"
+        ))
+        .expect("Failed to type check the weight of an edge");
+        let err = type_check_source(&format!(
+            "min 1\ns.t.\n    x >= b + 1 for (a, b) in T{constants}"
+        ))
+        .expect_err("Failed to detect arithmetic on a tuple string");
+        assert!(matches!(
+            err.base_error(),
+            TransformError::BinOpError {
+                lhs: PrimitiveKind::String,
+                ..
+            }
+        ));
+        let err = type_check_source(&format!(
+            "min 1\ns.t.\n    x >= 1 for (u, v, w, z) in edges(G){constants}"
+        ))
+        .expect_err("Failed to detect destructuring into too many variables");
+        assert!(matches!(
+            err.base_error(),
+            TransformError::SpreadError { .. }
+        ));
+        //elements of unknown type are checked at runtime
+        type_check_source(&format!(
+            "min 1\ns.t.\n    x >= a for (a, b) in Mixed{constants}"
+        ))
+        .expect("Failed to type check destructuring of mixed tuples");
+        type_check_source(&format!(
+            "min sum((a, b) in Rows){{ a * x }}\ns.t.\n    x >= 1{constants}"
+        ))
+        .expect("Failed to type check destructuring of a set of arrays");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_tuple_field_access() {
+        let input = "
+        min sum(t in T){ t.0 * x_{t.1} }
+        s.t.
+            x_{t.1} >= M[0][1].0 for t in T
+        where
+            let T = [(1, \"a\"), (2, \"b\")]
+            let M = [[(1, 2), (3, 4)]]
+        define
+            x_{t.1} as NonNegativeReal for t in T
+        ";
+        let pre_model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        assert!(pre_model.to_string().contains("M[0][1].0"));
+        pre_model
+            .create_type_checker(&vec![], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = pre_model
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem");
+        assert_eq!(
+            model.to_string(),
+            "min 1 * x_a + 2 * x_b\ns.t.\n    x_a >= 3\n    x_b >= 3\ndefine\n    x_a, x_b as NonNegativeReal"
+        );
+
+        let out_of_bounds = "
+        min 1
+        s.t.
+            x >= t.2 for t in T
+        where
+            let T = [(1, \"a\"), (2, \"b\")]
+        define
+            x as Real
+        ";
+        let err =
+            type_check_source(out_of_bounds).expect_err("Failed to detect out of bounds field");
+        assert!(matches!(err.base_error(), TransformError::OutOfBounds(_)));
+        let err = RoocParser::new(out_of_bounds.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect out of bounds field");
+        assert!(matches!(err.base_error(), TransformError::OutOfBounds(_)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_tuple_indexing() {
+        let input = "
+        min sum(t in T){ t[0] * x_{t[1]} }
+        s.t.
+            x_{t[1]} >= M[0][1][0] + P[i] for t in T, i in 0..2
+        where
+            let T = [(1, \"a\"), (2, \"b\")]
+            let M = [[(1, 2), (3, 4)]]
+            let P = (5, 6)
+        define
+            x_{t[1]} as NonNegativeReal for t in T
+        ";
+        let pre_model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        pre_model
+            .create_type_checker(&vec![], &IndexMap::new())
+            .expect("Failed to type check problem");
+        let model = pre_model
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem");
+        assert_eq!(
+            model.to_string(),
+            "min 1 * x_a + 2 * x_b\ns.t.\n    x_a >= 3 + 5\n    x_a >= 3 + 6\n    x_b >= 3 + 5\n    x_b >= 3 + 6\ndefine\n    x_a, x_b as NonNegativeReal"
+        );
+
+        let source = |exp: &str| {
+            format!("min 1\ns.t.\n    x >= {exp} for t in T, i in 0..2\nwhere\n    let T = [(1, \"a\"), (2, \"b\")]\ndefine\n    x as Real")
+        };
+        let err =
+            type_check_source(&source("t[2]")).expect_err("Failed to detect out of bounds index");
+        assert!(matches!(err.base_error(), TransformError::OutOfBounds(_)));
+        let err = RoocParser::new(source("t[2]"))
+            .parse()
+            .expect("Failed to parse problem")
+            .transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect out of bounds index");
+        assert!(matches!(err.base_error(), TransformError::OutOfBounds(_)));
+        let err = type_check_source(&source("t[i]"))
+            .expect_err("Failed to reject a variable index of a tuple with different types");
+        assert!(
+            err.to_string()
+                .contains("can only be indexed by a constant"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_iterated_constraint_rhs() {
+        let input = "
+        max sum(i in 0..3){ x_i }
+        s.t.
+            x_i <= cap[i] for i in 0..3
+            sum(j in 0..2){ y_i_j } <= sum(j in 0..2){ C[i][j] } for i in 0..2
+            y_i_j >= C[i][j] - cap[j] for i in 0..2, j in 0..2
+        where
+            let cap = [4, 5.5, 6]
+            let C = [[1, 2], [3, 4]]
+        define
+            x_i as NonNegativeReal for i in 0..3
+            y_i_j as NonNegativeReal for i in 0..2, j in 0..2
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let rhs = model
+            .constraints()
+            .iter()
+            .map(|c| c.clone().into_parts().2.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rhs,
+            vec!["4", "5.5", "6", "1 + 2", "3 + 4", "1 - 4", "2 - 5.5", "3 - 4", "4 - 5.5"]
+        );
+        let linear = Linearizer::linearize(model).expect("Failed to linearize problem");
+        let rhs = linear
+            .constraints()
+            .iter()
+            .map(|c| c.rhs())
+            .collect::<Vec<_>>();
+        assert_eq!(rhs, vec![4.0, 5.5, 6.0, 3.0, 7.0, -3.0, -3.5, -1.0, -1.5]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_all_pairs_shortest_paths() {
+        let graph =
+            parse_graph_constant("Graph { A -> [B:1, C:4], B -> [C:2, D:6], C -> [D:3], D }");
+        let inf = f64::INFINITY;
+        assert_eq!(
+            graph.shortest_paths(true).unwrap(),
+            vec![
+                vec![0.0, 1.0, 3.0, 6.0],
+                vec![inf, 0.0, 2.0, 5.0],
+                vec![inf, inf, 0.0, 3.0],
+                vec![inf, inf, inf, 0.0],
+            ]
+        );
+        let input = "
+        min sum(i in 0..4) { x_i }
+        s.t.
+            x_i >= D[0][i] for i in 0..4
+            y >= len(D[3]) + D[1][3]
+        where
+            let G = Graph { A -> [B:1, C:4], B -> [C:2, D:6], C -> [D:3], D }
+            let D = shortest_paths(G, true)
+        define
+            x_i as NonNegativeReal for i in 0..4
+            y as NonNegativeReal
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem");
+        assert!(model
+            .to_string()
+            .contains("x_0 >= 0\n    x_1 >= 1\n    x_2 >= 3\n    x_3 >= 6\n    y >= 4 + 5"));
+
+        let negative = parse_graph_constant("Graph { A -> [B:1], B -> [A:-2, C:1], C }");
+        assert!(negative.shortest_paths(true).is_err());
+        let distances = negative.shortest_paths(false).unwrap();
+        assert_eq!(distances[0][2], f64::NEG_INFINITY);
+        assert_eq!(distances[2], vec![inf, inf, 0.0]);
+        let input = "
+        min 1
+        s.t.
+            x >= D[0][0]
+        where
+            let G = Graph { A -> [B:1], B -> [A:-2] }
+            let D = shortest_paths(G, true)
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect the negative cycle");
+        assert!(err.base_error().to_string().contains("negative cycle"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_max_flow() {
+        let network = "Graph {
+                s -> [a:16, b:13],
+                a -> [c:12],
+                b -> [a:4, d:14],
+                c -> [b:9, t:20],
+                d -> [c:7, t:4],
+                t
+            }";
+        let graph = parse_graph_constant(network);
+        let (value, edges) = graph.max_flow("s", "t").unwrap();
+        assert_eq!(value, 23.0);
+        //the flow respects the capacities and is conserved in every node but the source and sink
+        let capacities = graph.clone().to_edges();
+        for (edge, capacity) in edges.iter().zip(capacities.iter()) {
+            let flow = edge.weight.unwrap();
+            assert!(flow >= 0.0 && flow <= capacity.weight.unwrap());
+        }
+        for node in ["a", "b", "c", "d"] {
+            let incoming: f64 = edges
+                .iter()
+                .filter(|e| e.to == node)
+                .map(|e| e.weight.unwrap())
+                .sum();
+            let outgoing: f64 = edges
+                .iter()
+                .filter(|e| e.from == node)
+                .map(|e| e.weight.unwrap())
+                .sum();
+            assert_eq!(incoming, outgoing);
+        }
+        assert!(graph.max_flow("s", "s").is_err());
+        assert!(graph.max_flow("s", "z").is_err());
+
+        let input = format!(
+            "
+        max f
+        s.t.
+            f <= max_flow(G, \"s\", \"t\")
+            x_{{u}}_{{v}} <= w for (u, v, w) in max_flow_edges(G, \"s\", \"t\")
+        where
+            let G = {network}
+        define
+            f as NonNegativeReal
+            x_{{u}}_{{v}} as NonNegativeReal for (u, v, w) in max_flow_edges(G, \"s\", \"t\")
+        "
+        );
+        let model = RoocParser::new(input)
+            .parse()
+            .expect("Failed to parse problem")
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem");
+        let model = model.to_string();
+        assert!(model.contains("f <= 23"));
+        assert!(model.contains("x_s_a <= "));
+        assert!(model.contains("x_d_t <= 4"));
+
+        let input = "
+        max f
+        s.t.
+            f <= max_flow(G, \"a\", \"b\")
+        where
+            let G = Graph { a -> [b:-1], b }
+        define
+            f as NonNegativeReal
+        ";
+        let err = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect the negative capacity");
+        assert!(err.base_error().to_string().contains("negative"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_number_format_options() {
+        let options = FormatOptions::new().with_significant_digits(3);
+        assert_eq!(options.format_number(0.25000000001), "0.25");
+        assert_eq!(options.format_number(1.0 / 3.0), "0.333");
+        assert_eq!(options.format_number(-0.000123456), "-0.000123");
+        assert_eq!(options.format_number(12345.678), "12300");
+        assert_eq!(
+            FormatOptions::new().format_number(0.1 + 0.2),
+            "0.30000000000000004"
+        );
+
+        let input = "
+        min 0.30000000000000004 * x_1 + 2 * y
+        s.t.
+            x_1 + 0.6666666666666666 * y <= 10.5
+            y >= 0
+        where
+            let label = \"0.123456\"
+        define
+            x_1, y as NonNegativeReal
+        ";
+        let pre_model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        let model = pre_model
+            .clone()
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem");
+        let formatted = model.to_string_with_options(&options);
+        assert!(formatted.contains("min 0.3 * x_1 + 2 * y"));
+        assert!(formatted.contains("x_1 + 0.667 * y <= 10.5"));
+        //the values of the model are not changed
+        assert!(model.to_string().contains("0.6666666666666666"));
+
+        let latex = pre_model.to_latex_with_options(&options);
+        assert!(latex.contains("0.667"));
+        assert!(!latex.contains("0.6666666666666666"));
+        assert!(latex.contains("0.123456"));
+        assert_eq!(
+            pre_model.to_latex_with_options(&FormatOptions::new()),
+            pre_model.to_latex_with_options(&FormatOptions::default())
+        );
+
+        let solution = LpSolution::new(
+            vec![Assignment {
+                name: "x".to_string(),
+                value: 2.0 / 3.0,
+            }],
+            1.0 / 3.0,
+        );
+        assert_eq!(
+            solution.to_string_with_options(&FormatOptions::new().with_significant_digits(2)),
+            "Optimal value: 0.33\n\nVariables:\nx = 0.67"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_array_access_as_function_argument() {
+        let input = "
+        min sum(i in range(0, Sizes[1], false)) { x_i }
+        s.t.
+            x_0 >= len(M[1])
+            x_1 >= len(neigh_edges_of(Names[0], Gs[0]))
+            x_2 >= max_flow(Gs[0], Names[0], Names[1]) + len(nodes(Gs[Pair.1]))
+            x_i <= D[0][1] for (e, i) in enumerate(edges(Gs[0]))
+        where
+            let Sizes = [1, 3]
+            let M = [[1, 2], [1, 2]]
+            let Names = [\"a\", \"b\"]
+            let Gs = [Graph { a -> [b:2, c], b, c -> [b:5] }]
+            let Pair = (1, 0)
+            let D = shortest_paths(Gs[0], false)
+        define
+            x_i as NonNegativeReal for i in 0..3
+        ";
+        type_check_source(input).expect("Failed to type check problem");
+        let model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem");
+        assert_eq!(
+            model.to_string(),
+            "min x_0 + x_1 + x_2\ns.t.\n    x_0 >= 2\n    x_1 >= 2\n    x_2 >= 3 + 3\n    x_0 <= 2\n    x_1 <= 2\n    x_2 <= 2\ndefine\n    x_0, x_1, x_2 as NonNegativeReal"
+        );
+
+        let out_of_bounds = "
+        min 1
+        s.t.
+            x >= len(M[2])
+        where
+            let M = [[1, 2], [1, 2]]
+        define
+            x as Real
+        ";
+        let err = RoocParser::new(out_of_bounds.to_string())
+            .parse()
+            .expect("Failed to parse problem")
+            .transform(vec![], &IndexMap::new())
+            .expect_err("Failed to detect out of bounds argument");
+        assert!(matches!(err.base_error(), TransformError::OutOfBounds(_)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_latex_fractions() {
+        let input = "
+        min 0.3333333333333333 * x + 0.5 * y
+        s.t.
+            0.00001 * x - 1.6666666666666667 * y <= 2.5
+            x + y >= 0.3334
+        define
+            x, y as NonNegativeReal
+        ";
+        let pre_model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        let latex =
+            pre_model.to_latex_with_options(&FormatOptions::new().with_latex_fractions(1e-9));
+        assert!(latex.contains("\\frac{1}{3}"));
+        assert!(latex.contains("\\frac{1}{2}"));
+        assert!(latex.contains("\\frac{5}{3}"));
+        assert!(latex.contains("\\frac{5}{2}"));
+        //not close enough to a simple fraction, or too small to be approximated
+        assert!(latex.contains("0.00001"));
+        assert!(latex.contains("\\frac{1667}{5000}"));
+        //a larger tolerance finds simpler fractions, falling back to the precision for decimals
+        let options = FormatOptions::new()
+            .with_latex_fractions(1e-3)
+            .with_significant_digits(2);
+        let latex = pre_model.to_latex_with_options(&options);
+        assert!(!latex.contains("1667"));
+        assert!(latex.contains("0.00001"));
+        //the text representation is never written with fractions
+        let text = pre_model
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem")
+            .to_string_with_options(&options);
+        assert!(!text.contains("frac"));
+        assert!(text.contains("0.33"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_greek_and_numbered_variable_names() {
+        let input = "
+        max alpha + x1 + sum(i in 0..2) { beta_i } + gamma2
+        s.t.
+            alpha + x1 <= Delta
+            beta_i <= 2 for i in 0..2
+            gamma2 + x_Delta <= 1
+        where
+            let Delta = 4
+        define
+            alpha, x1, gamma2 as NonNegativeReal
+            beta_i as NonNegativeReal for i in 0..2
+            x_Delta as NonNegativeReal
+        ";
+        let pre_model = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse problem");
+        let latex = pre_model.to_latex_with_options(&FormatOptions::new());
+        assert!(latex.contains("\\alpha + x_{1}"));
+        assert!(latex.contains("\\beta_{i}"));
+        assert!(latex.contains("\\gamma_{2}"));
+        assert!(latex.contains("x_{{\\Delta}}"));
+        assert!(latex.contains("\\Delta &= 4"));
+        let model = pre_model
+            .transform(vec![], &IndexMap::new())
+            .expect("Failed to transform problem");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize problem");
+        let mut variables = linear.variables().clone();
+        variables.sort();
+        assert_eq!(
+            variables,
+            vec!["alpha", "beta_0", "beta_1", "gamma2", "x1", "x_4"]
+        );
+        let solution = rooc::auto_solver(&linear).expect("Failed to solve problem");
+        assert!((solution.value() - 9.0).abs() < 1e-6);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_graph_edge_weights_with_constant_expressions() {
+        let input = "
+        min sum(u in nodes(G)) { x_u }
+        s.t.
+            sum((u, v, c) in edges(G)) { c * x_v } >= 20
+        where
+            let unit = 5
+            let G = Graph {
+                A -> [B: 2 * unit, A: -unit],
+                B
+            }
+        define
+            x_u as NonNegativeReal for u in nodes(G)
+        ";
+        let parser = RoocParser::new(input.to_string());
+        parser
+            .type_check(&vec![], &IndexMap::new())
+            .expect("Failed to type check graph weights");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to evaluate graph weights");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize");
+        assert_eq!(
+            linear.variables(),
+            &vec!["x_A".to_string(), "x_B".to_string()]
+        );
+        assert_eq!(linear.constraints()[0].coefficients(), &vec![-5.0, 10.0]);
+
+        for (weight, error) in [
+            ("\"a\"", "expected \"Number\", got \"String\""),
+            ("y", "Edge weights must be constant expressions but got: y"),
+        ] {
+            let input = format!(
+                "min 1\ns.t.\n    y >= 0\nwhere\n    let G = Graph {{ A -> [B: {weight}] }}\ndefine\n    y as Real"
+            );
+            let err = RoocParser::new(input)
+                .parse_and_transform(vec![], &IndexMap::new())
+                .expect_err("Failed to reject a wrong edge weight");
+            assert!(err.contains(error), "{}", err);
+            assert!(err.contains(&format!("at 5:30 \"{weight}\"")), "{}", err);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_indexed_variable_family_declaration() {
+        let input = "
+        min sum(i in 0..n) { x[i] }
+        s.t.
+            x[2] >= 4
+            x[i] >= 1 for i in 0..n
+        where
+            let n = 3
+        define
+            x[i] as NonNegativeReal for i in 0..n
+        ";
+        let parser = RoocParser::new(input.to_string());
+        parser
+            .type_check(&vec![], &IndexMap::new())
+            .expect("Failed to type check an indexed family");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to declare an indexed family");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize");
+        assert_eq!(
+            linear.variables(),
+            &vec!["x_0".to_string(), "x_1".to_string(), "x_2".to_string()]
+        );
+        assert_eq!(linear.constraints()[0].coefficients(), &vec![0.0, 0.0, 1.0]);
+        assert_eq!(linear.constraints().len(), 4);
+
+        let input = "
+        min x[5]
+        s.t.
+            x[0] >= 1
+        define
+            x[i] as NonNegativeReal for i in 0..3
+        ";
+        RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject an index out of the family");
+        let input = "
+        min 1
+        s.t.
+            x.0 >= 1
+        define
+            x[i] as NonNegativeReal for i in 0..3
+        ";
+        RoocParser::new(input.to_string())
+            .type_check(&vec![], &IndexMap::new())
+            .expect_err("Failed to reject a field access of a family");
+        //y is a single variable, not a family that can be accessed by index
+        let input = "
+        min y + y[1]
+        s.t.
+            y[1] >= 1
+        define
+            y as NonNegativeReal
+        ";
+        let err = RoocParser::new(input.to_string())
+            .with_default_domain(DefaultDomain::Real)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject an access to a variable that is not a family");
+        assert!(
+            err.contains("Variable \"y\" is a single domain variable"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_available_functions() {
+        let functions = rooc::available_functions();
+        let edges = functions
+            .iter()
+            .find(|f| f.name == "edges")
+            .expect("Failed to find edges");
+        assert_eq!(edges.params, vec![PrimitiveKind::Graph]);
+        assert_eq!(
+            edges.returns,
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::GraphEdge))
+        );
+        for name in ["nodes", "neigh_edges", "E", "len", "range", "zip"] {
+            assert!(functions.iter().any(|f| f.name == name), "{}", name);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_division_by_zero() {
+        for exp in ["1/0", "1/(n-n)"] {
+            let input =
+                format!("min x\ns.t.\n    x >= {exp}\nwhere\n    let n = 2\ndefine\n    x as Real");
+            let err = RoocParser::new(input)
+                .parse_and_transform(vec![], &IndexMap::new())
+                .expect_err("Failed to reject a division by zero");
+            assert!(err.contains("division by zero"), "{}", err);
+            assert!(err.contains("at 3:11 \"/\""), "{}", err);
+        }
+        let input = "min x\ns.t.\n    x >= k\nwhere\n    let k = 1/0\ndefine\n    x as Real";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject a division by zero in a constant");
+        assert!(err.contains("division by zero"), "{}", err);
+        //a division by a variable is not folded
+        let input = "min x\ns.t.\n    1 / x >= 1\ndefine\n    x as Real";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to keep a division by a variable");
+        assert_eq!(model.constraints().len(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_nested_block_functions_scopes() {
+        let input = "
+        min sum(i in 0..2){ max(j in 0..2){ A[i][j] * a_i_j } }
+        s.t.
+            a_i_j >= 1 for i in 0..2, j in 0..2
+        where
+            let A = [[1, 5], [7, 3]]
+        define
+            a_i_j as Real for i in 0..2, j in 0..2
+        ";
+        let parser = RoocParser::new(input.to_string());
+        parser
+            .type_check(&vec![], &IndexMap::new())
+            .expect("Failed to type check nested blocks");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to transform nested blocks");
+        assert!(
+            model
+                .to_string()
+                .starts_with("min max{ 1 * a_0_0, 5 * a_0_1 } + max{ 7 * a_1_0, 3 * a_1_1 }\n"),
+            "{}",
+            model
+        );
+
+        //the variable of the inner block is not visible once the block ends
+        let leaked = "
+        min x
+        s.t.
+            x >= sum(i in 0..2){ max(j in 0..2){ A[i][j] } + j }
+        where
+            let A = [[1, 5], [7, 3]]
+        define
+            x as Real
+        ";
+        let parser = RoocParser::new(leaked.to_string());
+        let err = parser
+            .type_check(&vec![], &IndexMap::new())
+            .expect_err("Failed to reject a leaked block variable");
+        assert!(
+            err.to_string().contains("\"j\" was not declared"),
+            "{}",
+            err
+        );
+        parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject a leaked block variable");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_constant_arrays_indexed_by_iteration_variables() {
+        let input = "max sum(i in 0..3){ c[i] * x_i }
+s.t.
+    x_0 <= 1
+where
+    let c = [1, 2, 3]
+define
+    x_i as Real for i in 0..3";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to index a 1D array");
+        assert_eq!(
+            model.objective().to_string(),
+            "max 1 * x_0 + 2 * x_1 + 3 * x_2"
+        );
+        let input = "max sum(i in 0..2, j in 0..2){ c[i][j] * x_i_j }
+s.t.
+    x_0_0 <= 1
+where
+    let c = [[1, 2], [3, 4]]
+define
+    x_i_j as Real for i in 0..2, j in 0..2";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to index a 2D array");
+        assert_eq!(
+            model.objective().to_string(),
+            "max 1 * x_0_0 + 2 * x_0_1 + 3 * x_1_0 + 4 * x_1_1"
+        );
+        let input = "max sum(i in 0..4){ c[i] * x_i }
+s.t.
+    x_0 <= 1
+where
+    let c = [1, 2, 3]
+define
+    x_i as Real for i in 0..4";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject an out of bounds index");
+        assert!(err.contains("cannot access index 3"), "{}", err);
+        assert!(err.contains("at 1:21 \"c[i]\""), "{}", err);
+        let input = "max sum(i in 0..2, j in 0..3){ c[i][j] * x_i_j }
+s.t.
+    x_0_0 <= 1
+where
+    let c = [[1, 2], [3, 4]]
+define
+    x_i_j as Real for i in 0..2, j in 0..3";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject an out of bounds inner index");
+        assert!(err.contains("cannot access index 2"), "{}", err);
+        assert!(err.contains("at 1:32 \"c[i][j]\""), "{}", err);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_find_occurrences() {
+        let input = "min y
+s.t.
+    x + y <= 1
+    2x >= 0
+define
+    x, y as Real";
+        let parsed = RoocParser::new(input.to_string()).parse().unwrap();
+        let spans = parsed
+            .find_occurrences("x")
+            .iter()
+            .map(|span| {
+                (
+                    span.start_line,
+                    span.start_column,
+                    span.span_text(input).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![(3, 5, "x"), (4, 6, "x"), (6, 5, "x")]);
+        let input = "max sum(i in 0..2){ c[i] * x_i }
+s.t.
+    x_i <= c[i] for i in 0..2
+where
+    let c = [1, 2]
+define
+    x_i as Real for i in 0..2";
+        let parsed = RoocParser::new(input.to_string()).parse().unwrap();
+        let texts = |name: &str| {
+            parsed
+                .find_occurrences(name)
+                .iter()
+                .map(|span| span.span_text(input).unwrap().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(texts("x"), vec!["x", "x", "x"]);
+        assert_eq!(texts("c"), vec!["c", "c", "c"]);
+        assert_eq!(texts("i").len(), 8);
+        assert!(texts("z").is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_nested_sums_in_objective() {
+        let input = "min sum(i in I){ sum(j in J){ c[i][j] * x[i][j] }}
+s.t.
+    x[0][0] >= 1
+where
+    let c = [[1, 2], [3, 4]]
+    let I = [0, 1]
+    let J = [0, 1]
+define
+    x[i][j] as Real for i in I, j in J";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to expand nested sums");
+        assert_eq!(
+            model.objective().to_string(),
+            "min 1 * x_0_0 + 2 * x_0_1 + 3 * x_1_0 + 4 * x_1_1"
+        );
+        //the type checker descends into the inner body
+        let input = "min sum(i in 0..2){ sum(j in 0..2){ \"a\" * x_i_j }}
+s.t.
+    x_0_0 >= 1
+define
+    x_i_j as Real for i in 0..2, j in 0..2";
+        let err = RoocParser::new(input.to_string())
+            .type_check(&vec![], &IndexMap::new())
+            .expect_err("Failed to type check the inner sum");
+        assert!(err.contains("at 1:41 \"*\""), "{}", err);
+        //an inner iteration variable cannot shadow an outer one
+        let input = "min sum(i in 0..2){ sum(i in 0..2){ x_i }}
+s.t.
+    x_0 >= 1
+define
+    x_i as Real for i in 0..2";
+        let err = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject a shadowed iteration variable");
+        assert!(err.contains("Variable i was already declared"), "{}", err);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_referenced_variables() {
+        let input = "max y + x_0
+s.t.
+    x_i + y <= i for i in 0..3
+    |z| <= 1
+define
+    x_i as Real for i in 0..4
+    y, z, w as Real";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        assert_eq!(
+            model.referenced_variables(),
+            vec!["x_0", "x_1", "x_2", "y", "z"]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_duplicate_terms_are_combined() {
+        let input = "min x
+s.t.
+    x + 2y + x <= 5
+    x - x + 3y - y >= 1
+define
+    x, y as Real";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse and transform problem");
+        let linear = Linearizer::linearize(model).expect("Failed to linearize");
+        assert_eq!(linear.variables(), &vec!["x".to_string(), "y".to_string()]);
+        let constraint = &linear.constraints()[0];
+        assert_eq!(constraint.coefficients(), &vec![2.0, 2.0]);
+        assert_eq!(constraint.rhs(), 5.0);
+        assert_eq!(linear.constraints()[1].coefficients(), &vec![0.0, 2.0]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_problem_name() {
+        let input = "problem \"Transport & Co\"
+min x
+s.t.
+    x >= 1
+define
+    x as Real";
+        let parsed = RoocParser::new(input.to_string())
+            .parse()
+            .expect("Failed to parse a named problem");
+        assert_eq!(parsed.name(), Some("Transport & Co"));
+        assert!(parsed
+            .to_string()
+            .starts_with("problem \"Transport & Co\"\nmin x"));
+        assert!(parsed
+            .to_latex_with_options(&FormatOptions::new())
+            .starts_with("\\textbf{Transport \\& Co}\\\\\n"));
+        let reparsed = RoocParser::new(parsed.to_string())
+            .parse()
+            .expect("Failed to parse the formatted problem");
+        assert_eq!(reparsed.name(), Some("Transport & Co"));
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to transform a named problem");
+        assert_eq!(model.name(), Some("Transport & Co"));
+        assert!(model
+            .to_string()
+            .starts_with("problem \"Transport & Co\"\n"));
+
+        let input = "min x\ns.t.\n    x >= 1\ndefine\n    x as Real";
+        let parsed = RoocParser::new(input.to_string()).parse().unwrap();
+        assert_eq!(parsed.name(), None);
+        assert!(parsed.to_string().starts_with("min x"));
+        assert!(!parsed.to_json().contains("\"name\""));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_tuple_destructuring_arity() {
+        let transform = |source: &str| {
+            RoocParser::new(source.to_string()).parse_and_transform(vec![], &IndexMap::new())
+        };
+        //as many values as variables, the constant sum is folded to 1 * 2 + 3 * 4
+        let model =
+            transform("min 1\ns.t.\n    sum((i, j) in [[1, 2], [3, 4]]) { i * j } <= 100").unwrap();
+        assert_eq!(model.constraints()[0].to_string(), "14 <= 100");
+        //extra values are ignored
+        let model =
+            transform("min 1\ns.t.\n    sum((i, j) in [[1, 2, 3], [3, 4, 5]]) { i * j } <= 100")
+                .unwrap();
+        assert_eq!(model.constraints()[0].to_string(), "14 <= 100");
+        //too few values
+        let err =
+            transform("min 1\ns.t.\n    sum((i, j, k) in [[1, 2], [3, 4]]) { i * j * k } <= 100")
+                .unwrap_err();
+        assert!(err.contains(
+            "Cannot destructure an element of 2 values into the 3 variables (i, j, k), each element must have at least 3 values"
+        ), "{}", err);
+        assert!(
+            err.contains("at 3:9 \"(i, j, k) in [[1, 2], [3, 4]]\""),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_chained_comparisons_are_rejected() {
+        for (chain, relation) in [
+            ("1 <= x <= 3", "<= 3"),
+            ("1 < x < 3", "< 3"),
+            ("x >= 1 >= y", ">= y"),
+            ("1 <= x + y >= 3 <= 4", ">= 3"),
+        ] {
+            let input = format!("min x\ns.t.\n    {}\ndefine\n    x, y as Real", chain);
+            let err = RoocParser::new(input.clone())
+                .parse()
+                .expect_err("Failed to reject a chained comparison");
+            match err.kind() {
+                rooc::ParseError::SemanticError(message) => {
+                    assert!(message.starts_with("Chained comparisons"), "{}", message)
+                }
+                kind => panic!("Expected a semantic error, got {}", kind),
+            }
+            let expected = format!(
+                "Error at line 3:{} ({}",
+                5 + chain.find(relation).unwrap(),
+                relation
+            );
+            let message = err.to_string_from_source(&input);
+            assert!(message.starts_with(&expected), "{}", message);
+        }
+        //the same bounds written as separate constraints
+        let model = RoocParser::new(
+            "min x\ns.t.\n    1 <= x\n    x <= 3\ndefine\n    x as Real".to_string(),
+        )
+        .parse_and_transform(vec![], &IndexMap::new())
+        .unwrap();
+        assert_eq!(model.constraints().len(), 2);
+        assert_eq!(model.constraints()[0].to_string(), "1 <= x");
+        assert_eq!(model.constraints()[1].to_string(), "x <= 3");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_free_domain() {
+        let input = "
+        min x - y
+        s.t.
+            x + y >= -5
+        define
+            x, y as Free
+        ";
+        let model = RoocParser::new(input.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        assert_eq!(
+            model.to_string(),
+            "min x - y\ns.t.\n    x + y >= -5\ndefine\n    x, y as Real"
+        );
+        let err = RoocParser::new("min x\ns.t.\n    x >= 1\ndefine\n    x as free".to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject an unknown domain");
+        assert!(err.contains("Free"));
+    }
 }
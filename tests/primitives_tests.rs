@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod primitive_tests {
     use indexmap::IndexMap;
-    use rooc::RoocParser;
+    use rooc::model_transformer::TransformError;
+    use rooc::{
+        Graph, GraphEdge, GraphNode, IterableKind, Primitive, PrimitiveKind, RoocParser, Tuple,
+    };
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test;
 
@@ -30,4 +33,491 @@ mod primitive_tests {
             .parse_and_transform(vec![], &IndexMap::new())
             .expect("Failed to parse");
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_primitive_conversions() {
+        assert_eq!(f64::try_from(Primitive::Number(1.5)).unwrap(), 1.5);
+        assert_eq!(f64::try_from(Primitive::Integer(-2)).unwrap(), -2.0);
+        assert_eq!(i64::try_from(Primitive::Integer(-2)).unwrap(), -2);
+        assert_eq!(i64::try_from(Primitive::PositiveInteger(3)).unwrap(), 3);
+        assert!(bool::try_from(Primitive::Boolean(true)).unwrap());
+        assert_eq!(
+            String::try_from(Primitive::String("rooc".to_string())).unwrap(),
+            "rooc"
+        );
+        assert_eq!(
+            Vec::<f64>::try_from(Primitive::Iterable(IterableKind::Integers(vec![1, 2]))).unwrap(),
+            vec![1.0, 2.0]
+        );
+        assert_eq!(
+            Primitive::Iterable(IterableKind::Numbers(vec![0.5]))
+                .into_vec_f64()
+                .unwrap(),
+            vec![0.5]
+        );
+        assert_eq!(
+            Vec::<String>::try_from(Primitive::Iterable(IterableKind::Strings(vec![
+                "a".to_string()
+            ])))
+            .unwrap(),
+            vec!["a".to_string()]
+        );
+    }
+
+    fn assert_wrong_argument<T: std::fmt::Debug>(
+        result: Result<T, TransformError>,
+        expected: PrimitiveKind,
+        got: PrimitiveKind,
+    ) {
+        match result {
+            Err(TransformError::WrongArgument {
+                expected: e,
+                got: g,
+            }) => {
+                assert_eq!(e, expected);
+                assert_eq!(g, got);
+            }
+            r => panic!("Expected a WrongArgument error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_primitive_conversions_errors() {
+        assert_wrong_argument(
+            f64::try_from(Primitive::String("1".to_string())),
+            PrimitiveKind::Number,
+            PrimitiveKind::String,
+        );
+        assert_wrong_argument(
+            i64::try_from(Primitive::Number(1.5)),
+            PrimitiveKind::Integer,
+            PrimitiveKind::Number,
+        );
+        assert_wrong_argument(
+            bool::try_from(Primitive::Integer(1)),
+            PrimitiveKind::Boolean,
+            PrimitiveKind::Integer,
+        );
+        assert_wrong_argument(
+            String::try_from(Primitive::Boolean(false)),
+            PrimitiveKind::String,
+            PrimitiveKind::Boolean,
+        );
+        assert_wrong_argument(
+            Vec::<f64>::try_from(Primitive::Iterable(IterableKind::Strings(vec![]))),
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::Number)),
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::String)),
+        );
+        assert_wrong_argument(
+            Vec::<String>::try_from(Primitive::Number(1.0)),
+            PrimitiveKind::Iterable(Box::new(PrimitiveKind::String)),
+            PrimitiveKind::Number,
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_iterable_conversions() {
+        let edge = GraphEdge::new("A".to_string(), "B".to_string(), Some(2.0));
+        let node = GraphNode::new("A".to_string(), vec![edge.clone()]);
+        let graph = Graph::new(vec![node.clone()]);
+        let tuple = Tuple::new(vec![Primitive::Integer(1), Primitive::Boolean(true)]);
+        assert_eq!(
+            IterableKind::Numbers(vec![0.5]).into_numbers().unwrap(),
+            vec![0.5]
+        );
+        assert_eq!(
+            IterableKind::PositiveIntegers(vec![3])
+                .into_numbers()
+                .unwrap(),
+            vec![3.0]
+        );
+        assert_eq!(
+            IterableKind::Integers(vec![-1]).into_integers().unwrap(),
+            vec![-1]
+        );
+        assert_eq!(
+            IterableKind::PositiveIntegers(vec![2])
+                .into_positive_integers()
+                .unwrap(),
+            vec![2]
+        );
+        assert_eq!(
+            IterableKind::Strings(vec!["a".to_string()])
+                .into_strings()
+                .unwrap(),
+            vec!["a".to_string()]
+        );
+        assert_eq!(
+            IterableKind::Booleans(vec![true]).into_booleans().unwrap(),
+            vec![true]
+        );
+        assert_eq!(
+            IterableKind::Edges(vec![edge.clone()])
+                .into_edges()
+                .unwrap(),
+            vec![edge]
+        );
+        assert_eq!(
+            IterableKind::Nodes(vec![node.clone()])
+                .into_nodes()
+                .unwrap(),
+            vec![node]
+        );
+        assert_eq!(
+            IterableKind::Graphs(vec![graph.clone()])
+                .into_graphs()
+                .unwrap(),
+            vec![graph]
+        );
+        assert_eq!(
+            IterableKind::Tuples(vec![tuple.clone()])
+                .into_tuples()
+                .unwrap(),
+            vec![tuple]
+        );
+        let nested = IterableKind::Iterables(vec![IterableKind::Numbers(vec![1.0])]);
+        assert_eq!(
+            nested.into_iterables().unwrap(),
+            vec![IterableKind::Numbers(vec![1.0])]
+        );
+        assert!(IterableKind::Anys(vec![]).into_nodes().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_iterable_conversions_errors() {
+        let strings = || IterableKind::Strings(vec!["a".to_string()]);
+        let iterable_of = |kind: PrimitiveKind| PrimitiveKind::Iterable(Box::new(kind));
+        let got = iterable_of(PrimitiveKind::String);
+        assert_wrong_argument(
+            strings().into_numbers(),
+            iterable_of(PrimitiveKind::Number),
+            got.clone(),
+        );
+        assert_wrong_argument(
+            IterableKind::Numbers(vec![1.0]).into_integers(),
+            iterable_of(PrimitiveKind::Integer),
+            iterable_of(PrimitiveKind::Number),
+        );
+        assert_wrong_argument(
+            IterableKind::Integers(vec![1]).into_positive_integers(),
+            iterable_of(PrimitiveKind::PositiveInteger),
+            iterable_of(PrimitiveKind::Integer),
+        );
+        assert_wrong_argument(
+            IterableKind::Booleans(vec![true]).into_strings(),
+            iterable_of(PrimitiveKind::String),
+            iterable_of(PrimitiveKind::Boolean),
+        );
+        assert_wrong_argument(
+            strings().into_booleans(),
+            iterable_of(PrimitiveKind::Boolean),
+            got.clone(),
+        );
+        assert_wrong_argument(
+            strings().into_edges(),
+            iterable_of(PrimitiveKind::GraphEdge),
+            got.clone(),
+        );
+        assert_wrong_argument(
+            strings().into_nodes(),
+            iterable_of(PrimitiveKind::GraphNode),
+            got.clone(),
+        );
+        assert_wrong_argument(
+            strings().into_graphs(),
+            iterable_of(PrimitiveKind::Graph),
+            got.clone(),
+        );
+        assert_wrong_argument(
+            strings().into_tuples(),
+            iterable_of(PrimitiveKind::Tuple(vec![])),
+            got.clone(),
+        );
+        assert_wrong_argument(
+            strings().into_iterables(),
+            iterable_of(PrimitiveKind::Iterable(Box::new(PrimitiveKind::Any))),
+            got,
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_graph_complement() {
+        let source = "
+        max sum(v in nodes(G)) { x_v }
+        s.t.
+            x_u + x_v <= 1 for (u, v) in edges(complement(G))
+        where
+            let G = Graph {
+                A -> [B, C:2],
+                B -> [C],
+                C
+            }
+        define
+            x_v as Boolean for v in nodes(G)
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse");
+        assert_eq!(
+            model.to_string(),
+            "max x_A + x_B + x_C\ns.t.\n    x_B + x_A <= 1\n    x_C + x_A <= 1\n    x_C + x_B <= 1\ndefine\n    x_A, x_B, x_C as Boolean"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_iterable_display() {
+        let edge = GraphEdge::new("A".to_string(), "B".to_string(), Some(2.0));
+        let node = GraphNode::new("A".to_string(), vec![edge.clone()]);
+        let graph = Graph::new(vec![node.clone()]);
+        let tuple = Tuple::new(vec![Primitive::Integer(1), Primitive::Boolean(true)]);
+        let cases = vec![
+            (IterableKind::Numbers(vec![1.0, 2.5]), "[1, 2.5]"),
+            (IterableKind::Integers(vec![-1, 2]), "[-1, 2]"),
+            (IterableKind::PositiveIntegers(vec![1, 2, 3]), "[1, 2, 3]"),
+            (
+                IterableKind::Strings(vec!["a".to_string(), "b".to_string()]),
+                "[\"a\", \"b\"]",
+            ),
+            (IterableKind::Booleans(vec![true, false]), "[true, false]"),
+            (IterableKind::Edges(vec![edge]), "[B:2]"),
+            (IterableKind::Nodes(vec![node]), "[A -> [ B:2 ]]"),
+            (IterableKind::Tuples(vec![tuple]), "[(1, true)]"),
+            (
+                IterableKind::Anys(vec![Primitive::Number(1.5), Primitive::Boolean(false)]),
+                "[1.5, false]",
+            ),
+            (
+                IterableKind::Graphs(vec![graph]),
+                "[Graph {\n    A -> [ B:2 ]\n}]",
+            ),
+        ];
+        for (iterable, expected) in cases {
+            assert_eq!(iterable.to_string(), expected);
+            assert_eq!(Primitive::Iterable(iterable).to_string(), expected);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_graph_transitive_closure() {
+        let source = "
+        min 1
+        s.t.
+            x_u <= x_v for (u, v) in edges(transitive_closure(G))
+        where
+            let G = Graph {
+                A -> [B:3],
+                B -> [C],
+                C -> [D],
+                D
+            }
+        define
+            x_v as Boolean for v in nodes(G)
+        ";
+        let parser = RoocParser::new(source.to_string());
+        parser
+            .type_check(&vec![], &IndexMap::new())
+            .expect("Failed to type check");
+        let model = parser
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to parse");
+        assert_eq!(
+            model.to_string(),
+            "min 1\ns.t.\n    x_A <= x_B\n    x_A <= x_C\n    x_A <= x_D\n    x_B <= x_C\n    x_B <= x_D\n    x_C <= x_D\ndefine\n    x_A, x_B, x_C, x_D as Boolean"
+        );
+
+        let edge = |from: &str, to: &str| GraphEdge::new(from.to_string(), to.to_string(), None);
+        let cycle = Graph::new(vec![
+            GraphNode::new("A".to_string(), vec![edge("A", "B")]),
+            GraphNode::new("B".to_string(), vec![edge("B", "A")]),
+            GraphNode::new("C".to_string(), vec![]),
+        ]);
+        let closure = cycle.transitive_closure(false).unwrap();
+        assert_eq!(
+            closure.to_string(),
+            "Graph {\n    A -> [ B ],\n    B -> [ A ],\n    C\n}"
+        );
+        let closure = cycle.transitive_closure(true).unwrap();
+        assert_eq!(
+            closure.to_string(),
+            "Graph {\n    A -> [ A, B ],\n    B -> [ A, B ],\n    C\n}"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_primitive_collection_conversions() {
+        let iterable = |i: IterableKind| Primitive::Iterable(i);
+        assert_eq!(
+            Vec::<i64>::try_from(iterable(IterableKind::PositiveIntegers(vec![1, 2]))).unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            iterable(IterableKind::Booleans(vec![true, false]))
+                .into_vec_bool()
+                .unwrap(),
+            vec![true, false]
+        );
+        let matrix = iterable(IterableKind::Iterables(vec![
+            IterableKind::Integers(vec![1, 2]),
+            IterableKind::Numbers(vec![0.5, 1.5]),
+        ]));
+        assert_eq!(
+            Vec::<Vec<f64>>::try_from(matrix).unwrap(),
+            vec![vec![1.0, 2.0], vec![0.5, 1.5]]
+        );
+        let graph = Graph::new(vec![
+            GraphNode::new(
+                "A".to_string(),
+                vec![GraphEdge::new("A".to_string(), "B".to_string(), Some(2.0))],
+            ),
+            GraphNode::new(
+                "B".to_string(),
+                vec![GraphEdge::new("B".to_string(), "A".to_string(), None)],
+            ),
+        ]);
+        let expected = vec![
+            ("A".to_string(), "B".to_string(), 2.0),
+            ("B".to_string(), "A".to_string(), 1.0),
+        ];
+        assert_eq!(
+            Primitive::Graph(graph.clone()).into_vec_edges().unwrap(),
+            expected
+        );
+        let edges = iterable(IterableKind::Edges(graph.to_edges()));
+        assert_eq!(
+            Vec::<(String, String, f64)>::try_from(edges).unwrap(),
+            expected
+        );
+
+        let iterable_of = |kind: PrimitiveKind| PrimitiveKind::Iterable(Box::new(kind));
+        let strings = || iterable(IterableKind::Strings(vec!["a".to_string()]));
+        assert_wrong_argument(
+            strings().into_vec_i64(),
+            iterable_of(PrimitiveKind::Integer),
+            iterable_of(PrimitiveKind::String),
+        );
+        assert_wrong_argument(
+            Primitive::Number(1.0).into_vec_bool(),
+            iterable_of(PrimitiveKind::Boolean),
+            PrimitiveKind::Number,
+        );
+        assert_wrong_argument(
+            iterable(IterableKind::Iterables(vec![IterableKind::Strings(vec![])]))
+                .into_matrix_f64(),
+            iterable_of(PrimitiveKind::Number),
+            iterable_of(PrimitiveKind::String),
+        );
+        assert_wrong_argument(
+            Primitive::Number(1.0).into_matrix_f64(),
+            iterable_of(iterable_of(PrimitiveKind::Number)),
+            PrimitiveKind::Number,
+        );
+        assert_wrong_argument(
+            strings().into_vec_edges(),
+            iterable_of(PrimitiveKind::GraphEdge),
+            iterable_of(PrimitiveKind::String),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_graph_edges_order() {
+        let build = || {
+            let source = "
+            min 1
+            s.t.
+                1 <= 1
+            where
+                let G = Graph {
+                    A -> [D, B:2, C],
+                    B -> [A, D],
+                    C,
+                    D -> [C]
+                }
+            ";
+            let parsed = RoocParser::new(source.to_string())
+                .parse()
+                .expect("Failed to parse");
+            parsed
+                .constants()
+                .iter()
+                .find(|c| c.name.value() == "G")
+                .and_then(|c| c.value.as_static_primitive())
+                .map(|p| p.as_graph().unwrap().clone())
+                .unwrap()
+        };
+        let edges = |graph: &Graph| {
+            graph
+                .edges()
+                .iter()
+                .map(|e| format!("{}{}", e.from, e.to))
+                .collect::<Vec<_>>()
+        };
+        let graph = build();
+        assert_eq!(edges(&graph), vec!["AD", "AB", "AC", "BA", "BD", "DC"]);
+        for _ in 0..5 {
+            assert_eq!(edges(&build()), edges(&graph));
+        }
+        let targets = graph.nodes()[0]
+            .edges()
+            .iter()
+            .map(|e| e.to.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(targets, vec!["D", "B", "C"]);
+        assert_eq!(
+            graph
+                .clone()
+                .to_edges()
+                .iter()
+                .map(|e| format!("{}{}", e.from, e.to))
+                .collect::<Vec<_>>(),
+            edges(&graph)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_weighted_edges() {
+        let source = "
+        min sum((u, v, w) in weighted_edges(G)){ w * x_u_v }
+        s.t.
+            sum(e in weighted_edges(G)){ e[2] } <= t
+        where
+            let G = Graph {
+                A -> [B: 2, C],
+                B -> [C: 0.5],
+                C
+            }
+        define
+            x_u_v as NonNegativeReal for (u, v) in edges(G)
+            t as Real
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect("Failed to sum weighted edges");
+        assert_eq!(
+            model.objective().to_string(),
+            "min 2 * x_A_B + 1 * x_A_C + 0.5 * x_B_C"
+        );
+        assert_eq!(model.constraints()[0].to_string(), "3.5 <= t");
+        let source = "
+        min 1
+        s.t.
+            x >= 1 for (u, v, w, z) in weighted_edges(G)
+        where
+            let G = Graph { A -> [B] }
+        define
+            x as Real
+        ";
+        RoocParser::new(source.to_string())
+            .type_check(&vec![], &IndexMap::new())
+            .expect_err("Failed to reject spreading a weighted edge into four variables");
+    }
 }
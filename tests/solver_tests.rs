@@ -4,8 +4,9 @@ pub mod solver_tests {
     use wasm_bindgen_test::*;
 
     use indexmap::IndexMap;
-    use rooc::common::LpSolution;
+    use rooc::common::{Assignment, LpSolution};
     use rooc::linear_integer_binary_solver::IntOrBoolValue;
+    use rooc::model_transformer::{DefaultDomain, DomainVariable};
     use rooc::pipe::{
         BinarySolverPipe, CompilerPipe, IntegerBinarySolverPipe, LinearModelPipe, MILPSolverPipe,
         ModelPipe, PreModelPipe, RealSolver, StandardLinearModelPipe, TableauPipe,
@@ -13,8 +14,26 @@ pub mod solver_tests {
     use rooc::pipe::{PipeContext, PipeRunner};
     use rooc::pipe::{PipeDataType, PipeError, PipeableData, StepByStepSimplexPipe};
     #[allow(unused_imports)]
-    use rooc::simplex::{CanonicalTransformError, OptimalTableau, SimplexError};
+    use rooc::simplex::{CanonicalTransformError, OptimalTableau, PrettyFraction, SimplexError};
+    use rooc::solvers::{self, SolverBackend, SolverConfig};
+    use rooc::{
+        auto_solver, Comparison, LinearModel, LinearModelBuilder, OptimizationType, VariableType,
+    };
     use rooc::{float_eq, float_ne};
+    use rooc::{solve_integer_heuristic, solve_preemptive_goals, SoftConstraint};
+    use rooc::{
+        solve_lexicographic, solve_real_lp_problem_clarabel,
+        solve_real_lp_problem_clarabel_with_warm_start, solve_real_lp_problem_slow_simplex,
+    };
+    use rooc::{solve_milp_lp_problem, solve_milp_lp_problem_with_node_limit, SolverError};
+    use rooc::{solve_milp_lp_problem_with_budget, SearchBudget};
+    use rooc::{
+        solve_real_lp_problem_slow_simplex_with_stats, to_standard_form, to_standard_form_with_map,
+        PivotRule, SolveStats, StandardizedVariable, Tableau,
+    };
+    use rooc::{CompiledProblem, IndicatorConstraint, LinearModelError};
+    use rooc::{DisjunctiveConstraintSet, LinearConstraint, Linearizer, Primitive, RoocParser};
+    use rooc::{FormatOptions, InputSpan};
     use rooc::{MILPValue, OptimalTableauWithSteps};
 
     #[allow(unused)]
@@ -730,4 +749,2373 @@ define
             false,
         )
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_create_solution_report() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::IntegerRange(0, 10));
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 5.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 2.0);
+        model.set_objective(vec![2.0, 1.0], OptimizationType::Max);
+        let solution = auto_solver(&model).unwrap();
+        let report = solution.report(&model);
+        assert_eq!(report, solution.report(&model));
+        assert!(report.contains("Optimal value: 7"));
+        assert!(report.contains("Constraints: 2"));
+        for variable in model.variables() {
+            assert!(report.contains(variable.as_str()));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_keep_variable_indexes() {
+        let source = "
+        max sum(i in 0..2, j in 0..2) { x_i_j }
+        s.t.
+            x_0_1 + y <= 1
+            sum(i in 0..2, j in 0..2) { x_i_j } <= 3
+        define
+            x_i_j as Boolean for i in 0..2, j in 0..2
+            y, z as Boolean
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let indexes = model.variable_indexes();
+        assert_eq!(indexes.len(), 4);
+        assert_eq!(
+            indexes.get("x_1_0"),
+            Some(&(
+                "x".to_string(),
+                vec![Primitive::PositiveInteger(1), Primitive::PositiveInteger(0)]
+            ))
+        );
+        assert_eq!(indexes.get("y"), None);
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(linear.variable_indexes().len(), 4);
+        let solution = auto_solver(&linear).unwrap();
+        for assignment in solution.assignment() {
+            if let Some((name, indexes)) = linear.variable_indexes().get(&assignment.name) {
+                assert_eq!(name, "x");
+                assert_eq!(indexes.len(), 2);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_disjunctive_constraints() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::NonNegativeReal(0.0, 10.0));
+        model.add_constraint(vec![1.0], Comparison::GreaterOrEqual, 3.0);
+        model.set_objective(vec![1.0], OptimizationType::Min);
+        // x <= 2 or x >= 8, they can't both hold
+        model
+            .add_disjunctive_constraints(DisjunctiveConstraintSet::new(
+                vec![
+                    LinearConstraint::new(vec![1.0], Comparison::LessOrEqual, 2.0),
+                    LinearConstraint::new(vec![1.0], Comparison::GreaterOrEqual, 8.0),
+                ],
+                1,
+            ))
+            .unwrap();
+        assert_eq!(model.variables(), &vec!["x", "$dis_0", "$dis_1"]);
+        let solution = auto_solver(&model).unwrap();
+        assert!(float_eq(solution.value(), 8.0));
+        let x = match solution.assignment().iter().find(|a| a.name == "x") {
+            Some(a) => match a.value {
+                MILPValue::Real(v) => v,
+                _ => panic!("x should be real"),
+            },
+            None => panic!("x should be in the solution"),
+        };
+        //only the second constraint holds
+        assert!(x >= 8.0);
+        let invalid = model.add_disjunctive_constraints(DisjunctiveConstraintSet::new(vec![], 1));
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reduce_iterations_with_steepest_edge() {
+        let solve_with = |source: &str, pivot_rule: PivotRule| {
+            let model = RoocParser::new(source.to_string())
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap();
+            let linear = Linearizer::linearize(model).unwrap();
+            let result = to_standard_form(linear)
+                .unwrap()
+                .into_tableau()
+                .unwrap()
+                .with_pivot_rule(pivot_rule)
+                .solve_step_by_step(1000)
+                .unwrap();
+            (result.steps().len(), result.result().optimal_value())
+        };
+        let models = [
+            // Klee-Minty cubes, worst cases for the Dantzig rule
+            (
+                "
+            max 100x_1 + 10x_2 + x_3
+            s.t.
+                x_1 <= 1
+                20x_1 + x_2 <= 100
+                200x_1 + 20x_2 + x_3 <= 10000
+            define
+                x_1, x_2, x_3 as NonNegativeReal
+            ",
+                10000.0,
+            ),
+            (
+                "
+            max 1000x_1 + 100x_2 + 10x_3 + x_4
+            s.t.
+                x_1 <= 1
+                20x_1 + x_2 <= 100
+                200x_1 + 20x_2 + x_3 <= 10000
+                2000x_1 + 200x_2 + 20x_3 + x_4 <= 1000000
+            define
+                x_1, x_2, x_3, x_4 as NonNegativeReal
+            ",
+                1000000.0,
+            ),
+            (
+                "
+            max 10x + y
+            s.t.
+                100x + y <= 150
+                x + y <= 10
+            define
+                x, y as NonNegativeReal
+            ",
+                22.727272727272727,
+            ),
+        ];
+        for (source, expected) in models {
+            let (dantzig_iterations, dantzig_value) = solve_with(source, PivotRule::Dantzig);
+            let (steepest_iterations, steepest_value) = solve_with(source, PivotRule::SteepestEdge);
+            assert_precision(dantzig_value, expected);
+            assert_precision(steepest_value, expected);
+            assert!(steepest_iterations <= dantzig_iterations);
+        }
+        let (dantzig_iterations, _) = solve_with(models[1].0, PivotRule::Dantzig);
+        let (steepest_iterations, _) = solve_with(models[1].0, PivotRule::SteepestEdge);
+        //both start from the slack basis, where Dantzig's rule visits all the 2^4 - 1 vertices
+        //of the cube while the Devex weights reach the optimum in 13 pivots
+        assert_eq!((dantzig_iterations, steepest_iterations), (15, 13));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_build_constraints_from_terms() {
+        let order = vec!["x".to_string(), "y".to_string(), "z".to_string()];
+        let first = LinearConstraint::from_terms(
+            &[("y".to_string(), 3.0), ("x".to_string(), 1.0)],
+            Comparison::LessOrEqual,
+            4.0,
+            &order,
+        )
+        .unwrap();
+        let second = LinearConstraint::from_terms(
+            &[("x".to_string(), 1.0), ("y".to_string(), 3.0)],
+            Comparison::LessOrEqual,
+            4.0,
+            &order,
+        )
+        .unwrap();
+        assert_eq!(first.coefficients(), second.coefficients());
+        assert_eq!(first.coefficients(), &vec![1.0, 3.0, 0.0]);
+        let repeated = LinearConstraint::from_terms(
+            &[("z".to_string(), 1.0), ("z".to_string(), 2.0)],
+            Comparison::Equal,
+            1.0,
+            &order,
+        )
+        .unwrap();
+        assert_eq!(repeated.coefficients(), &vec![0.0, 0.0, 3.0]);
+        let unknown =
+            LinearConstraint::from_terms(&[("w".to_string(), 1.0)], Comparison::Equal, 1.0, &order);
+        assert!(unknown.is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_solve_stats() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, -1.0], Comparison::LessOrEqual, 0.0);
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 2.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        let (solution, stats) = solve_real_lp_problem_slow_simplex_with_stats(&model, 100).unwrap();
+        assert_precision(solution.value(), 2.0);
+        //x enters at the origin, where x - y <= 0 is already tight
+        assert_eq!(stats, SolveStats::new(2, 1, 0));
+        let config = SolverConfig::new(SolverBackend::Simplex);
+        let solution = solvers::solve(&model, &config).unwrap();
+        assert_eq!(solution.stats(), Some(&SolveStats::new(2, 1, 0)));
+        //the other solvers don't count pivots
+        assert!(auto_solver(&model).unwrap().stats().is_none());
+
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::Equal, 2.0);
+        model.add_constraint(vec![1.0, -1.0], Comparison::LessOrEqual, 1.0);
+        model.set_objective(vec![1.0, 3.0], OptimizationType::Min);
+        let (solution, stats) = solve_real_lp_problem_slow_simplex_with_stats(&model, 100).unwrap();
+        assert_precision(solution.value(), 3.0);
+        assert_eq!(stats.phase1_iterations(), 2);
+        assert_eq!(stats.iterations(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reoptimize_after_rhs_change() {
+        let tableau = |source: &str| -> Tableau {
+            let model = RoocParser::new(source.to_string())
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap();
+            let linear = Linearizer::linearize(model).unwrap();
+            to_standard_form(linear).unwrap().into_tableau().unwrap()
+        };
+        let source = "
+        max 3x + 2y
+        s.t.
+            x + y <= 4
+            x + 3y <= 6
+            x <= 3
+        define
+            x, y as NonNegativeReal
+        ";
+        let mut t = tableau(source);
+        assert_precision(t.solve(100).unwrap().optimal_value(), 11.0);
+        t.update_rhs(0, 3.5).unwrap();
+        let result = t.reoptimize(100).unwrap();
+        assert_precision(result.optimal_value(), 10.0);
+        assert_variables(
+            &result.variables_values()[0..2].to_vec(),
+            &vec![vec![3.0, 0.5]],
+            false,
+        );
+        let fresh = tableau(&source.replace("x + y <= 4", "x + y <= 3.5"))
+            .solve(100)
+            .unwrap();
+        assert_precision(fresh.optimal_value(), result.optimal_value());
+        t.update_rhs(2, 5.0).unwrap();
+        assert_precision(t.reoptimize(100).unwrap().optimal_value(), 10.5);
+        t.update_rhs(0, -1.0).unwrap();
+        assert!(matches!(t.reoptimize(100), Err(SimplexError::Infeasible)));
+        assert!(matches!(
+            t.update_rhs(3, 1.0),
+            Err(SimplexError::InvalidIndex)
+        ));
+
+        //the tableau was not optimal yet, so it falls back to a first phase
+        let source = "
+        max x + y
+        s.t.
+            x - y <= 1
+            x + 2y <= 8
+        define
+            x, y as NonNegativeReal
+        ";
+        let mut t = tableau(source);
+        t.update_rhs(0, -1.0).unwrap();
+        let result = t.reoptimize(100).unwrap();
+        assert_precision(result.optimal_value(), 5.0);
+        assert_eq!(result.stats().phase1_iterations(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reoptimize_like_a_fresh_solve() {
+        let tableau = |source: &str| -> Tableau {
+            let model = RoocParser::new(source.to_string())
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap();
+            let linear = Linearizer::linearize(model).unwrap();
+            to_standard_form(linear).unwrap().into_tableau().unwrap()
+        };
+        //each change is applied on top of the previous ones, and compared with a fresh solve
+        let check = |source: &str, changes: &[(usize, &str, &str)]| {
+            let mut t = tableau(source);
+            t.solve(100).unwrap();
+            let mut changed = source.to_string();
+            for (constraint, from, to) in changes {
+                changed = changed.replace(from, to);
+                let new_value = to.rsplit(' ').next().unwrap().parse::<f64>().unwrap();
+                t.update_rhs(*constraint, new_value).unwrap();
+                let fresh = tableau(&changed).solve(100).unwrap();
+                assert_precision(
+                    t.reoptimize(100).unwrap().optimal_value(),
+                    fresh.optimal_value(),
+                );
+            }
+        };
+        //the artificial variables of the first phase are the inverse of the basis
+        check(
+            "
+        min 2x + 3y + w
+        s.t.
+            x + y >= 4
+            x + 2w = 6
+            y <= 3
+        define
+            x, y, w as NonNegativeReal
+        ",
+            &[
+                (0, "x + y >= 4", "x + y >= 3.5"),
+                (1, "x + 2w = 6", "x + 2w = 2"),
+                (0, "x + y >= 3.5", "x + y >= 4.5"),
+                (1, "x + 2w = 2", "x + 2w = 8"),
+                (2, "y <= 3", "y <= 1"),
+            ],
+        );
+        //the row of w is scaled when the tableau is created
+        check(
+            "
+        max x + y + 3w
+        s.t.
+            x + 2w = 6
+            x + y <= 5
+        define
+            x, y, w as NonNegativeReal
+        ",
+            &[
+                (0, "x + 2w = 6", "x + 2w = 4"),
+                (1, "x + y <= 5", "x + y <= 2"),
+                (0, "x + 2w = 4", "x + 2w = 10"),
+            ],
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_diff_linear_models() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.add_constraint(vec![1.0, 3.0], Comparison::LessOrEqual, 6.0);
+        model.set_objective(vec![3.0, 2.0], OptimizationType::Max);
+        assert!(model.diff(&model.clone()).is_empty());
+
+        let mut other = LinearModel::new();
+        other.add_variable("x", VariableType::non_negative_real());
+        other.add_variable("y", VariableType::non_negative_real());
+        other.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        other.add_constraint(vec![1.0, 2.0], Comparison::LessOrEqual, 6.0);
+        other.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 3.0);
+        other.set_objective(vec![3.0, 2.0], OptimizationType::Max);
+        let diff = model.diff(&other);
+        assert!(diff.added_variables.is_empty());
+        assert!(diff.removed_variables.is_empty());
+        assert!(diff.changed_objective.is_empty());
+        assert_eq!(diff.added_constraints, vec!["c2".to_string()]);
+        assert!(diff.removed_constraints.is_empty());
+        assert_eq!(diff.changed_constraints, vec!["c1".to_string()]);
+
+        let reverse = other.diff(&model);
+        assert_eq!(reverse.removed_constraints, vec!["c2".to_string()]);
+        assert_eq!(reverse.changed_constraints, vec!["c1".to_string()]);
+
+        let mut renamed = LinearModel::new();
+        renamed.add_variable("x", VariableType::non_negative_real());
+        renamed.add_variable("z", VariableType::non_negative_real());
+        renamed.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 4.0);
+        renamed.set_objective(vec![5.0, 1.0], OptimizationType::Max);
+        let diff = model.diff(&renamed);
+        assert_eq!(diff.added_variables, vec!["z".to_string()]);
+        assert_eq!(diff.removed_variables, vec!["y".to_string()]);
+        assert_eq!(
+            diff.changed_objective,
+            vec![
+                ("x".to_string(), 3.0, 5.0),
+                ("y".to_string(), 2.0, 0.0),
+                ("z".to_string(), 0.0, 1.0)
+            ]
+        );
+        assert_eq!(diff.changed_constraints, vec!["c0".to_string()]);
+        assert_eq!(diff.removed_constraints, vec!["c1".to_string()]);
+
+        //the same model minimized, with a tighter bound on y
+        let mut flipped = LinearModel::new();
+        flipped.add_variable("x", VariableType::non_negative_real());
+        flipped.add_variable("y", VariableType::NonNegativeReal(0.0, 1.0));
+        flipped.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        flipped.add_constraint(vec![1.0, 3.0], Comparison::LessOrEqual, 6.0);
+        flipped.set_objective(vec![3.0, 2.0], OptimizationType::Min);
+        let diff = model.diff(&flipped);
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.changed_optimization_type,
+            Some((OptimizationType::Max, OptimizationType::Min))
+        );
+        assert_eq!(
+            diff.changed_domains,
+            vec![(
+                "y".to_string(),
+                VariableType::non_negative_real(),
+                VariableType::NonNegativeReal(0.0, 1.0)
+            )]
+        );
+        assert!(diff.changed_objective.is_empty());
+        assert!(diff.changed_constraints.is_empty());
+        assert!(diff
+            .to_string()
+            .contains("Changed optimization type: max -> min"));
+
+        //named constraints are matched by name, wherever they are
+        let mut named = LinearModel::new();
+        named.add_variable("x", VariableType::non_negative_real());
+        named
+            .add_named_constraint("capacity", vec![1.0], Comparison::LessOrEqual, 4.0)
+            .unwrap();
+        let mut inserted = LinearModel::new();
+        inserted.add_variable("x", VariableType::non_negative_real());
+        inserted
+            .add_named_constraint("demand", vec![1.0], Comparison::GreaterOrEqual, 1.0)
+            .unwrap();
+        inserted
+            .add_named_constraint("capacity", vec![1.0], Comparison::LessOrEqual, 4.0)
+            .unwrap();
+        let diff = named.diff(&inserted);
+        assert_eq!(diff.added_constraints, vec!["demand".to_string()]);
+        assert!(diff.removed_constraints.is_empty());
+        assert!(diff.changed_constraints.is_empty());
+        assert!(matches!(
+            inserted.add_named_constraint("demand", vec![1.0], Comparison::LessOrEqual, 2.0),
+            Err(LinearModelError::DuplicateConstraint(name)) if name == "demand"
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_with_dual_simplex() {
+        // min x + y s.t. x + y >= 2, x + 2y >= 3, with the constraints negated
+        // so that the slack variables form an infeasible but dual feasible basis
+        let variables = vec!["x", "y", "$s1", "$s2"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let mut tableau = Tableau::new(
+            vec![1.0, 1.0, 0.0, 0.0],
+            vec![vec![-1.0, -1.0, 1.0, 0.0], vec![-1.0, -2.0, 0.0, 1.0]],
+            vec![-2.0, -3.0],
+            vec![2, 3],
+            0.0,
+            0.0,
+            variables.clone(),
+            false,
+        );
+        let result = tableau.solve_dual(100).unwrap();
+        assert_precision(result.optimal_value(), 2.0);
+        assert_variables(
+            result.variables_values(),
+            &vec![vec![1.0, 1.0, 0.0, 0.0]],
+            false,
+        );
+        assert_eq!(result.stats().iterations(), 2);
+
+        // x + y <= -1 can't be satisfied by non negative variables
+        let mut infeasible = Tableau::new(
+            vec![1.0, 1.0, 0.0],
+            vec![vec![1.0, 1.0, 1.0]],
+            vec![-1.0],
+            vec![2],
+            0.0,
+            0.0,
+            variables[0..3].to_vec(),
+            false,
+        );
+        assert!(matches!(
+            infeasible.solve_dual(100),
+            Err(SimplexError::Infeasible)
+        ));
+
+        // the primal simplex start is not dual feasible
+        let mut primal = Tableau::new(
+            vec![-1.0, -1.0, 0.0],
+            vec![vec![1.0, 1.0, 1.0]],
+            vec![1.0],
+            vec![2],
+            0.0,
+            0.0,
+            variables[0..3].to_vec(),
+            false,
+        );
+        assert!(primal.solve_dual(100).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_linearize_norms() {
+        let source = "
+        min norm1 { x - 2, y + 1 } + norm_inf(i in 0..2) { z_i - i }
+        s.t.
+            x + y >= 0
+        define
+            x, y as Real(-10, 10)
+            z_i as Real(-10, 10) for i in 0..2
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(
+            linear.to_string(),
+            "min $abs_0 + $abs_1 + $max_0
+s.t.
+    - $abs_2 + $max_0 >= 0
+    $abs_2 + z_1 >= 1
+    $abs_2 - z_1 >= -1
+    - $abs_3 + $max_0 >= 0
+    $abs_3 + z_0 >= 0
+    $abs_3 - z_0 >= 0
+    $abs_1 + y >= -1
+    $abs_1 - y >= 1
+    $abs_0 + x >= 2
+    $abs_0 - x >= -2
+    x + y >= 0
+define
+    x, y, z_0, z_1 as Real(-10, 10)
+    $abs_0, $abs_1, $abs_2, $abs_3 as NonNegativeReal
+    $max_0 as Real"
+        );
+        let solution = auto_solver(&linear).unwrap();
+        assert_precision(solution.value(), 0.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_break_down_objective() {
+        let source = "
+        max 3x + 2y - z + 5
+        s.t.
+            x + y <= 4
+            x + 3y <= 6
+            z >= 1
+            x <= 3
+        define
+            x, y, z as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let solution = auto_solver(&linear).unwrap();
+        let breakdown = solution.objective_breakdown(&linear);
+        assert_eq!(
+            breakdown.keys().collect::<Vec<_>>(),
+            vec!["x", "y", "z", "__offset"]
+        );
+        assert_precision(breakdown["x"], 9.0);
+        assert_precision(breakdown["y"], 2.0);
+        assert_precision(breakdown["z"], -1.0);
+        assert_precision(breakdown["__offset"], 5.0);
+        assert_precision(breakdown.values().sum(), solution.value());
+
+        let source = source.replace(
+            "x, y, z as NonNegativeReal",
+            "x, y as NonNegativeReal\n z as IntegerRange(0, 10)",
+        );
+        let model = RoocParser::new(source)
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let solution = auto_solver(&linear).unwrap();
+        let breakdown = solution.objective_breakdown(&linear);
+        assert_precision(breakdown["z"], -1.0);
+        assert_precision(breakdown.values().sum(), solution.value());
+        assert_precision(solution.value(), 15.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_find_binding_constraints() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.add_constraint(vec![1.0, 3.0], Comparison::LessOrEqual, 7.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 3.0);
+        model.set_objective(vec![3.0, 2.0], OptimizationType::Max);
+        let optimal = to_standard_form(model)
+            .unwrap()
+            .into_tableau()
+            .unwrap()
+            .solve(1000)
+            .unwrap();
+        assert_precision(optimal.optimal_value(), 11.0);
+        assert_eq!(optimal.binding_constraints(1e-9), vec![0, 2]);
+        //x + 3y is 6, one away from its bound
+        assert_eq!(optimal.binding_constraints(1.0), vec![0, 1, 2]);
+
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::Equal, 2.0);
+        model.add_constraint(vec![1.0, -1.0], Comparison::LessOrEqual, 1.0);
+        model.add_constraint(vec![0.0, 1.0], Comparison::GreaterOrEqual, 0.2);
+        model.set_objective(vec![1.0, 3.0], OptimizationType::Min);
+        let optimal = to_standard_form(model)
+            .unwrap()
+            .into_tableau()
+            .unwrap()
+            .solve(1000)
+            .unwrap();
+        assert_precision(optimal.optimal_value(), 3.0);
+        assert_eq!(optimal.binding_constraints(1e-9), vec![0, 1]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_bound_with_relaxation() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::integer_range(0, 10));
+        model.add_variable("y", VariableType::bool());
+        model.add_constraint(vec![2.0, 2.0], Comparison::LessOrEqual, 5.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        let bound = model.relaxation_bound().unwrap();
+        assert_precision(bound, 2.5);
+        let solution = auto_solver(&model).unwrap();
+        assert_precision(solution.value(), 2.0);
+        assert!(solution.value() <= bound);
+        assert_eq!(
+            *model.domain().get("x").unwrap().get_type(),
+            VariableType::integer_range(0, 10)
+        );
+        assert_eq!(
+            *model.domain().get("y").unwrap().get_type(),
+            VariableType::bool()
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_gap_at_node_limit() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::integer_range(0, 10));
+        model.add_variable("y", VariableType::integer_range(0, 10));
+        model.add_variable("z", VariableType::integer_range(0, 10));
+        model.add_constraint(vec![4.0, 6.0, 7.0], Comparison::LessOrEqual, 23.0);
+        model.add_constraint(vec![3.0, 1.0, 5.0], Comparison::LessOrEqual, 17.0);
+        model.set_objective(vec![5.0, 7.0, 9.0], OptimizationType::Max);
+        let optimal = solve_milp_lp_problem(&model).unwrap();
+        let solution = solve_milp_lp_problem_with_node_limit(&model, 1000).unwrap();
+        assert_precision(solution.value(), optimal.value());
+        assert_eq!(solution.gap(), 0.0);
+
+        let mut limited = None;
+        for limit in 1..1000 {
+            match solve_milp_lp_problem_with_node_limit(&model, limit) {
+                Err(SolverError::IntegerSearchIncomplete {
+                    incumbent: Some(incumbent),
+                    nodes_explored,
+                }) => {
+                    assert_eq!(nodes_explored, limit);
+                    limited = Some(incumbent);
+                    break;
+                }
+                Err(SolverError::IntegerSearchIncomplete {
+                    incumbent: None, ..
+                }) => continue,
+                Ok(_) => panic!("Search should not be complete before finding an incumbent"),
+                Err(e) => panic!("Unexpected error: {}", e),
+            }
+        }
+        let limited = limited.unwrap();
+        assert!(limited.value() <= optimal.value());
+        assert!(limited.gap() > 0.0);
+        assert!(limited.value() * (1.0 + limited.gap()) >= optimal.value() - 1e-6);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_incomplete_integer_search() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::integer_range(0, 10));
+        model.add_variable("y", VariableType::integer_range(0, 10));
+        model.add_constraint(vec![2.0, 2.0], Comparison::LessOrEqual, 7.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        //the root relaxation is fractional, so no incumbent is available after one node
+        match solve_milp_lp_problem_with_node_limit(&model, 1) {
+            Err(SolverError::IntegerSearchIncomplete {
+                incumbent,
+                nodes_explored,
+            }) => {
+                assert!(incumbent.is_none());
+                assert_eq!(nodes_explored, 1);
+            }
+            r => panic!("Expected an incomplete search, got {:?}", r),
+        }
+
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::integer_range(0, 10));
+        model.add_constraint(vec![2.0], Comparison::Equal, 1.0);
+        model.set_objective(vec![1.0], OptimizationType::Max);
+        match solve_milp_lp_problem_with_node_limit(&model, 1) {
+            Err(SolverError::IntegerSearchIncomplete {
+                incumbent: None, ..
+            }) => {}
+            r => panic!("Expected an incomplete search, got {:?}", r),
+        }
+        match solve_milp_lp_problem_with_node_limit(&model, 100) {
+            Err(SolverError::Infisible) => {}
+            r => panic!("Expected the problem to be infeasible, got {:?}", r),
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_add_indicator_constraints() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::NonNegativeReal(0.0, 10.0));
+        model.add_variable("y", VariableType::Boolean);
+        model.set_objective(vec![-1.0, 10.0], OptimizationType::Max);
+        // if y then x >= 5
+        let indicator = IndicatorConstraint::new(
+            "y",
+            LinearConstraint::new(vec![1.0], Comparison::GreaterOrEqual, 5.0),
+        );
+        model.add_indicator_constraint(indicator).unwrap();
+        let constraint = &model.constraints()[0];
+        assert_eq!(constraint.coefficients(), &vec![1.0, -5.0]);
+        assert_eq!(*constraint.constraint_type(), Comparison::GreaterOrEqual);
+        assert_precision(constraint.rhs(), 0.0);
+        let solution = auto_solver(&model).unwrap();
+        assert_precision(solution.value(), 5.0);
+
+        // if y then x <= 2, with a given big-M
+        let indicator = IndicatorConstraint::new(
+            "y",
+            LinearConstraint::new(vec![1.0], Comparison::LessOrEqual, 2.0),
+        )
+        .with_big_m(100.0);
+        model.add_indicator_constraint(indicator).unwrap();
+        let constraint = &model.constraints()[1];
+        assert_eq!(constraint.coefficients(), &vec![1.0, 100.0]);
+        assert_precision(constraint.rhs(), 102.0);
+        let solution = auto_solver(&model).unwrap();
+        assert_precision(solution.value(), 0.0);
+
+        let indicator = IndicatorConstraint::new(
+            "x",
+            LinearConstraint::new(vec![0.0, 1.0], Comparison::LessOrEqual, 0.0),
+        );
+        assert!(matches!(
+            model.add_indicator_constraint(indicator),
+            Err(LinearModelError::InvalidIndicator(_))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_lexicographically() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 3.0);
+        model.add_constraint(vec![0.0, 1.0], Comparison::LessOrEqual, 3.0);
+        //every point with x + y = 4 is optimal for the first objective, the second picks x = 3
+        let objectives = vec![
+            (OptimizationType::Max, vec![1.0, 1.0]),
+            (OptimizationType::Max, vec![1.0, 0.0]),
+        ];
+        let solution =
+            solve_lexicographic(&model, objectives.clone(), solve_real_lp_problem_clarabel)
+                .unwrap();
+        assert_variables(&solution.assignment_values(), &vec![vec![3.0, 1.0]], false);
+        assert_precision(solution.value(), 3.0);
+        let solution = solve_lexicographic(&model, objectives, |m| {
+            solve_real_lp_problem_slow_simplex(m, 1000)
+        })
+        .unwrap();
+        assert_variables(&solution.assignment_values(), &vec![vec![3.0, 1.0]], true);
+
+        let solution = solve_lexicographic(
+            &model,
+            vec![
+                (OptimizationType::Max, vec![1.0, 1.0]),
+                (OptimizationType::Min, vec![1.0, 0.0]),
+            ],
+            solve_real_lp_problem_clarabel,
+        )
+        .unwrap();
+        assert_variables(&solution.assignment_values(), &vec![vec![1.0, 3.0]], false);
+        assert!(solve_lexicographic(&model, vec![], solve_real_lp_problem_clarabel).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_with_every_backend() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.add_constraint(vec![1.0, 3.0], Comparison::LessOrEqual, 6.0);
+        model.set_objective(vec![3.0, 2.0], OptimizationType::Max);
+        for backend in [
+            SolverBackend::Auto,
+            SolverBackend::Real,
+            SolverBackend::Simplex,
+            SolverBackend::Milp,
+        ] {
+            let solution = solvers::solve(&model, &SolverConfig::new(backend)).unwrap();
+            assert_precision(solution.value(), 12.0);
+        }
+        let solution = solvers::solve(
+            &model,
+            &SolverConfig::new(SolverBackend::Milp).with_node_limit(10),
+        )
+        .unwrap();
+        assert_precision(solution.value(), 12.0);
+        assert!(matches!(
+            solvers::solve(&model, &SolverConfig::new(SolverBackend::Binary)),
+            Err(SolverError::InvalidDomain { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_explain_solver_errors() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::integer_range(0, 10));
+        model.add_constraint(vec![1.0], Comparison::LessOrEqual, 5.0);
+        model.set_objective(vec![1.0], OptimizationType::Max);
+        let error = solve_real_lp_problem_clarabel(&model).unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("x is declared as IntegerRange(0, 10)"));
+        assert!(message.contains("use the MILP solver"));
+        let serialized = serde_json::to_value(&error).unwrap();
+        assert_eq!(serialized["type"], "InvalidDomain");
+        assert_eq!(serialized["value"]["got"][0][0], "x");
+
+        let error = SolverError::IntegerSearchIncomplete {
+            incumbent: None,
+            nodes_explored: 3,
+        };
+        assert!(error.to_string().contains("after exploring 3 nodes"));
+        assert_eq!(error.remedy().unwrap(), "Increase the node limit");
+        let serialized = serde_json::to_value(&error).unwrap();
+        assert_eq!(serialized["value"]["nodes_explored"], 3);
+        assert!(serialized["value"]["incumbent"].is_null());
+        assert!(SolverError::Other("error".to_string()).remedy().is_none());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_format_pretty_fractions() {
+        let cases = [
+            (0.1, "1/10"),
+            (1.0 / 3.0, "1/3"),
+            (-2.0 / 3.0, "-2/3"),
+            (0.125, "1/8"),
+            (2.5, "5/2"),
+            (7.0, "7"),
+            (-4.0, "-4"),
+            (0.0, "0"),
+            (1.0 / 9999.0, "1/9999"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(PrettyFraction::new(value).pretty(), expected);
+        }
+        //no fraction with a small denominator is close enough, so the decimal is kept
+        let sqrt_two = std::f64::consts::SQRT_2;
+        assert_eq!(PrettyFraction::new(sqrt_two).pretty(), sqrt_two.to_string());
+        assert_eq!(
+            PrettyFraction::new(1.0 / 10007.0).pretty(),
+            (1.0 / 10007.0).to_string()
+        );
+        assert_eq!(PrettyFraction::new(f64::INFINITY).pretty(), "inf");
+        assert_eq!(PrettyFraction::new(1e20).pretty(), "100000000000000000000");
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_render_solution_table() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::integer_range(0, 10));
+        model.add_variable("y", VariableType::integer_range(0, 10));
+        model.add_constraint(vec![2.0, 2.0], Comparison::LessOrEqual, 7.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        let solution = solve_milp_lp_problem(&model).unwrap();
+        let table = solution.to_table();
+        let rows = table
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>();
+        assert!(rows.iter().any(|r| r == "║ Variable ║ Value ║"));
+        assert!(rows.iter().any(|r| r.starts_with("║ x ║")));
+        assert!(rows.iter().any(|r| r.starts_with("║ y ║")));
+        assert!(rows.iter().any(|r| r == "║ Objective value ║ 3 ║"));
+        assert!(rows.iter().any(|r| r == "║ Status ║ Optimal ║"));
+        assert!(!table.contains("Gap"));
+
+        let incomplete = solution.with_gap(0.25);
+        let table = incomplete.to_table();
+        assert!(table.contains("Feasible"));
+        assert!(table.contains("25.00%"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_with_warm_start() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.add_constraint(vec![1.0, 3.0], Comparison::LessOrEqual, 6.0);
+        model.set_objective(vec![3.0, 2.0], OptimizationType::Max);
+        let cold = solve_real_lp_problem_clarabel(&model).unwrap();
+        let warm_start =
+            std::collections::HashMap::from([("x".to_string(), 4.0), ("y".to_string(), 0.0)]);
+        let warm = solve_real_lp_problem_clarabel_with_warm_start(&model, &warm_start).unwrap();
+        assert_precision(warm.value(), cold.value());
+        assert_variables(&warm.assignment_values(), &vec![vec![4.0, 0.0]], false);
+        assert_eq!(warm.notes().len(), 1);
+        assert!(warm.notes()[0].contains("warm start was ignored"));
+        assert!(cold.notes().is_empty());
+
+        let unknown = std::collections::HashMap::from([("z".to_string(), 1.0)]);
+        match solve_real_lp_problem_clarabel_with_warm_start(&model, &unknown) {
+            Err(SolverError::UnknownVariable(name)) => assert_eq!(name, "z"),
+            r => panic!("Expected the unknown variable to be rejected, got {:?}", r),
+        }
+        let not_finite = std::collections::HashMap::from([("x".to_string(), f64::NAN)]);
+        assert!(solve_real_lp_problem_clarabel_with_warm_start(&model, &not_finite).is_err());
+
+        //solvers without a starting point ignore the warm start
+        let config = SolverConfig::new(SolverBackend::Milp).with_warm_start(warm_start.clone());
+        let solution = solvers::solve(&model, &config).unwrap();
+        assert_precision(solution.value(), cold.value());
+        assert!(solution.notes()[0].contains("Milp solver does not accept a starting point"));
+        let config = SolverConfig::new(SolverBackend::Real).with_warm_start(warm_start.clone());
+        assert_eq!(solvers::solve(&model, &config).unwrap().notes().len(), 1);
+        let config = SolverConfig::new(SolverBackend::Real).with_warm_start(unknown);
+        assert!(solvers::solve(&model, &config).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_preemptive_goals() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 10.0);
+        //the two goals conflict, x + y >= 8 can't hold together with x + y <= 5
+        let high = SoftConstraint::new(LinearConstraint::new(
+            vec![1.0, 1.0],
+            Comparison::GreaterOrEqual,
+            8.0,
+        ))
+        .with_priority(2);
+        let low = SoftConstraint::new(LinearConstraint::new(
+            vec![1.0, 1.0],
+            Comparison::LessOrEqual,
+            5.0,
+        ))
+        .with_priority(1);
+        let solution = solve_preemptive_goals(
+            &model,
+            vec![low.clone(), high.clone()],
+            solve_real_lp_problem_clarabel,
+        )
+        .unwrap();
+        let values = solution
+            .assignment()
+            .iter()
+            .map(|a| (a.name.clone(), a.value))
+            .collect::<IndexMap<_, _>>();
+        //the high priority goal is met, the low priority one is violated by 3
+        assert!(values["x"] + values["y"] >= 8.0 - 1e-4);
+        assert_precision(values["$under_2"], 0.0);
+        assert_precision(values["$over_1"], 3.0);
+        assert_precision(solution.value(), 3.0);
+
+        //swapping the priorities meets the other goal
+        let solution = solve_preemptive_goals(
+            &model,
+            vec![low.with_priority(3), high],
+            solve_real_lp_problem_clarabel,
+        )
+        .unwrap();
+        let values = solution
+            .assignment()
+            .iter()
+            .map(|a| (a.name.clone(), a.value))
+            .collect::<IndexMap<_, _>>();
+        assert!(values["x"] + values["y"] <= 5.0 + 1e-4);
+        assert_precision(values["$over_1"], 0.0);
+        assert_precision(values["$under_2"], 3.0);
+
+        assert!(solve_preemptive_goals(&model, vec![], solve_real_lp_problem_clarabel).is_err());
+
+        let too_long = SoftConstraint::new(LinearConstraint::new(
+            vec![1.0, 1.0, 1.0],
+            Comparison::LessOrEqual,
+            5.0,
+        ));
+        let err = solve_preemptive_goals(&model, vec![too_long], solve_real_lp_problem_clarabel)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid soft constraint: There are more coefficients than variables in the model"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_presolve_fixed_variables() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_variable("z", VariableType::real());
+        model.add_constraint(vec![2.0, 0.0, 0.0], Comparison::Equal, 6.0);
+        model.add_constraint(vec![1.0, 1.0, 1.0], Comparison::LessOrEqual, 10.0);
+        model.add_constraint(vec![0.0, 1.0, 2.0], Comparison::LessOrEqual, 8.0);
+        model.add_constraint(vec![0.0, 0.0, -1.0], Comparison::Equal, -2.0);
+        model.set_objective(vec![1.0, 2.0, 1.0], OptimizationType::Max);
+        let original = solve_real_lp_problem_clarabel(&model).unwrap();
+        let mut presolved = model.clone();
+        assert_eq!(presolved.presolve_fixed_variables(), 2);
+        assert_eq!(presolved.constraints().len(), 2);
+        assert_eq!(
+            *presolved.domain().get("x").unwrap().get_type(),
+            VariableType::NonNegativeReal(3.0, 3.0)
+        );
+        assert_eq!(
+            *presolved.domain().get("z").unwrap().get_type(),
+            VariableType::Real(2.0, 2.0)
+        );
+        let solution = solve_real_lp_problem_clarabel(&presolved).unwrap();
+        assert_precision(solution.value(), original.value());
+        let solution = solve_real_lp_problem_slow_simplex(&presolved, 1000).unwrap();
+        assert_precision(solution.value(), original.value());
+
+        let mut model = LinearModel::new();
+        model.add_variable("a", VariableType::integer_range(0, 10));
+        model.add_variable("b", VariableType::integer_range(0, 10));
+        model.add_variable("c", VariableType::Boolean);
+        model.add_variable("d", VariableType::non_negative_real());
+        model.add_constraint(vec![3.0], Comparison::Equal, 9.0);
+        //not an integer
+        model.add_constraint(vec![0.0, 2.0], Comparison::Equal, 3.0);
+        //booleans can't be fixed by their domain
+        model.add_constraint(vec![0.0, 0.0, 1.0], Comparison::Equal, 1.0);
+        //outside the domain, left to the solver to report
+        model.add_constraint(vec![0.0, 0.0, 0.0, 1.0], Comparison::Equal, -1.0);
+        assert_eq!(model.presolve_fixed_variables(), 1);
+        assert_eq!(model.constraints().len(), 3);
+        assert_eq!(
+            *model.domain().get("a").unwrap().get_type(),
+            VariableType::IntegerRange(3, 3)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_enumerate_integer_solutions() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::Boolean);
+        model.add_variable("y", VariableType::Boolean);
+        model.add_variable("z", VariableType::Boolean);
+        model.add_constraint(vec![1.0, 1.0, 1.0], Comparison::LessOrEqual, 2.0);
+        model.add_constraint(vec![1.0, 1.0, 0.0], Comparison::GreaterOrEqual, 1.0);
+        model.set_objective(vec![3.0, 2.0, 4.0], OptimizationType::Max);
+        let solutions = model.enumerate_integer_solutions(8).unwrap();
+        let points = solutions
+            .iter()
+            .map(|s| s.values().copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            vec![
+                vec![0.0, 1.0, 0.0],
+                vec![0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 0.0],
+                vec![1.0, 0.0, 1.0],
+                vec![1.0, 1.0, 0.0],
+            ]
+        );
+        //the best enumerated point is the optimum found by branch and bound
+        let best = solutions
+            .iter()
+            .map(|s| {
+                s.values()
+                    .zip(model.objective())
+                    .map(|(v, c)| v * c)
+                    .sum::<f64>()
+            })
+            .fold(f64::NEG_INFINITY, f64::max);
+        let solution = solve_milp_lp_problem(&model).unwrap();
+        assert_precision(solution.value(), best);
+
+        assert!(matches!(
+            model.enumerate_integer_solutions(7),
+            Err(LinearModelError::SearchSpaceTooLarge { size: 8, limit: 7 })
+        ));
+        model.add_variable("w", VariableType::non_negative_real());
+        assert!(matches!(
+            model.enumerate_integer_solutions(100),
+            Err(LinearModelError::NotInteger(name)) if name == "w"
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_solve_objective_less_model() {
+        let source = "
+        s.t.
+            x + y >= 3
+            x - y = 1
+        define
+            x, y as IntegerRange(0, 5)
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(*linear.optimization_type(), OptimizationType::Satisfy);
+        assert!(linear.objective().iter().all(|c| *c == 0.0));
+        let solution = auto_solver(&linear).unwrap();
+        let assignment = solution
+            .assignment()
+            .iter()
+            .map(|a| (a.name.clone(), f64::from(a.value)))
+            .collect::<IndexMap<_, _>>();
+        assert!(linear.check_feasibility(&assignment).is_empty());
+        assert_eq!(solution.value(), 0.0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_satisfy_with_simplex() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::GreaterOrEqual, 4.0);
+        model.add_constraint(vec![1.0, -1.0], Comparison::LessOrEqual, 1.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::Equal, 2.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Satisfy);
+        let (solution, stats) = solve_real_lp_problem_slow_simplex_with_stats(&model, 100).unwrap();
+        assert!(stats.phase1_iterations() > 0);
+        let assignment = solution
+            .assignment()
+            .iter()
+            .map(|a| (a.name.clone(), a.value))
+            .collect::<IndexMap<_, _>>();
+        assert!(model.check_feasibility(&assignment).is_empty());
+        assert_eq!(solution.value(), 0.0);
+
+        model.add_constraint(vec![0.0, 1.0], Comparison::LessOrEqual, 1.0);
+        assert!(solve_real_lp_problem_slow_simplex(&model, 100).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_non_finite_numbers() {
+        let source = "
+        max sum(i in 0..2){ x_i * W[i] * Infinity }
+        s.t.
+            x_i <= 1 for i in 0..2
+        where
+            let W = [0, 1]
+        define
+            x_i as NonNegativeReal for i in 0..2
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let assert_instability = |result: Result<(), SolverError>, expected: &str| match result {
+            Err(SolverError::NumericalInstability { position, value }) => {
+                assert_eq!(position, expected);
+                assert!(!value.is_finite());
+            }
+            r => panic!("Expected a numerical instability, got {:?}", r),
+        };
+        assert_eq!(
+            linear.validate(),
+            Err(vec![
+                "The objective coefficient of x_0 is NaN".to_string(),
+                "The objective coefficient of x_1 is inf".to_string(),
+                "The objective offset is NaN".to_string(),
+            ])
+        );
+        assert_instability(
+            solve_real_lp_problem_clarabel(&linear).map(|_| ()),
+            "objective coefficient of x_0",
+        );
+        assert_instability(
+            solve_real_lp_problem_slow_simplex(&linear, 100).map(|_| ()),
+            "objective coefficient of x_0",
+        );
+        assert_instability(
+            solve_milp_lp_problem(&linear).map(|_| ()),
+            "objective coefficient of x_0",
+        );
+
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::Boolean);
+        model.add_variable("y", VariableType::Boolean);
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 1.0);
+        model.add_constraint(vec![1.0, f64::NAN], Comparison::LessOrEqual, 1.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        assert_instability(
+            auto_solver(&model).map(|_| ()),
+            "coefficient of y in constraint 1",
+        );
+
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0], Comparison::LessOrEqual, f64::INFINITY);
+        model.set_objective(vec![1.0], OptimizationType::Max);
+        assert_eq!(
+            model.validate(),
+            Err(vec![
+                "The right hand side of constraint 0 is inf".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_fix_and_unfix_variables() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 10.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 6.0);
+        model.set_objective(vec![3.0, 1.0], OptimizationType::Max);
+        let original = model.constraints().len();
+        let solution = solve_real_lp_problem_clarabel(&model).unwrap();
+        assert!(float_eq(solution.value(), 22.0));
+
+        model.fix_variable("x", 2.0).unwrap();
+        let solution = solve_real_lp_problem_clarabel(&model).unwrap();
+        assert!(float_eq(solution.value(), 14.0));
+        //fixing again replaces the value
+        model.fix_variable("x", 1.0).unwrap();
+        assert_eq!(model.constraints().len(), original + 1);
+        assert_eq!(model.fixed_variables().get("x"), Some(&1.0));
+        let solution = solve_milp_lp_problem(&model).unwrap();
+        assert!(float_eq(solution.value(), 12.0));
+        //presolving keeps the constraint that fixes the variable
+        model.presolve_fixed_variables();
+        model.unfix_variable("x").unwrap();
+        assert_eq!(model.constraints().len(), original);
+        assert!(model.fixed_variables().is_empty());
+        let solution = solve_real_lp_problem_clarabel(&model).unwrap();
+        assert!(float_eq(solution.value(), 22.0));
+        //unfixing a variable that is not fixed does nothing
+        model.unfix_variable("y").unwrap();
+        assert_eq!(model.constraints().len(), original);
+
+        //a constraint written like the fixing one is not removed when unfixing
+        model.add_constraint(vec![1.0, 0.0], Comparison::Equal, 3.0);
+        model.fix_variable("x", 3.0).unwrap();
+        assert_eq!(model.constraints().len(), original + 2);
+        let json = model.to_json();
+        let mut model = LinearModel::from_json(&json).unwrap();
+        model.unfix_variable("x").unwrap();
+        assert_eq!(model.constraints().len(), original + 1);
+        let last = model.constraints().last().unwrap();
+        assert_eq!(last.coefficients(), &vec![1.0, 0.0]);
+        assert_eq!(last.rhs(), 3.0);
+
+        assert!(matches!(
+            model.fix_variable("z", 1.0),
+            Err(LinearModelError::UnknownVariable(name)) if name == "z"
+        ));
+        assert!(matches!(
+            model.unfix_variable("z"),
+            Err(LinearModelError::UnknownVariable(_))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_find_integer_solutions_with_diving() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::IntegerRange(0, 10));
+        model.add_variable("y", VariableType::IntegerRange(0, 10));
+        model.add_variable("z", VariableType::Boolean);
+        model.add_variable("w", VariableType::non_negative_real());
+        model.add_constraint(vec![5.0, 3.0, 4.0, 1.0], Comparison::LessOrEqual, 17.5);
+        model.add_constraint(vec![1.0, 2.0, 0.0, 0.0], Comparison::LessOrEqual, 7.3);
+        model.add_constraint(vec![0.0, 0.0, 1.0, 1.0], Comparison::LessOrEqual, 1.5);
+        model.set_objective(vec![8.0, 5.0, 6.0, 1.0], OptimizationType::Max);
+        let exact = solve_milp_lp_problem(&model).unwrap();
+        let heuristic = solve_integer_heuristic(&model, 10).unwrap();
+        let assignment = heuristic
+            .assignment()
+            .iter()
+            .map(|a| (a.name.clone(), f64::from(a.value)))
+            .collect::<IndexMap<_, _>>();
+        assert!(model.check_feasibility(&assignment).is_empty());
+        for a in heuristic.assignment() {
+            match (a.name.as_str(), a.value) {
+                ("x" | "y", MILPValue::Int(_)) | ("z", MILPValue::Bool(_)) => {}
+                ("w", MILPValue::Real(_)) => {}
+                (name, value) => panic!("Unexpected value {} for {}", value, name),
+            }
+        }
+        //the heuristic can't do better than the optimum, and its gap bounds how far it is from it
+        assert!(heuristic.value() <= exact.value() + 1e-9);
+        let relaxation = model.relaxation_bound().unwrap();
+        assert!(float_eq(
+            heuristic.gap(),
+            (relaxation - heuristic.value()).abs() / heuristic.value().abs()
+        ));
+
+        assert!(matches!(
+            solve_integer_heuristic(&model, 0),
+            Err(SolverError::LimitReached)
+        ));
+        model.add_constraint(vec![1.0, 0.0, 0.0, 0.0], Comparison::GreaterOrEqual, 11.0);
+        assert!(matches!(
+            solve_integer_heuristic(&model, 10),
+            Err(SolverError::Infisible)
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_view_constraints_by_name() {
+        let source = "
+        max x + 2y - z
+        s.t.
+            x + y <= 4
+            y - 3z >= -2
+            x + y + z = 3
+        define
+            x, y, z as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let model = Linearizer::linearize(model).unwrap();
+        let views = model.iter_constraints_named().collect::<Vec<_>>();
+        assert_eq!(views.len(), model.constraints().len());
+        for (i, (view, constraint)) in views.iter().zip(model.constraints()).enumerate() {
+            assert_eq!(view.name, format!("c{}", i));
+            assert_eq!(&view.constraint_type, constraint.constraint_type());
+            assert_eq!(view.rhs, constraint.rhs());
+            for (name, coefficient) in model.variables().iter().zip(constraint.coefficients()) {
+                let term = view.terms.get(name).copied().unwrap_or(0.0);
+                assert_eq!(term, *coefficient);
+            }
+            //only the non zero coefficients are terms
+            assert!(view.terms.values().all(|c| *c != 0.0));
+        }
+        let terms = views[1]
+            .terms
+            .iter()
+            .map(|(name, c)| (name.as_str(), *c))
+            .collect::<Vec<_>>();
+        assert_eq!(terms, vec![("y", 1.0), ("z", -3.0)]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_use_fewer_pivots_with_steepest_edge_on_larger_models() {
+        //dense models with 20 variables and 15 constraints, with pseudo random coefficients
+        let make_model = |seed: u64| {
+            let mut state = seed;
+            let mut next = || {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                ((state >> 33) % 100) as f64 + 1.0
+            };
+            let mut model = LinearModel::new();
+            for i in 0..20 {
+                model.add_variable(&format!("x_{}", i), VariableType::non_negative_real());
+            }
+            for _ in 0..15 {
+                let coefficients = (0..20).map(|_| next()).collect();
+                let rhs = next() * 10.0;
+                model.add_constraint(coefficients, Comparison::LessOrEqual, rhs);
+            }
+            let objective = (0..20).map(|_| next()).collect();
+            model.set_objective(objective, OptimizationType::Max);
+            model
+        };
+        let solve_with = |model: &LinearModel, pivot_rule: PivotRule| {
+            let result = to_standard_form(model.clone())
+                .unwrap()
+                .into_tableau()
+                .unwrap()
+                .with_pivot_rule(pivot_rule)
+                .solve_step_by_step(1000)
+                .unwrap();
+            (result.steps().len(), result.result().optimal_value())
+        };
+        let (mut dantzig_pivots, mut steepest_pivots) = (0, 0);
+        for seed in 1..30 {
+            let model = make_model(seed);
+            let expected = solve_real_lp_problem_clarabel(&model).unwrap().value();
+            let (dantzig_iterations, dantzig_value) = solve_with(&model, PivotRule::Dantzig);
+            let (steepest_iterations, steepest_value) = solve_with(&model, PivotRule::SteepestEdge);
+            assert!((dantzig_value - expected).abs() < 1e-4);
+            assert!((steepest_value - expected).abs() < 1e-4);
+            dantzig_pivots += dantzig_iterations;
+            steepest_pivots += steepest_iterations;
+        }
+        assert!(steepest_pivots <= dantzig_pivots);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_stop_integer_search_when_time_runs_out() {
+        //a knapsack with weights close to each other has a huge search tree
+        let mut seed: u64 = 3;
+        let weights = (0..40)
+            .map(|_| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                1000.0 + ((seed >> 33) % 1000) as f64
+            })
+            .collect::<Vec<_>>();
+        let mut model = LinearModel::new();
+        for i in 0..weights.len() {
+            model.add_variable(&format!("x{}", i), VariableType::Boolean);
+        }
+        let capacity = weights.iter().sum::<f64>() / 2.0 + 0.5;
+        model.add_constraint(weights.clone(), Comparison::LessOrEqual, capacity);
+        let values = weights.iter().map(|w| w + 1.0).collect::<Vec<_>>();
+        model.set_objective(values, OptimizationType::Max);
+
+        //the node budget makes the search stop at the same point on every machine
+        let budget = SearchBudget::new().with_node_limit(200);
+        let error = solve_milp_lp_problem_with_budget(&model, budget).unwrap_err();
+        let SolverError::IntegerSearchIncomplete {
+            incumbent,
+            nodes_explored,
+        } = error
+        else {
+            panic!("expected the search to be incomplete, got {:?}", error);
+        };
+        assert_eq!(nodes_explored, 200);
+        let incumbent = incumbent.unwrap();
+        let used = incumbent
+            .assignment()
+            .iter()
+            .zip(weights.iter())
+            .map(|(a, w)| a.value * w)
+            .sum::<f64>();
+        assert!(used <= capacity);
+        assert!(incumbent.gap() > 0.0);
+
+        //a time limit of zero runs out before the first node, whatever the speed of the machine
+        let budget = SearchBudget::new().with_time_limit(std::time::Duration::ZERO);
+        assert!(matches!(
+            solve_milp_lp_problem_with_budget(&model, budget),
+            Err(SolverError::IntegerSearchIncomplete {
+                incumbent: None,
+                nodes_explored: 0
+            })
+        ));
+        let config =
+            SolverConfig::new(SolverBackend::Milp).with_time_limit(std::time::Duration::ZERO);
+        assert!(matches!(
+            solvers::solve(&model, &config),
+            Err(SolverError::IntegerSearchIncomplete { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_add_inline_objective_constant_once() {
+        let solve = |objective: &str| {
+            let source = format!(
+                "
+            {}
+            s.t.
+                x + y >= 2
+                x <= 5
+            define
+                x, y as NonNegativeReal
+            ",
+                objective
+            );
+            let model = RoocParser::new(source)
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap();
+            let linear = Linearizer::linearize(model).unwrap();
+            let values = [
+                solve_real_lp_problem_clarabel(&linear).unwrap().value(),
+                solve_real_lp_problem_slow_simplex(&linear, 1000)
+                    .unwrap()
+                    .value(),
+                solve_milp_lp_problem(&linear).unwrap().value(),
+            ];
+            (linear.objective_offset(), values)
+        };
+        let (offset, without) = solve("min 3x + 4y");
+        assert_eq!(offset, 0.0);
+        let (offset, with) = solve("min 3x + 4y + 10");
+        assert_eq!(offset, 10.0);
+        for (without, with) in without.iter().zip(with.iter()) {
+            assert_precision(*without, 6.0);
+            assert_precision(*with, 16.0);
+        }
+        let (offset, values) = solve("max 10 - x - y");
+        assert_eq!(offset, 10.0);
+        for value in values {
+            assert_precision(value, 8.0);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_build_the_dual_with_the_same_optimum() {
+        let sources = [
+            "
+            max 3x + 5y + 2
+            s.t.
+                x <= 4
+                2y <= 12
+                3x + 2y <= 18
+            define
+                x, y as NonNegativeReal
+            ",
+            "
+            min 2x + 3y - z
+            s.t.
+                x + y >= 4
+                x - z = 1
+                y + z <= 6
+            define
+                x, y as NonNegativeReal
+                z as Real
+            ",
+            "
+            min x + 2y
+            s.t.
+                x + y >= 3
+            define
+                x as NonNegativeReal
+                y as NonNegativeReal(1, 5)
+            ",
+        ];
+        for source in sources {
+            let model = RoocParser::new(source.to_string())
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap();
+            let primal = Linearizer::linearize(model).unwrap();
+            let dual = primal.dual().unwrap();
+            assert_ne!(primal.optimization_type(), dual.optimization_type());
+            assert_eq!(dual.constraints().len(), primal.variables().len());
+            let primal_value = solve_real_lp_problem_clarabel(&primal).unwrap().value();
+            let dual_value = solve_real_lp_problem_clarabel(&dual).unwrap().value();
+            assert_precision(primal_value, dual_value);
+            let dual_value = solve_real_lp_problem_slow_simplex(&dual, 1000)
+                .unwrap()
+                .value();
+            assert_precision(primal_value, dual_value);
+        }
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::GreaterOrEqual, 1.0);
+        model.set_objective(vec![2.0, 3.0], OptimizationType::Max);
+        let dual = model.dual().unwrap();
+        assert_eq!(dual.variables(), &vec!["c0".to_string(), "c1".to_string()]);
+        let domain = dual.domain();
+        assert_eq!(
+            domain.get("c0").unwrap().get_type(),
+            &VariableType::non_negative_real()
+        );
+        assert_eq!(
+            domain.get("c1").unwrap().get_type(),
+            &VariableType::Real(f64::NEG_INFINITY, 0.0)
+        );
+
+        model.add_variable("b", VariableType::Boolean);
+        assert!(matches!(model.dual(), Err(LinearModelError::NotReal(name)) if name == "b"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_sum_repeated_objective_terms() {
+        let source = "
+        max 2x + 3x - y + 2y + sum(i in 0..2) { x }
+        s.t.
+            x + y <= 4
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        assert_eq!(linear.objective(), &vec![7.0, 1.0]);
+        assert_precision(
+            solve_real_lp_problem_clarabel(&linear).unwrap().value(),
+            28.0,
+        );
+
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        let terms = [
+            ("y".to_string(), -1.0),
+            ("x".to_string(), 2.0),
+            ("x".to_string(), 3.0),
+            ("y".to_string(), 2.0),
+        ];
+        model
+            .set_objective_from_terms(&terms, OptimizationType::Max)
+            .unwrap();
+        assert_eq!(model.objective(), &vec![5.0, 1.0]);
+        let unknown = [("z".to_string(), 1.0)];
+        assert!(matches!(
+            model.set_objective_from_terms(&unknown, OptimizationType::Min),
+            Err(LinearModelError::UnknownVariable(name)) if name == "z"
+        ));
+        assert_eq!(model.objective(), &vec![5.0, 1.0]);
+        assert_eq!(model.optimization_type(), &OptimizationType::Max);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_build_a_model_by_variable_name() {
+        let model = LinearModelBuilder::new()
+            .maximize()
+            .variable("x", VariableType::non_negative_real())
+            .variable("y", VariableType::IntegerRange(0, 10))
+            .objective_term("y", 1.0)
+            .objective_term("x", 2.0)
+            .constraint(&[("y", 1.0), ("x", 1.0)], Comparison::LessOrEqual, 4.5)
+            .constraint(&[("x", 1.0), ("x", 1.0)], Comparison::LessOrEqual, 5.0)
+            .build()
+            .unwrap();
+        assert_eq!(model.variables(), &vec!["x".to_string(), "y".to_string()]);
+        assert_eq!(model.objective(), &vec![2.0, 1.0]);
+        assert_eq!(model.constraints()[1].coefficients(), &vec![2.0, 0.0]);
+        let solution = solve_milp_lp_problem(&model).unwrap();
+        assert_precision(solution.value(), 7.0);
+
+        let unknown = LinearModelBuilder::new()
+            .variable("x", VariableType::non_negative_real())
+            .constraint(&[("x", 1.0), ("z", 1.0)], Comparison::LessOrEqual, 1.0)
+            .build();
+        assert!(matches!(
+            unknown,
+            Err(LinearModelError::UnknownVariable(name)) if name == "z"
+        ));
+        let duplicate = LinearModelBuilder::new()
+            .variable("x", VariableType::non_negative_real())
+            .variable("x", VariableType::Boolean)
+            .build();
+        assert!(matches!(
+            duplicate,
+            Err(LinearModelError::DuplicateVariable(name)) if name == "x"
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_round_trip_a_linear_model_through_json() {
+        let source = "
+        max 3x_1 + 2x_2 - y + 4
+        s.t.
+            x_1 + x_2 <= 4.5
+            x_1 - y <= 2
+            y >= -1
+        define
+            x_1, x_2 as IntegerRange(0, 10)
+            y as Real
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let mut linear = Linearizer::linearize(model).unwrap();
+        linear.fix_variable("x_2", 1.0).unwrap();
+        let json = linear.to_json();
+        let read = LinearModel::from_json(&json).unwrap();
+        assert_eq!(read.to_json(), json);
+        assert_eq!(read.to_string(), linear.to_string());
+        assert_eq!(read.variables(), linear.variables());
+        assert_eq!(
+            read.domain().keys().collect::<Vec<_>>(),
+            linear.domain().keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            read.domain().get("y").unwrap().get_type(),
+            &VariableType::real()
+        );
+        assert_eq!(read.fixed_variables(), linear.fixed_variables());
+        assert_eq!(read.variable_indexes(), linear.variable_indexes());
+        assert_precision(
+            solve_milp_lp_problem(&read).unwrap().value(),
+            solve_milp_lp_problem(&linear).unwrap().value(),
+        );
+
+        assert!(matches!(
+            LinearModel::from_json("{\"variables\": []}"),
+            Err(LinearModelError::InvalidJson(_))
+        ));
+        let missing_coefficient = json.replacen("\"objective\":[3.0,", "\"objective\":[", 1);
+        assert!(matches!(
+            LinearModel::from_json(&missing_coefficient),
+            Err(LinearModelError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_check_the_coefficient_count_of_a_new_model() {
+        let variables = vec!["x".to_string(), "y".to_string()];
+        let mut domain = IndexMap::new();
+        for name in &variables {
+            domain.insert(
+                name.clone(),
+                DomainVariable::new(VariableType::non_negative_real(), InputSpan::default()),
+            );
+        }
+        let constraints = || {
+            vec![
+                LinearConstraint::new(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0),
+                LinearConstraint::new(vec![1.0], Comparison::LessOrEqual, 3.0),
+            ]
+        };
+        let new = |objective: Vec<f64>, constraints: Vec<LinearConstraint>, pad: bool| {
+            LinearModel::try_new(
+                objective,
+                OptimizationType::Max,
+                0.0,
+                constraints,
+                variables.clone(),
+                domain.clone(),
+                pad,
+            )
+        };
+        assert!(matches!(
+            new(vec![1.0, 2.0], constraints(), false),
+            Err(LinearModelError::WrongCoefficientCount {
+                constraint: Some(1),
+                expected: 2,
+                got: 1
+            })
+        ));
+        assert!(matches!(
+            new(vec![1.0, 2.0, 3.0], constraints(), true),
+            Err(LinearModelError::WrongCoefficientCount {
+                constraint: None,
+                expected: 2,
+                got: 3
+            })
+        ));
+        let model = new(vec![2.0], constraints(), true).unwrap();
+        assert_eq!(model.objective(), &vec![2.0, 0.0]);
+        assert_eq!(model.constraints()[1].coefficients(), &vec![1.0, 0.0]);
+        assert_precision(solve_real_lp_problem_clarabel(&model).unwrap().value(), 6.0);
+
+        domain.shift_remove("y");
+        let missing_domain = LinearModel::try_new(
+            vec![1.0, 2.0],
+            OptimizationType::Max,
+            0.0,
+            vec![],
+            variables,
+            domain,
+            false,
+        );
+        assert!(matches!(
+            missing_domain,
+            Err(LinearModelError::UnknownVariable(name)) if name == "y"
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reconstruct_the_original_variables_from_the_standard_form() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::real());
+        model.add_variable("y", VariableType::NonNegativeReal(0.0, 3.0));
+        model.add_variable("z", VariableType::Real(-5.0, 5.0));
+        model.add_constraint(vec![1.0, 1.0, 1.0], Comparison::GreaterOrEqual, -10.0);
+        model.add_constraint(vec![1.0, -1.0, 0.0], Comparison::LessOrEqual, 2.0);
+        model.add_constraint(vec![0.0, 1.0, 2.0], Comparison::Equal, -4.0);
+        model.set_objective(vec![1.0, -2.0, 1.0], OptimizationType::Min);
+
+        let (standard, map) = to_standard_form_with_map(model.clone()).unwrap();
+        assert_eq!(
+            map.variables().keys().collect::<Vec<_>>(),
+            vec!["x", "y", "z"]
+        );
+        assert!(matches!(
+            map.variables().get("y"),
+            Some(StandardizedVariable::Column(0))
+        ));
+        let optimal = standard.into_tableau().unwrap().solve(1000).unwrap();
+        let values = map.reconstruct(optimal.variables_values());
+        let expected = solve_real_lp_problem_clarabel(&model).unwrap();
+        assert_precision(optimal.optimal_value(), expected.value());
+        for assignment in expected.assignment() {
+            assert_precision(values[&assignment.name], assignment.value);
+        }
+        let objective = model
+            .objective()
+            .iter()
+            .zip(values.values())
+            .map(|(c, v)| c * v)
+            .sum::<f64>();
+        assert_precision(objective, expected.value());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_find_alternate_optima() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 3.0);
+        model.add_constraint(vec![0.0, 1.0], Comparison::LessOrEqual, 3.0);
+        model.set_objective(vec![1.0, 1.0], OptimizationType::Max);
+        let optimal = to_standard_form(model.clone())
+            .unwrap()
+            .into_tableau()
+            .unwrap()
+            .solve(1000)
+            .unwrap();
+        assert_precision(optimal.optimal_value(), 4.0);
+        let alternate = optimal.alternate_optima(10);
+        assert_eq!(alternate.len(), 1);
+        let (x, y) = (alternate[0][0], alternate[0][1]);
+        let (current_x, current_y) = (optimal.variables_values()[0], optimal.variables_values()[1]);
+        assert_precision(x + y, 4.0);
+        assert_precision(x, current_y);
+        assert_precision(y, current_x);
+        assert!(optimal.alternate_optima(0).is_empty());
+
+        model.set_objective(vec![2.0, 1.0], OptimizationType::Max);
+        let optimal = to_standard_form(model)
+            .unwrap()
+            .into_tableau()
+            .unwrap()
+            .solve(1000)
+            .unwrap();
+        assert!(optimal.alternate_optima(10).is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_prefer_sparse_solutions() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::NonNegativeReal(0.0, 3.0));
+        model.add_variable("y", VariableType::NonNegativeReal(0.0, 3.0));
+        model.add_variable("z", VariableType::NonNegativeReal(0.0, 3.0));
+        model.add_constraint(vec![1.0, 1.0, 1.0], Comparison::LessOrEqual, 3.0);
+        model.add_constraint(vec![1.0, 1.0, 0.0], Comparison::LessOrEqual, 3.0);
+        model.add_constraint(vec![0.0, 1.0, 1.0], Comparison::LessOrEqual, 3.0);
+        model.set_objective(vec![1.0, 1.0, 1.0], OptimizationType::Max);
+        let nonzero = |solution: &LpSolution<MILPValue>| {
+            solution
+                .assignment()
+                .iter()
+                .filter(|a| f64::from(a.value).abs() > 1e-6)
+                .count()
+        };
+        let naive = solvers::solve(&model, &SolverConfig::new(SolverBackend::Real)).unwrap();
+        assert_precision(naive.value(), 3.0);
+        assert_eq!(nonzero(&naive), 3);
+
+        let config = SolverConfig::new(SolverBackend::Milp).with_prefer_sparse();
+        let sparse = solvers::solve(&model, &config).unwrap();
+        assert_precision(sparse.value(), 3.0);
+        assert_eq!(nonzero(&sparse), 1);
+        assert_eq!(sparse.assignment().len(), 3);
+
+        let config = SolverConfig::new(SolverBackend::Real).with_prefer_sparse();
+        assert!(matches!(
+            solvers::solve(&model, &config),
+            Err(SolverError::Other(_))
+        ));
+
+        //without a bound the optimum could be cut off by any assumed one
+        let mut unbounded = model.clone();
+        unbounded.add_variable("w", VariableType::non_negative_real());
+        let config = SolverConfig::new(SolverBackend::Milp).with_prefer_sparse();
+        assert!(matches!(
+            solvers::solve(&unbounded, &config),
+            Err(SolverError::Other(message)) if message.contains("Variable \"w\" has no finite bound")
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn test_to_matrices_reconstructs_constraints() {
+        let source = "
+        max 2x + 3y - z + 5
+        s.t.
+            x + y <= 4
+            y - z >= -1
+            x + z = 2
+        define
+            x, y, z as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let (a, b, c, senses, offset) = linear.to_matrices();
+        let columns = linear.variables().len();
+        assert_eq!(a.len(), linear.constraints().len());
+        assert!(a.iter().all(|row| row.len() == columns));
+        assert_eq!(c.len(), columns);
+        assert_eq!(offset, linear.objective_offset());
+
+        let mut rebuilt = LinearModel::new();
+        for name in linear.variables() {
+            rebuilt.add_variable(name, *linear.domain()[name].get_type());
+        }
+        for ((row, rhs), sense) in a.into_iter().zip(b).zip(senses) {
+            rebuilt.add_constraint(row, sense, rhs);
+        }
+        rebuilt.set_objective(c, linear.optimization_type().clone());
+        for (original, rebuilt) in linear.constraints().iter().zip(rebuilt.constraints()) {
+            let mut coefficients = original.coefficients().clone();
+            coefficients.resize(columns, 0.0);
+            assert_eq!(&coefficients, rebuilt.coefficients());
+            assert_eq!(original.rhs(), rebuilt.rhs());
+            assert_eq!(original.constraint_type(), rebuilt.constraint_type());
+        }
+        assert_eq!(linear.objective(), rebuilt.objective());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_stop_within_gap_tolerance() {
+        let mut seed = 7u64;
+        let weights = (0..20)
+            .map(|_| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                10.0 + ((seed >> 33) % 90) as f64
+            })
+            .collect::<Vec<_>>();
+        let mut model = LinearModel::new();
+        for i in 0..weights.len() {
+            model.add_variable(&format!("x{}", i), VariableType::Boolean);
+        }
+        let capacity = weights.iter().sum::<f64>() / 2.0 + 0.5;
+        model.add_constraint(weights.clone(), Comparison::LessOrEqual, capacity);
+        let values = weights
+            .iter()
+            .enumerate()
+            .map(|(i, w)| w + (i % 3) as f64)
+            .collect::<Vec<_>>();
+        model.set_objective(values, OptimizationType::Max);
+
+        let exact = solve_milp_lp_problem_with_budget(&model, SearchBudget::new()).unwrap();
+        assert_eq!(exact.gap(), 0.0);
+        let budget = SearchBudget::new().with_gap_tolerance(0.01);
+        let near = solve_milp_lp_problem_with_budget(&model, budget).unwrap();
+        assert!(near.gap() > 0.0 && near.gap() <= 0.01);
+        assert!(!near.is_optimal());
+        assert!(near.value() <= exact.value());
+        assert!(near.value() >= exact.value() * (1.0 - 0.01));
+
+        let config = SolverConfig::new(SolverBackend::Milp).with_gap_tolerance(0.01);
+        let solution = solvers::solve(&model, &config).unwrap();
+        assert_precision(solution.value(), near.value());
+        assert_eq!(solution.gap(), near.gap());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_report_constraint_slacks() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::IntegerRange(0, 10));
+        model.add_variable("y", VariableType::IntegerRange(0, 10));
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::LessOrEqual, 3.0);
+        model.add_constraint(vec![1.0, 2.0], Comparison::GreaterOrEqual, 2.0);
+        model.add_constraint(vec![1.0, -1.0], Comparison::Equal, 2.0);
+        model.set_objective(vec![2.0, 1.0], OptimizationType::Max);
+        let expected = vec![0.0, 0.0, 3.0, 0.0];
+
+        let milp = solve_milp_lp_problem(&model).unwrap();
+        assert_eq!(milp.slacks(), Some(&expected));
+        let budget = solve_milp_lp_problem_with_budget(&model, SearchBudget::new()).unwrap();
+        assert_eq!(budget.slacks(), Some(&expected));
+        let real = solve_real_lp_problem_clarabel(&model.relaxed()).unwrap();
+        for (slack, expected) in real.slacks().unwrap().iter().zip(expected.iter()) {
+            assert_precision(*slack, *expected);
+        }
+        for backend in [
+            SolverBackend::Auto,
+            SolverBackend::Simplex,
+            SolverBackend::IntegerBinary,
+        ] {
+            let model = match backend {
+                SolverBackend::Simplex => model.relaxed(),
+                _ => model.clone(),
+            };
+            let solution = solvers::solve(&model, &SolverConfig::new(backend)).unwrap();
+            for (slack, expected) in solution.slacks().unwrap().iter().zip(expected.iter()) {
+                assert_precision(*slack, *expected);
+            }
+        }
+
+        let infeasible = LpSolution::new(
+            vec![
+                Assignment {
+                    name: "x".to_string(),
+                    value: 5.0,
+                },
+                Assignment {
+                    name: "y".to_string(),
+                    value: 0.0,
+                },
+            ],
+            10.0,
+        );
+        assert_eq!(infeasible.slacks(), None);
+        let infeasible = infeasible.with_slacks(&model);
+        assert_eq!(infeasible.slacks(), Some(&vec![-1.0, -2.0, 3.0, 3.0]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_substitute_equal_variables() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::IntegerRange(0, 10));
+        model.add_variable("y", VariableType::IntegerRange(2, 6));
+        model.add_variable("z", VariableType::IntegerRange(0, 10));
+        model.add_constraint(vec![1.0, -1.0, 0.0], Comparison::Equal, 0.0);
+        model.add_constraint(vec![1.0, 1.0, 1.0], Comparison::LessOrEqual, 12.0);
+        model.set_objective(vec![1.0, 2.0, 1.0], OptimizationType::Max);
+        let original = model.clone();
+
+        let substitutions = model.substitute_equalities().unwrap();
+        assert_eq!(model.variables(), &vec!["x".to_string(), "z".to_string()]);
+        assert_eq!(model.constraints().len(), 1);
+        assert_eq!(model.constraints()[0].coefficients(), &vec![2.0, 1.0]);
+        assert_eq!(model.objective(), &vec![3.0, 1.0]);
+        assert_eq!(
+            *model.domain()["x"].get_type(),
+            VariableType::IntegerRange(2, 6)
+        );
+
+        let reduced = solve_milp_lp_problem(&model).unwrap();
+        let solution = substitutions.reconstruct_solution(&reduced);
+        let expected = solve_milp_lp_problem(&original).unwrap();
+        assert_precision(solution.value(), expected.value());
+        let names = solution
+            .assignment()
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["x", "y", "z"]);
+        let values = solution
+            .assignment()
+            .iter()
+            .map(|a| f64::from(a.value))
+            .collect::<Vec<_>>();
+        assert_eq!(values[0], values[1]);
+        assert_eq!(values, vec![6.0, 6.0, 0.0]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_reject_contradictory_equalities() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, -1.0], Comparison::Equal, 0.0);
+        model.add_constraint(vec![1.0, 0.0], Comparison::Equal, 1.0);
+        model.add_constraint(vec![0.0, 1.0], Comparison::Equal, 2.0);
+        let original = model.to_string();
+        assert!(matches!(
+            model.substitute_equalities(),
+            Err(LinearModelError::ContradictoryEquality(_))
+        ));
+        assert_eq!(model.to_string(), original);
+
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::IntegerRange(0, 5));
+        model.add_constraint(vec![2.0], Comparison::Equal, 3.0);
+        assert!(matches!(
+            model.substitute_equalities(),
+            Err(LinearModelError::ContradictoryEquality(name)) if name == "x"
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_export_compiled_problem_as_json() {
+        let source = "
+        max sum(i in 0..2, j in 0..2) { (i + j) * x_i_j }
+        s.t.
+            sum(i in 0..2, j in 0..2) { x_i_j } <= 2
+        define
+            x_i_j as Boolean for i in 0..2, j in 0..2
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let linear = Linearizer::linearize(model).unwrap();
+        let solution = solvers::solve(&linear, &SolverConfig::new(SolverBackend::Milp)).unwrap();
+        let json = CompiledProblem::new(linear.clone(), Some(solution)).to_json();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value["model"]["variables"].is_array());
+        //the indexes are only written once, as part of the model
+        assert_eq!(value["model"]["variable_indexes"]["x_1_1"][0], "x");
+        assert!(value["variable_indexes"].is_null());
+        assert_eq!(value["solution"]["value"], 3.0);
+        let x_1_1 = value["solution"]["assignment"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|a| a["name"] == "x_1_1")
+            .unwrap();
+        assert_eq!(x_1_1["value"]["type"], "Bool");
+        assert_eq!(x_1_1["value"]["value"], true);
+
+        let read = CompiledProblem::from_json(&json).unwrap();
+        assert_eq!(read.model().to_string(), linear.to_string());
+        assert_eq!(read.variable_indexes(), linear.variable_indexes());
+        assert_eq!(read.solution().unwrap().value(), 3.0);
+
+        let without_solution = CompiledProblem::new(linear.clone(), None).to_json();
+        assert!(!without_solution.contains("\"solution\""));
+        assert!(CompiledProblem::from_json(&without_solution)
+            .unwrap()
+            .solution()
+            .is_none());
+
+        let unknown = json.replace("\"name\":\"x_1_1\"", "\"name\":\"w\"");
+        assert!(matches!(
+            CompiledProblem::from_json(&unknown),
+            Err(LinearModelError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_round_noisy_values_in_solution_table() {
+        let assignment = [("x", 2.0 / 3.0), ("y", -1e-16), ("z", 3.00000000000004)]
+            .into_iter()
+            .map(|(name, value)| Assignment {
+                name: name.to_string(),
+                value,
+            })
+            .collect();
+        let solution = LpSolution::new(assignment, 1e-17);
+        let rows = |table: String| {
+            table
+                .lines()
+                .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect::<Vec<_>>()
+        };
+        let table = rows(solution.to_table());
+        assert!(table.iter().any(|r| r == "║ x ║ 0.6667 ║"));
+        assert!(table.iter().any(|r| r == "║ y ║ 0 ║"));
+        assert!(table.iter().any(|r| r == "║ z ║ 3 ║"));
+        assert!(table.iter().any(|r| r == "║ Objective value ║ 0 ║"));
+
+        let options = FormatOptions::new().with_decimals(2);
+        let table = rows(solution.to_table_with_options(&options));
+        assert!(table.iter().any(|r| r == "║ x ║ 0.67 ║"));
+
+        let table = rows(solution.to_table_with_options(&FormatOptions::new()));
+        assert!(table.iter().any(|r| r == &format!("║ x ║ {} ║", 2.0 / 3.0)));
+        assert!(table.iter().any(|r| r == &format!("║ y ║ {} ║", -1e-16)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_validate_all_model_problems() {
+        let mut model = LinearModel::new();
+        model.add_variable("x", VariableType::non_negative_real());
+        model.add_variable("y", VariableType::non_negative_real());
+        model.add_constraint(vec![1.0, 1.0], Comparison::LessOrEqual, 4.0);
+        model.set_objective(vec![1.0, 2.0], OptimizationType::Max);
+        assert_eq!(model.validate(), Ok(()));
+
+        //numbers that are not finite
+        let mut infinite = model.clone();
+        infinite.add_constraint(
+            vec![f64::INFINITY, 0.0],
+            Comparison::GreaterOrEqual,
+            f64::NAN,
+        );
+        assert_eq!(
+            infinite.validate(),
+            Err(vec![
+                "The coefficient of x in constraint 1 is inf".to_string(),
+                "The right hand side of constraint 1 is NaN".to_string(),
+            ])
+        );
+
+        let domain = model.domain().clone();
+        let variables = model.variables().clone();
+        //wrong number of coefficients
+        let wrong_size = LinearModel::new_from_parts(
+            vec![1.0],
+            OptimizationType::Max,
+            0.0,
+            vec![
+                LinearConstraint::new(vec![1.0], Comparison::LessOrEqual, 1.0),
+                LinearConstraint::new(vec![1.0, 1.0, 1.0], Comparison::LessOrEqual, 1.0),
+            ],
+            variables.clone(),
+            domain.clone(),
+        );
+        assert_eq!(
+            wrong_size.validate(),
+            Err(vec![
+                "The objective has 1 coefficients but there are 2 variables".to_string(),
+                "Constraint 0 has 1 coefficients but there are 2 variables".to_string(),
+                "Constraint 1 has 3 coefficients but there are 2 variables".to_string(),
+            ])
+        );
+
+        //constraints without variables
+        let no_variables = LinearModel::new_from_parts(
+            vec![],
+            OptimizationType::Min,
+            f64::NEG_INFINITY,
+            vec![LinearConstraint::new(vec![], Comparison::LessOrEqual, 1.0)],
+            vec![],
+            IndexMap::new(),
+        );
+        assert_eq!(
+            no_variables.validate(),
+            Err(vec![
+                "The objective offset is -inf".to_string(),
+                "The model has 1 constraints but no variables".to_string(),
+            ])
+        );
+
+        //variables without a domain
+        let mut partial_domain = domain.clone();
+        partial_domain.shift_remove("y");
+        let no_domain = LinearModel::new_from_parts(
+            vec![1.0, 2.0],
+            OptimizationType::Max,
+            0.0,
+            vec![],
+            variables,
+            partial_domain,
+        );
+        assert_eq!(
+            no_domain.validate(),
+            Err(vec!["Variable \"y\" has no domain".to_string()])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_return_constraint_column() {
+        let source = "
+        max x + 2y - z
+        s.t.
+            x + y <= 4
+            y - 3z >= -2
+            x + y + z = 3
+        define
+            x, y, z as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let model = Linearizer::linearize(model).unwrap();
+        assert_eq!(model.column("x"), Some(vec![(0, 1.0), (2, 1.0)]));
+        assert_eq!(model.column("y"), Some(vec![(0, 1.0), (1, 1.0), (2, 1.0)]));
+        assert_eq!(model.column("z"), Some(vec![(1, -3.0), (2, 1.0)]));
+        assert_eq!(model.column("w"), None);
+        //the columns are the transpose of the constraint rows
+        for (j, name) in model.variables().iter().enumerate() {
+            for (i, coefficient) in model.column(name).unwrap() {
+                assert_eq!(model.constraints()[i].coefficients()[j], coefficient);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_maximize_product_objective() {
+        let source = "
+        max 0
+        s.t.
+            2x + y <= 8
+        define
+            x, y as NonNegativeReal
+        ";
+        let model = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .unwrap();
+        let model = Linearizer::linearize(model).unwrap();
+        //max x * y with y = 8 - 2x is reached where 8 - 4x = 0, at x = 2 and y = 4
+        let product = LinearModel::from_product_objective(model, &["x", "y"]).unwrap();
+        let solution = product
+            .solve(&SolverConfig::new(SolverBackend::Real))
+            .unwrap();
+        assert!((solution.value() - 8.0).abs() < 1e-4);
+        let values = solution
+            .assignment()
+            .iter()
+            .map(|a| a.value)
+            .collect::<Vec<_>>();
+        assert!((values[0] - 2.0).abs() < 1e-4);
+        assert!((values[1] - 4.0).abs() < 1e-4);
+        let err = product
+            .solve(&SolverConfig::new(SolverBackend::Simplex))
+            .unwrap_err();
+        assert!(matches!(err, SolverError::Other(s) if s.contains("SolverBackend::Real")));
+        let err = LinearModel::from_product_objective(product.model().clone(), &["z"]).unwrap_err();
+        assert!(matches!(err, SolverError::Other(s) if s.contains("\"z\"")));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn should_give_undeclared_variables_the_default_domain() {
+        let source = "
+        min x + y
+        s.t.
+            x + y >= -5
+            x <= 2
+        define
+            y as NonNegativeReal
+        ";
+        let err = RoocParser::new(source.to_string())
+            .parse_and_transform(vec![], &IndexMap::new())
+            .expect_err("Failed to reject an undeclared variable");
+        assert!(err.contains("The domain of variable \"x\" was not defined"));
+        for (default_domain, expected, free) in [
+            (DefaultDomain::NonNegativeReal, 0.0, 0),
+            (DefaultDomain::Real, -5.0, 1),
+        ] {
+            let model = RoocParser::new(source.to_string())
+                .with_default_domain(default_domain)
+                .parse_and_transform(vec![], &IndexMap::new())
+                .unwrap();
+            assert_eq!(model.warnings().len(), 1);
+            assert!(model.warnings()[0].contains("Variable \"x\" was not declared"));
+            let model = Linearizer::linearize(model).unwrap();
+            let stats = model.stats();
+            assert_eq!(stats.real_variables, 2);
+            assert_eq!(stats.free_variables, free);
+            for backend in [SolverBackend::Real, SolverBackend::Simplex] {
+                let solution = solvers::solve(&model, &SolverConfig::new(backend)).unwrap();
+                assert_precision(solution.value(), expected);
+            }
+        }
+    }
 }
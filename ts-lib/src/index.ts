@@ -14,6 +14,7 @@ import {
     PreModel as _PreModel,
     RoocParser as _RoocParser,
     SerializedCompilationError,
+    SerializedFormatOptions,
     SerializedModel,
     SerializedPreModel,
     SerializedPrimitive,
@@ -283,6 +284,15 @@ export class PreModel {
         return this.instance.create_token_type_map_wasm(data, cloneJsFunction(fns))
     }
 
+    /**
+     * Finds every occurrence of a variable or constant, where it is declared and where it is used,
+     * sorted by their position in the source, useful to rename it
+     * @param name the name of the variable or constant, like "x" for "x_i"
+     */
+    findOccurrences(name: string): InputSpan[] {
+        return this.instance.find_occurrences_wasm(name)
+    }
+
     /**
      * Converts the PreModel into a latex string
      */
@@ -290,6 +300,13 @@ export class PreModel {
         return this.instance.to_latex_wasm()
     }
 
+    /**
+     * Converts the PreModel into a latex string, formatting the numbers with the given options
+     */
+    toLatexWithOptions(options: SerializedFormatOptions): string {
+        return this.instance.to_latex_with_options_wasm(options)
+    }
+
     /**
      * Formats the source code
      */
@@ -387,6 +404,35 @@ export class LinearModel {
         return this.instance.wasm_to_string()
     }
 
+    /**
+     * Stringifies back the linear model into a string, formatting the numbers with the given options
+     */
+    stringifyWithOptions(options: SerializedFormatOptions) {
+        return this.instance.wasm_to_string_with_options(options)
+    }
+
+    /**
+     * Serializes the linear model to JSON, infinite bounds of the variables are written as null
+     */
+    toJson() {
+        return this.instance.wasm_to_json()
+    }
+
+    /**
+     * Reads a linear model serialized with toJson, throws if the JSON is not a valid model
+     */
+    static fromJson(json: string) {
+        return new LinearModel(_LinearModel.wasm_from_json(json))
+    }
+
+    /**
+     * Serializes the linear model together with the indexes of its variables and, if given, its solution,
+     * so that a viewer can show the value of each indexed variable
+     */
+    toCompiledJson(solution?: LpSolution<MILPValue>): string {
+        return this.instance.wasm_to_compiled_json(solution)
+    }
+
     /**
      * Get all the variable names of the linear model
      */
@@ -570,6 +616,14 @@ export class OptimalTableau {
     getOptimalValue() {
         return this.instance.wasm_get_optimal_value()
     }
+
+    /**
+     * Get other optimal vertices when the optimal solution is not unique
+     * @param max the maximum number of vertices to return
+     */
+    getAlternateOptima(max: number): number[][] {
+        return this.instance.wasm_alternate_optima(max)
+    }
 }
 
 export class SimplexStep {
@@ -869,6 +923,13 @@ export class Model {
     stringify(): string {
         return this.instance.to_string_wasm()
     }
+
+    /**
+     * Returns the sorted names of the variables used in the objective and in the constraints
+     */
+    getReferencedVariables(): string[] {
+        return this.instance.wasm_get_referenced_variables()
+    }
 }
 
 export type VarValue = {
@@ -898,6 +959,14 @@ export type LpAssignment<T> = {
 export type LpSolution<T> = {
     assignment: LpAssignment<T>[]
     value: number
+    gap: number
+    slacks?: number[]
+    notes?: string[]
+    stats?: {
+        iterations: number
+        degenerate_pivots: number
+        phase1_iterations: number
+    }
 }
 
 
@@ -906,10 +975,13 @@ export * from './runtime'
 
 export type {
     SerializedAddressableAccess,
+    SerializedPointerAccess,
     SerializedBlockFunction,
     SerializedBlockScopedFunction,
     SerializedCompilationError,
+    SerializedCompiledProblem,
     SerializedCompoundVariable,
+    SerializedFormatOptions,
     SerializedFunctionCall,
     SerializedCondition,
     SerializedConstant,
@@ -919,17 +991,23 @@ export type {
     SerializedGraphNode,
     SerializedIterable,
     SerializedIterableSet,
+    SerializedLinearConstraint,
+    SerializedLinearModel,
     SerializedObjective,
     SerializedPreExp,
+    SerializedPreGraph,
+    SerializedPreGraphEdge,
     SerializedPreConstraint,
     SerializedPreObjective,
     SerializedPreModel,
     SerializedModel,
     SerializedPrimitive,
+    SerializedPrimitiveSet,
     SerializedSpanned,
     SerializedTuple,
     SerializedVariableKind,
     SerializedTransformError,
+    SerializedSolverError,
     SerializedTokenType,
     SerializedTypedToken,
     SerializedPrimitiveKind,
@@ -11,6 +11,7 @@ export const PrimitiveKind = {
     GraphEdge: {type: 'GraphEdge'},
     GraphNode: {type: 'GraphNode'},
     Tuple: <T extends SerializedPrimitiveKind>(value: T[]) => ({type: 'Tuple', value} as const),
+    Set: <T extends SerializedPrimitiveKind>(value: T) => ({type: 'Set', value} as const),
     Boolean: {type: 'Boolean'},
     Undefined: {type: 'Undefined'},
     Any: {type: 'Any'},
@@ -123,6 +124,13 @@ export const FN_edges = makeRuntimeFunction("edges", [
     "Returns the edges of a graph"
 )
 
+export const FN_weighted_edges = makeRuntimeFunction("weighted_edges", [
+        {name: "of_graph", value: {type: "Graph"}}
+    ],
+    {type: "Iterable", value: {type: "Tuple", value: [{type: "String"}, {type: "String"}, {type: "Number"}]}},
+    "Returns the edges of a graph as a list of (from, to, weight) tuples, unweighted edges have a weight of 1"
+)
+
 export const FN_nodes = makeRuntimeFunction("nodes", [
         {name: "of_graph", value: {type: "Graph"}}
     ],
@@ -144,6 +152,46 @@ export const FN_neigh_edges_of = makeRuntimeFunction("neigh_edges_of", [
     "Returns the neighbour edges of a node name in a graph"
 )
 
+export const FN_complement = makeRuntimeFunction("complement", [
+        {name: "of_graph", value: {type: "Graph"}},
+    ],
+    {type: "Graph"},
+    "Returns the complement of a graph, where each node has an edge to all the other nodes it was not connected to"
+)
+
+export const FN_transitive_closure = makeRuntimeFunction("transitive_closure", [
+        {name: "of_graph", value: {type: "Graph"}},
+    ],
+    {type: "Graph"},
+    "Returns the transitive closure of a graph, where each node has an unweighted edge to all the nodes reachable from it. Pass true as a second argument to also add an edge from a node to itself when it lies on a cycle"
+)
+
+export const FN_shortest_paths = makeRuntimeFunction("shortest_paths", [
+        {name: "of_graph", value: {type: "Graph"}},
+        {name: "error_on_negative_cycle", value: {type: "Boolean"}},
+    ],
+    {type: "Iterable", value: {type: "Iterable", value: {type: "Number"}}},
+    "Returns the matrix of the shortest path lengths between every pair of nodes, in the order the nodes are declared. Unreachable nodes have a distance of Infinity, paths through a negative cycle have a distance of MinusInfinity, or an error is thrown if error_on_negative_cycle is true"
+)
+
+export const FN_max_flow = makeRuntimeFunction("max_flow", [
+        {name: "of_graph", value: {type: "Graph"}},
+        {name: "source", value: {type: "String"}},
+        {name: "sink", value: {type: "String"}},
+    ],
+    {type: "Number"},
+    "Returns the value of the maximum flow from the source to the sink node, using the weights of the edges as capacities, unweighted edges have a capacity of 1"
+)
+
+export const FN_max_flow_edges = makeRuntimeFunction("max_flow_edges", [
+        {name: "of_graph", value: {type: "Graph"}},
+        {name: "source", value: {type: "String"}},
+        {name: "sink", value: {type: "String"}},
+    ],
+    {type: "Iterable", value: {type: "Tuple", value: [{type: "String"}, {type: "String"}, {type: "Number"}]}},
+    "Returns the flow going through each edge of the graph in a maximum flow from the source to the sink node, as a list of (from, to, flow) tuples"
+)
+
 export const FN_zip = makeRuntimeFunction("zip", [
         {name: "arg1", value: {type: "Iterable", value: {type: "Any"}}},
         {name: "arg2", value: {type: "Iterable", value: {type: "Any"}}},
@@ -205,6 +253,20 @@ export const FN_intersection = makeRuntimeFunction("intersection", [
     "Returns the intersection of two iterables"
 )
 
+export const FN_norm1 = makeRuntimeFunction("norm1", [
+        {name: "of_array", value: {type: "Iterable", value: {type: "Number"}}},
+    ],
+    {type: "Number"},
+    "Returns the sum of the absolute values of the numbers"
+)
+
+export const FN_norm_inf = makeRuntimeFunction("norm_inf", [
+        {name: "of_array", value: {type: "Iterable", value: {type: "Number"}}},
+    ],
+    {type: "Number"},
+    "Returns the largest absolute value of the numbers"
+)
+
 function variant(name: string, fun: RuntimeFunction<NamedParameter[], SerializedPrimitiveKind>){
     return {...fun, name}
 }
@@ -215,17 +277,25 @@ export const ROOC_RUNTIME_FUNCTIONS = new Map<string, RuntimeFunction<NamedParam
     ["enum", variant("enum", FN_enumerateArray)],
     [FN_edges.name, FN_edges],
     ["E", variant("E", FN_edges)],
+    [FN_weighted_edges.name, FN_weighted_edges],
     [FN_nodes.name, FN_nodes],
     ["V", variant("V",FN_nodes)],
     [FN_neigh_edges.name, FN_neigh_edges],
     ["N", variant("N", FN_neigh_edges)],
     [FN_neigh_edges_of.name, FN_neigh_edges_of],
     ["N_of", variant("N_of", FN_neigh_edges_of)],
+    [FN_complement.name, FN_complement],
+    [FN_transitive_closure.name, FN_transitive_closure],
+    [FN_shortest_paths.name, FN_shortest_paths],
+    [FN_max_flow.name, FN_max_flow],
+    [FN_max_flow_edges.name, FN_max_flow_edges],
     [FN_rangeArray.name, FN_rangeArray],
     [FN_zip.name, FN_zip],
     [FN_difference.name, FN_difference],
     [FN_union.name, FN_union],
     [FN_intersection.name, FN_intersection],
+    [FN_norm1.name, FN_norm1],
+    [FN_norm_inf.name, FN_norm_inf],
 ])
 
 export type RuntimeBlockScopedFunction = {
@@ -244,6 +314,8 @@ export const RUNTIME_BLOCK_SCOPED_FUNCTIONS = new Map([
     makeRuntimeBlockScopedFunctionEntry("min", "Expands the inner expression into the minimum of all elements"),
     makeRuntimeBlockScopedFunctionEntry("max", "Expands the inner expression into the maximum of all elements"),
     makeRuntimeBlockScopedFunctionEntry("avg", "Expands the inner expression into the average of all elements"),
+    makeRuntimeBlockScopedFunctionEntry("norm1", "Expands the inner expression into the sum of the absolute values of all elements"),
+    makeRuntimeBlockScopedFunctionEntry("norm_inf", "Expands the inner expression into the largest absolute value of all elements"),
 ])
 
 export type RuntimeBlockFunction = {
@@ -260,6 +332,8 @@ export const RUNTIME_BLOCK_FUNCTIONS = new Map([
     makeRuntimeBlockFunctionEntry("min", "Computes the inner expression as the minimum of all elements"),
     makeRuntimeBlockFunctionEntry("max", "Computes the inner expression as the maximum of all elements"),
     makeRuntimeBlockFunctionEntry("avg", "Computes the inner expression as the average of all elements"),
+    makeRuntimeBlockFunctionEntry("norm1", "Computes the inner expression as the sum of the absolute values of all elements"),
+    makeRuntimeBlockFunctionEntry("norm_inf", "Computes the inner expression as the largest absolute value of all elements"),
 ])
 
 export const RUNTIME_FUNCTIONS = ROOC_RUNTIME_FUNCTIONS.values()